@@ -0,0 +1,240 @@
+//! Behavior tree that decides what an idle orc does next.
+
+use std::sync::OnceLock;
+
+use rand::RngCore;
+
+use crate::animal::Animal;
+use crate::calendar::DayPhase;
+use crate::config::AiConfig;
+use crate::emote::EmoteKind;
+use crate::event::{EventCategory, EventLog, Severity};
+use crate::orc::{self, Activity, BERRY_POUCH, Orc, Purpose};
+use crate::tool::ToolKind;
+use crate::world::World;
+
+/// Orcs stop cooking once this many cooked meals are waiting on the rack
+const MAX_COOKED_MEALS: u32 = 4;
+
+/// Everything a node may look at or change besides the orc itself
+pub struct Ctx<'a> {
+    pub world: &'a mut World,
+    pub animals: &'a mut [Animal],
+    pub rng: &'a mut dyn RngCore,
+    pub log: &'a mut EventLog,
+    pub tick: u64,
+    pub phase: DayPhase,
+    pub config: &'a AiConfig,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Status {
+    Success,
+    Failure,
+}
+
+impl Status {
+    fn from_bool(ok: bool) -> Status {
+        if ok { Status::Success } else { Status::Failure }
+    }
+}
+
+pub enum Node {
+    Selector(Vec<Node>),
+    Sequence(Vec<Node>),
+    Condition(fn(&Orc, &Ctx) -> bool),
+    Action(fn(&mut Orc, &mut Ctx) -> Status),
+}
+
+impl Node {
+    pub fn run(&self, orc: &mut Orc, ctx: &mut Ctx) -> Status {
+        match self {
+            Node::Selector(children) => {
+                let ok = children.iter().any(|child| child.run(orc, ctx) == Status::Success);
+                Status::from_bool(ok)
+            }
+            Node::Sequence(children) => {
+                let ok = children.iter().all(|child| child.run(orc, ctx) == Status::Success);
+                Status::from_bool(ok)
+            }
+            Node::Condition(test) => Status::from_bool(test(orc, ctx)),
+            Node::Action(act) => act(orc, ctx),
+        }
+    }
+}
+
+/// Run `action` only when `condition` holds
+fn when(condition: fn(&Orc, &Ctx) -> bool, action: fn(&mut Orc, &mut Ctx) -> Status) -> Node {
+    Node::Sequence(vec![Node::Condition(condition), Node::Action(action)])
+}
+
+/// The clan's priorities, most urgent first
+pub fn clan_tree() -> &'static Node {
+    static TREE: OnceLock<Node> = OnceLock::new();
+    TREE.get_or_init(|| {
+        use Node::*;
+        Selector(vec![
+            // Too weak to travel: stay put while a clanmate brings food
+            when(|o, _| o.awaiting_food, |_, _| Status::Success),
+            // Health critical: fix whichever need is worst
+            Sequence(vec![
+                Condition(|o, c| o.health < c.config.critical_health),
+                Action(raise_alarm),
+                Selector(vec![
+                    when(thirst_is_worst, desperate_for_water),
+                    when(hunger_is_worst, desperate_for_food),
+                    when(|o, c| !thirst_is_worst(o, c) && !hunger_is_worst(o, c), desperate_for_rest),
+                ]),
+            ]),
+            when(|o, c| o.thirst > c.config.thirst, seek_water),
+            when(|o, c| o.hunger > c.config.hunger, seek_food),
+            when(|o, c| o.energy < c.config.exhaustion, seek_sleep),
+            when(|o, _| o.carrying_food || o.berries >= BERRY_POUCH, stock_the_rack),
+            when(should_cook, start_cooking),
+            // Evening: back to camp and to bed. Night owls only come in to warm up.
+            Sequence(vec![
+                Condition(|o, c| !c.phase.is_daylight() && (o.keeps_schedule() || o.warmth < 50.0)),
+                Selector(vec![
+                    when(|o, c| o.is_bedtime(c.phase) && o.hut.is_some() && !o.in_own_hut(), head_to_hut),
+                    when(|o, c| o.is_bedtime(c.phase) && o.in_own_hut(), turn_in),
+                    when(|o, c| !o.near_campfire(c.world), head_to_camp),
+                    when(|o, c| o.is_bedtime(c.phase), turn_in),
+                    Action(sit_by_fire),
+                ]),
+            ]),
+            Action(wander),
+        ])
+    })
+}
+
+// Conditions
+
+fn thirst_is_worst(o: &Orc, _: &Ctx) -> bool {
+    o.thirst > o.hunger && o.thirst > 100.0 - o.energy
+}
+
+fn hunger_is_worst(o: &Orc, c: &Ctx) -> bool {
+    !thirst_is_worst(o, c) && o.hunger > 100.0 - o.energy
+}
+
+fn should_cook(_: &Orc, c: &Ctx) -> bool {
+    c.phase.is_daylight()
+        && c.world.fire_lit()
+        && c.world.food_stockpile > 0
+        && c.world.cooked_stockpile < MAX_COOKED_MEALS
+        && !c.world.claimed_tiles.contains(&c.world.campfire_pos)
+}
+
+// Actions
+
+fn raise_alarm(o: &mut Orc, _: &mut Ctx) -> Status {
+    o.emote = Some(EmoteKind::Alarm);
+    Status::Success
+}
+
+fn desperate_for_water(o: &mut Orc, c: &mut Ctx) -> Status {
+    let Some((wx, wy)) = c.world.find_drink(o.x, o.y) else {
+        return Status::Failure;
+    };
+    c.log.log_at(c.tick, EventCategory::Alert, format!("{} desperately needs water!", o.name), Severity::Danger, (o.x, o.y));
+    o.go_to(wx, wy, Purpose::Drink, "Desperate for water", c.world);
+    Status::Success
+}
+
+fn desperate_for_food(o: &mut Orc, c: &mut Ctx) -> Status {
+    let Some(target) = o.find_food_target(c.world, c.animals, c.phase) else {
+        return Status::Failure;
+    };
+    c.log.log_at(c.tick, EventCategory::Alert, format!("{} desperately needs food!", o.name), Severity::Danger, (o.x, o.y));
+    go_for_food(o, target, c);
+    Status::Success
+}
+
+/// Claim a food target and set off for it, taking a spear down from the rack for a hunt
+fn go_for_food(o: &mut Orc, target: Activity, c: &mut Ctx) {
+    orc::claim_target(&target, c.world, c.animals);
+    if matches!(target, Activity::Hunting { .. }) {
+        o.take_tool(ToolKind::Spear, c.world, c.log, c.tick);
+    }
+    o.set_activity_with_path(target, c.world);
+}
+
+fn desperate_for_rest(o: &mut Orc, c: &mut Ctx) -> Status {
+    let (cx, cy) = c.world.campfire_pos;
+    let (sx, sy) = o.find_spot_near(cx, cy, c.world, &mut c.rng);
+    c.log.log_at(c.tick, EventCategory::Alert, format!("{} desperately needs rest!", o.name), Severity::Danger, (o.x, o.y));
+    o.go_to(sx, sy, Purpose::Sleep, "Desperate for sleep", c.world);
+    Status::Success
+}
+
+fn seek_water(o: &mut Orc, c: &mut Ctx) -> Status {
+    let Some((wx, wy)) = c.world.find_drink(o.x, o.y) else {
+        return Status::Failure;
+    };
+    c.log.log_at(c.tick, EventCategory::General, format!("{} is thirsty, heading to water", o.name), Severity::Warning, (o.x, o.y));
+    o.go_to(wx, wy, Purpose::Drink, "Going to drink", c.world);
+    Status::Success
+}
+
+fn seek_food(o: &mut Orc, c: &mut Ctx) -> Status {
+    let Some(target) = o.find_food_target(c.world, c.animals, c.phase) else {
+        return Status::Failure;
+    };
+    c.log.log_at(c.tick, EventCategory::General, format!("{} is hungry, looking for food", o.name), Severity::Warning, (o.x, o.y));
+    go_for_food(o, target, c);
+    Status::Success
+}
+
+fn seek_sleep(o: &mut Orc, c: &mut Ctx) -> Status {
+    if o.head_to_hut(c.world) {
+        c.log.log_at(c.tick, EventCategory::General, format!("{} is exhausted, heading to bed", o.name), Severity::Warning, (o.x, o.y));
+        return Status::Success;
+    }
+    let (cx, cy) = c.world.campfire_pos;
+    let (sx, sy) = o.find_spot_near(cx, cy, c.world, &mut c.rng);
+    c.log.log_at(c.tick, EventCategory::General, format!("{} is exhausted, heading to campfire", o.name), Severity::Warning, (o.x, o.y));
+    o.go_to(sx, sy, Purpose::Sleep, "Going to sleep", c.world);
+    Status::Success
+}
+
+fn stock_the_rack(o: &mut Orc, c: &mut Ctx) -> Status {
+    o.head_to_rack(c.world);
+    Status::Success
+}
+
+fn start_cooking(o: &mut Orc, c: &mut Ctx) -> Status {
+    let (cx, cy) = c.world.campfire_pos;
+    c.world.claimed_tiles.insert((cx, cy));
+    let (sx, sy) = o.find_spot_near(cx, cy, c.world, &mut c.rng);
+    o.plan_path(sx, sy, c.world, false);
+    o.activity = Activity::Cooking { progress: 0 };
+    Status::Success
+}
+
+fn head_to_camp(o: &mut Orc, c: &mut Ctx) -> Status {
+    let (cx, cy) = c.world.campfire_pos;
+    let (sx, sy) = o.find_spot_near(cx, cy, c.world, &mut c.rng);
+    let reason = if o.keeps_schedule() { "Heading back to camp" } else { "Heading to the fire" };
+    o.go_to(sx, sy, Purpose::Camp, reason, c.world);
+    Status::Success
+}
+
+fn head_to_hut(o: &mut Orc, c: &mut Ctx) -> Status {
+    Status::from_bool(o.head_to_hut(c.world))
+}
+
+fn turn_in(o: &mut Orc, c: &mut Ctx) -> Status {
+    o.turn_in(c.log, c.tick);
+    Status::Success
+}
+
+fn sit_by_fire(o: &mut Orc, c: &mut Ctx) -> Status {
+    c.log.log_at(c.tick, EventCategory::General, format!("{} sits down by the fire", o.name), Severity::Info, (o.x, o.y));
+    o.activity = Activity::Warming;
+    Status::Success
+}
+
+fn wander(o: &mut Orc, c: &mut Ctx) -> Status {
+    o.wander(c.world, &mut c.rng);
+    Status::Success
+}
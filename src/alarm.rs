@@ -0,0 +1,53 @@
+//! The clan standing to arms.
+
+use crate::watch::Threat;
+
+/// Wolves or goblins this close to the campfire put the clan on alert
+pub const ALERT_RADIUS: usize = 20;
+/// Ticks without a threat near camp before the clan stands down
+const STAND_DOWN: u64 = 30;
+/// What an orc sent back to the fire tells anyone who asks
+pub const TAKING_COVER: &str = "Taking cover by the fire";
+/// Ticks the map's border spends red, then plain, while the clan is on alert
+pub const FLASH_TICKS: u64 = 2;
+
+#[derive(Clone, Copy, Default)]
+pub enum Alarm {
+    #[default]
+    Calm,
+    Alert {
+        threat: Threat,
+        /// Where the threat was last seen
+        x: usize,
+        y: usize,
+        /// Last tick the threat was near camp
+        last_seen: u64,
+    },
+}
+
+impl Alarm {
+    pub fn is_raised(&self) -> bool {
+        matches!(self, Alarm::Alert { .. })
+    }
+
+    /// Note `threat` at (x, y) near camp at `tick`. Returns whether this is
+    /// what raised the alarm.
+    pub fn sight(&mut self, threat: Threat, x: usize, y: usize, tick: u64) -> bool {
+        let raised = !self.is_raised();
+        *self = Alarm::Alert { threat, x, y, last_seen: tick };
+        raised
+    }
+
+    /// Stand down if nothing has been near camp for long enough. Returns
+    /// whether the clan stood down.
+    pub fn lapse(&mut self, tick: u64) -> bool {
+        let Alarm::Alert { last_seen, .. } = *self else {
+            return false;
+        };
+        if tick < last_seen + STAND_DOWN {
+            return false;
+        }
+        *self = Alarm::Calm;
+        true
+    }
+}
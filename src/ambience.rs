@@ -0,0 +1,167 @@
+//! Drifting leaves, campfire sparks and rain drawn over the map.
+
+use std::time::Instant;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use ratatui::layout::Rect;
+
+use crate::calendar::{self, Season};
+use crate::world::{Terrain, World};
+
+/// Most particles in the air at once
+const MAX_PARTICLES: usize = 150;
+
+/// Sparks thrown up by a burning campfire each second
+const SPARK_RATE: f32 = 4.0;
+/// Leaves let go each second for every hundred tiles in view, autumn's worth;
+/// other seasons shed a fraction of that
+const LEAF_RATE: f32 = 0.6;
+/// Raindrops each second for every column in view during a shower
+const RAIN_RATE: f32 = 1.5;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Mote {
+    Leaf,
+    Spark,
+    Rain,
+}
+
+struct Particle {
+    mote: Mote,
+    x: f32,
+    y: f32,
+    dx: f32,
+    dy: f32,
+    /// Seconds left before it fades
+    ttl: f32,
+}
+
+pub struct Ambience {
+    pub enabled: bool,
+    particles: Vec<Particle>,
+    rng: StdRng,
+    last_frame: Instant,
+    /// Seconds of unpaused animation, which the wind follows
+    clock: f32,
+}
+
+impl Ambience {
+    pub fn new() -> Self {
+        Ambience { enabled: true, particles: Vec::new(), rng: StdRng::from_entropy(), last_frame: Instant::now(), clock: 0.0 }
+    }
+
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        self.particles.clear();
+    }
+
+    /// Tiles a second the wind is blowing, east when positive. It gusts and
+    /// turns over a few seconds.
+    fn wind(&self) -> f32 {
+        (self.clock / 8.0).sin() * 1.5 + (self.clock / 3.1).sin() * 0.5
+    }
+
+    /// Move everything along by the time since the last frame and let new
+    /// particles into `view`, in map tiles
+    pub fn update(&mut self, world: &World, tick: u64, paused: bool, view: Rect) {
+        let dt = self.last_frame.elapsed().as_secs_f32().min(0.25);
+        self.last_frame = Instant::now();
+        if !self.enabled || paused || view.is_empty() {
+            return;
+        }
+        self.clock += dt;
+
+        let wind = self.wind();
+        for p in &mut self.particles {
+            // Leaves ride the wind, rain leans into it, sparks barely feel it
+            let carried = match p.mote {
+                Mote::Leaf => 1.0,
+                Mote::Rain => 0.6,
+                Mote::Spark => 0.3,
+            };
+            p.x += (p.dx + wind * carried) * dt;
+            p.y += p.dy * dt;
+            p.ttl -= dt;
+        }
+        let (left, top) = (view.x as f32, view.y as f32);
+        let (right, bottom) = (left + view.width as f32, top + view.height as f32);
+        self.particles.retain(|p| p.ttl > 0.0 && p.x >= left && p.x < right && p.y >= top && p.y < bottom);
+
+        let (fx, fy) = world.campfire_pos;
+        if world.fire_lit() && view.contains((fx as u16, fy as u16).into()) {
+            for _ in 0..self.arrivals(SPARK_RATE * dt) {
+                let dx = self.rng.gen_range(-0.3..0.3);
+                let dy = self.rng.gen_range(-2.5..-1.5);
+                let ttl = self.rng.gen_range(0.6..1.2);
+                self.spawn(Particle { mote: Mote::Spark, x: fx as f32 + 0.5, y: fy as f32, dx, dy, ttl });
+            }
+        }
+
+        let shedding = match Season::of(tick, world.day_length) {
+            Season::Autumn => 1.0,
+            Season::Spring => 0.2,
+            Season::Summer => 0.1,
+            Season::Winter => 0.0,
+        };
+        let area = view.width as f32 * view.height as f32;
+        for _ in 0..self.arrivals(LEAF_RATE * shedding * area / 100.0 * dt) {
+            let x = self.rng.gen_range(view.left()..view.right()) as usize;
+            let y = self.rng.gen_range(view.top()..view.bottom()) as usize;
+            if matches!(world.get(x, y), Terrain::Tree | Terrain::FruitTree | Terrain::BareFruitTree) {
+                let dy = self.rng.gen_range(0.4..1.0);
+                let ttl = self.rng.gen_range(2.5..5.0);
+                self.spawn(Particle { mote: Mote::Leaf, x: x as f32 + 0.5, y: y as f32 + 0.5, dx: 0.0, dy, ttl });
+            }
+        }
+
+        if calendar::showery(tick, world.day_length) {
+            for _ in 0..self.arrivals(RAIN_RATE * view.width as f32 * dt) {
+                let x = self.rng.gen_range(left..right);
+                let y = self.rng.gen_range(top..bottom);
+                let ttl = self.rng.gen_range(0.1..0.3);
+                self.spawn(Particle { mote: Mote::Rain, x, y, dx: 0.0, dy: 12.0, ttl });
+            }
+        }
+    }
+
+    /// How many particles turn up this frame when `expected` are due on
+    /// average, so that slow rates still come through at a low frame rate
+    fn arrivals(&mut self, expected: f32) -> usize {
+        let whole = expected.floor();
+        whole as usize + usize::from(self.rng.gen_bool((expected - whole).clamp(0.0, 1.0) as f64))
+    }
+
+    fn spawn(&mut self, particle: Particle) {
+        if self.particles.len() < MAX_PARTICLES {
+            self.particles.push(particle);
+        }
+    }
+
+    /// What to draw over each tile of `view`, row by row
+    pub fn layer(&self, view: Rect) -> Vec<Option<(char, Mote)>> {
+        let mut layer = vec![None; view.width as usize * view.height as usize];
+        if !self.enabled {
+            return layer;
+        }
+        let wind = self.wind();
+        for p in &self.particles {
+            let (x, y) = (p.x as usize, p.y as usize);
+            if !view.contains((x as u16, y as u16).into()) {
+                continue;
+            }
+            let glyph = match p.mote {
+                // Leaves flutter as they fall, and sparks die down to embers
+                Mote::Leaf if ((p.ttl * 3.0) as u32).is_multiple_of(2) => ',',
+                Mote::Leaf => '\'',
+                Mote::Spark if p.ttl > 0.4 => '*',
+                Mote::Spark => '.',
+                Mote::Rain if wind > 0.5 => '\\',
+                Mote::Rain if wind < -0.5 => '/',
+                Mote::Rain => '|',
+            };
+            layer[(y - view.y as usize) * view.width as usize + x - view.x as usize] = Some((glyph, p.mote));
+        }
+        layer
+    }
+}
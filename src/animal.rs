@@ -1,34 +1,324 @@
+
 use rand::Rng;
 
-use crate::event::EventLog;
+use crate::bestiary::Deed;
+use crate::building::Building;
+use crate::calendar::DayPhase;
+use crate::combat::{Combatant, Weapon};
+use crate::event::{EventCategory, EventLog, Severity};
+use crate::herd::{HERD_SIZE, Herds};
+use crate::markers::{self, Mark};
+use crate::pack::{PACK_SIZE, Packs};
+use crate::pathfinding;
+use crate::spirits;
 use crate::world::{MAP_HEIGHT, MAP_WIDTH, Terrain, World};
 
-#[derive(Clone, Copy, PartialEq)]
+/// How far off a deer on the move notices an orc, the furthest of any animal
+pub const WARY_RADIUS: usize = 5;
+/// How far a grazing deer strays from its herd's anchor
+const HERD_SPREAD: usize = 3;
+
+/// How fast deer and boars get hungry and thirsty, each tick
+const HUNGER_RATE: f32 = 0.4;
+const THIRST_RATE: f32 = 0.6;
+/// Livestock lying about in the pens gets hungry slower, and is watered with its feed
+const PEN_HUNGER_RATE: f32 = 0.2;
+/// Past these an animal up and about goes looking for a bush or for water
+const HUNGRY: f32 = 50.0;
+const THIRSTY: f32 = 60.0;
+/// So thirsty it gets up to drink even when it should be bedded down
+const PARCHED: f32 = 90.0;
+/// How far off an animal knows where to find water, and spots a bush
+const WATER_SIGHT: usize = 30;
+const BROWSE_SIGHT: usize = 6;
+/// Thirst slaked by each tick at the water, and hunger by stripping a bush
+/// or by a tick cropping grass
+const DRINK: f32 = 8.0;
+const BROWSE: f32 = 40.0;
+const GRAZE: f32 = 1.5;
+/// How close an orc gets unnoticed to an animal with its head down drinking
+const DRINKING_WARINESS: usize = 2;
+
+/// How far off a prowling wolf picks up the scent of prey
+pub const PROWL_RADIUS: usize = 8;
+/// A wolf's health when unhurt
+const WOLF_HEALTH: f32 = 30.0;
+/// Below this health a wolf gives up the fight
+pub const WOLF_RETREAT: f32 = 12.0;
+/// Ticks a wolf leaves prey alone after a meal or a beating
+pub const WOLF_RESPITE: u64 = 300;
+/// Chance a wolf's bite lands, and the harm it does
+pub const BITE: Weapon = Weapon { chance: 0.6, damage: 8.0..15.0 };
+/// Wolves keep at least this far from a burning campfire
+const FIRE_FEAR: usize = 5;
+/// Most wolves roaming the map at once
+const MAX_WOLVES: usize = 4;
+/// Most nodes a wolf searches for a way round to its quarry before it gives
+/// up and just heads straight for it
+const WOLF_SEARCH: usize = 400;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum AnimalKind {
     Deer,
     Boar,
+    Wolf,
+    Rabbit,
+    Grouse,
+    Crow,
 }
 
+/// Everything that sets one kind of animal apart
+pub struct Stats {
+    pub name: &'static str,
+    pub symbol: char,
+    pub color: (u8, u8, u8),
+    /// Units of meat a kill leaves on the ground
+    pub meat: u32,
+    /// Health when unhurt
+    pub health: f32,
+    /// The parts of the day it's up and about; the rest it lies bedded down
+    pub active: &'static [DayPhase],
+    /// How far off it notices an orc while up and about and runs for it, or
+    /// None if it doesn't run
+    pub wary: Option<usize>,
+    /// Tiles it bolts each time it runs
+    pub bolt: usize,
+    /// Chance of wandering a tile each tick while up and about
+    pub roam: f64,
+    /// Odds that an orc who reaches it brings it down, up and about and bedded
+    pub catch: (f64, f64),
+    /// What it gores a hunter closing in with while it's up and about, landing
+    /// half as often when roused from its bed, if it fights back at all
+    pub gore: Option<Weapon>,
+    /// Whether a herder can bring one in alive to be kept in the pens
+    pub tameable: bool,
+    /// How often it turns up, against the other kinds
+    pub spawn_weight: u32,
+    /// Everything it can be seen doing, for the bestiary
+    pub ways: &'static [Behaviour],
+}
+
+/// Deer are crepuscular and skittish, and come in herds
+const DEER: Stats = Stats {
+    name: "Deer",
+    symbol: 'δ',
+    color: (180, 140, 80),
+    meat: 2,
+    health: 8.0,
+    active: &[DayPhase::Dawn, DayPhase::Dusk],
+    wary: Some(WARY_RADIUS),
+    bolt: 2,
+    roam: 0.4,
+    catch: (0.4, 0.9),
+    gore: None,
+    tameable: false,
+    spawn_weight: 40,
+    ways: &[Behaviour::Grazing, Behaviour::Browsing, Behaviour::Drinking, Behaviour::Sleeping, Behaviour::Fleeing],
+};
+
+/// Boars root about at night and stand their ground, and gore the hunters
+/// who close in on them
+const BOAR: Stats = Stats {
+    name: "Boar",
+    symbol: 'β',
+    color: (140, 100, 60),
+    meat: 4,
+    health: 20.0,
+    active: &[DayPhase::Night],
+    wary: None,
+    bolt: 2,
+    roam: 0.3,
+    catch: (0.3, 0.8),
+    gore: Some(Weapon { chance: 0.4, damage: 10.0..25.0 }),
+    tameable: true,
+    spawn_weight: 25,
+    ways: &[Behaviour::Grazing, Behaviour::Browsing, Behaviour::Drinking, Behaviour::Sleeping, Behaviour::Fighting, Behaviour::Carried, Behaviour::Penned],
+};
+
+/// Wolves hunt from dusk through the night, and nobody goes looking for one
+const WOLF: Stats = Stats {
+    name: "Wolf",
+    symbol: 'ω',
+    color: (150, 150, 165),
+    meat: 1,
+    health: WOLF_HEALTH,
+    active: &[DayPhase::Dusk, DayPhase::Night],
+    wary: None,
+    bolt: 2,
+    roam: 0.3,
+    catch: (0.0, 0.0),
+    gore: None,
+    tameable: false,
+    spawn_weight: 10,
+    ways: &[Behaviour::Roaming, Behaviour::Sleeping, Behaviour::Hunting, Behaviour::Fighting, Behaviour::Retreating],
+};
+
+/// Rabbits sit tight until an orc is nearly on them and then dart off, but
+/// there's little to them and any hunter can run one down
+const RABBIT: Stats = Stats {
+    name: "Rabbit",
+    symbol: 'ρ',
+    color: (200, 190, 170),
+    meat: 1,
+    health: 4.0,
+    active: &[DayPhase::Dawn, DayPhase::Dusk, DayPhase::Night],
+    wary: Some(3),
+    bolt: 3,
+    roam: 0.5,
+    catch: (0.6, 0.95),
+    gore: None,
+    tameable: false,
+    spawn_weight: 15,
+    ways: &[Behaviour::Grazing, Behaviour::Browsing, Behaviour::Drinking, Behaviour::Sleeping, Behaviour::Fleeing],
+};
+
+/// Grouse peck about by day and flush a long way when disturbed
+const GROUSE: Stats = Stats {
+    name: "Grouse",
+    symbol: 'γ',
+    color: (120, 130, 80),
+    meat: 1,
+    health: 4.0,
+    active: &[DayPhase::Day],
+    wary: Some(4),
+    bolt: 4,
+    roam: 0.4,
+    catch: (0.5, 0.9),
+    gore: None,
+    tameable: false,
+    spawn_weight: 10,
+    ways: &[Behaviour::Grazing, Behaviour::Browsing, Behaviour::Drinking, Behaviour::Sleeping, Behaviour::Fleeing],
+};
+
+/// Crows only ever turn up to scavenge, and fly off once there's nothing left
+const CROW: Stats = Stats {
+    name: "Crow",
+    symbol: 'ν',
+    color: (90, 90, 110),
+    meat: 0,
+    health: 4.0,
+    active: &[DayPhase::Dawn, DayPhase::Day, DayPhase::Dusk],
+    wary: Some(3),
+    bolt: 4,
+    roam: 0.5,
+    catch: (0.0, 0.0),
+    gore: None,
+    tameable: false,
+    spawn_weight: 0,
+    ways: &[Behaviour::Roaming, Behaviour::Scavenging, Behaviour::Sleeping, Behaviour::Fleeing],
+};
+
 impl AnimalKind {
-    pub fn symbol(&self) -> char {
-        match self {
-            AnimalKind::Deer => 'δ',
-            AnimalKind::Boar => 'β',
-        }
+    pub const ALL: [AnimalKind; 6] = [AnimalKind::Deer, AnimalKind::Boar, AnimalKind::Wolf, AnimalKind::Rabbit, AnimalKind::Grouse, AnimalKind::Crow];
+
+    /// The kind going by `name`, e.g. `deer` or `Wolf`
+    pub fn parse(name: &str) -> Option<AnimalKind> {
+        AnimalKind::ALL.into_iter().find(|k| k.name().eq_ignore_ascii_case(name))
     }
 
-    pub fn color(&self) -> ratatui::style::Color {
-        use ratatui::style::Color;
+    pub fn stats(&self) -> &'static Stats {
         match self {
-            AnimalKind::Deer => Color::Rgb(180, 140, 80),
-            AnimalKind::Boar => Color::Rgb(140, 100, 60),
+            AnimalKind::Deer => &DEER,
+            AnimalKind::Boar => &BOAR,
+            AnimalKind::Wolf => &WOLF,
+            AnimalKind::Rabbit => &RABBIT,
+            AnimalKind::Grouse => &GROUSE,
+            AnimalKind::Crow => &CROW,
         }
     }
 
+    pub fn symbol(&self) -> char {
+        self.stats().symbol
+    }
+
     pub fn name(&self) -> &str {
+        self.stats().name
+    }
+
+    /// Units of meat a kill leaves on the ground
+    pub fn meat_yield(&self) -> u32 {
+        self.stats().meat
+    }
+
+    pub fn is_active(&self, phase: DayPhase) -> bool {
+        self.stats().active.contains(&phase)
+    }
+
+    /// Whether orcs go hunting it for meat. Nobody goes looking for a wolf,
+    /// and there's nothing worth eating on a crow.
+    pub fn is_game(&self) -> bool {
+        !matches!(self, AnimalKind::Wolf | AnimalKind::Crow)
+    }
+
+    /// Whether it feeds off ripe carcasses
+    pub fn is_scavenger(&self) -> bool {
+        matches!(self, AnimalKind::Wolf | AnimalKind::Crow)
+    }
+
+    /// Any kind at all, as often as each turns up
+    fn roll(rng: &mut impl Rng) -> AnimalKind {
+        let total: u32 = AnimalKind::ALL.iter().map(|k| k.stats().spawn_weight).sum();
+        let mut pick = rng.gen_range(0..total);
+        for kind in AnimalKind::ALL {
+            if pick < kind.stats().spawn_weight {
+                return kind;
+            }
+            pick -= kind.stats().spawn_weight;
+        }
+        AnimalKind::Deer
+    }
+
+    /// Odds that an orc who reaches the animal brings it down
+    pub fn catch_chance(&self, phase: DayPhase) -> f64 {
+        let (active, bedded) = self.stats().catch;
+        if self.is_active(phase) { active } else { bedded }
+    }
+}
+
+/// What draws an animal along besides its own whims
+pub enum Lead {
+    Alone,
+    /// The quarry a prowling wolf has caught the scent of, or carrion a
+    /// scavenger is making for
+    Prey(usize, usize),
+    /// The anchor a deer's herd grazes around, or the leader a wolf's pack keeps about
+    Herd(usize, usize),
+    /// Danger the deer's herd is scattering from
+    Scatter(usize, usize),
+}
+
+/// What an animal was up to on its last turn, for the player to see
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Behaviour {
+    Grazing,
+    Roaming,
+    Sleeping,
+    Fleeing,
+    Drinking,
+    Browsing,
+    Hunting,
+    Scavenging,
+    Retreating,
+    Fighting,
+    Penned,
+    Carried,
+}
+
+impl Behaviour {
+    pub fn name(&self) -> &'static str {
         match self {
-            AnimalKind::Deer => "Deer",
-            AnimalKind::Boar => "Boar",
+            Behaviour::Grazing => "grazing",
+            Behaviour::Roaming => "roaming",
+            Behaviour::Sleeping => "sleeping",
+            Behaviour::Fleeing => "fleeing",
+            Behaviour::Drinking => "drinking",
+            Behaviour::Browsing => "browsing",
+            Behaviour::Hunting => "hunting",
+            Behaviour::Scavenging => "scavenging",
+            Behaviour::Retreating => "slinking off hurt",
+            Behaviour::Fighting => "fighting",
+            Behaviour::Penned => "penned",
+            Behaviour::Carried => "tied up and carried",
         }
     }
 }
@@ -37,7 +327,21 @@ pub struct Animal {
     pub kind: AnimalKind,
     pub x: usize,
     pub y: usize,
+    pub was: (usize, usize), // where it stood before the latest tick, for smoothing the map
     pub alive: bool,
+    pub claimed: bool, // an orc is already hunting this animal
+    pub health: f32, // hurt by hunters and wolves that don't kill it outright
+    pub rests_until: u64, // a wolf that has eaten or been beaten off leaves prey alone until then
+    pub herd: Option<u32>, // the herd a deer keeps with
+    pub pack: Option<u32>, // the pack a wolf hunts with
+    pub path: Vec<(usize, usize)>, // a wolf's way round to its quarry
+    pub hunger: f32,
+    pub thirst: f32,
+    pub drinking: bool, // at the water and staying until it has drunk its fill
+    pub tame: Option<f32>, // how tame a captive is, 0-100; None for a wild animal
+    pub carried_by: Option<String>, // the herder carrying a fresh captive to the pens
+    pub behaviour: Behaviour,
+    pub spotted: bool, // has come into view, and been counted in the bestiary
 }
 
 impl Animal {
@@ -46,119 +350,415 @@ impl Animal {
             kind,
             x,
             y,
+            was: (x, y),
             alive: true,
+            claimed: false,
+            health: kind.stats().health,
+            rests_until: 0,
+            herd: None,
+            pack: None,
+            path: Vec::new(),
+            hunger: 0.0,
+            thirst: 0.0,
+            drinking: false,
+            tame: None,
+            carried_by: None,
+            behaviour: Behaviour::Sleeping,
+            spotted: false,
         }
     }
 
-    pub fn spawn_initial(world: &World, rng: &mut impl Rng) -> Vec<Animal> {
-        let mut animals = Vec::new();
-        let count = rng.gen_range(8..13);
-        let (cx, cy) = world.campfire_pos;
+    /// The wildlife a new world starts with: `count` animals, or a handful
+    /// picked at random
+    pub fn spawn_initial(count: Option<usize>, world: &World, herds: &mut Herds, packs: &mut Packs, rng: &mut impl Rng) -> Vec<Animal> {
+        let count = count.unwrap_or_else(|| rng.gen_range(8..13));
+        Animal::spawn_herd(count, world, herds, packs, rng)
+    }
 
-        for _ in 0..count {
-            let kind = if rng.gen_bool(0.6) {
-                AnimalKind::Deer
-            } else {
-                AnimalKind::Boar
+    /// Scatter `count` animals across the map, away from camp. Deer turn up
+    /// a few at a time as a new herd, and wolves as a new pack.
+    pub fn spawn_herd(count: usize, world: &World, herds: &mut Herds, packs: &mut Packs, rng: &mut impl Rng) -> Vec<Animal> {
+        let mut animals = Vec::new();
+        while animals.len() < count {
+            let kind = AnimalKind::roll(rng);
+            let size = match kind {
+                AnimalKind::Deer => rng.gen_range(HERD_SIZE).min(count - animals.len()),
+                AnimalKind::Wolf => rng.gen_range(PACK_SIZE).min(count - animals.len()),
+                _ => 1,
             };
 
             // Spawn away from campfire (at least 15 tiles)
-            for _ in 0..100 {
-                let x = rng.gen_range(5..MAP_WIDTH - 5);
-                let y = rng.gen_range(5..MAP_HEIGHT - 5);
-                let dist = cx.abs_diff(x) + cy.abs_diff(y);
-                if dist > 15 && world.is_walkable(x, y) {
-                    animals.push(Animal::new(kind, x, y));
-                    break;
-                }
+            match wild_spot(15, 100, world, rng) {
+                Some((x, y)) if kind == AnimalKind::Deer => animals.extend(gather_herd(size, x, y, world, herds, rng)),
+                Some((x, y)) if kind == AnimalKind::Wolf => animals.extend(gather_pack(size, x, y, world, packs, rng)),
+                Some((x, y)) => animals.push(Animal::new(kind, x, y)),
+                None => break,
             }
         }
 
         animals
     }
 
-    pub fn update(&mut self, world: &World, orcs: &[(usize, usize)], rng: &mut impl Rng) {
+    /// `count` animals of a kind come in from the wilds together, deer as a
+    /// new herd and wolves as a new pack, somewhere well away from camp
+    pub fn arrive(kind: AnimalKind, count: usize, world: &World, herds: &mut Herds, packs: &mut Packs, rng: &mut impl Rng) -> Vec<Animal> {
+        let Some((x, y)) = wild_spot(20, 100, world, rng) else {
+            return Vec::new();
+        };
+        match kind {
+            AnimalKind::Deer => gather_herd(count, x, y, world, herds, rng),
+            AnimalKind::Wolf => gather_pack(count, x, y, world, packs, rng),
+            _ => gather(kind, count, x, y, world, rng),
+        }
+    }
+
+    /// `orcs` need only hold the living orcs within `WARY_RADIUS`. Returns
+    /// where the orc was if a deer spotted one and bolted, so its herd can
+    /// scatter too.
+    pub fn update(&mut self, world: &mut World, orcs: &[(usize, usize)], lead: Lead, rng: &mut impl Rng, phase: DayPhase, tick: u64) -> Option<(usize, usize)> {
         if !self.alive {
-            return;
-        }
-
-        // Deer flee from nearby orcs
-        if self.kind == AnimalKind::Deer {
-            if let Some((ox, oy)) = orcs.iter().find(|&&(ox, oy)| {
-                self.x.abs_diff(ox) + self.y.abs_diff(oy) <= 5
-            }) {
-                // Flee away from orc
-                let dx = (self.x as i32 - *ox as i32).signum();
-                let dy = (self.y as i32 - *oy as i32).signum();
-                let nx = (self.x as i32 + dx * 2).clamp(0, MAP_WIDTH as i32 - 1) as usize;
-                let ny = (self.y as i32 + dy * 2).clamp(0, MAP_HEIGHT as i32 - 1) as usize;
-                if world.is_walkable(nx, ny) {
-                    self.x = nx;
-                    self.y = ny;
+            return None;
+        }
+        // Livestock potters about the pens waiting to be fed, and goes where
+        // it's carried
+        if self.tame.is_some() {
+            self.behaviour = if self.carried_by.is_some() { Behaviour::Carried } else { Behaviour::Penned };
+            self.hunger = (self.hunger + PEN_HUNGER_RATE).min(100.0);
+            self.thirst = 0.0;
+            if self.carried_by.is_none() && rng.gen_bool(self.kind.stats().roam) {
+                self.step(rng.gen_range(-1..=1), rng.gen_range(-1..=1), world);
+            }
+            return None;
+        }
+        let active = self.kind.is_active(phase);
+        if self.kind.is_game() {
+            self.hunger = (self.hunger + HUNGER_RATE).min(100.0);
+            self.thirst = (self.thirst + THIRST_RATE).min(100.0);
+        }
+
+        // A wolf closes in on its prey, loping a little slower than an orc can
+        // run, or slinks off once it's hurt. A crow just flies straight there.
+        if let Lead::Prey(px, py) = lead {
+            if self.health < WOLF_RETREAT {
+                self.behaviour = Behaviour::Retreating;
+                self.path.clear();
+                self.flee_from(px, py, world);
+            } else if self.kind == AnimalKind::Crow {
+                self.behaviour = Behaviour::Scavenging;
+                self.approach(px, py, world, rng);
+            } else {
+                self.behaviour = Behaviour::Hunting;
+                if rng.gen_bool(0.8) {
+                    self.stalk(px, py, world, rng);
                 }
-                return;
             }
+            return None;
         }
+        self.path.clear();
 
-        // Random wander (boars move less often)
-        let move_chance = match self.kind {
-            AnimalKind::Deer => 0.4,
-            AnimalKind::Boar => 0.2,
+        // Skittish animals flee from nearby orcs; one bedded down, or
+        // drinking, only notices them up close
+        if let Some(wary) = self.kind.stats().wary {
+            let wariness = if active && !self.drinking { wary } else { wary.min(DRINKING_WARINESS) };
+            if let Some(&(ox, oy)) = orcs.iter().find(|&&(ox, oy)| self.x.abs_diff(ox) + self.y.abs_diff(oy) <= wariness) {
+                self.behaviour = Behaviour::Fleeing;
+                self.flee_from(ox, oy, world);
+                return Some((ox, oy));
+            }
+        }
+
+        // Random wander, at each kind's own pace; nothing roams far while bedded
+        let move_chance = if active { self.kind.stats().roam } else { 0.05 };
+        self.behaviour = if !active {
+            Behaviour::Sleeping
+        } else if self.kind.is_game() {
+            Behaviour::Grazing
+        } else {
+            Behaviour::Roaming
         };
 
-        if rng.gen_bool(move_chance) {
-            let dx = rng.gen_range(-1..=1i32);
-            let dy = rng.gen_range(-1..=1i32);
-            let nx = (self.x as i32 + dx).clamp(0, MAP_WIDTH as i32 - 1) as usize;
-            let ny = (self.y as i32 + dy).clamp(0, MAP_HEIGHT as i32 - 1) as usize;
-            if world.is_walkable(nx, ny) {
+        // A panicked deer runs before it thinks of eating or drinking
+        if !matches!(lead, Lead::Scatter(..)) && self.see_to_needs(world, rng, active, tick) {
+            return None;
+        }
+
+        match lead {
+            Lead::Scatter(ox, oy) => {
+                self.behaviour = Behaviour::Fleeing;
+                self.scatter_from(ox, oy, world, rng);
+            }
+            // Stragglers hurry back to the herd, faster than it drifts
+            Lead::Herd(hx, hy) if self.x.abs_diff(hx).max(self.y.abs_diff(hy)) > HERD_SPREAD => {
+                if rng.gen_bool(if active { 0.6 } else { 0.1 }) {
+                    self.approach(hx, hy, world, rng);
+                }
+            }
+            Lead::Herd(hx, hy) => {
+                if rng.gen_bool(move_chance) {
+                    let dx = rng.gen_range(-1..=1i32);
+                    let dy = rng.gen_range(-1..=1i32);
+                    let (nx, ny) = (self.x as i32 + dx, self.y as i32 + dy);
+                    if nx.abs_diff(hx as i32).max(ny.abs_diff(hy as i32)) as usize <= HERD_SPREAD {
+                        self.step(dx, dy, world);
+                    }
+                }
+            }
+            Lead::Alone | Lead::Prey(..) => {
+                if rng.gen_bool(move_chance) {
+                    let dx = rng.gen_range(-1..=1i32);
+                    let dy = rng.gen_range(-1..=1i32);
+                    self.step(dx, dy, world);
+                }
+            }
+        }
+        None
+    }
+
+    /// Drink or eat if it's time to, walking off to water or a bush first.
+    /// Returns true if that took the animal's turn. Deer and boars crop the
+    /// grass as they go, but only a bush fills them up.
+    fn see_to_needs(&mut self, world: &mut World, rng: &mut impl Rng, active: bool, tick: u64) -> bool {
+        if !self.kind.is_game() {
+            return false;
+        }
+        if self.drinking || self.thirst >= PARCHED || (active && self.thirst >= THIRSTY) {
+            if world.nearest_within(self.x, self.y, 1, Terrain::Water).is_some() {
+                self.thirst = (self.thirst - DRINK).max(0.0);
+                self.drinking = self.thirst > 0.0;
+                self.behaviour = Behaviour::Drinking;
+                return true;
+            }
+            self.drinking = false;
+            if let Some((wx, wy)) = world.nearest_within(self.x, self.y, WATER_SIGHT, Terrain::Water) {
+                self.approach(wx, wy, world, rng);
+                self.behaviour = Behaviour::Drinking;
+                return true;
+            }
+        }
+        if !active {
+            return false;
+        }
+        if self.hunger >= HUNGRY {
+            if let Some((bx, by)) = world.nearest_within(self.x, self.y, 1, Terrain::Bush) {
+                world.browse_bush(bx, by, tick);
+                self.hunger = (self.hunger - BROWSE).max(0.0);
+                self.behaviour = Behaviour::Browsing;
+                return true;
+            }
+            if let Some((bx, by)) = world.nearest_within(self.x, self.y, BROWSE_SIGHT, Terrain::Bush) {
+                self.approach(bx, by, world, rng);
+                self.behaviour = Behaviour::Browsing;
+                return true;
+            }
+        }
+        if world.get(self.x, self.y) == Terrain::Grass {
+            self.hunger = (self.hunger - GRAZE).max(0.0);
+        }
+        false
+    }
+
+    /// Take a step towards (x, y), or any way at all if that's blocked
+    fn approach(&mut self, x: usize, y: usize, world: &World, rng: &mut impl Rng) {
+        let dx = (x as i32 - self.x as i32).signum();
+        let dy = (y as i32 - self.y as i32).signum();
+        if !self.step(dx, dy, world) {
+            self.step(rng.gen_range(-1..=1), rng.gen_range(-1..=1), world);
+        }
+    }
+
+    /// Take a step along a way round to (x, y), working out a fresh one
+    /// whenever the quarry has moved off the end of the last. With no way
+    /// round to be found, it heads straight for it.
+    fn stalk(&mut self, x: usize, y: usize, world: &World, rng: &mut impl Rng) {
+        let stale = self.path.last().is_none_or(|&(gx, gy)| gx.abs_diff(x).max(gy.abs_diff(y)) > 1);
+        if stale {
+            self.path = pathfinding::find_wildlife_path(world, self.x, self.y, x, y, self.kind == AnimalKind::Wolf, WOLF_SEARCH)
+                .unwrap_or_default();
+        }
+        match self.path.first().copied() {
+            Some((nx, ny)) if self.step(nx as i32 - self.x as i32, ny as i32 - self.y as i32, world) => {
+                self.path.remove(0);
+            }
+            _ => {
+                self.path.clear();
+                self.approach(x, y, world, rng);
+            }
+        }
+    }
+
+    /// What it gores a hunter closing in with, if it fights back
+    pub fn tusks(&self, phase: DayPhase) -> Option<Weapon> {
+        let tusks = self.kind.stats().gore.as_ref()?;
+        let chance = if self.kind.is_active(phase) { tusks.chance } else { tusks.chance / 2.0 };
+        Some(Weapon { chance, damage: tusks.damage.clone() })
+    }
+
+    /// Bolt directly away from (ox, oy), as far as its kind runs at a go
+    pub fn flee_from(&mut self, ox: usize, oy: usize, world: &World) {
+        let dx = (self.x as i32 - ox as i32).signum();
+        let dy = (self.y as i32 - oy as i32).signum();
+        for _ in 0..self.kind.stats().bolt {
+            if !self.step(dx, dy, world) {
+                break;
+            }
+        }
+    }
+
+    /// Bolt away from (ox, oy) like `flee_from`, but veering off at random so
+    /// that a herd breaks up in every direction
+    fn scatter_from(&mut self, ox: usize, oy: usize, world: &World, rng: &mut impl Rng) {
+        let away = ((self.x as i32 - ox as i32).signum(), (self.y as i32 - oy as i32).signum());
+        let dx = (away.0 + rng.gen_range(-1..=1)).clamp(-1, 1);
+        let dy = (away.1 + rng.gen_range(-1..=1)).clamp(-1, 1);
+        let (dx, dy) = if (dx, dy) == (0, 0) { away } else { (dx, dy) };
+        if self.step(dx, dy, world) {
+            self.step(dx, dy, world);
+        }
+    }
+
+    /// Move one tile unless a wall, a building or rough ground is in the way,
+    /// or for a wolf, the light of the campfire. Livestock never leaves the pens.
+    fn step(&mut self, dx: i32, dy: i32, world: &World) -> bool {
+        let nx = (self.x as i32 + dx).clamp(0, MAP_WIDTH as i32 - 1) as usize;
+        let ny = (self.y as i32 + dy).clamp(0, MAP_HEIGHT as i32 - 1) as usize;
+        if self.tame.is_some() {
+            let penned = world.get(nx, ny) == Terrain::Built(Building::Pen);
+            if penned {
                 self.x = nx;
                 self.y = ny;
             }
+            return penned;
+        }
+        if !world.is_open_to_wildlife(nx, ny) || world.cuts_corner(self.x, self.y, nx, ny, Terrain::open_to_wildlife) {
+            return false;
+        }
+        if self.kind == AnimalKind::Wolf && world.fire_lit() && near_fire(world, nx, ny) {
+            return false;
         }
+        self.x = nx;
+        self.y = ny;
+        true
     }
 
-    pub fn kill(&mut self, world: &mut World, log: &mut EventLog, tick: u64) {
+    /// Bring the animal down, leaving its carcass to be butchered. Returns
+    /// where the carcass lies, if there was room for it.
+    pub fn kill(&mut self, world: &mut World, log: &mut EventLog, tick: u64) -> Option<(usize, usize)> {
         self.alive = false;
-        // Drop food (meat) at the animal's position
-        if world.get(self.x, self.y) == Terrain::Grass {
-            world.set(self.x, self.y, Terrain::Food);
+        let meat = self.kind.meat_yield();
+        let carcass = world.lay_carcass(self.x, self.y, self.kind, meat, tick);
+        world.record(self.kind, Deed::Killed);
+        if meat >= markers::BIG_GAME {
+            world.markers.note(Mark::Hunt, self.x, self.y, tick, format!("A {} brought down", self.kind.name().to_lowercase()));
         }
-        log.log(
+        world.spirits.offend(spirits::HUNT_OFFENCE);
+        log.log_at(
             tick,
-            format!("A {} was hunted!", self.kind.name()),
-            ratatui::style::Color::Rgb(180, 140, 80),
+            EventCategory::Hunt,
+            format!("A {} was hunted! ({} meat)", self.kind.name(), meat),
+            Severity::Info,
+            (self.x, self.y),
         );
+        carcass
     }
 }
 
-pub fn try_respawn(animals: &mut Vec<Animal>, world: &World, rng: &mut impl Rng, tick: u64) {
+impl Combatant for Animal {
+    fn health(&self) -> f32 {
+        self.health
+    }
+
+    fn max_health(&self) -> f32 {
+        self.kind.stats().health
+    }
+
+    /// Whatever kills it lays the carcass, as only it knows whose kill it is
+    fn hurt(&mut self, damage: f32, _tick: u64) {
+        self.health -= damage;
+        if self.health <= 0.0 {
+            self.alive = false;
+        }
+    }
+
+    /// Game runs the moment it's hurt; a wolf only once it's badly hurt
+    fn breaks(&self) -> bool {
+        self.kind != AnimalKind::Wolf || self.health < WOLF_RETREAT
+    }
+}
+
+/// Up to `size` animals of a kind, on open ground around (x, y)
+fn gather(kind: AnimalKind, size: usize, x: usize, y: usize, world: &World, rng: &mut impl Rng) -> Vec<Animal> {
+    let mut animals = Vec::new();
+    for _ in 0..size {
+        let spot = (0..10)
+            .map(|_| ((x as i32 + rng.gen_range(-2..=2)) as usize, (y as i32 + rng.gen_range(-2..=2)) as usize))
+            .find(|&(dx, dy)| world.is_open_to_wildlife(dx, dy));
+        if let Some((dx, dy)) = spot {
+            animals.push(Animal::new(kind, dx, dy));
+        }
+    }
+    animals
+}
+
+/// Up to `size` deer of a new herd, on open ground around (x, y)
+fn gather_herd(size: usize, x: usize, y: usize, world: &World, herds: &mut Herds, rng: &mut impl Rng) -> Vec<Animal> {
+    let herd = herds.form(x, y, world, rng);
+    gather(AnimalKind::Deer, size, x, y, world, rng).into_iter().map(|deer| Animal { herd: Some(herd), ..deer }).collect()
+}
+
+/// Up to `size` wolves of a new pack, on open ground around (x, y)
+fn gather_pack(size: usize, x: usize, y: usize, world: &World, packs: &mut Packs, rng: &mut impl Rng) -> Vec<Animal> {
+    let pack = packs.form();
+    gather(AnimalKind::Wolf, size, x, y, world, rng).into_iter().map(|wolf| Animal { pack: Some(pack), ..wolf }).collect()
+}
+
+pub fn try_respawn(animals: &mut Vec<Animal>, herds: &mut Herds, packs: &mut Packs, world: &World, rng: &mut impl Rng, tick: u64, cap: usize) {
     // Respawn every ~200 ticks if population is low
-    if tick % 200 != 0 {
+    if !tick.is_multiple_of(200) {
         return;
     }
 
     let alive_count = animals.iter().filter(|a| a.alive).count();
-    if alive_count >= 12 {
+    if alive_count >= cap {
         return;
     }
 
-    let (cx, cy) = world.campfire_pos;
     let spawn_count = rng.gen_range(1..=3);
+    let mut deer = 0;
     for _ in 0..spawn_count {
-        let kind = if rng.gen_bool(0.6) {
-            AnimalKind::Deer
-        } else {
-            AnimalKind::Boar
+        let wolves = animals.iter().filter(|a| a.alive && a.kind == AnimalKind::Wolf).count();
+        let kind = match AnimalKind::roll(rng) {
+            AnimalKind::Wolf if wolves >= MAX_WOLVES => AnimalKind::Deer,
+            kind => kind,
         };
-        for _ in 0..50 {
-            let x = rng.gen_range(5..MAP_WIDTH - 5);
-            let y = rng.gen_range(5..MAP_HEIGHT - 5);
-            let dist = cx.abs_diff(x) + cy.abs_diff(y);
-            if dist > 20 && world.is_walkable(x, y) {
-                animals.push(Animal::new(kind, x, y));
-                break;
+        if kind == AnimalKind::Deer {
+            deer += 1;
+        } else if let Some((x, y)) = wild_spot(20, 50, world, rng) {
+            match kind {
+                AnimalKind::Wolf => {
+                    let size = rng.gen_range(PACK_SIZE).min(MAX_WOLVES - wolves);
+                    animals.extend(gather_pack(size, x, y, world, packs, rng));
+                }
+                _ => animals.push(Animal::new(kind, x, y)),
             }
         }
     }
+    // Deer that turn up together come as one new herd
+    if deer > 0 && let Some((x, y)) = wild_spot(20, 50, world, rng) {
+        animals.extend(gather_herd(deer, x, y, world, herds, rng));
+    }
+}
+
+/// Open ground more than `berth` tiles from the campfire, if any turns up in
+/// `tries` random picks
+fn wild_spot(berth: usize, tries: usize, world: &World, rng: &mut impl Rng) -> Option<(usize, usize)> {
+    let (cx, cy) = world.campfire_pos;
+    (0..tries)
+        .map(|_| (rng.gen_range(5..MAP_WIDTH - 5), rng.gen_range(5..MAP_HEIGHT - 5)))
+        .find(|&(x, y)| cx.abs_diff(x) + cy.abs_diff(y) > berth && world.is_open_to_wildlife(x, y))
+}
+
+/// Within the ring of light a burning campfire throws, which wolves won't enter
+pub fn near_fire(world: &World, x: usize, y: usize) -> bool {
+    let (cx, cy) = world.campfire_pos;
+    x.abs_diff(cx).max(y.abs_diff(cy)) < FIRE_FEAR
 }
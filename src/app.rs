@@ -1,189 +1,290 @@
-use rand::rngs::ThreadRng;
-use rand::Rng;
-
-use crate::animal::{self, Animal};
-use crate::event::EventLog;
-use crate::orc::{self, Orc};
-use crate::world::{MAP_HEIGHT, MAP_WIDTH, Terrain, World};
+use std::env;
+use std::time::Instant;
+
+use crate::ambience::Ambience;
+use crate::animal::Animal;
+use crate::building::Building;
+use crate::commands::DebugCommand;
+use crate::designation::Designation;
+use crate::expedition::Venture;
+use crate::export;
+use crate::event::{Event, EventCategory, Severity};
+use crate::help;
+use crate::input::{Command, InputState};
+use crate::orc::{Activity, Purpose};
+use crate::mouse::{Gesture, Mouse};
+use crate::perf::PerfStats;
+use crate::scheduler::Speed;
+use crate::sim::{MAX_DROP, Sim};
+use crate::spatial::Entity;
+use crate::term::{GlyphMode, TermCaps};
+use crate::theme::Theme;
+use crate::tool::ToolKind;
+use crate::tutorial::Tutorial;
+use crate::view::ViewMode;
+use crate::world::{MAP_HEIGHT, MAP_WIDTH};
+use crate::zoom::Zoom;
+
+/// Longest name the player can give an orc or the clan
+const MAX_NAME: usize = 20;
+/// Columns the sidebar starts at, can be narrowed to and widened to, and
+/// steps by
+const SIDEBAR_WIDTH: u16 = 32;
+const SIDEBAR_WIDTHS: std::ops::RangeInclusive<u16> = 20..=80;
+const SIDEBAR_STEP: u16 = 4;
+/// Rows the event log starts at, can be shrunk to and grown to, borders
+/// and all, and steps by
+const LOG_HEIGHT: u16 = 10;
+const LOG_HEIGHTS: std::ops::RangeInclusive<u16> = 4..=30;
+const LOG_STEP: u16 = 2;
+
+/// Whether the keys are working the game or the pause menu over it
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Screen {
+    #[default]
+    Playing,
+    /// The pause menu, with the item picked out
+    Menu(usize),
+    /// The menu's settings page, with the setting picked out
+    Settings(usize),
+}
 
-const MAX_CLAN_SIZE: usize = 15;
+/// What the name typed at the naming prompt is for
+#[derive(Clone, PartialEq, Debug)]
+pub enum Naming {
+    /// The orc called this, which keeps to it as others die and move up
+    Orc(String),
+    Clan,
+}
 
 pub struct App {
-    pub world: World,
-    pub orcs: Vec<Orc>,
-    pub animals: Vec<Animal>,
-    pub event_log: EventLog,
-    pub tick: u64,
-    pub paused: bool,
-    pub speed: u32,
+    /// The game itself; everything else here is how it's shown and played
+    pub sim: Sim,
+    pub speed: Speed,
+    /// Whether the world runs as fast as it'll go, drawn only now and then
+    pub turbo: bool,
     pub cursor_x: usize,
     pub cursor_y: usize,
     pub camera_x: usize,
     pub camera_y: usize,
+    /// Width and height in tiles of the map as last drawn; nothing is in
+    /// view until it has been
+    viewport: (usize, usize),
     pub selected_orc: Option<usize>,
+    /// Order the cursor places while designation mode is on
+    pub designating: Option<Designation>,
+    /// First end of a wall line waiting for the second
+    pub wall_start: Option<(usize, usize)>,
+    pub caps: TermCaps,
+    /// Built-in theme first, then any loaded from the config directory
+    pub themes: Vec<Theme>,
+    theme: usize,
+    pub perf: PerfStats,
+    pub show_perf: bool,
+    pub show_minimap: bool,
+    /// Whether what the cursor is over is told in a box beside it
+    pub show_tooltip: bool,
+    /// Whether the legend to the marks left on the map is up
+    pub show_legend: bool,
+    /// Whether the cursor is picking out where to send the selected orc
+    pub ordering: bool,
+    /// How far the clock has got towards the next tick, from 0 to 1, for
+    /// fading out the tiles orcs and animals have just left
+    pub tick_progress: f32,
+    /// Whether the sidebar and the event log are up, and how big they are;
+    /// with both down, the map fills the screen
+    pub show_sidebar: bool,
+    pub show_log: bool,
+    pub sidebar_width: u16,
+    pub log_height: u16,
+    /// How far out the map is zoomed
+    pub zoom: Zoom,
+    pub ambience: Ambience,
+    /// What the orcs on the map are colored by
+    pub view: ViewMode,
+    /// Lessons still to come, when playing the tutorial
+    pub tutorial: Option<Tutorial>,
+    /// Whether the ventures on offer at the campfire are up on screen
+    pub campfire_menu: bool,
+    /// The amount typed so far while asking how much food to drop, if asking
+    pub food_prompt: Option<String>,
+    /// The debug command typed so far at the `:` prompt, while it's open
+    pub console: Option<String>,
+    /// What's being named and the name typed so far, while asking for one
+    pub naming: Option<(Naming, String)>,
+    /// The name the player has given the clan, if any
+    pub clan_name: Option<String>,
+    /// Whether the bestiary is up on screen
+    pub show_bestiary: bool,
+    /// Whether the graphs of how the clan has fared day by day are up on screen
+    pub show_graphs: bool,
+    /// Whether the help screen is up, and how many lines down it's scrolled
+    pub show_help: bool,
+    pub help_scroll: u16,
+    /// Whether the selected orc's details are up on screen
+    pub inspecting: bool,
+    pub input: InputState,
+    pub mouse: Mouse,
+    /// Events the log is scrolled back from the latest
+    pub log_scroll: usize,
+    /// The number of the event picked out in the log, while picking one to
+    /// jump to where it happened
+    pub log_pick: Option<u64>,
     pub should_quit: bool,
-    rng: ThreadRng,
+    /// The pause menu, while it's up the world stands still
+    pub screen: Screen,
+    /// How the last save or load from the menu went, shown under it
+    pub menu_note: Option<String>,
 }
 
 impl App {
-    pub fn new() -> Self {
-        let mut rng = rand::thread_rng();
-        let world = World::generate(&mut rng);
-        let orcs = Orc::spawn_clan(5, &world, &mut rng);
-        let animals = Animal::spawn_initial(&world, &mut rng);
-        let mut event_log = EventLog::new();
-
-        event_log.log(0, "A clan of orcs settles in a new land...".to_string(), ratatui::style::Color::White);
-        for orc in &orcs {
-            event_log.log(0, format!("{} joins the clan", orc.name), ratatui::style::Color::Green);
-        }
-
-        let (cx, cy) = world.campfire_pos;
-
+    /// Play `sim`, looking out from the campfire
+    pub fn new(sim: Sim) -> Self {
+        let (cx, cy) = sim.world.campfire_pos;
         App {
-            world,
-            orcs,
-            animals,
-            event_log,
-            tick: 0,
-            paused: false,
-            speed: 1,
+            sim,
+            speed: Speed::default(),
+            turbo: false,
             cursor_x: cx,
             cursor_y: cy,
             camera_x: 0,
             camera_y: 0,
+            viewport: (0, 0),
             selected_orc: None,
+            designating: None,
+            wall_start: None,
+            caps: TermCaps::detect(),
+            themes: Theme::built_in(),
+            theme: 0,
+            perf: PerfStats::default(),
+            show_perf: false,
+            show_minimap: false,
+            show_tooltip: true,
+            show_legend: false,
+            ordering: false,
+            tick_progress: 0.0,
+            show_sidebar: true,
+            show_log: true,
+            sidebar_width: SIDEBAR_WIDTH,
+            log_height: LOG_HEIGHT,
+            zoom: Zoom::default(),
+            ambience: Ambience::new(),
+            view: ViewMode::default(),
+            tutorial: None,
+            campfire_menu: false,
+            food_prompt: None,
+            naming: None,
+            clan_name: None,
+            console: None,
+            show_bestiary: false,
+            show_help: false,
+            help_scroll: 0,
+            show_graphs: false,
+            inspecting: false,
+            input: InputState::default(),
+            mouse: Mouse::default(),
+            log_scroll: 0,
+            log_pick: None,
             should_quit: false,
-            rng,
+            screen: Screen::Playing,
+            menu_note: None,
         }
     }
 
-    pub fn is_night(&self) -> bool {
-        let time_of_day = self.tick % 100;
-        time_of_day >= 60
-    }
-
+    /// Move the world on a tick, and keep what's shown of it in step
     pub fn tick(&mut self) {
-        if self.paused {
+        if self.sim.paused {
             return;
         }
-
-        self.tick += 1;
-
-        // Day/night transition messages
-        let time_of_day = self.tick % 100;
-        if time_of_day == 0 {
-            let day = self.tick / 100 + 1;
-            self.event_log.log(self.tick, format!("=== Day {} begins ===", day), ratatui::style::Color::White);
-        } else if time_of_day == 60 {
-            self.event_log.log(self.tick, "Night falls...".to_string(), ratatui::style::Color::Blue);
-        }
-
-        let is_night = self.is_night();
-
-        // Update animals
-        let orc_positions: Vec<(usize, usize)> = self.orcs.iter()
-            .filter(|o| o.alive)
-            .map(|o| (o.x, o.y))
-            .collect();
-        for animal in &mut self.animals {
-            animal.update(&self.world, &orc_positions, &mut self.rng);
-        }
-
-        // Update each orc
-        let num_orcs = self.orcs.len();
-        for i in 0..num_orcs {
-            let mut orc = std::mem::replace(&mut self.orcs[i], Orc::new(String::new(), 0, 0));
-            orc.update(&mut self.world, &mut self.animals, &mut self.rng, &mut self.event_log, self.tick, is_night);
-            self.orcs[i] = orc;
-        }
-
-        // Remove dead orcs after a few ticks (show tombstone briefly)
-        self.orcs.retain(|orc| {
-            if !orc.alive {
-                if let Some(death_tick) = orc.death_tick {
-                    return self.tick - death_tick < 20; // keep tombstone for 20 ticks
-                }
-            }
-            true
-        });
+        let started = Instant::now();
+        self.sim.tick();
 
         // Fix selected_orc index if orcs were removed
-        if let Some(idx) = self.selected_orc {
-            if idx >= self.orcs.len() {
-                self.selected_orc = if self.orcs.is_empty() { None } else { Some(self.orcs.len() - 1) };
-            }
+        if let Some(idx) = self.selected_orc && idx >= self.sim.orcs.len() {
+            self.selected_orc = if self.sim.orcs.is_empty() { None } else { Some(self.sim.orcs.len() - 1) };
         }
 
-        // Remove dead animals
-        self.animals.retain(|a| a.alive);
+        // An orc that died while being renamed takes the prompt with it
+        if let Some((Naming::Orc(name), _)) = &self.naming
+            && self.living_orc(name).is_none()
+        {
+            self.naming = None;
+        }
 
-        // Animal respawn
-        animal::try_respawn(&mut self.animals, &self.world, &mut self.rng, self.tick);
+        // Note in the bestiary what's on screen
+        self.watch_wildlife();
 
-        // Bush regrowth
-        self.world.tick_regrowth(self.tick);
+        let budget = &self.sim.world.path_budget;
+        self.perf.path_searches = budget.searches();
+        self.perf.path_nodes = budget.nodes();
+        self.perf.paths_deferred = budget.deferred();
+        self.perf.record_tick(started.elapsed());
+    }
 
-        // Birth system - check every 300 ticks
-        if self.tick % 300 == 0 {
-            self.check_birth();
+    /// Note in the bestiary the animals the player can see on the map and
+    /// what they're up to
+    fn watch_wildlife(&mut self) {
+        let view = self.in_view();
+        for achievement in self.sim.bestiary.watch(&mut self.sim.animals, view) {
+            self.sim.event_log.log(self.sim.tick, achievement.describe(), Severity::Good);
         }
+    }
 
-        // Game over if all orcs are gone
-        if self.orcs.is_empty() {
-            self.event_log.log(self.tick, "The clan has perished...".to_string(), ratatui::style::Color::Red);
-            self.paused = true;
+    /// Move the cursor by (dx, dy) characters on the map, however far out it's zoomed
+    pub fn move_cursor(&mut self, dx: i32, dy: i32) {
+        let scale = self.zoom.scale() as i32;
+        let (dx, dy) = (dx * scale, dy * scale);
+        let nx = (self.cursor_x as i32 + dx).clamp(0, MAP_WIDTH as i32 - 1) as usize;
+        let ny = (self.cursor_y as i32 + dy).clamp(0, MAP_HEIGHT as i32 - 1) as usize;
+        self.cursor_x = nx;
+        self.cursor_y = ny;
+        // In designation mode the cursor paints its order onto every tile it passes
+        if let Some(order) = self.designating {
+            self.sim.world.mark(nx, ny, order);
         }
     }
 
-    fn check_birth(&mut self) {
-        let living: Vec<&Orc> = self.orcs.iter().filter(|o| o.alive).collect();
-        let count = living.len();
-
-        if count < 2 || count >= MAX_CLAN_SIZE {
-            return;
-        }
+    pub fn move_cursor_to(&mut self, x: usize, y: usize) {
+        self.cursor_x = x.min(MAP_WIDTH - 1);
+        self.cursor_y = y.min(MAP_HEIGHT - 1);
+    }
 
-        let avg_hunger: f32 = living.iter().map(|o| o.hunger).sum::<f32>() / count as f32;
-        let avg_energy: f32 = living.iter().map(|o| o.energy).sum::<f32>() / count as f32;
-
-        // Birth conditions: well-fed, rested, have stockpile
-        if avg_hunger < 40.0 && avg_energy > 40.0 && self.world.food_stockpile > 0 {
-            self.world.food_stockpile -= 1;
-
-            let existing_names: Vec<String> = self.orcs.iter().map(|o| o.name.clone()).collect();
-            let name = orc::pick_name(&mut self.rng, &existing_names);
-
-            let (cx, cy) = self.world.campfire_pos;
-            let mut x = cx;
-            let mut y = cy;
-            for _ in 0..20 {
-                let nx = (cx as i32 + self.rng.gen_range(-2..=2)).clamp(0, MAP_WIDTH as i32 - 1) as usize;
-                let ny = (cy as i32 + self.rng.gen_range(-2..=2)).clamp(0, MAP_HEIGHT as i32 - 1) as usize;
-                if self.world.is_walkable(nx, ny) {
-                    x = nx;
-                    y = ny;
-                    break;
+    /// Act on what the player did with the mouse: a click moves the cursor
+    /// and picks out the orc clicked on, dragging the map pans the view, and
+    /// the wheel scrolls the event log
+    pub fn gesture(&mut self, gesture: Gesture) {
+        match gesture {
+            Gesture::Click { x, y } => {
+                self.move_cursor_to(x, y);
+                let clicked = self.sim.entities_at(self.cursor_x, self.cursor_y).find_map(|entity| match entity {
+                    Entity::Orc(i) if self.sim.orcs[i].alive => Some(i),
+                    _ => None,
+                });
+                if clicked.is_some() {
+                    self.selected_orc = clicked;
                 }
             }
-
-            self.event_log.log(
-                self.tick,
-                format!("{} is born into the clan!", name),
-                ratatui::style::Color::LightGreen,
-            );
-            self.orcs.push(Orc::new(name, x, y));
+            // The view stays centered on the cursor, so dragging the map one
+            // way takes the cursor the other
+            Gesture::Pan { dx, dy } => {
+                let x = (self.cursor_x as i32 - dx).max(0) as usize;
+                let y = (self.cursor_y as i32 - dy).max(0) as usize;
+                self.move_cursor_to(x, y);
+            }
+            Gesture::Scroll(n) => self.scroll_log(n),
         }
     }
 
-    pub fn move_cursor(&mut self, dx: i32, dy: i32) {
-        let nx = (self.cursor_x as i32 + dx).clamp(0, MAP_WIDTH as i32 - 1) as usize;
-        let ny = (self.cursor_y as i32 + dy).clamp(0, MAP_HEIGHT as i32 - 1) as usize;
-        self.cursor_x = nx;
-        self.cursor_y = ny;
+    /// The animal the cursor is over, if any
+    pub fn animal_at_cursor(&self) -> Option<&Animal> {
+        self.sim.animals.iter().find(|a| a.alive && a.x == self.cursor_x && a.y == self.cursor_y)
     }
 
-    pub fn update_camera(&mut self, viewport_w: usize, viewport_h: usize) {
+    /// Keep the cursor in the middle of a map `cells_w` by `cells_h` characters
+    pub fn update_camera(&mut self, cells_w: usize, cells_h: usize) {
+        let scale = self.zoom.scale();
+        let (viewport_w, viewport_h) = (cells_w * scale, cells_h * scale);
+        self.viewport = (viewport_w, viewport_h);
         let half_w = viewport_w / 2;
         let half_h = viewport_h / 2;
 
@@ -202,26 +303,68 @@ impl App {
         } else {
             self.cursor_y - half_h
         };
+        // Zoomed out, blocks start on whole multiples of the scale so they
+        // don't shift about as the cursor moves
+        self.camera_x -= self.camera_x % scale;
+        self.camera_y -= self.camera_y % scale;
+    }
+
+    /// The part of the map in view as last drawn: left, top, width and height
+    pub fn in_view(&self) -> (usize, usize, usize, usize) {
+        (self.camera_x, self.camera_y, self.viewport.0, self.viewport.1)
+    }
+
+    /// Pop up the next tutorial lesson if its moment has come, pausing the game for it
+    pub fn advance_tutorial(&mut self) {
+        let Some(mut tutorial) = self.tutorial.take() else {
+            return;
+        };
+        if tutorial.check(self, self.sim.paused) {
+            self.sim.paused = true;
+        }
+        self.tutorial = Some(tutorial);
+    }
+
+    /// Close the tutorial lesson on screen, picking up where the game left off.
+    /// Returns false if there was none.
+    pub fn dismiss_lesson(&mut self) -> bool {
+        let Some(tutorial) = self.tutorial.as_mut().filter(|t| t.showing().is_some()) else {
+            return false;
+        };
+        if tutorial.dismiss() {
+            self.sim.paused = false;
+        }
+        true
     }
 
     pub fn toggle_pause(&mut self) {
-        self.paused = !self.paused;
+        self.sim.paused = !self.sim.paused;
     }
 
-    pub fn speed_up(&mut self) {
-        if self.speed < 10 {
-            self.speed += 1;
+    /// Move the world on by exactly one tick, while it's paused
+    pub fn step(&mut self) {
+        if !self.sim.paused {
+            return;
         }
+        self.sim.paused = false;
+        self.tick();
+        self.sim.paused = true;
+    }
+
+    pub fn toggle_turbo(&mut self) {
+        self.turbo = !self.turbo;
+    }
+
+    pub fn speed_up(&mut self) {
+        self.speed = self.speed.faster();
     }
 
     pub fn speed_down(&mut self) {
-        if self.speed > 1 {
-            self.speed -= 1;
-        }
+        self.speed = self.speed.slower();
     }
 
     pub fn cycle_selected_orc(&mut self) {
-        let living: Vec<usize> = self.orcs.iter().enumerate()
+        let living: Vec<usize> = self.sim.orcs.iter().enumerate()
             .filter(|(_, o)| o.alive)
             .map(|(i, _)| i)
             .collect();
@@ -244,24 +387,423 @@ impl App {
 
         // Snap cursor to selected orc
         if let Some(i) = self.selected_orc {
-            self.cursor_x = self.orcs[i].x;
-            self.cursor_y = self.orcs[i].y;
+            self.cursor_x = self.sim.orcs[i].x;
+            self.cursor_y = self.sim.orcs[i].y;
+        }
+    }
+
+    /// Evacuate to the tile under the cursor, or call the clan home if it's already out
+    pub fn order_evacuation(&mut self) {
+        self.sim.evacuate_to(self.cursor_x, self.cursor_y);
+    }
+
+    /// Bring up the bestiary, or put it away
+    pub fn toggle_bestiary(&mut self) {
+        self.show_bestiary = !self.show_bestiary;
+    }
+
+    /// Stop the world and bring up the pause menu
+    pub fn open_menu(&mut self) {
+        self.screen = Screen::Menu(0);
+        self.menu_note = None;
+    }
+
+    /// Bring up the help screen at the top, or put it away
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+        self.help_scroll = 0;
+    }
+
+    /// Scroll the help screen `lines` down, or up if negative, no further
+    /// than its last line
+    pub fn scroll_help(&mut self, lines: i32) {
+        let last = help::lines(self).len().saturating_sub(1) as i32;
+        self.help_scroll = (self.help_scroll as i32 + lines).clamp(0, last) as u16;
+    }
+
+    pub fn toggle_graphs(&mut self) {
+        self.show_graphs = !self.show_graphs;
+    }
+
+    /// Write the event log and daily statistics out to CSV files in the
+    /// working folder, saying in the log where they went
+    pub fn export(&mut self) {
+        let sim = &mut self.sim;
+        let exported = env::current_dir().and_then(|dir| export::export(&dir, sim.seed, &sim.event_log, &sim.stats, sim.world.day_length));
+        let (message, severity) = match exported {
+            Ok((events, days)) => (format!("Exported the log to {} and daily statistics to {}", events.display(), days.display()), Severity::Info),
+            Err(e) => (format!("Could not export: {}", e), Severity::Danger),
+        };
+        sim.event_log.log(sim.tick, message, severity);
+    }
+
+    /// Bring up the ventures on offer at the campfire, or put them away
+    pub fn toggle_campfire_menu(&mut self) {
+        self.campfire_menu = !self.campfire_menu;
+    }
+
+    /// Take up the `n`th venture on the campfire menu, counting from 1
+    pub fn choose_venture(&mut self, n: usize) {
+        if let Some(&venture) = n.checked_sub(1).and_then(|i| Venture::ALL.get(i)) {
+            self.campfire_menu = false;
+            self.sim.send_expedition(venture);
+        }
+    }
+
+    /// Ask how much food to drop at the cursor
+    pub fn open_food_prompt(&mut self) {
+        self.food_prompt = Some(String::new());
+    }
+
+    /// Type another digit of the amount to drop
+    pub fn type_food_amount(&mut self, digit: char) {
+        if let Some(typed) = &mut self.food_prompt
+            && typed.len() < 2
+        {
+            typed.push(digit);
+        }
+    }
+
+    /// Ask for a new name for the selected orc, starting from the one it has
+    pub fn open_rename(&mut self) {
+        if let Some(i) = self.selected_orc.filter(|&i| self.sim.orcs.get(i).is_some_and(|o| o.alive)) {
+            let name = self.sim.orcs[i].name.clone();
+            self.naming = Some((Naming::Orc(name.clone()), name));
+        }
+    }
+
+    /// Where the living orc called `name` is in the clan
+    fn living_orc(&self, name: &str) -> Option<usize> {
+        self.sim.orcs.iter().position(|o| o.alive && o.name == name)
+    }
+
+    /// Ask what the clan should be called
+    pub fn open_clan_naming(&mut self) {
+        self.naming = Some((Naming::Clan, self.clan_name.clone().unwrap_or_default()));
+    }
+
+    /// Type another letter of the name being asked for
+    pub fn type_name(&mut self, c: char) {
+        if let Some((_, typed)) = &mut self.naming
+            && typed.chars().count() < MAX_NAME
+            && (c.is_alphanumeric() || " '-".contains(c))
+        {
+            typed.push(c);
+        }
+    }
+
+    /// Give the name typed to whatever was being named. Left empty, an orc
+    /// keeps the name it had and the clan goes without one.
+    pub fn confirm_naming(&mut self) {
+        let Some((naming, typed)) = self.naming.take() else {
+            return;
+        };
+        let name = typed.split_whitespace().collect::<Vec<_>>().join(" ");
+        match naming {
+            Naming::Orc(old) => {
+                let Some(i) = self.living_orc(&old).filter(|_| !name.is_empty() && name != old) else {
+                    return;
+                };
+                if self.sim.orcs.iter().any(|o| o.name.eq_ignore_ascii_case(&name)) {
+                    self.sim.event_log.log(self.sim.tick, format!("There's already an orc called {}", name), Severity::Warning);
+                    return;
+                }
+                self.sim.rename_orc(i, name);
+            }
+            Naming::Clan if name.is_empty() => {
+                if self.clan_name.take().is_some() {
+                    self.sim.event_log.log_as(self.sim.tick, EventCategory::Social, "The clan goes without a name".to_string(), Severity::Good);
+                }
+            }
+            Naming::Clan => {
+                self.sim.event_log.log_as(self.sim.tick, EventCategory::Social, format!("The clan takes the name {}", name), Severity::Good);
+                self.clan_name = Some(name);
+            }
+        }
+    }
+
+    /// Open the `:` prompt for a debug command
+    pub fn open_console(&mut self) {
+        self.console = Some(String::new());
+    }
+
+    /// Run the debug command typed at the prompt and close it, putting what
+    /// came of it in the event log
+    pub fn run_console(&mut self) {
+        let Some(typed) = self.console.take().filter(|t| !t.trim().is_empty()) else {
+            return;
+        };
+        let (outcome, color) = match DebugCommand::parse(&typed).and_then(|command| self.sim.debug(command, (self.cursor_x, self.cursor_y))) {
+            Ok(done) => (done, Severity::Good),
+            Err(msg) => (msg, Severity::Warning),
+        };
+        self.sim.event_log.log(self.sim.tick, format!(":{} - {}", typed.trim(), outcome), color);
+    }
+
+    /// Drop the amount typed at the cursor, a single unit if nothing was
+    pub fn confirm_food_prompt(&mut self) {
+        let Some(typed) = self.food_prompt.take() else {
+            return;
+        };
+        let units = typed.parse().unwrap_or(1).clamp(1, MAX_DROP);
+        self.sim.drop_food_at(self.cursor_x, self.cursor_y, units);
+    }
+
+    /// Make the selected orc drop what it is doing
+    pub fn cancel_selected(&mut self) {
+        if let Some(i) = self.selected_orc {
+            self.sim.cancel_orc(i);
+        }
+    }
+
+    /// The selected orc, if it's alive, grown and here to be given orders
+    fn orderable(&self) -> Option<usize> {
+        let i = self.selected_orc?;
+        let orc = self.sim.orcs.get(i).filter(|o| o.alive)?;
+        match orc.activity {
+            Activity::Infant { .. } | Activity::Expedition { .. } => None,
+            _ => Some(i),
+        }
+    }
+
+    /// Start picking out with the cursor where to send the selected orc, or
+    /// stop if already picking
+    pub fn start_move_order(&mut self) {
+        if self.ordering || self.orderable().is_none() {
+            self.ordering = false;
+            return;
+        }
+        self.ordering = true;
+        self.designating = None;
+        self.wall_start = None;
+    }
+
+    /// Send the selected orc to the cursor. Once there it stands about, and
+    /// goes back to looking after itself.
+    fn order_move(&mut self) {
+        self.ordering = false;
+        let Some(i) = self.orderable() else {
+            return;
+        };
+        let (x, y) = (self.cursor_x, self.cursor_y);
+        let orc = &mut self.sim.orcs[i];
+        if !self.sim.world.is_walkable(x, y) {
+            self.sim.event_log.log(self.sim.tick, format!("{} can't stand at ({}, {})", orc.name, x, y), Severity::Warning);
+            return;
+        }
+        orc.cancel(&mut self.sim.world, &mut self.sim.event_log, self.sim.tick);
+        orc.go_to(x, y, Purpose::Ordered, "Going where sent", &self.sim.world);
+        self.sim.event_log.log(self.sim.tick, format!("{} is sent to ({}, {})", orc.name, x, y), Severity::Info);
+    }
+
+    /// Send the selected orc after the animal under the cursor, with a spear
+    /// from the rack if there's one to take. The hunt goes as any other.
+    pub fn order_hunt(&mut self) {
+        let Some(i) = self.orderable() else {
+            return;
+        };
+        let (x, y) = (self.cursor_x, self.cursor_y);
+        let quarry = self.sim.animals.iter().position(|a| a.alive && (a.x, a.y) == (x, y) && a.kind.is_game() && a.tame.is_none());
+        let Some(a) = quarry else {
+            self.sim.event_log.log(self.sim.tick, format!("Nothing to hunt at ({}, {})", x, y), Severity::Warning);
+            return;
+        };
+        let orc = &mut self.sim.orcs[i];
+        orc.cancel(&mut self.sim.world, &mut self.sim.event_log, self.sim.tick);
+        orc.take_tool(ToolKind::Spear, &mut self.sim.world, &mut self.sim.event_log, self.sim.tick);
+        orc.set_activity_with_path(Activity::Hunting { target_idx: a }, &self.sim.world);
+        self.sim.animals[a].claimed = true;
+        let kind = self.sim.animals[a].kind.name().to_lowercase();
+        self.sim.event_log.log_as(self.sim.tick, EventCategory::Hunt, format!("{} is sent after the {}", orc.name, kind), Severity::Info);
+    }
+
+    /// Step through the designation modes: chop, gather, then off
+    pub fn cycle_designation(&mut self) {
+        self.designating = match self.designating {
+            None => Some(Designation::Chop),
+            Some(Designation::Chop) => Some(Designation::Gather),
+            Some(Designation::Gather) => None,
+        };
+    }
+
+    /// Place the current order on the tile under the cursor, or lift one
+    /// already there. With no order chosen, bring up the selected orc's details
+    pub fn mark_tile(&mut self) {
+        let (x, y) = (self.cursor_x, self.cursor_y);
+        if self.ordering {
+            self.order_move();
+            return;
+        }
+        if self.sim.world.designations.remove(&(x, y)).is_some() {
+            return;
+        }
+        let Some(order) = self.designating else {
+            if self.selected_orc.is_some_and(|i| self.sim.orcs[i].alive) {
+                self.inspecting = true;
+                return;
+            }
+            let key = self.input.keymap.describe(Command::CycleDesignation);
+            self.sim.event_log.log(self.sim.tick, format!("Press {} to choose what to mark", key), Severity::Warning);
+            return;
+        };
+        if !self.sim.world.mark(x, y, order) {
+            self.sim.event_log.log(self.sim.tick, format!("Nothing to {} at ({}, {})", order.name(), x, y), Severity::Warning);
+        }
+    }
+
+    /// Mark out a building at the cursor
+    pub fn designate(&mut self, kind: Building) {
+        self.sim.designate_at(kind, self.cursor_x, self.cursor_y);
+    }
+
+    /// Pin one end of a wall line at the cursor, or lay the wall out to it
+    pub fn wall_line(&mut self) {
+        let here = (self.cursor_x, self.cursor_y);
+        match self.wall_start.take() {
+            None => {
+                self.wall_start = Some(here);
+                self.sim.event_log.log(self.sim.tick, format!("A wall line starts at ({}, {}); move to the other end", here.0, here.1), Severity::Info);
+            }
+            Some(start) => self.sim.designate_line(Building::Wall, start, here),
+        }
+    }
+
+    pub fn toggle_perf(&mut self) {
+        self.show_perf = !self.show_perf;
+    }
+
+    /// Scroll the event log `n` events further back, or forward if negative
+    pub fn scroll_log(&mut self, n: i32) {
+        self.log_scroll = (self.log_scroll as i32 + n).clamp(0, self.sim.event_log.held() as i32) as usize;
+    }
+
+    /// Scroll the event log back a page, as many events as it shows, or
+    /// forward if `back` is false
+    pub fn page_log(&mut self, back: bool) {
+        let page = self.log_height.saturating_sub(2) as i32;
+        self.scroll_log(if back { page } else { -page });
+    }
+
+    /// Pick out the latest event in view in the log, to move through with
+    /// the arrow keys and jump to with Enter
+    pub fn start_log_pick(&mut self) {
+        self.show_log = true;
+        let back = self.log_scroll.min(self.sim.event_log.held().saturating_sub(1));
+        self.log_pick = self.sim.event_log.shown_number(back);
+    }
+
+    /// Pick out the event `n` shown events further back, or forward if
+    /// negative, scrolling the log to keep it in view
+    pub fn move_log_pick(&mut self, n: i32) {
+        let (Some(number), held) = (self.log_pick, self.sim.event_log.held()) else {
+            return;
+        };
+        if held == 0 {
+            return;
+        }
+        let back = (self.sim.event_log.shown_since(number) as i32 + n).clamp(0, held as i32 - 1) as usize;
+        self.log_pick = self.sim.event_log.shown_number(back);
+        let page = self.log_height.saturating_sub(2).max(1) as usize;
+        if back < self.log_scroll {
+            self.log_scroll = back;
+        } else if back >= self.log_scroll + page {
+            self.log_scroll = back + 1 - page;
         }
     }
 
-    pub fn drop_food(&mut self) {
-        let terrain = self.world.get(self.cursor_x, self.cursor_y);
-        if terrain == Terrain::Grass {
-            self.world.set(self.cursor_x, self.cursor_y, Terrain::Food);
-            self.event_log.log(
-                self.tick,
-                format!("Food dropped at ({}, {})", self.cursor_x, self.cursor_y),
-                ratatui::style::Color::Magenta,
-            );
+    /// The event picked out in the log, if it's still in memory
+    pub fn picked_event(&self) -> Option<&Event> {
+        self.sim.event_log.numbered(self.log_pick?)
+    }
+
+    /// Move the cursor, and the view with it, to where the picked event happened
+    pub fn jump_to_pick(&mut self) {
+        if let Some((x, y)) = self.picked_event().and_then(|e| e.at) {
+            self.move_cursor_to(x, y);
         }
     }
 
-    pub fn tick_interval_ms(&self) -> u64 {
-        1000 / self.speed as u64
+    pub fn toggle_sidebar(&mut self) {
+        self.show_sidebar = !self.show_sidebar;
+    }
+
+    pub fn toggle_log(&mut self) {
+        self.show_log = !self.show_log;
+    }
+
+    /// Widen the sidebar a step, or narrow it if `wider` is false
+    pub fn resize_sidebar(&mut self, wider: bool) {
+        let width = if wider { self.sidebar_width + SIDEBAR_STEP } else { self.sidebar_width.saturating_sub(SIDEBAR_STEP) };
+        self.sidebar_width = width.clamp(*SIDEBAR_WIDTHS.start(), *SIDEBAR_WIDTHS.end());
+        self.show_sidebar = true;
+    }
+
+    /// Make the event log a step taller, or shorter if `taller` is false
+    pub fn resize_log(&mut self, taller: bool) {
+        let height = if taller { self.log_height + LOG_STEP } else { self.log_height.saturating_sub(LOG_STEP) };
+        self.log_height = height.clamp(*LOG_HEIGHTS.start(), *LOG_HEIGHTS.end());
+        self.show_log = true;
+    }
+
+    /// Hide a category of events from the log, or show it again, going back
+    /// to the latest
+    pub fn toggle_log_filter(&mut self, category: EventCategory) {
+        self.sim.event_log.toggle_hidden(category);
+        self.log_scroll = 0;
+    }
+
+    pub fn cycle_zoom(&mut self) {
+        self.zoom = self.zoom.next();
+    }
+
+    /// Switch between Unicode glyphs and their plain ASCII stand-ins
+    pub fn toggle_glyphs(&mut self) {
+        self.caps.glyphs = match self.caps.glyphs {
+            GlyphMode::Unicode => GlyphMode::Ascii,
+            GlyphMode::Ascii => GlyphMode::Unicode,
+        };
+    }
+
+    pub fn toggle_legend(&mut self) {
+        self.show_legend = !self.show_legend;
+    }
+
+    pub fn toggle_tooltip(&mut self) {
+        self.show_tooltip = !self.show_tooltip;
+    }
+
+    pub fn toggle_minimap(&mut self) {
+        self.show_minimap = !self.show_minimap;
+    }
+
+    pub fn toggle_dawn_news(&mut self) {
+        self.sim.dawn_news = !self.sim.dawn_news;
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.themes[self.theme]
+    }
+
+    pub fn cycle_view(&mut self) {
+        self.view = self.view.next();
+    }
+
+    pub fn cycle_theme(&mut self) {
+        self.theme = (self.theme + 1) % self.themes.len();
+    }
+
+    pub fn cycle_theme_back(&mut self) {
+        self.theme = (self.theme + self.themes.len() - 1) % self.themes.len();
+    }
+
+    /// Switch to the theme called `name`, ignoring case
+    pub fn select_theme(&mut self, name: &str) -> bool {
+        match self.themes.iter().position(|t| t.name.eq_ignore_ascii_case(name)) {
+            Some(i) => {
+                self.theme = i;
+                true
+            }
+            None => false,
+        }
     }
 }
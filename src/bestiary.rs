@@ -0,0 +1,115 @@
+//! What the player has learned of the wildlife by watching it.
+
+use crate::animal::{Animal, AnimalKind, Behaviour};
+
+/// Something the clan did to an animal, for the bestiary to tally
+#[derive(Clone, Copy)]
+pub enum Deed {
+    Killed,
+    Tamed,
+}
+
+/// One kind's page
+pub struct Page {
+    pub kind: AnimalKind,
+    /// Animals of the kind that have come into view
+    pub seen: u32,
+    pub killed: u32,
+    pub tamed: u32,
+    /// Its ways the player has watched one at, in the order first seen
+    pub witnessed: Vec<Behaviour>,
+}
+
+impl Page {
+    /// Whether every one of the kind's ways has been seen
+    pub fn complete(&self) -> bool {
+        self.witnessed.len() == self.kind.stats().ways.len()
+    }
+}
+
+/// A milestone worth telling the player about
+pub enum Achievement {
+    FirstSighting(AnimalKind),
+    NewWay(AnimalKind, Behaviour),
+    AllWays(AnimalKind),
+}
+
+impl Achievement {
+    pub fn describe(&self) -> String {
+        match self {
+            Achievement::FirstSighting(kind) => format!("Bestiary: first sighting of a {}", kind.name().to_lowercase()),
+            Achievement::NewWay(kind, way) => format!("Bestiary: a {} seen {}", kind.name().to_lowercase(), way.name()),
+            Achievement::AllWays(kind) => format!("Bestiary: every way of the {} witnessed!", kind.name().to_lowercase()),
+        }
+    }
+}
+
+pub struct Bestiary {
+    /// One page per kind, in the order of `AnimalKind::ALL`
+    pages: Vec<Page>,
+}
+
+impl Default for Bestiary {
+    fn default() -> Self {
+        Bestiary::new()
+    }
+}
+
+impl Bestiary {
+    pub fn new() -> Self {
+        Bestiary {
+            pages: AnimalKind::ALL.iter()
+                .map(|&kind| Page { kind, seen: 0, killed: 0, tamed: 0, witnessed: Vec::new() })
+                .collect(),
+        }
+    }
+
+    pub fn pages(&self) -> &[Page] {
+        &self.pages
+    }
+
+    fn page_mut(&mut self, kind: AnimalKind) -> &mut Page {
+        let i = AnimalKind::ALL.iter().position(|&k| k == kind).expect("every kind has a page");
+        &mut self.pages[i]
+    }
+
+    pub fn record(&mut self, kind: AnimalKind, deed: Deed) {
+        let page = self.page_mut(kind);
+        match deed {
+            Deed::Killed => page.killed += 1,
+            Deed::Tamed => page.tamed += 1,
+        }
+    }
+
+    /// Take note of the living animals inside the `view` the player has on
+    /// screen, given as (x, y, width, height) in tiles. Returns the
+    /// milestones this reached.
+    pub fn watch(&mut self, animals: &mut [Animal], (vx, vy, vw, vh): (usize, usize, usize, usize)) -> Vec<Achievement> {
+        let mut reached = Vec::new();
+        for animal in animals.iter_mut().filter(|a| a.alive && (vx..vx + vw).contains(&a.x) && (vy..vy + vh).contains(&a.y)) {
+            let kind = animal.kind;
+            let behaviour = animal.behaviour;
+            let first_sighting = !animal.spotted;
+            animal.spotted = true;
+            let page = self.page_mut(kind);
+            let new_kind = first_sighting && page.seen == 0;
+            if new_kind {
+                reached.push(Achievement::FirstSighting(kind));
+            }
+            if first_sighting {
+                page.seen += 1;
+            }
+            if !kind.stats().ways.contains(&behaviour) || page.witnessed.contains(&behaviour) {
+                continue;
+            }
+            page.witnessed.push(behaviour);
+            if page.complete() {
+                reached.push(Achievement::AllWays(kind));
+            } else if !new_kind {
+                // The first way a kind is seen at goes with its first sighting
+                reached.push(Achievement::NewWay(kind, behaviour));
+            }
+        }
+        reached
+    }
+}
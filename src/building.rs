@@ -0,0 +1,215 @@
+//! Structures the clan can build and the sites where they go up.
+
+use crate::world::Terrain;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Building {
+    Hut,
+    Wall,
+    Gate,
+    Well,
+    Watchtower,
+    Storage,
+    Workbench,
+    Bridge,
+    Pen,
+}
+
+impl Building {
+    pub const ALL: [Building; 9] = [
+        Building::Hut,
+        Building::Wall,
+        Building::Gate,
+        Building::Well,
+        Building::Watchtower,
+        Building::Storage,
+        Building::Workbench,
+        Building::Bridge,
+        Building::Pen,
+    ];
+
+    /// Parse the one-word form used by the agent interface, e.g. `hut` or `storage`
+    pub fn parse(s: &str) -> Option<Building> {
+        match s {
+            "hut" => Some(Building::Hut),
+            "wall" => Some(Building::Wall),
+            "gate" => Some(Building::Gate),
+            "well" => Some(Building::Well),
+            "watchtower" => Some(Building::Watchtower),
+            "storage" => Some(Building::Storage),
+            "workbench" => Some(Building::Workbench),
+            "bridge" => Some(Building::Bridge),
+            "pen" => Some(Building::Pen),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Building::Hut => "hut",
+            Building::Wall => "wall",
+            Building::Gate => "gate",
+            Building::Well => "well",
+            Building::Watchtower => "watchtower",
+            Building::Storage => "storage hut",
+            Building::Workbench => "workbench",
+            Building::Bridge => "bridge",
+            Building::Pen => "pen",
+        }
+    }
+
+    pub fn symbol(&self) -> char {
+        match self {
+            Building::Hut => '⌂',
+            Building::Wall => '█',
+            Building::Gate => '▥',
+            Building::Well => 'Θ',
+            Building::Watchtower => '♜',
+            Building::Storage => '▤',
+            Building::Workbench => 'π',
+            Building::Bridge => '═',
+            Building::Pen => '⊞',
+        }
+    }
+
+    /// Units of `material` that have to be brought to the site
+    pub fn cost(&self, material: Material) -> u32 {
+        match (self, material) {
+            (Building::Hut, Material::Wood) => 4,
+            (Building::Hut, Material::Stone) => 0,
+            (Building::Wall, Material::Wood) => 0,
+            (Building::Wall, Material::Stone) => 2,
+            (Building::Gate, Material::Wood) => 2,
+            (Building::Gate, Material::Stone) => 0,
+            (Building::Well, Material::Wood) => 1,
+            (Building::Well, Material::Stone) => 4,
+            (Building::Watchtower, Material::Wood) => 5,
+            (Building::Watchtower, Material::Stone) => 2,
+            (Building::Storage, Material::Wood) => 4,
+            (Building::Storage, Material::Stone) => 2,
+            (Building::Workbench, Material::Wood) => 3,
+            (Building::Workbench, Material::Stone) => 1,
+            (Building::Bridge, Material::Wood) => 3,
+            (Building::Bridge, Material::Stone) => 0,
+            (Building::Pen, Material::Wood) => 2,
+            (Building::Pen, Material::Stone) => 0,
+        }
+    }
+
+    /// Ticks of labour once the materials are in
+    pub fn work(&self) -> u32 {
+        match self {
+            Building::Hut => 20,
+            Building::Wall => 8,
+            Building::Gate => 6,
+            Building::Well => 25,
+            Building::Watchtower => 30,
+            Building::Storage => 25,
+            Building::Workbench => 15,
+            Building::Bridge => 12,
+            Building::Pen => 6,
+        }
+    }
+
+    /// Orcs can step inside huts, storage and pens, through gates, up to the
+    /// workbench and over bridges, but not through the rest
+    pub fn walkable(&self) -> bool {
+        matches!(self, Building::Hut | Building::Gate | Building::Storage | Building::Workbench | Building::Bridge | Building::Pen)
+    }
+
+    /// What the site has to be marked out on: water for a bridge, open
+    /// ground for everything else
+    pub fn ground(&self) -> Terrain {
+        match self {
+            Building::Bridge => Terrain::Water,
+            _ => Terrain::Grass,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Material {
+    Wood,
+    Stone,
+}
+
+impl Material {
+    pub const ALL: [Material; 2] = [Material::Wood, Material::Stone];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Material::Wood => "wood",
+            Material::Stone => "stone",
+        }
+    }
+
+    /// What an orc chops or breaks up to get this material
+    pub fn source(&self) -> Terrain {
+        match self {
+            Material::Wood => Terrain::Tree,
+            Material::Stone => Terrain::Rock,
+        }
+    }
+
+    /// Ticks spent felling a tree or breaking a rock
+    pub fn gather_ticks(&self) -> u32 {
+        match self {
+            Material::Wood => 5,
+            Material::Stone => 8,
+        }
+    }
+}
+
+/// A marked-out building that is still waiting on materials or work
+#[derive(Clone, Debug)]
+pub struct Site {
+    pub x: usize,
+    pub y: usize,
+    pub kind: Building,
+    pub wood: u32,
+    pub stone: u32,
+    pub work: u32,
+}
+
+impl Site {
+    pub fn new(x: usize, y: usize, kind: Building) -> Site {
+        Site { x, y, kind, wood: 0, stone: 0, work: 0 }
+    }
+
+    pub fn delivered(&self, material: Material) -> u32 {
+        match material {
+            Material::Wood => self.wood,
+            Material::Stone => self.stone,
+        }
+    }
+
+    /// Units of `material` still to be brought
+    pub fn missing(&self, material: Material) -> u32 {
+        self.kind.cost(material).saturating_sub(self.delivered(material))
+    }
+
+    pub fn deliver(&mut self, material: Material) {
+        match material {
+            Material::Wood => self.wood += 1,
+            Material::Stone => self.stone += 1,
+        }
+    }
+
+    /// Every material is on site and work can begin
+    pub fn is_stocked(&self) -> bool {
+        Material::ALL.iter().all(|&m| self.missing(m) == 0)
+    }
+
+    /// Short progress note for the status line, e.g. `hut 2/4 wood` or `hut 40%`
+    pub fn describe(&self) -> String {
+        if self.is_stocked() {
+            return format!("{} {}%", self.kind.name(), self.work * 100 / self.kind.work());
+        }
+        let needs: Vec<String> = Material::ALL
+            .iter()
+            .filter(|&&m| self.kind.cost(m) > 0)
+            .map(|&m| format!("{}/{} {}", self.delivered(m), self.kind.cost(m), m.name()))
+            .collect();
+        format!("{} {}", self.kind.name(), needs.join(" "))
+    }
+}
@@ -0,0 +1,30 @@
+//! The clan's dead, and the graveyard they're laid to rest in.
+
+/// How far south of the campfire the graveyard lies
+pub const GRAVEYARD_DISTANCE: usize = 10;
+/// Morale every orc in the clan takes from seeing one of its own buried
+pub const BURIAL_MORALE: f32 = 5.0;
+/// Orcs this close to an unburied body lose this much morale each tick
+pub const GLOOM_RADIUS: usize = 6;
+pub const CORPSE_GLOOM: f32 = 0.1;
+
+/// An orc's body, waiting to be buried
+pub struct Corpse {
+    pub name: String,
+    pub x: usize,
+    pub y: usize,
+}
+
+pub struct Grave {
+    pub name: String,
+    pub x: usize,
+    pub y: usize,
+    /// Day of the burial
+    pub day: u64,
+}
+
+impl Grave {
+    pub fn describe(&self) -> String {
+        format!("grave of {}, buried day {}", self.name, self.day)
+    }
+}
@@ -0,0 +1,133 @@
+/// Ticks in one full day/night cycle, unless `[world] day_length` says otherwise
+pub const DAY_LENGTH: u64 = 100;
+/// Shortest day that still has a tick of dawn in it
+pub const MIN_DAY_LENGTH: u64 = 10;
+/// Days in one year
+pub const YEAR_LENGTH: u64 = 40;
+/// Days in each of the four seasons
+pub const SEASON_LENGTH: u64 = YEAR_LENGTH / 4;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DayPhase {
+    Dawn,
+    Day,
+    Dusk,
+    Night,
+}
+
+impl DayPhase {
+    /// The phase `tick` falls in, with days `day_length` ticks long
+    pub fn of(tick: u64, day_length: u64) -> DayPhase {
+        match time_of_day(tick, day_length) {
+            t if t < day_length / 10 => DayPhase::Dawn,
+            t if t < dusk(day_length) => DayPhase::Day,
+            t if t < nightfall(day_length) => DayPhase::Dusk,
+            _ => DayPhase::Night,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            DayPhase::Dawn => "Dawn",
+            DayPhase::Day => "Day",
+            DayPhase::Dusk => "Dusk",
+            DayPhase::Night => "Night",
+        }
+    }
+
+    /// Daylight hours when orcs are out working
+    pub fn is_daylight(&self) -> bool {
+        matches!(self, DayPhase::Dawn | DayPhase::Day)
+    }
+}
+
+/// The year starts in spring, on day 1
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Season {
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub fn of(tick: u64, day_length: u64) -> Season {
+        match (day_number(tick, day_length) - 1) / SEASON_LENGTH % 4 {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Season::Spring => "Spring",
+            Season::Summer => "Summer",
+            Season::Autumn => "Autumn",
+            Season::Winter => "Winter",
+        }
+    }
+
+    /// Logged on the first morning of the season
+    pub fn herald(&self) -> &'static str {
+        match self {
+            Season::Spring => "Spring arrives and the land thaws",
+            Season::Summer => "Summer arrives, fruit is ripening on the trees",
+            Season::Autumn => "Autumn arrives, mushrooms are springing up around the rocks",
+            Season::Winter => "Winter arrives, the nights are bitter",
+        }
+    }
+
+    /// Warmth an orc out in the open loses each tick of night
+    pub fn night_chill(&self) -> f32 {
+        match self {
+            Season::Summer => 1.5,
+            Season::Spring | Season::Autumn => 2.5,
+            Season::Winter => 3.5,
+        }
+    }
+
+    /// Warmth an orc out in the open regains each tick of daylight
+    pub fn day_warmth(&self) -> f32 {
+        match self {
+            Season::Summer => 1.0,
+            Season::Spring | Season::Autumn => 0.5,
+            Season::Winter => 0.0,
+        }
+    }
+}
+
+/// Tick within the day that dusk falls, halfway through
+pub fn dusk(day_length: u64) -> u64 {
+    day_length / 2
+}
+
+/// Tick within the day that night falls, three fifths of the way through
+pub fn nightfall(day_length: u64) -> u64 {
+    day_length * 3 / 5
+}
+
+/// 1-based day number for a tick
+pub fn day_number(tick: u64, day_length: u64) -> u64 {
+    tick / day_length + 1
+}
+
+/// Tick within the current day, 0 at dawn
+pub fn time_of_day(tick: u64, day_length: u64) -> u64 {
+    tick % day_length
+}
+
+/// Whether it's raining today: now and then in spring and autumn
+pub fn showery(tick: u64, day_length: u64) -> bool {
+    let day = day_number(tick, day_length);
+    matches!(Season::of(tick, day_length), Season::Spring | Season::Autumn) && day.wrapping_mul(2654435761) % 7 < 2
+}
+
+/// Age for display, e.g. `23y 5d`, or just `5d` in the first year
+pub fn describe_age(days: u64) -> String {
+    match (days / YEAR_LENGTH, days % YEAR_LENGTH) {
+        (0, d) => format!("{}d", d),
+        (y, d) => format!("{}y {}d", y, d),
+    }
+}
@@ -0,0 +1,55 @@
+//! The remains of a kill, left lying where the animal fell.
+
+use crate::animal::AnimalKind;
+
+/// Ticks a carcass stays fresh after the kill, half a day
+fn fresh_for(day_length: u64) -> u64 {
+    day_length / 2
+}
+/// Once ripe, a carcass loses a unit of meat to rot every this many ticks
+pub const ROT_TICKS: u64 = 40;
+/// How far off scavengers smell a ripe carcass
+pub const CARRION_SCENT: usize = 15;
+/// Most crows one carcass draws, and the chance each tick that it draws
+/// another while there's room
+pub const CROWS_PER_CARCASS: usize = 3;
+pub const CROW_CHANCE: f64 = 0.1;
+/// Chance a crow at a carcass tears off a unit of meat each tick
+pub const PECK_CHANCE: f64 = 0.1;
+/// Chance each tick that a crow with no carrion to smell flies off for good
+pub const CROW_DEPARTS: f64 = 0.05;
+/// Units of meat a wolf bolts down at a carcass
+pub const WOLF_FEED: u32 = 2;
+
+pub struct Carcass {
+    pub x: usize,
+    pub y: usize,
+    pub kind: AnimalKind,
+    /// Units of meat left on it
+    pub meat: u32,
+    /// The tick it fell
+    pub fallen: u64,
+}
+
+impl Carcass {
+    pub fn is_ripe(&self, tick: u64, day_length: u64) -> bool {
+        tick >= self.fallen + fresh_for(day_length)
+    }
+
+    /// Whether this tick it loses another unit to rot
+    pub fn rots(&self, tick: u64, day_length: u64) -> bool {
+        tick > self.fallen + fresh_for(day_length) && (tick - self.fallen - fresh_for(day_length)).is_multiple_of(ROT_TICKS)
+    }
+
+    pub fn describe(&self, tick: u64, day_length: u64) -> String {
+        let state = if self.is_ripe(tick, day_length) { "ripe" } else { "fresh" };
+        format!("{} {} carcass, {} meat", state, self.kind.name().to_lowercase(), self.meat)
+    }
+}
+
+/// The nearest ripe carcass a scavenger at (x, y) can smell, if any
+pub fn carrion(carcasses: &[Carcass], x: usize, y: usize, tick: u64, day_length: u64) -> Option<&Carcass> {
+    carcasses.iter()
+        .filter(|c| c.is_ripe(tick, day_length) && c.x.abs_diff(x).max(c.y.abs_diff(y)) <= CARRION_SCENT)
+        .min_by_key(|c| (c.x.abs_diff(x) + c.y.abs_diff(y), c.x, c.y))
+}
@@ -0,0 +1,199 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use crate::scenario::Scenario;
+use crate::sim::Stress;
+use crate::term::{ColorDepth, GlyphMode};
+
+pub const USAGE: &str = "Usage: orcs [options]
+
+Options:
+  --seed <n>               Generate the world from a fixed seed
+  --news <file>            Append a daily village summary to <file>
+  --config <file>          Load settings such as AI thresholds from a
+                           TOML file
+  --glyphs <unicode|ascii> Override the detected glyph set
+  --colors <truecolor|256|16>
+                           Override the detected color depth
+  --gym                    Run headless, stepping the simulation from
+                           stdin and printing JSON observations
+  --headless --ticks <n>   Run the simulation for <n> ticks without a UI
+                           and print how the clan fared
+  --stats-json <file>      With --headless, write the results to <file>
+                           as JSON instead
+  --stress [orcs=<n>] [animals=<n>]
+                           Crowd the world to see how the simulation
+                           copes (default orcs=100 animals=200)
+  --record <file>          Save the seed, settings and every key press
+                           to <file> for a bug report
+  --play <file>            Play back a session saved with --record
+  --tutorial               Learn the game in a guided scenario
+  --scenario <sandbox|defense|file>
+                           Play the open-ended sandbox (the default),
+                           hold the campfire against waves of goblins, or
+                           start as a scenario file sets out
+  --event-archive <file>   Write every event logged to <file>, keeping
+                           the full history of a long game on disk
+  --dump-state [every=<n>] <file>
+                           Append a JSON snapshot of the game to <file>
+                           every <n> ticks (default every=1)
+  --serve <address>        Send the game to WebSocket viewers on
+                           <address>, e.g. 127.0.0.1:8765, every tick
+  --metrics <address>      Answer Prometheus scrapes of tick times, path
+                           searches and head counts on <address>
+  -h, --help               Show this help";
+
+/// Where and how often `--dump-state` writes snapshots
+#[derive(Clone, Debug, PartialEq)]
+pub struct Dump {
+    pub every: u64,
+    pub path: PathBuf,
+}
+
+#[derive(Default)]
+pub struct Options {
+    pub show_help: bool,
+    pub seed: Option<u64>,
+    pub gym: bool,
+    pub headless: bool,
+    pub ticks: Option<u64>,
+    pub stats_path: Option<PathBuf>,
+    pub news_path: Option<PathBuf>,
+    pub config_path: Option<PathBuf>,
+    pub glyphs: Option<GlyphMode>,
+    pub colors: Option<ColorDepth>,
+    pub stress: Option<Stress>,
+    pub record_path: Option<PathBuf>,
+    pub play_path: Option<PathBuf>,
+    pub tutorial: bool,
+    pub scenario: Scenario,
+    /// Scenario file to start the world from
+    pub scenario_path: Option<PathBuf>,
+    pub dump: Option<Dump>,
+    pub archive_path: Option<PathBuf>,
+    /// Where to take WebSocket viewers
+    pub serve: Option<SocketAddr>,
+    /// Where to answer Prometheus scrapes
+    pub metrics: Option<SocketAddr>,
+}
+
+impl Options {
+    /// Parse command-line arguments (without the program name)
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+        let mut opts = Options::default();
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--seed" => {
+                    let value = args.next().ok_or("--seed requires a number")?;
+                    opts.seed = Some(value.parse().map_err(|_| format!("Invalid seed: {}", value))?);
+                }
+                "--gym" => opts.gym = true,
+                "--headless" => opts.headless = true,
+                "--ticks" => {
+                    let value = args.next().ok_or("--ticks requires a number")?;
+                    opts.ticks = Some(value.parse().map_err(|_| format!("Invalid tick count: {}", value))?);
+                }
+                "--stats-json" => {
+                    let path = args.next().ok_or("--stats-json requires a file path")?;
+                    opts.stats_path = Some(PathBuf::from(path));
+                }
+                "--news" => {
+                    let path = args.next().ok_or("--news requires a file path")?;
+                    opts.news_path = Some(PathBuf::from(path));
+                }
+                "--config" => {
+                    let path = args.next().ok_or("--config requires a file path")?;
+                    opts.config_path = Some(PathBuf::from(path));
+                }
+                "--glyphs" => {
+                    let value = args.next().ok_or("--glyphs requires a value")?;
+                    opts.glyphs = Some(GlyphMode::parse(&value).ok_or(format!("Unknown glyph mode: {}", value))?);
+                }
+                "--colors" => {
+                    let value = args.next().ok_or("--colors requires a value")?;
+                    opts.colors = Some(ColorDepth::parse(&value).ok_or(format!("Unknown color depth: {}", value))?);
+                }
+                "--stress" => {
+                    let mut stress = Stress::default();
+                    while let Some(setting) = args.next_if(|a| a.contains('=') && !a.starts_with('-')) {
+                        let (key, value) = setting.split_once('=').unwrap_or_default();
+                        let n = value.parse().map_err(|_| format!("Invalid count in --stress: {}", setting))?;
+                        match key {
+                            "orcs" => stress.orcs = n,
+                            "animals" => stress.animals = n,
+                            _ => return Err(format!("Unknown --stress setting: {}", key)),
+                        }
+                    }
+                    opts.stress = Some(stress);
+                }
+                "--record" => {
+                    let path = args.next().ok_or("--record requires a file path")?;
+                    opts.record_path = Some(PathBuf::from(path));
+                }
+                "--play" => {
+                    let path = args.next().ok_or("--play requires a file path")?;
+                    opts.play_path = Some(PathBuf::from(path));
+                }
+                "--tutorial" => opts.tutorial = true,
+                "--scenario" => {
+                    let value = args.next().ok_or("--scenario requires a name or a file path")?;
+                    match Scenario::parse(&value) {
+                        Some(scenario) => opts.scenario = scenario,
+                        None => opts.scenario_path = Some(PathBuf::from(value)),
+                    }
+                }
+                "--event-archive" => {
+                    let path = args.next().ok_or("--event-archive requires a file path")?;
+                    opts.archive_path = Some(PathBuf::from(path));
+                }
+                "--dump-state" => {
+                    let mut every = 1;
+                    if let Some(setting) = args.next_if(|a| a.starts_with("every=")) {
+                        let value = &setting["every=".len()..];
+                        every = value.parse().ok().filter(|&n| n > 0).ok_or(format!("Invalid interval in --dump-state: {}", setting))?;
+                    }
+                    let path = args.next().ok_or("--dump-state requires a file path")?;
+                    opts.dump = Some(Dump { every, path: PathBuf::from(path) });
+                }
+                "--serve" => {
+                    let value = args.next().ok_or("--serve requires an address")?;
+                    opts.serve = Some(value.parse().map_err(|_| format!("Invalid address: {} (try 127.0.0.1:8765)", value))?);
+                }
+                "--metrics" => {
+                    let value = args.next().ok_or("--metrics requires an address")?;
+                    opts.metrics = Some(value.parse().map_err(|_| format!("Invalid address: {} (try 127.0.0.1:9100)", value))?);
+                }
+                "-h" | "--help" => opts.show_help = true,
+                other => return Err(format!("Unknown argument: {}\n\n{}", other, USAGE)),
+            }
+        }
+        let scenario_given = opts.scenario != Scenario::Sandbox || opts.scenario_path.is_some();
+        let settings_given = opts.seed.is_some() || opts.config_path.is_some() || opts.stress.is_some() || scenario_given;
+        if opts.play_path.is_some() && (settings_given || opts.gym || opts.record_path.is_some() || opts.tutorial) {
+            return Err("--play takes the seed and settings from the recording and can't be combined with --seed, --config, --stress, --scenario, --gym, --record or --tutorial".to_string());
+        }
+        if opts.headless != opts.ticks.is_some() {
+            return Err("--headless and --ticks go together".to_string());
+        }
+        if opts.stats_path.is_some() && !opts.headless {
+            return Err("--stats-json needs --headless".to_string());
+        }
+        if opts.headless && (opts.gym || opts.play_path.is_some() || opts.record_path.is_some() || opts.tutorial) {
+            return Err("--headless can't be combined with --gym, --play, --record or --tutorial".to_string());
+        }
+        if opts.serve.is_some() && (opts.gym || opts.headless) {
+            return Err("--serve needs the UI and can't be combined with --gym or --headless".to_string());
+        }
+        if opts.metrics.is_some() && opts.gym {
+            return Err("--metrics can't be combined with --gym".to_string());
+        }
+        if opts.tutorial && (opts.gym || opts.stress.is_some()) {
+            return Err("--tutorial can't be combined with --gym or --stress".to_string());
+        }
+        if scenario_given && (opts.tutorial || opts.gym) {
+            return Err("--scenario can't be combined with --tutorial or --gym".to_string());
+        }
+        Ok(opts)
+    }
+}
@@ -0,0 +1,69 @@
+//! Blows traded in a fight.
+
+use std::ops::Range;
+
+use rand::Rng;
+
+/// A fighter worn down to nothing still lands this share of the blows it
+/// would unhurt
+const SPENT_STRENGTH: f64 = 0.5;
+
+/// Whatever something fights with, teeth and fists included
+pub struct Weapon {
+    /// Chance a swing lands, for a fighter at full strength
+    pub chance: f64,
+    /// The harm a blow that lands does
+    pub damage: Range<f32>,
+}
+
+/// Anything that can be hurt in a fight
+pub trait Combatant {
+    fn health(&self) -> f32;
+
+    /// Health when unhurt
+    fn max_health(&self) -> f32;
+
+    /// Take `damage` at `tick`, dying if it's the end of it
+    fn hurt(&mut self, damage: f32, tick: u64);
+
+    /// Share of a blow's harm that gets through its guard
+    fn guard(&self) -> f32 {
+        1.0
+    }
+
+    /// Whether it has lost heart and gives up the fight
+    fn breaks(&self) -> bool;
+
+    /// Share of its full strength it still fights with
+    fn strength(&self) -> f64 {
+        (self.health() / self.max_health()).clamp(0.0, 1.0) as f64
+    }
+}
+
+/// How a blow went
+#[derive(Clone, Copy, PartialEq)]
+pub enum Blow {
+    Missed,
+    /// It landed doing this much harm, and the defender fights on
+    Landed(f32),
+    /// It landed, and the defender has had enough
+    Routed(f32),
+    Killed,
+}
+
+/// One swing by `attacker` with `weapon` at `defender` at `tick`
+pub fn attack(attacker: &impl Combatant, weapon: &Weapon, defender: &mut impl Combatant, rng: &mut impl Rng, tick: u64) -> Blow {
+    let chance = weapon.chance * (SPENT_STRENGTH + (1.0 - SPENT_STRENGTH) * attacker.strength());
+    if !rng.gen_bool(chance.clamp(0.0, 1.0)) {
+        return Blow::Missed;
+    }
+    let damage = rng.gen_range(weapon.damage.clone()) * defender.guard();
+    defender.hurt(damage, tick);
+    if defender.health() <= 0.0 {
+        Blow::Killed
+    } else if defender.breaks() {
+        Blow::Routed(damage)
+    } else {
+        Blow::Landed(damage)
+    }
+}
@@ -0,0 +1,65 @@
+//! Debug commands typed at the `:` prompt.
+
+use crate::animal::AnimalKind;
+use crate::diary::NEEDS;
+
+/// Most orcs or animals one `spawn` puts down
+const MAX_SPAWN: usize = 50;
+
+#[derive(Clone, PartialEq)]
+pub enum DebugCommand {
+    SpawnOrcs(usize),
+    SpawnAnimals(AnimalKind, usize),
+    /// Set the need at `need` in `NEEDS` of the orc named
+    SetNeed { need: usize, orc: String, value: f32 },
+    Teleport { orc: String, x: usize, y: usize },
+    Reveal,
+    Kill(Target),
+}
+
+/// What `kill` strikes down
+#[derive(Clone, PartialEq)]
+pub enum Target {
+    Kind(AnimalKind),
+    Orc(String),
+}
+
+impl DebugCommand {
+    /// Parse a line typed at the prompt, e.g. `spawn orc`, `spawn deer 10`,
+    /// `set hunger Grok 90`, `teleport Grok 40 30`, `reveal` or `kill wolf`
+    pub fn parse(s: &str) -> Result<DebugCommand, String> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        match words.as_slice() {
+            ["spawn", kind, count @ ..] if count.len() <= 1 => {
+                let count = match count {
+                    [n] => n.parse().ok().filter(|n| (1..=MAX_SPAWN).contains(n)).ok_or_else(|| format!("bad count: {}", n))?,
+                    _ => 1,
+                };
+                if kind.eq_ignore_ascii_case("orc") {
+                    return Ok(DebugCommand::SpawnOrcs(count));
+                }
+                let kind = AnimalKind::parse(kind).ok_or_else(|| format!("unknown creature: {}", kind))?;
+                Ok(DebugCommand::SpawnAnimals(kind, count))
+            }
+            ["set", need, orc @ .., value] if !orc.is_empty() => {
+                let need = NEEDS.iter().position(|n| n.eq_ignore_ascii_case(need)).ok_or_else(|| format!("unknown need: {}", need))?;
+                let value = value.parse().ok().filter(|v| (0.0..=100.0).contains(v)).ok_or_else(|| format!("bad value: {}", value))?;
+                Ok(DebugCommand::SetNeed { need, orc: orc.join(" "), value })
+            }
+            ["teleport", orc @ .., x, y] if !orc.is_empty() => {
+                let x = x.parse().map_err(|_| format!("bad x coordinate: {}", x))?;
+                let y = y.parse().map_err(|_| format!("bad y coordinate: {}", y))?;
+                Ok(DebugCommand::Teleport { orc: orc.join(" "), x, y })
+            }
+            ["reveal"] => Ok(DebugCommand::Reveal),
+            ["kill", target @ ..] if !target.is_empty() => {
+                let name = target.join(" ");
+                Ok(DebugCommand::Kill(match AnimalKind::parse(&name) {
+                    Some(kind) => Target::Kind(kind),
+                    None => Target::Orc(name),
+                }))
+            }
+            _ => Err(format!("unknown command: {}", s.trim())),
+        }
+    }
+}
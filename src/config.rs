@@ -0,0 +1,309 @@
+//! Settings loaded from a TOML file with `--config <file>`.
+
+use std::env;
+use std::path::{Path, PathBuf};
+
+use crate::calendar;
+use crate::notify::{Kind, Response, Responses};
+use crate::nursery::Childcare;
+use crate::script::Hook;
+use crate::toml::{self, Document, Value};
+use crate::world;
+
+/// Need levels at which the orc AI changes what it is doing. Lower thresholds
+/// make orcs cautious, higher ones make them reckless.
+#[derive(Clone, Copy, Debug)]
+pub struct AiConfig {
+    /// Below this health an orc drops everything to fix its worst need
+    pub critical_health: f32,
+    /// Thirst at which an orc goes looking for water
+    pub thirst: f32,
+    /// Hunger at which an orc goes looking for food
+    pub hunger: f32,
+    /// Energy below which an orc heads back to camp to sleep
+    pub exhaustion: f32,
+    /// Thirst bad enough to get an orc out of bed at night
+    pub wake_thirst: f32,
+    /// Hunger bad enough to get an orc out of bed at night
+    pub wake_hunger: f32,
+}
+
+impl Default for AiConfig {
+    fn default() -> Self {
+        AiConfig {
+            critical_health: 20.0,
+            thirst: 60.0,
+            hunger: 70.0,
+            exhaustion: 20.0,
+            wake_thirst: 80.0,
+            wake_hunger: 85.0,
+        }
+    }
+}
+
+impl AiConfig {
+    /// Read the `[ai]` table, keeping defaults for anything left out
+    fn from_toml(doc: &mut Document) -> Result<AiConfig, String> {
+        let mut ai = AiConfig::default();
+        let fields = [
+            ("critical_health", &mut ai.critical_health),
+            ("thirst", &mut ai.thirst),
+            ("hunger", &mut ai.hunger),
+            ("exhaustion", &mut ai.exhaustion),
+            ("wake_thirst", &mut ai.wake_thirst),
+            ("wake_hunger", &mut ai.wake_hunger),
+        ];
+        for (name, field) in fields {
+            let Some(value) = doc.take(&format!("ai.{}", name)) else {
+                continue;
+            };
+            match value.as_f64() {
+                Some(v) if (0.0..=100.0).contains(&v) => *field = v as f32,
+                _ => return Err(format!("ai.{} must be a number from 0 to 100, got {}", name, value)),
+            }
+        }
+        Ok(ai)
+    }
+}
+
+/// Caps that keep the simulation responsive as the world fills up
+#[derive(Clone, Copy, Debug)]
+pub struct Limits {
+    /// Births stop once the clan is this big
+    pub max_clan: usize,
+    /// Animals only respawn while there are fewer than this
+    pub max_animals: usize,
+    /// Pathfinding nodes all orcs together may search per tick
+    pub path_budget: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_clan: 15,
+            max_animals: 12,
+            path_budget: 50_000,
+        }
+    }
+}
+
+impl Limits {
+    /// Read the `[limits]` table, keeping defaults for anything left out
+    fn from_toml(doc: &mut Document) -> Result<Limits, String> {
+        let mut limits = Limits::default();
+        let fields = [
+            ("max_clan", &mut limits.max_clan),
+            ("max_animals", &mut limits.max_animals),
+            ("path_budget", &mut limits.path_budget),
+        ];
+        for (name, field) in fields {
+            let Some(value) = doc.take(&format!("limits.{}", name)) else {
+                continue;
+            };
+            match value {
+                Value::Int(n) if n > 0 => *field = n as usize,
+                _ => return Err(format!("limits.{} must be a positive whole number, got {}", name, value)),
+            }
+        }
+        Ok(limits)
+    }
+}
+
+/// How much an orc's needs build up each tick
+#[derive(Clone, Copy, Debug)]
+pub struct NeedRates {
+    /// Hunger gained each tick of the day
+    pub hunger: f32,
+    /// Hunger gained each tick of the night
+    pub night_hunger: f32,
+    /// Thirst gained each tick, day or night
+    pub thirst: f32,
+    /// Energy lost each tick of the day by an orc that isn't asleep
+    pub fatigue: f32,
+    /// Energy lost each tick of the night by an orc that isn't asleep
+    pub night_fatigue: f32,
+}
+
+impl Default for NeedRates {
+    fn default() -> Self {
+        NeedRates {
+            hunger: 0.5,
+            night_hunger: 0.3,
+            thirst: 0.6,
+            fatigue: 0.4,
+            night_fatigue: 0.8,
+        }
+    }
+}
+
+impl NeedRates {
+    /// Read the `[needs]` table, keeping defaults for anything left out
+    fn from_toml(doc: &mut Document) -> Result<NeedRates, String> {
+        let mut rates = NeedRates::default();
+        let fields = [
+            ("hunger", &mut rates.hunger),
+            ("night_hunger", &mut rates.night_hunger),
+            ("thirst", &mut rates.thirst),
+            ("fatigue", &mut rates.fatigue),
+            ("night_fatigue", &mut rates.night_fatigue),
+        ];
+        for (name, field) in fields {
+            let Some(value) = doc.take(&format!("needs.{}", name)) else {
+                continue;
+            };
+            match value.as_f64() {
+                Some(v) if (0.0..=100.0).contains(&v) => *field = v as f32,
+                _ => return Err(format!("needs.{} must be a number from 0 to 100, got {}", name, value)),
+            }
+        }
+        Ok(rates)
+    }
+}
+
+/// How a new world is set up and how fast its days go by
+#[derive(Clone, Copy, Debug)]
+pub struct WorldConfig {
+    /// Orcs in the clan when it settles
+    pub clan: usize,
+    /// Animals roaming the map at the start, or a handful picked at random
+    pub animals: Option<usize>,
+    /// Ticks in one full day/night cycle
+    pub day_length: u64,
+    /// Ticks a picked bush takes to fruit again
+    pub bush_regrowth: u64,
+}
+
+impl Default for WorldConfig {
+    fn default() -> Self {
+        WorldConfig {
+            clan: 5,
+            animals: None,
+            day_length: calendar::DAY_LENGTH,
+            bush_regrowth: world::BUSH_REGROWTH,
+        }
+    }
+}
+
+impl WorldConfig {
+    /// Read the `[world]` table, keeping defaults for anything left out
+    fn from_toml(doc: &mut Document) -> Result<WorldConfig, String> {
+        let mut setup = WorldConfig::default();
+        let whole = |name: &str, value: Option<Value>, min: u64| match value {
+            None => Ok(None),
+            Some(Value::Int(n)) if n >= min as i64 => Ok(Some(n as u64)),
+            Some(value) => Err(format!("world.{} must be a whole number of at least {}, got {}", name, min, value)),
+        };
+        if let Some(n) = whole("clan", doc.take("world.clan"), 1)? {
+            setup.clan = n as usize;
+        }
+        if let Some(n) = whole("animals", doc.take("world.animals"), 0)? {
+            setup.animals = Some(n as usize);
+        }
+        if let Some(n) = whole("day_length", doc.take("world.day_length"), calendar::MIN_DAY_LENGTH)? {
+            setup.day_length = n;
+        }
+        if let Some(n) = whole("bush_regrowth", doc.take("world.bush_regrowth"), 1)? {
+            setup.bush_regrowth = n;
+        }
+        Ok(setup)
+    }
+}
+
+/// Read the `[keys]` table as it's written, leaving the frontend to make
+/// sense of the commands and keys
+fn keys_from_toml(doc: &mut Document) -> Result<Vec<(String, String)>, String> {
+    let names: Vec<String> = doc.keys().filter_map(|k| k.strip_prefix("keys.")).map(String::from).collect();
+    let mut keys = Vec::new();
+    for name in names {
+        match doc.take(&format!("keys.{}", name)) {
+            Some(Value::Str(spec)) => keys.push((name, spec)),
+            Some(value) => return Err(format!("keys.{} must be a string like \"ctrl+f\" or \"g c\", got {}", name, value)),
+            None => {}
+        }
+    }
+    Ok(keys)
+}
+
+/// Read the `[notify]` table, bannering anything left out
+fn notify_from_toml(doc: &mut Document) -> Result<Responses, String> {
+    let mut responses = Responses::default();
+    for kind in Kind::ALL {
+        let Some(value) = doc.take(&format!("notify.{}", kind.name())) else {
+            continue;
+        };
+        let response = match &value {
+            Value::Str(name) => Response::parse(name),
+            _ => None,
+        };
+        let Some(response) = response else {
+            let names: Vec<&str> = Response::ALL.iter().map(|r| r.name()).collect();
+            return Err(format!("notify.{} must be one of {}, got {}", kind.name(), names.join(", "), value));
+        };
+        responses.set(kind, response);
+    }
+    Ok(responses)
+}
+
+#[derive(Default)]
+pub struct Config {
+    pub ai: AiConfig,
+    /// Key bindings from `[keys]`, each a command name and the keys for it
+    pub keys: Vec<(String, String)>,
+    pub limits: Limits,
+    pub needs: NeedRates,
+    pub world: WorldConfig,
+    /// How each kind of notice is brought to the player's attention
+    pub notify: Responses,
+    /// Who looks after the clan's babies
+    pub childcare: Childcare,
+    /// Name of the theme to start with
+    pub theme: Option<String>,
+    /// What the scripts in the `scripts` folder have the world do, read
+    /// apart from the file itself
+    pub hooks: Vec<Hook>,
+}
+
+impl Config {
+    /// Read settings from the text of a config file
+    pub fn parse(src: &str) -> Result<Config, String> {
+        let mut doc = toml::parse(src).map_err(|e| e.to_string())?;
+        let config = Config {
+            ai: AiConfig::from_toml(&mut doc)?,
+            keys: keys_from_toml(&mut doc)?,
+            limits: Limits::from_toml(&mut doc)?,
+            needs: NeedRates::from_toml(&mut doc)?,
+            world: WorldConfig::from_toml(&mut doc)?,
+            notify: notify_from_toml(&mut doc)?,
+            childcare: match doc.take("childcare") {
+                Some(Value::Str(name)) => Childcare::parse(&name).ok_or_else(|| {
+                    let names: Vec<&str> = Childcare::ALL.iter().map(|c| c.name()).collect();
+                    format!("childcare must be one of {}, got \"{}\"", names.join(", "), name)
+                })?,
+                Some(value) => return Err(format!("childcare must be a string, got {}", value)),
+                None => Childcare::default(),
+            },
+            theme: match doc.take("theme") {
+                Some(Value::Str(name)) => Some(name),
+                Some(value) => return Err(format!("theme must be a string, got {}", value)),
+                None => None,
+            },
+            hooks: Vec::new(),
+        };
+        if let Some(key) = doc.keys().next() {
+            return Err(format!("unknown setting '{}'", key));
+        }
+        Ok(config)
+    }
+}
+
+/// Folder holding the config file and its `themes`: the one `--config` points
+/// into, or else `$XDG_CONFIG_HOME/orcs` (`~/.config/orcs`)
+pub fn dir(config_path: Option<&Path>) -> Option<PathBuf> {
+    if let Some(path) = config_path {
+        return Some(path.parent().unwrap_or(Path::new(".")).to_path_buf());
+    }
+    match env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty()) {
+        Some(base) => Some(PathBuf::from(base).join("orcs")),
+        None => env::var_os("HOME").map(|home| PathBuf::from(home).join(".config").join("orcs")),
+    }
+}
@@ -0,0 +1,335 @@
+//! Scenarios of the player's own, read from a file with `--scenario <file>`.
+
+use std::fs;
+use std::path::Path;
+
+use crate::animal::AnimalKind;
+use crate::calendar;
+use crate::diary::NEEDS;
+use crate::event::Severity;
+use crate::orc::{Job, Orc, Sex};
+use crate::scenario::Scenario;
+use crate::script::{self, Action, Arrival, Hook, Store, Trigger};
+use crate::toml::{self, Document, Value};
+use crate::world::{BARREL_CAPACITY, MAP_HEIGHT, MAP_WIDTH, Terrain, World};
+
+/// Ages, in years, the founders of a clan can be
+const FOUNDER_AGES: std::ops::RangeInclusive<i64> = 16..=80;
+
+/// How a world is to start, as a scenario file sets it out
+#[derive(Clone, Default)]
+pub struct Custom {
+    /// The file as it was read, to keep in recordings and saves
+    pub source: String,
+    pub name: Option<String>,
+    /// Seed to grow the map from, when none is given on the command line
+    pub seed: Option<u64>,
+    /// Which built-in scenario it plays out as
+    pub scenario: Scenario,
+    pub terrain: Option<Patch>,
+    /// The clan, if not the usual one rolled at random
+    pub founders: Vec<Founder>,
+    /// What the stores start with, where not the usual
+    pub stores: Vec<(Store, u32)>,
+    /// What comes on the days after, as hooks on their day
+    pub events: Vec<Hook>,
+}
+
+/// Terrain drawn over part of the map
+#[derive(Clone)]
+pub struct Patch {
+    pub x: usize,
+    pub y: usize,
+    /// Row by row from the top; None leaves a tile as it was
+    pub rows: Vec<Vec<Option<Terrain>>>,
+}
+
+/// One of the orcs a clan starts with, as much as the file says of it
+#[derive(Clone, Default)]
+pub struct Founder {
+    pub name: Option<String>,
+    pub sex: Option<Sex>,
+    pub job: Option<Job>,
+    /// In years
+    pub age: Option<u64>,
+    pub hunting: Option<f32>,
+    pub foraging: Option<f32>,
+    /// Needs set, by their index in `NEEDS`
+    pub needs: Vec<(usize, f32)>,
+}
+
+impl Custom {
+    pub fn load(path: &Path) -> Result<Custom, String> {
+        let src = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Custom::parse(&src).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    /// Read a scenario from the text of its file
+    pub fn parse(src: &str) -> Result<Custom, String> {
+        let mut doc = toml::parse(src).map_err(|e| e.to_string())?;
+        let custom = Custom {
+            source: src.to_string(),
+            name: string(&mut doc, "name")?,
+            seed: match doc.take("seed") {
+                Some(Value::Int(n)) if n >= 0 => Some(n as u64),
+                Some(value) => return Err(format!("seed must be a whole number, got {}", value)),
+                None => None,
+            },
+            scenario: match string(&mut doc, "scenario")? {
+                Some(name) => Scenario::parse(&name).ok_or_else(|| format!("scenario must be sandbox or defense, got \"{}\"", name))?,
+                None => Scenario::default(),
+            },
+            terrain: Patch::from_toml(&mut doc)?,
+            founders: (0..doc.count("orc")).map(|i| Founder::from_toml(&mut doc, i)).collect::<Result<_, _>>()?,
+            stores: stores_from_toml(&mut doc)?,
+            events: (0..doc.count("event")).map(|i| event_from_toml(&mut doc, i)).collect::<Result<_, _>>()?,
+        };
+        let mut names: Vec<&str> = custom.founders.iter().filter_map(|f| f.name.as_deref()).collect();
+        names.sort();
+        if let Some(pair) = names.windows(2).find(|pair| pair[0] == pair[1]) {
+            return Err(format!("two orcs are named {}", pair[0]));
+        }
+        if let Some(key) = doc.keys().next() {
+            return Err(match key.split('.').collect::<Vec<_>>()[..] {
+                [table, n, name] if let Ok(n) = n.parse::<usize>() => format!("{} {}: unknown setting '{}'", table, n + 1, name),
+                _ => format!("unknown setting '{}'", key),
+            });
+        }
+        Ok(custom)
+    }
+
+    /// How many orcs the clan starts with, `clan` unless the file names them
+    pub fn clan(&self, clan: usize) -> usize {
+        if self.founders.is_empty() { clan } else { self.founders.len() }
+    }
+
+    /// Draw the terrain over a newly grown world and fill its stores
+    pub fn lay_out(&self, world: &mut World) {
+        if let Some(patch) = &self.terrain {
+            patch.draw(world);
+        }
+        for &(store, amount) in &self.stores {
+            match store {
+                Store::Meat => world.restock(amount),
+                Store::Cooked => world.cooked_stockpile = amount,
+                Store::Berries => world.berry_stockpile = amount,
+                Store::Wood => world.wood_stockpile = amount,
+                Store::Stone => world.stone_stockpile = amount,
+                Store::Water => world.water_barrel = amount,
+            }
+        }
+    }
+
+    /// Make the clan just rolled into the one the file sets out
+    pub fn found(&self, orcs: &mut [Orc]) {
+        for (orc, founder) in orcs.iter_mut().zip(&self.founders) {
+            founder.fit(orc);
+        }
+    }
+}
+
+impl Patch {
+    /// Read the `[terrain]` table, if there is one
+    fn from_toml(doc: &mut Document) -> Result<Option<Patch>, String> {
+        let corner = |doc: &mut Document, key: &str| match doc.take(key) {
+            Some(Value::Int(n)) if n >= 0 => Ok(n as usize),
+            Some(value) => Err(format!("{} must be a whole number, got {}", key, value)),
+            None => Ok(0),
+        };
+        let (x, y) = (corner(doc, "terrain.x")?, corner(doc, "terrain.y")?);
+        let Some(map) = string(doc, "terrain.map")? else {
+            return Ok(None);
+        };
+        let mut rows = Vec::new();
+        for (i, line) in map.lines().enumerate() {
+            let row = line
+                .chars()
+                .map(|c| match c {
+                    ' ' => Ok(None),
+                    '.' => Ok(Some(Terrain::Grass)),
+                    'T' => Ok(Some(Terrain::Tree)),
+                    'f' => Ok(Some(Terrain::BareFruitTree)),
+                    '*' => Ok(Some(Terrain::Bush)),
+                    'h' => Ok(Some(Terrain::Herb)),
+                    '#' => Ok(Some(Terrain::Rock)),
+                    '~' => Ok(Some(Terrain::Water)),
+                    _ => Err(format!("terrain.map row {}: unknown terrain '{}'", i + 1, c)),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            rows.push(row);
+        }
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        if x + width > MAP_WIDTH || y + rows.len() > MAP_HEIGHT {
+            return Err(format!("terrain.map runs off the map, which is {} by {} tiles", MAP_WIDTH, MAP_HEIGHT));
+        }
+        Ok(Some(Patch { x, y, rows }))
+    }
+
+    fn covers(&self, x: usize, y: usize) -> bool {
+        y >= self.y && x >= self.x && self.rows.get(y - self.y).is_some_and(|row| x - self.x < row.len())
+    }
+
+    /// Draw the patch over `world`, leaving the campfire and the racks by it
+    /// standing, and keep the spots mushrooms and herbs come up on in step
+    fn draw(&self, world: &mut World) {
+        for (dy, row) in self.rows.iter().enumerate() {
+            for (dx, terrain) in row.iter().enumerate() {
+                let (x, y) = (self.x + dx, self.y + dy);
+                let camp = matches!(world.get(x, y), Terrain::Campfire | Terrain::MeatRack | Terrain::WaterBarrel | Terrain::ToolRack);
+                if let Some(terrain) = *terrain
+                    && !camp
+                {
+                    world.set(x, y, terrain);
+                }
+            }
+        }
+        let (mut mushroom_spots, mut herb_spots) = (std::mem::take(&mut world.mushroom_spots), std::mem::take(&mut world.herb_spots));
+        mushroom_spots.retain(|&(x, y)| !self.covers(x, y) || world.get(x, y) == Terrain::Grass);
+        herb_spots.retain(|&(x, y)| !self.covers(x, y) || world.get(x, y) == Terrain::Herb);
+        for (dy, row) in self.rows.iter().enumerate() {
+            for dx in 0..row.len() {
+                let (x, y) = (self.x + dx, self.y + dy);
+                if world.get(x, y) == Terrain::Herb && !herb_spots.contains(&(x, y)) {
+                    herb_spots.push((x, y));
+                }
+            }
+        }
+        (world.mushroom_spots, world.herb_spots) = (mushroom_spots, herb_spots);
+    }
+}
+
+impl Founder {
+    /// Read the `n`th `[[orc]]` table
+    fn from_toml(doc: &mut Document, n: usize) -> Result<Founder, String> {
+        let key = |name: &str| format!("orc.{}.{}", n, name);
+        let fail = |msg: String| format!("orc {}: {}", n + 1, msg);
+        let mut founder = Founder { name: string(doc, &key("name")).map_err(fail)?, ..Founder::default() };
+        if let Some(sex) = string(doc, &key("sex")).map_err(fail)? {
+            let sex = [Sex::Female, Sex::Male].into_iter().find(|s| s.name() == sex);
+            founder.sex = Some(sex.ok_or_else(|| fail("sex must be female or male".to_string()))?);
+        }
+        if let Some(job) = string(doc, &key("job")).map_err(fail)? {
+            let found = Job::ALL.into_iter().find(|j| j.name() == job);
+            founder.job = Some(found.ok_or_else(|| {
+                let names: Vec<&str> = Job::ALL.iter().map(|j| j.name()).collect();
+                fail(format!("job must be one of {}, got \"{}\"", names.join(", "), job))
+            })?);
+        }
+        founder.age = match doc.take(&key("age")) {
+            Some(Value::Int(n)) if FOUNDER_AGES.contains(&n) => Some(n as u64),
+            Some(value) => return Err(fail(format!("age must be a whole number of years from {} to {}, got {}", FOUNDER_AGES.start(), FOUNDER_AGES.end(), value))),
+            None => None,
+        };
+        founder.hunting = percent(doc, &key("hunting")).map_err(fail)?;
+        founder.foraging = percent(doc, &key("foraging")).map_err(fail)?;
+        for (i, need) in NEEDS.iter().enumerate() {
+            if let Some(value) = percent(doc, &key(&need.to_lowercase())).map_err(fail)? {
+                founder.needs.push((i, value));
+            }
+        }
+        Ok(founder)
+    }
+
+    /// Make `orc` this founder, keeping what was rolled for anything left out
+    fn fit(&self, orc: &mut Orc) {
+        if let Some(name) = &self.name {
+            orc.name = name.clone();
+        }
+        if let Some(sex) = self.sex {
+            orc.sex = sex;
+        }
+        if let Some(job) = self.job {
+            orc.job = job;
+        }
+        if let Some(age) = self.age {
+            orc.birth_day = 1 - (age * calendar::YEAR_LENGTH) as i64;
+        }
+        if let Some(hunting) = self.hunting {
+            orc.hunt_skill = hunting;
+        }
+        if let Some(foraging) = self.foraging {
+            orc.forage_skill = foraging;
+        }
+        for &(need, value) in &self.needs {
+            *orc.need_mut(need) = value;
+        }
+    }
+}
+
+/// Read the `[stores]` table
+fn stores_from_toml(doc: &mut Document) -> Result<Vec<(Store, u32)>, String> {
+    let mut stores = Vec::new();
+    for store in Store::ALL {
+        let key = format!("stores.{}", store.name());
+        let most = if store == Store::Water { BARREL_CAPACITY as i64 } else { u32::MAX as i64 };
+        match doc.take(&key) {
+            Some(Value::Int(n)) if (0..=most).contains(&n) => stores.push((store, n as u32)),
+            Some(value) if store == Store::Water => return Err(format!("{} must be a whole number from 0 to {}, got {}", key, most, value)),
+            Some(value) => return Err(format!("{} must be a whole number, got {}", key, value)),
+            None => {}
+        }
+    }
+    Ok(stores)
+}
+
+/// Read the `n`th `[[event]]` table as the hook it comes to
+fn event_from_toml(doc: &mut Document, n: usize) -> Result<Hook, String> {
+    let key = |name: &str| format!("event.{}.{}", n, name);
+    let fail = |msg: String| format!("event {}: {}", n + 1, msg);
+    let day = match doc.take(&key("day")) {
+        Some(Value::Int(day)) => script::parse_day(&day.to_string()).map_err(fail)?,
+        Some(value) => return Err(fail(format!("day must be a whole number, got {}", value))),
+        None => return Err(fail("needs a day".to_string())),
+    };
+    let mut actions = Vec::new();
+    let severity = match string(doc, &key("severity")).map_err(fail)? {
+        Some(name) => Severity::ALL.into_iter().find(|s| s.name() == name).ok_or_else(|| fail(format!("unknown severity \"{}\"", name)))?,
+        None => Severity::Info,
+    };
+    if let Some(text) = string(doc, &key("log")).map_err(fail)? {
+        actions.push(Action::Log(severity, text));
+    }
+    let count = match doc.take(&key("count")) {
+        Some(Value::Int(count)) if count > 0 => count as usize,
+        Some(value) => return Err(fail(format!("count must be a positive whole number, got {}", value))),
+        None => 1,
+    };
+    if let Some(who) = string(doc, &key("arrive")).map_err(fail)? {
+        let arrival = Arrival::parse(&who).ok_or_else(|| {
+            let names: Vec<&str> = AnimalKind::ALL.iter().map(|k| k.name()).collect();
+            fail(format!("arrive must be goblin or a creature ({}), got \"{}\"", names.join(", ").to_lowercase(), who))
+        })?;
+        actions.push(Action::Arrive(arrival, count));
+    }
+    if actions.is_empty() {
+        return Err(fail("needs something to happen: arrive, log or both".to_string()));
+    }
+    Ok(Hook { trigger: Trigger::Day(day), chance: 1.0, actions })
+}
+
+fn string(doc: &mut Document, key: &str) -> Result<Option<String>, String> {
+    match doc.take(key) {
+        Some(Value::Str(s)) => Ok(Some(s)),
+        Some(value) => Err(format!("{} must be a string, got {}", shown(key), value)),
+        None => Ok(None),
+    }
+}
+
+fn percent(doc: &mut Document, key: &str) -> Result<Option<f32>, String> {
+    match doc.take(key) {
+        Some(value) => match value.as_f64() {
+            Some(v) if (0.0..=100.0).contains(&v) => Ok(Some(v as f32)),
+            _ => Err(format!("{} must be a number from 0 to 100, got {}", shown(key), value)),
+        },
+        None => Ok(None),
+    }
+}
+
+/// A key as it's written in the file: `age` for `orc.0.age`, whose error
+/// says which orc it's about
+fn shown(key: &str) -> &str {
+    match key.split('.').collect::<Vec<_>>()[..] {
+        [_, n, name] if n.parse::<usize>().is_ok() => name,
+        _ => key,
+    }
+}
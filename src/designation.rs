@@ -0,0 +1,52 @@
+//! Standing orders the player paints onto the map.
+
+use crate::orc::Job;
+use crate::world::Terrain;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Designation {
+    Chop,
+    Gather,
+}
+
+impl Designation {
+    /// Parse the one-word form used by the agent interface, `chop` or `gather`
+    pub fn parse(s: &str) -> Option<Designation> {
+        match s {
+            "chop" => Some(Designation::Chop),
+            "gather" => Some(Designation::Gather),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Designation::Chop => "chop",
+            Designation::Gather => "gather",
+        }
+    }
+
+    /// What the cursor is marking, for the status bar
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Designation::Chop => "trees to chop",
+            Designation::Gather => "bushes to gather",
+        }
+    }
+
+    /// Whether this order can be placed on (and still applies to) `terrain`
+    pub fn applies_to(&self, terrain: Terrain) -> bool {
+        match self {
+            Designation::Chop => terrain == Terrain::Tree,
+            Designation::Gather => matches!(terrain, Terrain::Bush | Terrain::DepletedBush),
+        }
+    }
+
+    /// Who carries the order out
+    pub fn job(&self) -> Job {
+        match self {
+            Designation::Chop => Job::Woodcutter,
+            Designation::Gather => Job::Forager,
+        }
+    }
+}
@@ -0,0 +1,48 @@
+//! What an orc has been through lately, kept for the inspection screen.
+
+use std::collections::VecDeque;
+
+/// Ticks between one reading of the needs and the next
+const READING_TICKS: u64 = 5;
+/// Readings kept, three days' worth
+const READINGS: usize = 60;
+/// Changes of activity kept
+const DOINGS: usize = 12;
+
+/// The needs in a reading, in the order they're read
+pub const NEEDS: [&str; 6] = ["Health", "Hunger", "Energy", "Thirst", "Warmth", "Morale"];
+
+#[derive(Default)]
+pub struct Diary {
+    readings: VecDeque<[f32; 6]>,
+    doings: VecDeque<(u64, String)>,
+}
+
+impl Diary {
+    /// Put down the needs if a reading is due at `tick`, and what the orc is
+    /// doing if that has changed
+    pub fn note(&mut self, tick: u64, needs: [f32; 6], doing: &str) {
+        if tick.is_multiple_of(READING_TICKS) {
+            if self.readings.len() == READINGS {
+                self.readings.pop_front();
+            }
+            self.readings.push_back(needs);
+        }
+        if self.doings.back().is_none_or(|(_, last)| last != doing) {
+            if self.doings.len() == DOINGS {
+                self.doings.pop_front();
+            }
+            self.doings.push_back((tick, doing.to_string()));
+        }
+    }
+
+    /// The last `count` readings of the need at `need` in `NEEDS`, oldest first
+    pub fn history(&self, need: usize, count: usize) -> impl Iterator<Item = f32> + '_ {
+        self.readings.iter().skip(self.readings.len().saturating_sub(count)).map(move |r| r[need])
+    }
+
+    /// What the orc has turned to and when, latest first
+    pub fn doings(&self) -> impl Iterator<Item = &(u64, String)> {
+        self.doings.iter().rev()
+    }
+}
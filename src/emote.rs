@@ -0,0 +1,68 @@
+/// How many ticks an emote stays above an orc's head
+const EMOTE_TTL: u64 = 4;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum EmoteKind {
+    Alarm,
+    Love,
+    Sleepy,
+    Confused,
+}
+
+impl EmoteKind {
+    pub fn symbol(&self) -> char {
+        match self {
+            EmoteKind::Alarm => '!',
+            EmoteKind::Love => '♥',
+            EmoteKind::Sleepy => 'z',
+            EmoteKind::Confused => '?',
+        }
+    }
+}
+
+pub struct Emote {
+    pub x: usize,
+    pub y: usize,
+    pub kind: EmoteKind,
+    expires_at: u64,
+}
+
+/// Transient overlay of emote glyphs drawn one tile above the orc that triggered them
+pub struct EmoteQueue {
+    emotes: Vec<Emote>,
+}
+
+impl Default for EmoteQueue {
+    fn default() -> Self {
+        EmoteQueue::new()
+    }
+}
+
+impl EmoteQueue {
+    pub fn new() -> Self {
+        EmoteQueue { emotes: Vec::new() }
+    }
+
+    /// Queue an emote above the orc standing at (x, y)
+    pub fn push(&mut self, x: usize, y: usize, kind: EmoteKind, tick: u64) {
+        if y == 0 {
+            return; // no room above the top row
+        }
+        let y = y - 1;
+        self.emotes.retain(|e| e.x != x || e.y != y);
+        self.emotes.push(Emote {
+            x,
+            y,
+            kind,
+            expires_at: tick + EMOTE_TTL,
+        });
+    }
+
+    pub fn prune(&mut self, tick: u64) {
+        self.emotes.retain(|e| tick < e.expires_at);
+    }
+
+    pub fn at(&self, x: usize, y: usize) -> Option<&Emote> {
+        self.emotes.iter().find(|e| e.x == x && e.y == y)
+    }
+}
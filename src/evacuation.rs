@@ -0,0 +1,121 @@
+//! The clan leaving camp together when it can't hold out there.
+
+use crate::pathfinding;
+use crate::world::World;
+
+/// Tiles walked between stops to let the clan catch up
+const LEG_LENGTH: usize = 10;
+/// Ticks the clan waits for stragglers before setting off on the next leg regardless
+const LEG_PATIENCE: u64 = 30;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Reason {
+    Overrun,
+    Famine,
+    Ordered,
+}
+
+impl Reason {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Reason::Overrun => "overrun",
+            Reason::Famine => "famine",
+            Reason::Ordered => "ordered",
+        }
+    }
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Reason::Overrun => "Wolves have overrun the camp",
+            Reason::Famine => "The food has run out",
+            Reason::Ordered => "The chief gives the word",
+        }
+    }
+}
+
+pub enum Stage {
+    /// On the road, out to the rally point or back home
+    Marching { homeward: bool },
+    /// Holed up at the rally point since the given tick
+    Sheltering { since: u64 },
+}
+
+pub struct Evacuation {
+    pub reason: Reason,
+    pub rally: (usize, usize),
+    pub stage: Stage,
+    /// Stops along the way from camp to the rally point, ending at it
+    stops: Vec<(usize, usize)>,
+    /// The stop the clan is making for, and since when
+    leg: usize,
+    leg_started: u64,
+}
+
+impl Evacuation {
+    /// Set off from `home` for `rally`
+    pub fn new(reason: Reason, home: (usize, usize), rally: (usize, usize), world: &World, tick: u64) -> Self {
+        let route = pathfinding::find_path(world, home.0, home.1, rally.0, rally.1, false)
+            .unwrap_or_else(|| straight_line(home, rally));
+        let mut stops: Vec<(usize, usize)> = route.iter().copied().skip(LEG_LENGTH - 1).step_by(LEG_LENGTH).collect();
+        if stops.last() != Some(&rally) {
+            stops.push(rally);
+        }
+        Evacuation { reason, rally, stage: Stage::Marching { homeward: false }, stops, leg: 0, leg_started: tick }
+    }
+
+    /// Where the clan is gathering now
+    pub fn stop(&self) -> (usize, usize) {
+        match self.stage {
+            Stage::Sheltering { .. } => self.rally,
+            Stage::Marching { .. } => self.stops[self.leg],
+        }
+    }
+
+    /// Move on to the next stop once everyone on the march is there, or the
+    /// clan has waited long enough. Returns true when the last one is reached.
+    pub fn advance(&mut self, all_arrived: bool, tick: u64) -> bool {
+        if !all_arrived && tick < self.leg_started + LEG_PATIENCE {
+            return false;
+        }
+        if self.leg + 1 == self.stops.len() {
+            return true;
+        }
+        self.leg += 1;
+        self.leg_started = tick;
+        false
+    }
+
+    /// Settle in at the rally point
+    pub fn shelter(&mut self, tick: u64) {
+        self.stage = Stage::Sheltering { since: tick };
+    }
+
+    /// Walk back by the stops already reached, ending at `home`
+    pub fn head_home(&mut self, home: (usize, usize), tick: u64) {
+        self.stops.truncate(self.leg);
+        self.stops.reverse();
+        self.stops.push(home);
+        self.leg = 0;
+        self.leg_started = tick;
+        self.stage = Stage::Marching { homeward: true };
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self.stage {
+            Stage::Marching { homeward: false } => "Evacuating",
+            Stage::Sheltering { .. } => "Sheltering",
+            Stage::Marching { homeward: true } => "Returning",
+        }
+    }
+}
+
+/// Every tile from `from` to `to` in a straight line, for when no path turns up
+fn straight_line(from: (usize, usize), to: (usize, usize)) -> Vec<(usize, usize)> {
+    let steps = from.0.abs_diff(to.0).max(from.1.abs_diff(to.1));
+    (1..=steps)
+        .map(|i| {
+            let lerp = |a: usize, b: usize| (a as i64 + (b as i64 - a as i64) * i as i64 / steps as i64) as usize;
+            (lerp(from.0, to.0), lerp(from.1, to.1))
+        })
+        .collect()
+}
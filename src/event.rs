@@ -1,37 +1,232 @@
-use ratatui::style::Color;
+//! What happens in the game, as told in the event log.
+
+use std::collections::VecDeque;
+use std::collections::vec_deque::Iter;
+use std::fs::File;
+use std::io::{self, LineWriter, Write};
+use std::path::Path;
+
+use crate::json;
+
+/// Events kept in memory for the UI
+const MAX_EVENTS: usize = 1000;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum EventCategory {
+    General,
+    Birth,
+    Death,
+    Hunt,
+    Food,
+    Alert,
+    Spirit, // omens from the spirits of the wild
+    Social, // orcs' lives together: joining the clan, birthdays, huts, care of babies
+    Combat, // blows traded with wolves, boars, goblins and rivals
+}
+
+impl EventCategory {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EventCategory::General => "general",
+            EventCategory::Birth => "birth",
+            EventCategory::Death => "death",
+            EventCategory::Hunt => "hunt",
+            EventCategory::Food => "food",
+            EventCategory::Alert => "alert",
+            EventCategory::Spirit => "spirit",
+            EventCategory::Social => "social",
+            EventCategory::Combat => "combat",
+        }
+    }
+}
+
+/// How much an event matters to the clan, for the log to tell it accordingly
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Severity {
+    /// The everyday comings and goings
+    Info,
+    /// Something that went well
+    Good,
+    /// Something that wants seeing to
+    Warning,
+    /// Harm done, or about to be
+    Danger,
+}
+
+impl Severity {
+    pub const ALL: [Severity; 4] = [Severity::Info, Severity::Good, Severity::Warning, Severity::Danger];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Good => "good",
+            Severity::Warning => "warning",
+            Severity::Danger => "danger",
+        }
+    }
+}
 
 pub struct Event {
     pub tick: u64,
+    pub category: EventCategory,
     pub message: String,
-    pub color: Color,
+    pub severity: Severity,
+    pub at: Option<(usize, usize)>, // the tile it happened on, if it happened anywhere in particular
+}
+
+/// Every event logged, appended to a file one JSON line at a time
+pub struct Archive {
+    file: LineWriter<File>,
+}
+
+impl Archive {
+    pub fn create(path: &Path) -> io::Result<Archive> {
+        Ok(Archive { file: LineWriter::new(File::create(path)?) })
+    }
+
+    fn write(&mut self, event: &Event) -> io::Result<()> {
+        let mut line = json::Object::new()
+            .num("tick", event.tick)
+            .str("category", event.category.name())
+            .str("severity", event.severity.name())
+            .str("message", &event.message);
+        if let Some((x, y)) = event.at {
+            line = line.num("x", x).num("y", y);
+        }
+        let line = line.finish();
+        writeln!(self.file, "{}", line)
+    }
 }
 
 pub struct EventLog {
-    pub events: Vec<Event>,
-    pub max_events: usize,
+    events: VecDeque<Event>, // the latest, oldest first
+    pub total: u64, // number of events ever logged, including discarded ones
+    archive: Option<Archive>,
+    hidden: Vec<EventCategory>, // categories left out when the log is shown
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        EventLog::new()
+    }
 }
 
 impl EventLog {
     pub fn new() -> Self {
         EventLog {
-            events: Vec::new(),
-            max_events: 100,
+            events: VecDeque::with_capacity(MAX_EVENTS),
+            total: 0,
+            archive: None,
+            hidden: Vec::new(),
+        }
+    }
+
+    /// Write every event from now on to `archive`, starting with those still in memory
+    pub fn archive_to(&mut self, mut archive: Archive) -> io::Result<()> {
+        for event in &self.events {
+            archive.write(event)?;
         }
+        self.archive = Some(archive);
+        Ok(())
     }
 
-    pub fn log(&mut self, tick: u64, message: String, color: Color) {
-        self.events.push(Event {
-            tick,
-            message,
-            color,
-        });
-        if self.events.len() > self.max_events {
-            self.events.remove(0);
+    /// Stop archiving, handing back the archive to carry on with elsewhere
+    pub fn take_archive(&mut self) -> Option<Archive> {
+        self.archive.take()
+    }
+
+    pub fn log(&mut self, tick: u64, message: String, severity: Severity) {
+        self.log_as(tick, EventCategory::General, message, severity);
+    }
+
+    pub fn log_as(&mut self, tick: u64, category: EventCategory, message: String, severity: Severity) {
+        self.record(Event { tick, category, message, severity, at: None });
+    }
+
+    /// Log an event that happened on the tile `at`
+    pub fn log_at(&mut self, tick: u64, category: EventCategory, message: String, severity: Severity, at: (usize, usize)) {
+        self.record(Event { tick, category, message, severity, at: Some(at) });
+    }
+
+    fn record(&mut self, event: Event) {
+        let tick = event.tick;
+        // A disk that fills up shouldn't take the game down with it
+        let archived = self.archive.as_mut().map(|archive| archive.write(&event));
+        if self.events.len() == MAX_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+        self.total += 1;
+        if let Some(Err(e)) = archived {
+            self.archive = None;
+            self.log_as(tick, EventCategory::Alert, format!("Could not write to the event archive: {}", e), Severity::Danger);
         }
     }
 
-    pub fn recent(&self, count: usize) -> &[Event] {
+    /// The latest `count` events still in memory, oldest first
+    pub fn recent(&self, count: usize) -> Iter<'_, Event> {
         let start = self.events.len().saturating_sub(count);
-        &self.events[start..]
+        self.events.range(start..)
+    }
+
+    /// The `count` shown events that end `back` shown events short of the
+    /// latest, oldest first
+    pub fn recent_before(&self, count: usize, back: usize) -> Vec<&Event> {
+        let mut events: Vec<&Event> = self.events.iter().rev().filter(|e| self.shows(e.category)).skip(back).take(count).collect();
+        events.reverse();
+        events
+    }
+
+    /// The event numbered `number`, counting every event ever logged from
+    /// zero, if it's still in memory
+    pub fn numbered(&self, number: u64) -> Option<&Event> {
+        let first = self.total - self.events.len() as u64;
+        self.events.get(number.checked_sub(first)? as usize)
+    }
+
+    /// The number of the shown event `back` shown events short of the latest
+    pub fn shown_number(&self, back: usize) -> Option<u64> {
+        let first = self.total - self.events.len() as u64;
+        let index = self.events.iter().enumerate().rev().filter(|(_, e)| self.shows(e.category)).nth(back)?.0;
+        Some(first + index as u64)
+    }
+
+    /// How many shown events have come in since the event numbered `number`
+    pub fn shown_since(&self, number: u64) -> usize {
+        self.since(number + 1).filter(|e| self.shows(e.category)).count()
+    }
+
+    /// Events still in memory that aren't hidden
+    pub fn held(&self) -> usize {
+        self.events.iter().filter(|e| self.shows(e.category)).count()
+    }
+
+    pub fn shows(&self, category: EventCategory) -> bool {
+        !self.hidden.contains(&category)
+    }
+
+    /// Hide `category` when the log is shown, or show it again if it's hidden
+    pub fn toggle_hidden(&mut self, category: EventCategory) {
+        if self.shows(category) {
+            self.hidden.push(category);
+        } else {
+            self.hidden.retain(|&c| c != category);
+        }
+    }
+
+    /// The categories hidden, in the order they were hidden
+    pub fn hidden(&self) -> &[EventCategory] {
+        &self.hidden
+    }
+
+    /// The latest event still in memory of one of `categories`, hidden or not
+    pub fn latest(&self, categories: &[EventCategory]) -> Option<&Event> {
+        self.events.iter().rev().find(|e| categories.contains(&e.category))
+    }
+
+    /// Events logged after `total` had the value `mark` (as far as they are still retained)
+    pub fn since(&self, mark: u64) -> Iter<'_, Event> {
+        let count = self.total.saturating_sub(mark) as usize;
+        self.recent(count)
     }
 }
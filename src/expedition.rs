@@ -0,0 +1,158 @@
+//! Ventures the clan sets out on at the chief's word.
+
+use crate::calendar::Season;
+
+/// Most orcs sent on a venture, and the fewest worth sending
+pub const PARTY_SIZE: usize = 4;
+pub const MIN_PARTY: usize = 2;
+/// Ticks a party waits for stragglers on the way before getting on with it regardless
+pub fn patience(day_length: u64) -> u64 {
+    day_length / 2
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Venture {
+    Hunt,
+    Gathering,
+    WinterForage,
+}
+
+impl Venture {
+    pub const ALL: [Venture; 3] = [Venture::Hunt, Venture::Gathering, Venture::WinterForage];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Venture::Hunt => "hunt",
+            Venture::Gathering => "gathering",
+            Venture::WinterForage => "forage",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Venture> {
+        Venture::ALL.into_iter().find(|v| v.name() == name)
+    }
+
+    /// What the campfire menu offers
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Venture::Hunt => "Send a hunting party after a distant herd",
+            Venture::Gathering => "Spend a day gathering berries",
+            Venture::WinterForage => "Risk a forage out in the winter cold",
+        }
+    }
+
+    /// What the clan calls the orcs sent
+    pub fn party(&self) -> &'static str {
+        match self {
+            Venture::Hunt => "Hunting party",
+            Venture::Gathering => "Gatherers",
+            Venture::WinterForage => "Foragers",
+        }
+    }
+
+    /// What the party brings home
+    pub fn food(&self) -> &'static str {
+        match self {
+            Venture::Hunt => "meat",
+            Venture::Gathering => "berries",
+            Venture::WinterForage => "roots and nuts",
+        }
+    }
+
+    /// How far out from camp the venture takes the party
+    pub fn distance(&self) -> usize {
+        match self {
+            Venture::Hunt => 40,
+            Venture::Gathering => 20,
+            Venture::WinterForage => 35,
+        }
+    }
+
+    /// Ticks the party spends at it once there
+    pub fn work_ticks(&self) -> u64 {
+        match self {
+            Venture::Hunt => 30,
+            Venture::Gathering => 40,
+            Venture::WinterForage => 30,
+        }
+    }
+
+    /// Why it can't be tried in `season`, if it can't
+    pub fn out_of_season(&self, season: Season) -> Option<&'static str> {
+        match self {
+            Venture::Gathering if season == Season::Winter => Some("There are no berries to gather in winter"),
+            Venture::WinterForage if season != Season::Winter => Some("A winter forage has to wait for winter"),
+            _ => None,
+        }
+    }
+
+    /// Odds it comes off, for a party of the given skill on average (0-100)
+    pub fn odds(&self, skill: f32) -> f64 {
+        let skill = skill as f64;
+        match self {
+            Venture::Hunt => 0.35 + skill / 200.0,
+            Venture::Gathering => 0.6 + skill / 300.0,
+            Venture::WinterForage => 0.25 + skill / 200.0,
+        }
+    }
+}
+
+pub enum Stage {
+    /// On the way out to the site
+    Outbound,
+    /// At it until the given tick
+    Working { until: u64 },
+    /// Carrying the haul home
+    Homeward,
+}
+
+impl Stage {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stage::Outbound => "outbound",
+            Stage::Working { .. } => "working",
+            Stage::Homeward => "homeward",
+        }
+    }
+}
+
+pub struct Expedition {
+    pub venture: Venture,
+    pub site: (usize, usize),
+    /// The herd a hunting party is after, whose tracks it follows
+    pub quarry: Option<u32>,
+    /// Names of the orcs sent
+    pub party: Vec<String>,
+    pub stage: Stage,
+    /// Food the party is bringing home
+    pub haul: u32,
+    /// When the party set off on the current leg, out or home
+    pub leg_started: u64,
+}
+
+impl Expedition {
+    pub fn new(venture: Venture, site: (usize, usize), quarry: Option<u32>, party: Vec<String>, tick: u64) -> Self {
+        Expedition { venture, site, quarry, party, stage: Stage::Outbound, haul: 0, leg_started: tick }
+    }
+
+    /// Where the party should be making for
+    pub fn destination(&self, home: (usize, usize)) -> (usize, usize) {
+        match self.stage {
+            Stage::Homeward => home,
+            Stage::Outbound | Stage::Working { .. } => self.site,
+        }
+    }
+
+    pub fn includes(&self, name: &str) -> bool {
+        self.party.iter().any(|n| n == name)
+    }
+
+    pub fn label(&self) -> String {
+        let doing = match self.stage {
+            Stage::Outbound => "setting out",
+            Stage::Working { .. } => "at work",
+            Stage::Homeward => "returning",
+        };
+        format!("{} {}", self.venture.party(), doing)
+    }
+}
@@ -0,0 +1,52 @@
+//! The game's history written out for looking at elsewhere.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::calendar;
+use crate::event::EventLog;
+use crate::stats::StatsCollector;
+
+/// Write out the events and daily statistics, returning where they went
+pub fn export(dir: &Path, seed: u64, log: &EventLog, stats: &StatsCollector, day_length: u64) -> io::Result<(PathBuf, PathBuf)> {
+    let events_path = dir.join(format!("orcs-{}-events.csv", seed));
+    let days_path = dir.join(format!("orcs-{}-days.csv", seed));
+    fs::write(&events_path, events(log, day_length))?;
+    fs::write(&days_path, days(stats))?;
+    Ok((events_path, days_path))
+}
+
+fn events(log: &EventLog, day_length: u64) -> String {
+    let mut out = String::from("tick,day,category,severity,x,y,message\n");
+    for event in log.recent(usize::MAX) {
+        let (x, y) = event.at.map_or((String::new(), String::new()), |(x, y)| (x.to_string(), y.to_string()));
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            event.tick,
+            calendar::day_number(event.tick, day_length),
+            event.category.name(),
+            event.severity.name(),
+            x,
+            y,
+            field(&event.message),
+        ));
+    }
+    out
+}
+
+fn days(stats: &StatsCollector) -> String {
+    let mut out = String::from("day,population,births,deaths,hunts,meat_start,meat_end\n");
+    for day in &stats.history {
+        out.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            day.day, day.population, day.births, day.deaths, day.hunts, day.food_start, day.food_end,
+        ));
+    }
+    out
+}
+
+/// A CSV field, quoted if it holds a comma, a quote or a line break
+fn field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) { format!("\"{}\"", s.replace('"', "\"\"")) } else { s.to_string() }
+}
@@ -0,0 +1,592 @@
+//! Gym-style stepping interface for training agents to manage the village.
+
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use crate::alarm::Alarm;
+use crate::sim::{MAX_DROP, Sim};
+use crate::building::Building;
+use crate::calendar::Season;
+use crate::config::{Config, WorldConfig};
+use crate::designation::Designation;
+use crate::event::{Archive, EventCategory};
+use crate::expedition::Venture;
+use crate::json;
+use crate::scenario::Scenario;
+use crate::simulation::Simulation;
+use crate::tool::ToolKind;
+
+const REWARD_PER_ORC: f32 = 0.1;
+const REWARD_BIRTH: f32 = 5.0;
+const PENALTY_DEATH: f32 = 10.0;
+
+/// Interventions available to the player
+#[derive(Clone, Debug, PartialEq)]
+pub enum Action {
+    Noop,
+    DropFood { x: usize, y: usize, units: u32 },
+    Build { kind: Building, x: usize, y: usize },
+    BuildLine { kind: Building, from: (usize, usize), to: (usize, usize) },
+    Cancel { orc: usize },
+    Recall,
+    Evacuate { x: usize, y: usize },
+    Mark { order: Designation, x: usize, y: usize },
+    Venture(Venture),
+}
+
+impl Action {
+    /// Parse the text form used by the stdio protocol, e.g. `noop`, `drop 150 70 3`, `build hut 152 70`,
+    /// `line wall 140 60 150 60` or `cancel 2`
+    pub fn parse(s: &str) -> Result<Action, String> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        match words.as_slice() {
+            [] | ["noop"] => Ok(Action::Noop),
+            ["drop", x, y, units @ ..] if units.len() <= 1 => {
+                let x = x.parse().map_err(|_| format!("bad x coordinate: {}", x))?;
+                let y = y.parse().map_err(|_| format!("bad y coordinate: {}", y))?;
+                let units = match units {
+                    [n] => n.parse().ok().filter(|n| (1..=MAX_DROP).contains(n)).ok_or_else(|| format!("bad amount: {}", n))?,
+                    _ => 1,
+                };
+                Ok(Action::DropFood { x, y, units })
+            }
+            ["build", kind, x, y] => {
+                let kind = Building::parse(kind).ok_or_else(|| format!("unknown building: {}", kind))?;
+                let x = x.parse().map_err(|_| format!("bad x coordinate: {}", x))?;
+                let y = y.parse().map_err(|_| format!("bad y coordinate: {}", y))?;
+                Ok(Action::Build { kind, x, y })
+            }
+            ["line", kind, x0, y0, x1, y1] => {
+                let kind = Building::parse(kind).ok_or_else(|| format!("unknown building: {}", kind))?;
+                let coord = |s: &str| s.parse().map_err(|_| format!("bad coordinate: {}", s));
+                Ok(Action::BuildLine { kind, from: (coord(x0)?, coord(y0)?), to: (coord(x1)?, coord(y1)?) })
+            }
+            ["cancel", orc] => {
+                let orc = orc.parse().map_err(|_| format!("bad orc index: {}", orc))?;
+                Ok(Action::Cancel { orc })
+            }
+            ["recall"] => Ok(Action::Recall),
+            ["evacuate", x, y] => {
+                let x = x.parse().map_err(|_| format!("bad x coordinate: {}", x))?;
+                let y = y.parse().map_err(|_| format!("bad y coordinate: {}", y))?;
+                Ok(Action::Evacuate { x, y })
+            }
+            ["mark", order, x, y] => {
+                let order = Designation::parse(order).ok_or_else(|| format!("unknown order: {}", order))?;
+                let x = x.parse().map_err(|_| format!("bad x coordinate: {}", x))?;
+                let y = y.parse().map_err(|_| format!("bad y coordinate: {}", y))?;
+                Ok(Action::Mark { order, x, y })
+            }
+            ["venture", name] => Venture::parse(name).map(Action::Venture).ok_or_else(|| format!("unknown venture: {}", name)),
+            _ => Err(format!("unknown action: {}", s.trim())),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct OrcObservation {
+    pub name: String,
+    pub x: usize,
+    pub y: usize,
+    pub health: f32,
+    pub hunger: f32,
+    pub thirst: f32,
+    pub energy: f32,
+    pub warmth: f32,
+    pub morale: f32,
+    pub forage_skill: f32,
+    pub hunt_skill: f32,
+    pub berries: u32,
+    pub age_days: u64,
+    pub sex: String,
+    pub sick: bool,
+    /// Harm still to heal, and whether a healer has dressed it
+    pub wounds: f32,
+    pub dressed: bool,
+    /// Healing herbs carried
+    pub herbs: u32,
+    /// Carrying a lit torch, and out in the dark beyond any light
+    pub torch: bool,
+    pub in_dark: bool,
+    pub traits: Vec<String>,
+    pub tools: Vec<String>,
+    pub job: String,
+    pub activity: String,
+}
+
+#[derive(Clone)]
+pub struct AnimalObservation {
+    pub kind: String,
+    pub x: usize,
+    pub y: usize,
+    pub active: bool,
+    pub drinking: bool,
+    /// Kept in the pens, and how tame (0-100); wild animals are 0
+    pub penned: bool,
+    pub tameness: f32,
+}
+
+#[derive(Clone)]
+pub struct RaiderObservation {
+    pub x: usize,
+    pub y: usize,
+    pub health: f32,
+    /// Meals it has grabbed off the rack
+    pub loot: u32,
+    pub fleeing: bool,
+}
+
+#[derive(Clone)]
+pub struct RivalObservation {
+    pub name: String,
+    pub x: usize,
+    pub y: usize,
+    pub health: f32,
+    /// Running home to mend
+    pub beaten: bool,
+    /// Going after one of the clan
+    pub hostile: bool,
+}
+
+#[derive(Clone)]
+pub struct SiteObservation {
+    pub kind: String,
+    pub x: usize,
+    pub y: usize,
+    pub wood: u32,
+    pub stone: u32,
+    pub work: u32,
+}
+
+/// A body lying unburied, or a grave in the graveyard
+#[derive(Clone)]
+pub struct DeadObservation {
+    pub name: String,
+    pub x: usize,
+    pub y: usize,
+}
+
+#[derive(Clone)]
+pub struct OrderObservation {
+    pub kind: String,
+    pub x: usize,
+    pub y: usize,
+}
+
+#[derive(Clone)]
+pub struct Observation {
+    pub seed: u64,
+    pub tick: u64,
+    pub is_night: bool,
+    pub season: String,
+    pub food_stockpile: u32,
+    /// Part of the raw meat kept in storage huts, safe from spoiling
+    pub cellared_meat: u32,
+    pub cooked_stockpile: u32,
+    pub berry_stockpile: u32,
+    pub wood_stockpile: u32,
+    pub stone_stockpile: u32,
+    pub water_barrel: u32,
+    pub fire_fuel: u32,
+    /// Where the clan is in an evacuation, if it's away from camp: why it
+    /// left, whether it's on the way out, sheltering or returning, and the
+    /// rally point
+    pub evacuation: Option<(String, String, (usize, usize))>,
+    /// The venture a party is out on, if any: which, how far along it is,
+    /// where, and how many went
+    pub expedition: Option<(String, String, (usize, usize), usize)>,
+    /// What the clan is on alert over, if anything, and where it was last seen
+    pub alert: Option<(String, (usize, usize))>,
+    /// Tools of each kind hanging on the rack
+    pub tool_rack: Vec<(String, u32)>,
+    pub orcs: Vec<OrcObservation>,
+    pub animals: Vec<AnimalObservation>,
+    /// Goblins out raiding the camp
+    pub raiders: Vec<RaiderObservation>,
+    /// Where the rival clan's campfire burns, and its orcs
+    pub rival_camp: (usize, usize),
+    pub rivals: Vec<RivalObservation>,
+    pub sites: Vec<SiteObservation>,
+    pub orders: Vec<OrderObservation>,
+    pub corpses: Vec<DeadObservation>,
+    pub graves: Vec<DeadObservation>,
+}
+
+impl Observation {
+    pub fn to_json(&self) -> String {
+        let orcs = self.orcs.iter().map(|o| {
+            json::Object::new()
+                .str("name", &o.name)
+                .num("x", o.x)
+                .num("y", o.y)
+                .float("health", o.health)
+                .float("hunger", o.hunger)
+                .float("thirst", o.thirst)
+                .float("energy", o.energy)
+                .float("warmth", o.warmth)
+                .float("morale", o.morale)
+                .float("forage_skill", o.forage_skill)
+                .float("hunt_skill", o.hunt_skill)
+                .num("berries", o.berries)
+                .num("age_days", o.age_days)
+                .str("sex", &o.sex)
+                .bool("sick", o.sick)
+                .float("wounds", o.wounds)
+                .bool("dressed", o.dressed)
+                .num("herbs", o.herbs)
+                .bool("torch", o.torch)
+                .bool("in_dark", o.in_dark)
+                .raw("traits", &json::array(o.traits.iter().map(|t| json::string(t))))
+                .raw("tools", &json::array(o.tools.iter().map(|t| json::string(t))))
+                .str("job", &o.job)
+                .str("activity", &o.activity)
+                .finish()
+        });
+        let animals = self.animals.iter().map(|a| {
+            json::Object::new()
+                .str("kind", &a.kind)
+                .num("x", a.x)
+                .num("y", a.y)
+                .bool("active", a.active)
+                .bool("drinking", a.drinking)
+                .bool("penned", a.penned)
+                .float("tameness", a.tameness)
+                .finish()
+        });
+        let raiders = self.raiders.iter().map(|r| {
+            json::Object::new()
+                .num("x", r.x)
+                .num("y", r.y)
+                .float("health", r.health)
+                .num("loot", r.loot)
+                .bool("fleeing", r.fleeing)
+                .finish()
+        });
+        let rivals = self.rivals.iter().map(|r| {
+            json::Object::new()
+                .str("name", &r.name)
+                .num("x", r.x)
+                .num("y", r.y)
+                .float("health", r.health)
+                .bool("beaten", r.beaten)
+                .bool("hostile", r.hostile)
+                .finish()
+        });
+        let sites = self.sites.iter().map(|s| {
+            json::Object::new()
+                .str("kind", &s.kind)
+                .num("x", s.x)
+                .num("y", s.y)
+                .num("wood", s.wood)
+                .num("stone", s.stone)
+                .num("work", s.work)
+                .finish()
+        });
+        let orders = self.orders.iter().map(|d| {
+            json::Object::new()
+                .str("kind", &d.kind)
+                .num("x", d.x)
+                .num("y", d.y)
+                .finish()
+        });
+        let dead = |d: &DeadObservation| json::Object::new().str("name", &d.name).num("x", d.x).num("y", d.y).finish();
+        let evacuation = match &self.evacuation {
+            Some((reason, stage, (x, y))) => json::Object::new().str("reason", reason).str("stage", stage).num("x", x).num("y", y).finish(),
+            None => "null".to_string(),
+        };
+        let expedition = match &self.expedition {
+            Some((venture, stage, (x, y), party)) => {
+                json::Object::new().str("venture", venture).str("stage", stage).num("x", x).num("y", y).num("party", party).finish()
+            }
+            None => "null".to_string(),
+        };
+        let alert = match &self.alert {
+            Some((threat, (x, y))) => json::Object::new().str("threat", threat).num("x", x).num("y", y).finish(),
+            None => "null".to_string(),
+        };
+        let tool_rack = self.tool_rack.iter().fold(json::Object::new(), |rack, (kind, count)| rack.num(kind, count));
+        json::Object::new()
+            .num("seed", self.seed)
+            .num("tick", self.tick)
+            .bool("is_night", self.is_night)
+            .str("season", &self.season)
+            .num("food_stockpile", self.food_stockpile)
+            .num("cellared_meat", self.cellared_meat)
+            .num("cooked_stockpile", self.cooked_stockpile)
+            .num("berry_stockpile", self.berry_stockpile)
+            .num("wood_stockpile", self.wood_stockpile)
+            .num("stone_stockpile", self.stone_stockpile)
+            .num("water_barrel", self.water_barrel)
+            .num("fire_fuel", self.fire_fuel)
+            .raw("evacuation", &evacuation)
+            .raw("expedition", &expedition)
+            .raw("alert", &alert)
+            .raw("tool_rack", &tool_rack.finish())
+            .raw("orcs", &json::array(orcs))
+            .raw("animals", &json::array(animals))
+            .raw("raiders", &json::array(raiders))
+            .raw("rival_camp", &json::Object::new().num("x", self.rival_camp.0).num("y", self.rival_camp.1).finish())
+            .raw("rivals", &json::array(rivals))
+            .raw("sites", &json::array(sites))
+            .raw("orders", &json::array(orders))
+            .raw("corpses", &json::array(self.corpses.iter().map(dead)))
+            .raw("graves", &json::array(self.graves.iter().map(dead)))
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+pub struct Step {
+    pub observation: Observation,
+    pub reward: f32,
+    pub done: bool,
+}
+
+pub struct Env {
+    sim: Sim,
+    setup: WorldConfig, // how each episode's world is set up
+    event_mark: u64, // event_log.total at the end of the previous step
+}
+
+impl Env {
+    pub fn new(seed: u64, config: &Config) -> Self {
+        let mut sim = Sim::with_seed(seed, Scenario::Sandbox, &config.world);
+        sim.ai = config.ai;
+        sim.limits = config.limits;
+        sim.needs = config.needs;
+        sim.childcare = config.childcare;
+        sim.hooks = config.hooks.clone();
+        let event_mark = sim.event_log.total;
+        Env { sim, setup: config.world, event_mark }
+    }
+
+    /// Start a new episode, keeping the loaded settings, and the event
+    /// archive and state dump if there are any
+    pub fn reset(&mut self, seed: u64) -> io::Result<Observation> {
+        let (ai, limits, needs, world) = (self.sim.ai, self.sim.limits, self.sim.needs, self.setup);
+        let hooks = std::mem::take(&mut self.sim.hooks);
+        let (archive, dump) = (self.sim.event_log.take_archive(), self.sim.dump.take());
+        *self = Env::new(seed, &Config { ai, limits, needs, world, hooks, ..Config::default() });
+        self.sim.dump = dump;
+        if let Some(archive) = archive {
+            self.sim.event_log.archive_to(archive)?;
+        }
+        Ok(self.observe())
+    }
+
+    pub fn done(&self) -> bool {
+        self.sim.orcs.is_empty()
+    }
+
+    pub fn step(&mut self, actions: &[Action]) -> Step {
+        if !self.done() {
+            for action in actions {
+                apply(&mut self.sim, action);
+            }
+            self.sim.tick();
+        }
+
+        let mut reward = 0.0;
+        for event in self.sim.event_log.since(self.event_mark) {
+            match event.category {
+                EventCategory::Birth => reward += REWARD_BIRTH,
+                EventCategory::Death => reward -= PENALTY_DEATH,
+                _ => {}
+            }
+        }
+        self.event_mark = self.sim.event_log.total;
+        reward += REWARD_PER_ORC * self.sim.orcs.iter().filter(|o| o.alive).count() as f32;
+
+        Step {
+            observation: self.observe(),
+            reward,
+            done: self.done(),
+        }
+    }
+
+    pub fn observe(&self) -> Observation {
+        observe(&self.sim)
+    }
+}
+
+/// Carry out what a player could do, as an agent asked it
+pub fn apply(sim: &mut Sim, action: &Action) {
+    match action {
+        Action::Noop => {}
+        Action::DropFood { x, y, units } => sim.drop_food_at(*x, *y, *units),
+        Action::Build { kind, x, y } => sim.designate_at(*kind, *x, *y),
+        Action::BuildLine { kind, from, to } => sim.designate_line(*kind, *from, *to),
+        Action::Cancel { orc } => {
+            // Indices follow the observation, which only lists the living
+            let living = sim.orcs.iter().enumerate().filter(|(_, o)| o.alive).nth(*orc);
+            if let Some((i, _)) = living {
+                sim.cancel_orc(i);
+            }
+        }
+        Action::Recall => sim.recall_all(),
+        Action::Evacuate { x, y } => sim.evacuate_to(*x, *y),
+        Action::Mark { order, x, y } => sim.mark_at(*order, *x, *y),
+        Action::Venture(venture) => sim.send_expedition(*venture),
+    }
+}
+
+/// Snapshots of the game appended to a file every so many ticks, one JSON
+/// observation per line
+pub struct StateDump {
+    every: u64,
+    file: File,
+}
+
+impl StateDump {
+    pub fn create(path: &Path, every: u64) -> io::Result<StateDump> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(StateDump { every, file })
+    }
+
+    /// Write out the state of `sim` if a snapshot is due this tick
+    pub fn record(&mut self, sim: &Sim) -> io::Result<()> {
+        if !sim.tick.is_multiple_of(self.every) {
+            return Ok(());
+        }
+        writeln!(self.file, "{}", observe(sim).to_json())
+    }
+}
+
+/// Everything an agent or an outside tool gets to see of the game
+pub fn observe(sim: &Sim) -> Observation {
+    Observation {
+        seed: sim.seed,
+        tick: sim.tick,
+        is_night: sim.is_night(),
+        season: Season::of(sim.tick, sim.world.day_length).name().to_string(),
+        food_stockpile: sim.world.food_stockpile,
+        cellared_meat: sim.world.cellared_meat(),
+        cooked_stockpile: sim.world.cooked_stockpile,
+        berry_stockpile: sim.world.berry_stockpile,
+        wood_stockpile: sim.world.wood_stockpile,
+        stone_stockpile: sim.world.stone_stockpile,
+        water_barrel: sim.world.water_barrel,
+        fire_fuel: sim.world.fire_fuel,
+        evacuation: sim.evacuation.as_ref().map(|e| (e.reason.name().to_string(), e.label().to_lowercase(), e.rally)),
+        expedition: sim.expedition.as_ref().map(|e| (e.venture.name().to_string(), e.stage.name().to_string(), e.site, e.party.len())),
+        alert: match sim.alarm {
+            Alarm::Alert { threat, x, y, .. } => Some((threat.name().to_string(), (x, y))),
+            Alarm::Calm => None,
+        },
+        tool_rack: ToolKind::ALL.iter().map(|&k| (k.name().to_string(), sim.world.tool_rack.count(k))).collect(),
+        orcs: sim.orcs.iter().filter(|o| o.alive).map(|o| OrcObservation {
+            name: o.name.clone(),
+            x: o.x,
+            y: o.y,
+            health: o.health,
+            hunger: o.hunger,
+            thirst: o.thirst,
+            energy: o.energy,
+            warmth: o.warmth,
+            morale: o.morale,
+            forage_skill: o.forage_skill,
+            hunt_skill: o.hunt_skill,
+            berries: o.berries,
+            age_days: o.age_days(sim.tick, sim.world.day_length),
+            sex: o.sex.name().to_string(),
+            sick: o.sick_ticks > 0,
+            wounds: o.wounds,
+            dressed: o.dressed_ticks > 0,
+            herbs: o.herbs,
+            torch: o.torch > 0,
+            in_dark: o.in_dark,
+            traits: o.traits.iter().map(|t| t.name().to_string()).collect(),
+            tools: o.tools.iter().map(|t| t.kind.name().to_string()).collect(),
+            job: o.job.name().to_string(),
+            activity: o.activity.label().to_string(),
+        }).collect(),
+        animals: sim.animals.iter().filter(|a| a.alive).map(|a| AnimalObservation {
+            kind: a.kind.name().to_string(),
+            x: a.x,
+            y: a.y,
+            active: a.kind.is_active(sim.phase()),
+            drinking: a.drinking,
+            penned: a.tame.is_some(),
+            tameness: a.tame.unwrap_or(0.0),
+        }).collect(),
+        raiders: sim.raid.iter().flat_map(|r| &r.goblins).map(|g| RaiderObservation {
+            x: g.x,
+            y: g.y,
+            health: g.health,
+            loot: g.loot,
+            fleeing: g.fleeing,
+        }).collect(),
+        rival_camp: sim.rivals.camp,
+        rivals: sim.rivals.members.iter().map(|r| RivalObservation {
+            name: r.name.clone(),
+            x: r.x,
+            y: r.y,
+            health: r.health,
+            beaten: r.beaten,
+            hostile: r.hostile,
+        }).collect(),
+        sites: sim.world.sites.iter().map(|s| SiteObservation {
+            kind: s.kind.name().to_string(),
+            x: s.x,
+            y: s.y,
+            wood: s.wood,
+            stone: s.stone,
+            work: s.work,
+        }).collect(),
+        orders: sim.world.designations.iter().map(|(&(x, y), order)| OrderObservation {
+            kind: order.name().to_string(),
+            x,
+            y,
+        }).collect(),
+        corpses: sim.world.corpses.iter().map(|c| DeadObservation { name: c.name.clone(), x: c.x, y: c.y }).collect(),
+        graves: sim.world.graves.iter().map(|g| DeadObservation { name: g.name.clone(), x: g.x, y: g.y }).collect(),
+    }
+}
+
+/// Drive an environment over stdin/stdout.
+///
+/// Each input line is one step: actions separated by `;` (an empty line is a
+/// no-op step), or `reset [seed]` to start a new episode. Each output line is a
+/// JSON object with `reward`, `done` and `observation`. The environment is
+/// stepped through a `Simulation`, the same handle other threads would share.
+pub fn run_stdio(seed: u64, config: Config, archive: Option<Archive>, dump: Option<StateDump>) -> io::Result<()> {
+    let mut env = Env::new(seed, &config);
+    if let Some(archive) = archive {
+        env.sim.event_log.archive_to(archive)?;
+    }
+    env.sim.dump = dump;
+    let sim = Simulation::new(env);
+    let stdin = io::stdin();
+    let mut out = io::stdout().lock();
+
+    writeln!(out, "{}", step_json(&sim.snapshot(), 0.0, sim.done()))?;
+    for line in stdin.lock().lines() {
+        let line = line?;
+        let reply = if let Some(rest) = line.trim().strip_prefix("reset") {
+            let seed = rest.trim().parse().unwrap_or_else(|_| rand::random());
+            let observation = sim.reset(seed)?;
+            step_json(&observation, 0.0, sim.done())
+        } else {
+            let actions: Result<Vec<Action>, String> = line.split(';').map(Action::parse).collect();
+            match actions {
+                Ok(actions) => {
+                    let step = sim.step(&actions);
+                    step_json(&step.observation, step.reward, step.done)
+                }
+                Err(e) => json::Object::new().str("error", &e).finish(),
+            }
+        };
+        writeln!(out, "{}", reply)?;
+        out.flush()?;
+    }
+    Ok(())
+}
+
+fn step_json(observation: &Observation, reward: f32, done: bool) -> String {
+    json::Object::new()
+        .float("reward", reward)
+        .bool("done", done)
+        .raw("observation", &observation.to_json())
+        .finish()
+}
@@ -0,0 +1,184 @@
+//! The header across the top of the map.
+
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+
+use crate::animal::AnimalKind;
+use crate::app::App;
+use crate::calendar::{self, Season};
+use crate::view::ViewMode;
+use crate::world::FIRE_MAX_FUEL;
+
+/// Wolves within this many tiles of the campfire put the clan on its guard
+const WATCH_RADIUS: usize = 30;
+
+const NAME_HEADS: &[&str] = &["Grim", "Blood", "Skull", "Mud", "Ash", "Iron", "Rot", "Bone", "Black", "Tusk"];
+const NAME_TAILS: &[&str] = &["hold", "fen", "gash", "moor", "rock", "pit", "hollow", "crag", "maw", "ford"];
+
+pub struct Item {
+    pub icon: Option<char>,
+    pub text: String,
+    /// None leaves it in the header's own color
+    pub color: Option<Color>,
+}
+
+impl Item {
+    fn plain(text: String) -> Item {
+        Item { icon: None, text, color: None }
+    }
+}
+
+/// Something with a say in the header
+type Source = fn(&App) -> Option<Item>;
+
+/// Every source, in the order their items appear
+const SOURCES: &[Source] = &[
+    village, date, clock, weather, population, threat, meat, cooked, berries, wood, water, fire, speed, siege, raid, evacuation, expedition, view,
+];
+
+/// The header as it stands, one item per source with something to show
+pub fn items(app: &App) -> Vec<Item> {
+    SOURCES.iter().filter_map(|source| source(app)).collect()
+}
+
+/// The header laid out as a block title
+pub fn line(app: &App) -> Line<'static> {
+    let mut spans = vec![Span::raw(" ")];
+    for (i, item) in items(app).into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(" | "));
+        }
+        let style = item.color.map_or_else(Style::default, |color| Style::default().fg(color));
+        let text = match item.icon {
+            Some(icon) => format!("{} {}", icon, item.text),
+            None => item.text,
+        };
+        spans.push(Span::styled(text, style));
+    }
+    spans.push(Span::raw(" "));
+    Line::from(spans)
+}
+
+/// The village's name, which comes with the world's seed
+fn village_name(seed: u64) -> String {
+    let head = NAME_HEADS[(seed % NAME_HEADS.len() as u64) as usize];
+    let tail = NAME_TAILS[(seed / NAME_HEADS.len() as u64 % NAME_TAILS.len() as u64) as usize];
+    format!("{}{}", head, tail)
+}
+
+fn village(app: &App) -> Option<Item> {
+    let text = match &app.clan_name {
+        Some(clan) => format!("{} of {}", clan, village_name(app.sim.seed)),
+        None => village_name(app.sim.seed),
+    };
+    Some(Item { icon: None, text, color: Some(app.theme().heading) })
+}
+
+fn date(app: &App) -> Option<Item> {
+    Some(Item::plain(format!("Day {} {}", calendar::day_number(app.sim.tick, app.sim.world.day_length), Season::of(app.sim.tick, app.sim.world.day_length).name())))
+}
+
+/// The hour, reckoning dawn at six in the morning and dusk at six at night,
+/// with a clock face whose hand sweeps round every three hours
+fn clock(app: &App) -> Option<Item> {
+    let minutes = (calendar::time_of_day(app.sim.tick, app.sim.world.day_length) * 24 * 60 / app.sim.world.day_length + 6 * 60) % (24 * 60);
+    let (hour, minute) = (minutes / 60, minutes % 60);
+    let face = ['◷', '◶', '◵', '◴'][(hour % 12 / 3) as usize];
+    Some(Item { icon: Some(face), text: format!("{:02}:{:02} {}", hour, minute, app.sim.phase().name()), color: None })
+}
+
+fn weather(app: &App) -> Option<Item> {
+    let (icon, text) = if calendar::showery(app.sim.tick, app.sim.world.day_length) {
+        ('☂', "Rain")
+    } else if Season::of(app.sim.tick, app.sim.world.day_length) == Season::Winter {
+        ('❄', "Frost")
+    } else {
+        ('☀', "Fair")
+    };
+    Some(Item { icon: Some(icon), text: text.to_string(), color: None })
+}
+
+fn population(app: &App) -> Option<Item> {
+    Some(Item::plain(format!("Pop: {}", app.sim.orcs.iter().filter(|o| o.alive).count())))
+}
+
+/// Calm, wary with wolves about, goblins on the way or the fire out at
+/// night, or in danger once wolves have overrun the camp or goblins reached it
+fn threat(app: &App) -> Option<Item> {
+    let theme = app.theme();
+    let (cx, cy) = app.sim.world.campfire_pos;
+    let wolves_about = app.sim.animals.iter()
+        .any(|a| a.alive && a.kind == AnimalKind::Wolf && a.x.abs_diff(cx).max(a.y.abs_diff(cy)) <= WATCH_RADIUS);
+    let raiders_close = app.sim.raid.as_ref()
+        .is_some_and(|r| r.goblins.iter().any(|g| g.x.abs_diff(cx).max(g.y.abs_diff(cy)) <= WATCH_RADIUS));
+    let (level, color) = if app.sim.overrun() || raiders_close {
+        ("danger", theme.bad)
+    } else if wolves_about || app.sim.raid.is_some() || (app.sim.is_night() && !app.sim.world.fire_lit()) {
+        ("wary", theme.warn)
+    } else {
+        ("calm", theme.good)
+    };
+    Some(Item { icon: Some('⚠'), text: format!("Threat: {}", level), color: Some(color) })
+}
+
+fn meat(app: &App) -> Option<Item> {
+    Some(Item::plain(format!("Meat: {}", app.sim.world.food_stockpile)))
+}
+
+fn cooked(app: &App) -> Option<Item> {
+    Some(Item::plain(format!("Cooked: {}", app.sim.world.cooked_stockpile)))
+}
+
+fn berries(app: &App) -> Option<Item> {
+    Some(Item::plain(format!("Berries: {}", app.sim.world.berry_stockpile)))
+}
+
+fn wood(app: &App) -> Option<Item> {
+    Some(Item::plain(format!("Wood: {}", app.sim.world.wood_stockpile)))
+}
+
+fn water(app: &App) -> Option<Item> {
+    Some(Item::plain(format!("Water: {}", app.sim.world.water_barrel)))
+}
+
+fn fire(app: &App) -> Option<Item> {
+    if !app.sim.world.fire_lit() {
+        return Some(Item { icon: None, text: "Fire: out".to_string(), color: Some(app.theme().bad) });
+    }
+    Some(Item::plain(format!("Fire: {}%", app.sim.world.fire_fuel * 100 / FIRE_MAX_FUEL)))
+}
+
+fn speed(app: &App) -> Option<Item> {
+    let speed = if app.turbo { "turbo".to_string() } else { app.speed.name() };
+    Some(Item::plain(format!("Speed: {}{}", speed, if app.sim.paused { " [PAUSED]" } else { "" })))
+}
+
+fn raid(app: &App) -> Option<Item> {
+    let raid = app.sim.raid.as_ref()?;
+    Some(Item { icon: None, text: raid.label(), color: Some(app.theme().bad) })
+}
+
+/// The wave and score while playing the defense scenario
+fn siege(app: &App) -> Option<Item> {
+    let siege = app.sim.siege.as_ref()?;
+    let color = if siege.fallen.is_some() { app.theme().bad } else { app.theme().accent_bright };
+    Some(Item { icon: Some('⚔'), text: siege.label(calendar::day_number(app.sim.tick, app.sim.world.day_length)), color: Some(color) })
+}
+
+fn evacuation(app: &App) -> Option<Item> {
+    let evacuation = app.sim.evacuation.as_ref()?;
+    Some(Item { icon: None, text: evacuation.label().to_string(), color: Some(app.theme().warn) })
+}
+
+fn expedition(app: &App) -> Option<Item> {
+    let expedition = app.sim.expedition.as_ref()?;
+    Some(Item { icon: None, text: expedition.label(), color: Some(app.theme().accent_bright) })
+}
+
+fn view(app: &App) -> Option<Item> {
+    match app.view {
+        ViewMode::Normal => None,
+        ViewMode::Territory => Some(Item::plain(format!("Showing territory: {}", app.sim.territory.hold(app.cursor_x, app.cursor_y).describe()))),
+        mode => Some(Item::plain(format!("Showing {}", mode.name()))),
+    }
+}
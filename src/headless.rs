@@ -0,0 +1,128 @@
+//! Batch runs without a screen.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crate::calendar;
+use crate::config::Config;
+use crate::custom::Custom;
+use crate::event::Archive;
+use crate::gym::StateDump;
+use crate::json;
+use crate::metrics::Metrics;
+use crate::scenario::Scenario;
+use crate::sim::{Sim, Stress};
+
+/// What to run and where the results go
+pub struct Run {
+    pub seed: u64,
+    pub ticks: u64,
+    pub scenario: Scenario,
+    /// Scenario file to start from, in place of `scenario`
+    pub custom: Option<Custom>,
+    pub stress: Option<Stress>,
+    /// Write the results here as JSON instead of printing them
+    pub stats_path: Option<PathBuf>,
+    /// Answer scrapes of how the run is going
+    pub metrics: Option<Metrics>,
+}
+
+/// What came of a run
+pub struct Summary {
+    pub seed: u64,
+    pub ticks: u64,
+    pub days: u64,
+    pub population: usize,
+    pub peak: usize,
+    /// How many died of each cause, in the order first seen
+    pub deaths: Vec<(String, usize)>,
+}
+
+impl Summary {
+    fn died(&mut self, cause: String) {
+        match self.deaths.iter_mut().find(|(c, _)| *c == cause) {
+            Some((_, n)) => *n += 1,
+            None => self.deaths.push((cause, 1)),
+        }
+    }
+
+    pub fn total_deaths(&self) -> usize {
+        self.deaths.iter().map(|(_, n)| n).sum()
+    }
+
+    pub fn to_json(&self) -> String {
+        let deaths = self.deaths.iter().fold(json::Object::new(), |o, (cause, n)| o.num(cause, n));
+        json::Object::new()
+            .num("seed", self.seed)
+            .num("ticks", self.ticks)
+            .num("days_survived", self.days)
+            .num("population", self.population)
+            .num("peak_population", self.peak)
+            .num("total_deaths", self.total_deaths())
+            .raw("deaths_by_cause", &deaths.finish())
+            .finish()
+    }
+
+    pub fn report(&self) -> String {
+        let mut lines = vec![
+            format!("seed            {}", self.seed),
+            format!("ticks           {}", self.ticks),
+            format!("days survived   {}", self.days),
+            format!("population      {}", self.population),
+            format!("peak population {}", self.peak),
+            format!("deaths          {}", self.total_deaths()),
+        ];
+        lines.extend(self.deaths.iter().map(|(cause, n)| format!("  {:<13} {}", cause, n)));
+        lines.join("\n")
+    }
+}
+
+pub fn run(run: Run, config: Config, archive: Option<Archive>, dump: Option<StateDump>) -> io::Result<()> {
+    let mut sim = match &run.custom {
+        Some(custom) => Sim::with_custom(run.seed, custom, &config.world),
+        None => Sim::with_seed(run.seed, run.scenario, &config.world),
+    };
+    sim.ai = config.ai;
+    sim.limits = config.limits;
+    sim.needs = config.needs;
+    sim.hooks = config.hooks;
+    sim.childcare = config.childcare;
+    if let Some(stress) = run.stress {
+        sim.populate(stress);
+    }
+    if let Some(archive) = archive {
+        sim.event_log.archive_to(archive)?;
+    }
+    sim.dump = dump;
+
+    let living = |sim: &Sim| sim.orcs.iter().filter(|o| o.alive).count();
+    let mut summary = Summary { seed: run.seed, ticks: 0, days: 0, population: 0, peak: living(&sim), deaths: Vec::new() };
+    // Paused on its own, the clan has perished or the siege is lost
+    while sim.tick < run.ticks && !sim.paused {
+        let started = Instant::now();
+        sim.tick();
+        if let Some(metrics) = &run.metrics {
+            metrics.record_tick(&sim, started.elapsed());
+        }
+        for orc in sim.orcs.iter().filter(|o| o.death_tick == Some(sim.tick)) {
+            summary.died(orc.died_of.as_ref().map_or_else(|| "unknown".to_string(), |c| c.name()));
+        }
+        summary.peak = summary.peak.max(living(&sim));
+        if living(&sim) > 0 {
+            summary.days = calendar::day_number(sim.tick, sim.world.day_length);
+        }
+    }
+    summary.ticks = sim.tick;
+    summary.population = living(&sim);
+
+    match run.stats_path {
+        Some(path) => fs::write(&path, summary.to_json() + "\n")
+            .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e))),
+        None => {
+            println!("{}", summary.report());
+            Ok(())
+        }
+    }
+}
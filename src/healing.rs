@@ -0,0 +1,18 @@
+//! Wounds, and the healers who see to them.
+
+use std::ops::Range;
+
+/// Wounds that close with each tick of sleep, and of any other rest
+pub const SLEEP_HEALING: f32 = 0.25;
+pub const REST_HEALING: f32 = 0.1;
+/// How many times as fast dressed wounds heal, and for how many ticks
+pub const DRESSED_HEALING: f32 = 3.0;
+pub const DRESSING_TICKS: u32 = 200;
+/// Wounds bad enough for a healer to come and dress them
+pub const TEND_AT: f32 = 15.0;
+/// Herbs a healer carries
+pub const HERB_POUCH: u32 = 3;
+/// Furthest from the campfire a healer goes to pick herbs
+pub const HERB_RADIUS: usize = 50;
+/// Harm done to an orc caught under a building as it falls
+pub const COLLAPSE_INJURY: Range<f32> = 10.0..25.0;
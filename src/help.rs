@@ -0,0 +1,110 @@
+//! The help screen, opened over the map with `?`.
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::animal::AnimalKind;
+use crate::app::App;
+use crate::building::Building;
+use crate::input::Command;
+use crate::markers::Mark;
+use crate::nursery::INFANCY_DAYS;
+use crate::orc::{FREEZING, WASTING};
+use crate::world::Terrain;
+
+/// The ground and what the clan keeps on it, in the order the glyphs are listed
+const GROUND: [(Terrain, &str); 15] = [
+    (Terrain::Grass, "open grass"),
+    (Terrain::Tree, "tree, for wood"),
+    (Terrain::Rock, "rock, for stone; nobody walks through it"),
+    (Terrain::Water, "water, to drink from; nobody walks through it"),
+    (Terrain::Campfire, "the campfire, dim once it has gone out"),
+    (Terrain::Carcass, "a carcass, to butcher for meat"),
+    (Terrain::Bush, "berry bush, picked bare until it fruits again"),
+    (Terrain::FruitTree, "fruit tree"),
+    (Terrain::Mushroom, "mushrooms"),
+    (Terrain::Herb, "healing herbs, for the healers"),
+    (Terrain::Grave, "a grave"),
+    (Terrain::MeatRack, "the meat rack, where the food is kept"),
+    (Terrain::WaterBarrel, "the water barrel"),
+    (Terrain::ToolRack, "the tool rack"),
+    (Terrain::Site(Building::Hut), "a building site, waiting on wood and stone"),
+];
+
+/// How the setting a command switches stands, for those that switch one
+fn setting(app: &App, command: Command) -> Option<&str> {
+    match command {
+        Command::CycleTheme => Some(&app.theme().name),
+        Command::CycleView => Some(app.view.name()),
+        Command::CycleZoom => Some(app.zoom.name()),
+        Command::ToggleGlyphs => Some(app.caps.glyphs.name()),
+        _ => None,
+    }
+}
+
+/// Everything on the help screen, a line at a time
+pub fn lines(app: &App) -> Vec<Line<'static>> {
+    let theme = app.theme();
+    let heading = |text: &str| Line::styled(text.to_string(), Style::default().fg(theme.heading).add_modifier(Modifier::BOLD));
+    let glyph = |symbol: char, color, meaning: &str| {
+        Line::from(vec![
+            Span::styled(format!("  {} ", symbol), Style::default().fg(color).add_modifier(Modifier::BOLD)),
+            Span::raw(meaning.to_string()),
+        ])
+    };
+
+    let mut lines = vec![heading("Keys")];
+    for command in Command::ALL {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<12}", app.input.keymap.describe(command)), Style::default().fg(theme.accent_bright)),
+            Span::raw(command.help()),
+            Span::styled(setting(app, command).map(|now| format!(" (now {})", now)).unwrap_or_default(), Style::default().fg(theme.muted)),
+        ]));
+    }
+
+    lines.push(Line::raw(""));
+    lines.push(heading("The map"));
+    for (terrain, meaning) in GROUND {
+        lines.push(glyph(terrain.symbol(), theme.terrain(terrain), meaning));
+    }
+    lines.push(glyph('⚘', theme.food, "food dropped on the ground"));
+    for building in Building::ALL {
+        let terrain = Terrain::Built(building);
+        lines.push(glyph(terrain.symbol(), theme.terrain(terrain), building.name()));
+    }
+    for mark in Mark::ALL {
+        lines.push(glyph(mark.symbol(), theme.mark(mark), mark.meaning()));
+    }
+    lines.push(glyph('☻', theme.orc, "an orc; the selected one is drawn reversed, rivals in their own color"));
+    lines.push(glyph('◎', theme.orc, "an orc asleep"));
+    lines.push(glyph('⚔', theme.orc, "an orc out hunting"));
+    lines.push(glyph('•', theme.orc, "a baby, carried about by its carer"));
+    lines.push(glyph('†', theme.tombstone, "a body not yet buried"));
+    lines.push(glyph('g', theme.goblin, "a raiding goblin"));
+    for kind in AnimalKind::ALL {
+        lines.push(glyph(kind.symbol(), theme.creature(kind), kind.name()));
+    }
+    lines.push(glyph('▣', theme.cursor, "the cursor"));
+
+    lines.push(Line::raw(""));
+    lines.push(heading("How the clan lives"));
+    let rules = [
+        format!(
+            "An orc loses health while starving or parched (hunger or thirst at {:.0} or more), worn out, freezing (warmth at {:.0} or less) or sick, and in a fight. Fed, watered, rested and warm, it heals, but only as far as its wounds let it. At no health at all it dies.",
+            WASTING, FREEZING
+        ),
+        "The campfire keeps the orcs about it warm and lights the camp at night. It burns through its fuel and the clan has to keep bringing logs; a night without it is cold and grim.".to_string(),
+        format!(
+            "A baby may come when the clan is well fed and rested on the whole, there's a grown orc of each sex, the mother isn't still nursing her last, and there's meat on the rack to spare. It needs carrying and feeding for {} days before it joins the clan, taking up whichever job is shortest of hands.",
+            INFANCY_DAYS
+        ),
+        "Hunters bring meat back to the rack and foragers berries; marked trees are chopped and marked bushes picked. Goblins come raiding for the stores, and the guards go out to meet them.".to_string(),
+        "The game is lost once the last orc is dead.".to_string(),
+    ];
+    for rule in rules {
+        lines.push(Line::raw(rule));
+        lines.push(Line::raw(""));
+    }
+    lines.push(Line::styled("Up/Down/PageUp/PageDown: scroll  Esc: close", Style::default().fg(theme.muted)));
+    lines
+}
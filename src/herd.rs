@@ -0,0 +1,132 @@
+//! Deer keep together in herds that roam the map.
+
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+
+use crate::animal::{Animal, Lead};
+use crate::world::{MAP_HEIGHT, MAP_WIDTH, World};
+
+/// Deer in a newly spawned herd
+pub const HERD_SIZE: RangeInclusive<usize> = 3..=6;
+/// Ticks a panicked herd keeps running before it regroups
+const SCATTER_TICKS: u64 = 5;
+/// How far from the danger a scattered herd gathers again
+const SCATTER_DISTANCE: usize = 15;
+/// How far off a herd's next pasture lies
+const PASTURE_DISTANCE: RangeInclusive<usize> = 40..=80;
+/// Pastures are never this close to the campfire
+const CAMP_BERTH: usize = 20;
+/// Chance the anchor moves a tile on each tick the deer are up, a little
+/// slower than the deer themselves so they keep up
+const DRIFT_CHANCE: f64 = 0.5;
+
+struct Herd {
+    id: u32,
+    x: usize,
+    y: usize,
+    pasture: (usize, usize),
+    /// Where the danger was, and the tick the herd stops running from it
+    scare: Option<(usize, usize, u64)>,
+}
+
+#[derive(Default)]
+pub struct Herds {
+    herds: Vec<Herd>,
+    next_id: u32,
+}
+
+impl Herds {
+    /// Start a new herd grazing around (x, y), and return its id
+    pub fn form(&mut self, x: usize, y: usize, world: &World, rng: &mut impl Rng) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        let pasture = pick_pasture(x, y, world, rng);
+        self.herds.push(Herd { id, x, y, pasture, scare: None });
+        id
+    }
+
+    /// Where the deer of herd `id` should be going this tick
+    pub fn lead(&self, id: u32, tick: u64) -> Lead {
+        match self.herds.iter().find(|h| h.id == id) {
+            Some(Herd { scare: Some((ox, oy, until)), .. }) if tick < *until => Lead::Scatter(*ox, *oy),
+            Some(herd) => Lead::Herd(herd.x, herd.y),
+            None => Lead::Alone,
+        }
+    }
+
+    /// Panic herd `id` away from an orc at (ox, oy). The anchor only moves off
+    /// the first time, so deer that keep spotting the orc don't push it further.
+    pub fn scatter(&mut self, id: u32, ox: usize, oy: usize, world: &World, tick: u64) {
+        let Some(herd) = self.herds.iter_mut().find(|h| h.id == id) else {
+            return;
+        };
+        let already_running = herd.scare.is_some_and(|(_, _, until)| tick < until);
+        herd.scare = Some((ox, oy, tick + SCATTER_TICKS));
+        if already_running {
+            return;
+        }
+        let dx = (herd.x as i32 - ox as i32).signum();
+        let dy = (herd.y as i32 - oy as i32).signum();
+        let (dx, dy) = if (dx, dy) == (0, 0) { (1, 0) } else { (dx, dy) };
+        let (nx, ny) = offset(ox, oy, dx, dy, SCATTER_DISTANCE);
+        if world.is_open_to_wildlife(nx, ny) {
+            (herd.x, herd.y) = (nx, ny);
+        }
+        // And graze on further off in the same direction
+        let (px, py) = offset(ox, oy, dx, dy, *PASTURE_DISTANCE.start());
+        if world.is_open_to_wildlife(px, py) {
+            herd.pasture = (px, py);
+        }
+    }
+
+    /// Move every anchor along towards its pasture while the deer are up,
+    /// picking a new pasture on arrival or when the way is blocked
+    pub fn drift(&mut self, world: &World, rng: &mut impl Rng, awake: bool) {
+        if !awake {
+            return;
+        }
+        for herd in &mut self.herds {
+            if !rng.gen_bool(DRIFT_CHANCE) {
+                continue;
+            }
+            let (px, py) = herd.pasture;
+            let nx = (herd.x as i32 + (px as i32 - herd.x as i32).signum()) as usize;
+            let ny = (herd.y as i32 + (py as i32 - herd.y as i32).signum()) as usize;
+            if (nx, ny) == (px, py) || !world.is_open_to_wildlife(nx, ny) {
+                herd.pasture = pick_pasture(herd.x, herd.y, world, rng);
+            } else {
+                (herd.x, herd.y) = (nx, ny);
+            }
+        }
+    }
+
+    /// Forget herds that have no deer left
+    pub fn prune(&mut self, animals: &[Animal]) {
+        self.herds.retain(|h| animals.iter().any(|a| a.alive && a.herd == Some(h.id)));
+    }
+}
+
+/// A tile `distance` steps from (x, y) in direction (dx, dy), kept off the map's edge
+fn offset(x: usize, y: usize, dx: i32, dy: i32, distance: usize) -> (usize, usize) {
+    let nx = (x as i32 + dx * distance as i32).clamp(5, MAP_WIDTH as i32 - 6) as usize;
+    let ny = (y as i32 + dy * distance as i32).clamp(5, MAP_HEIGHT as i32 - 6) as usize;
+    (nx, ny)
+}
+
+/// Somewhere open a good way off from (x, y) and well clear of camp to head
+/// for next, or (x, y) itself if nowhere turns up
+fn pick_pasture(x: usize, y: usize, world: &World, rng: &mut impl Rng) -> (usize, usize) {
+    let (cx, cy) = world.campfire_pos;
+    for _ in 0..20 {
+        let (dx, dy) = (rng.gen_range(-1..=1), rng.gen_range(-1..=1));
+        if (dx, dy) == (0, 0) {
+            continue;
+        }
+        let (px, py) = offset(x, y, dx, dy, rng.gen_range(PASTURE_DISTANCE));
+        if cx.abs_diff(px) + cy.abs_diff(py) > CAMP_BERTH && world.is_open_to_wildlife(px, py) {
+            return (px, py);
+        }
+    }
+    (x, y)
+}
@@ -0,0 +1,41 @@
+//! Livestock the clan keeps in pens by the camp.
+
+use crate::animal::Animal;
+use crate::world::World;
+
+/// A penned animal is fed once its hunger gets this high
+pub const FEED_AT: f32 = 50.0;
+/// How much tamer each meal makes a captive, out of `TAME`
+pub const TAME_PER_FEED: f32 = 25.0;
+/// Tameness of a captive that is fully tame, and of a young one born in the pens
+pub const TAME: f32 = 100.0;
+pub const BORN_TAMENESS: f32 = 50.0;
+/// Tame animals kept back for breeding rather than butchered
+pub const BREEDING_STOCK: usize = 2;
+/// Chance each dawn that a fed breeding pair has young
+pub const BREED_CHANCE: f64 = 0.3;
+/// An orc this close to a penned animal stands guard over it against wolves
+pub const GUARD_RADIUS: usize = 2;
+/// How far from where it stands a herder reaches to feed or butcher an animal
+pub const PEN_REACH: usize = 2;
+/// Furthest from camp a herder goes after a boar to bring in
+pub const CAPTURE_RADIUS: usize = 60;
+/// Meals there have to be on the rack before a herder brings in another mouth to feed
+pub const CAPTURE_STOCK: u32 = 6;
+
+/// Whether `animal` is kept in the pens, or being carried to them
+pub fn is_livestock(animal: &Animal) -> bool {
+    animal.alive && animal.tame.is_some()
+}
+
+/// A pen tile for one more animal, if the pens aren't full: an empty one if
+/// there is, or else any, since each pen tile holds one animal but they
+/// wander among them
+pub fn free_pen(world: &World, animals: &[Animal]) -> Option<(usize, usize)> {
+    let pens: Vec<(usize, usize)> = world.pens().collect();
+    if animals.iter().filter(|a| is_livestock(a)).count() >= pens.len() {
+        return None;
+    }
+    let occupied = |&(x, y): &(usize, usize)| animals.iter().any(|a| is_livestock(a) && (a.x, a.y) == (x, y));
+    pens.iter().copied().find(|pen| !occupied(pen)).or(pens.first().copied())
+}
@@ -0,0 +1,605 @@
+//! Key bindings and the input state machine.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Command {
+    Quit,
+    Menu,
+    TogglePause,
+    SpeedUp,
+    SpeedDown,
+    StepTick,
+    ToggleTurbo,
+    CursorUp,
+    CursorDown,
+    CursorLeft,
+    CursorRight,
+    CursorUpFast,
+    CursorDownFast,
+    CursorLeftFast,
+    CursorRightFast,
+    CycleOrc,
+    DropFood,
+    ToggleDawnNews,
+    GotoCampfire,
+    GotoMeatRack,
+    GotoSelectedOrc,
+    CycleTheme,
+    BuildHut,
+    BuildWall,
+    BuildWallLine,
+    BuildGate,
+    BuildWell,
+    BuildWatchtower,
+    BuildStorage,
+    BuildWorkbench,
+    BuildBridge,
+    BuildPen,
+    TogglePerf,
+    ToggleMinimap,
+    ToggleTooltip,
+    ToggleGlyphs,
+    ToggleLegend,
+    ToggleHelp,
+    Console,
+    OrderMove,
+    OrderHunt,
+    RenameOrc,
+    NameClan,
+    DismissNotice,
+    ToggleSidebar,
+    ToggleLog,
+    WidenSidebar,
+    NarrowSidebar,
+    GrowLog,
+    ShrinkLog,
+    CycleZoom,
+    ToggleAmbience,
+    CycleView,
+    CancelActivity,
+    RecallAll,
+    Evacuate,
+    CampfireMenu,
+    ToggleBestiary,
+    ToggleGraphs,
+    Export,
+    CycleDesignation,
+    MarkTile,
+    ScrollLogBack,
+    ScrollLogForward,
+    PickEvent,
+    FilterGeneral,
+    FilterBirths,
+    FilterDeaths,
+    FilterHunts,
+    FilterFood,
+    FilterAlerts,
+    FilterOmens,
+    FilterSocial,
+    FilterCombat,
+}
+
+impl Command {
+    pub const ALL: [Command; 74] = [
+        Command::Quit,
+        Command::Menu,
+        Command::TogglePause,
+        Command::SpeedUp,
+        Command::SpeedDown,
+        Command::StepTick,
+        Command::ToggleTurbo,
+        Command::CursorUp,
+        Command::CursorDown,
+        Command::CursorLeft,
+        Command::CursorRight,
+        Command::CursorUpFast,
+        Command::CursorDownFast,
+        Command::CursorLeftFast,
+        Command::CursorRightFast,
+        Command::CycleOrc,
+        Command::DropFood,
+        Command::ToggleDawnNews,
+        Command::GotoCampfire,
+        Command::GotoMeatRack,
+        Command::GotoSelectedOrc,
+        Command::CycleTheme,
+        Command::BuildHut,
+        Command::BuildWall,
+        Command::BuildWallLine,
+        Command::BuildGate,
+        Command::BuildWell,
+        Command::BuildWatchtower,
+        Command::BuildStorage,
+        Command::BuildWorkbench,
+        Command::BuildBridge,
+        Command::BuildPen,
+        Command::TogglePerf,
+        Command::ToggleMinimap,
+        Command::ToggleTooltip,
+        Command::ToggleGlyphs,
+        Command::ToggleLegend,
+        Command::ToggleHelp,
+        Command::Console,
+        Command::OrderMove,
+        Command::OrderHunt,
+        Command::RenameOrc,
+        Command::NameClan,
+        Command::DismissNotice,
+        Command::ToggleSidebar,
+        Command::ToggleLog,
+        Command::WidenSidebar,
+        Command::NarrowSidebar,
+        Command::GrowLog,
+        Command::ShrinkLog,
+        Command::CycleZoom,
+        Command::ToggleAmbience,
+        Command::CycleView,
+        Command::CancelActivity,
+        Command::RecallAll,
+        Command::Evacuate,
+        Command::CampfireMenu,
+        Command::ToggleBestiary,
+        Command::ToggleGraphs,
+        Command::Export,
+        Command::CycleDesignation,
+        Command::MarkTile,
+        Command::ScrollLogBack,
+        Command::ScrollLogForward,
+        Command::PickEvent,
+        Command::FilterGeneral,
+        Command::FilterBirths,
+        Command::FilterDeaths,
+        Command::FilterHunts,
+        Command::FilterFood,
+        Command::FilterAlerts,
+        Command::FilterOmens,
+        Command::FilterSocial,
+        Command::FilterCombat,
+    ];
+
+    /// Name used in the `[keys]` table of the config file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Command::Quit => "quit",
+            Command::Menu => "menu",
+            Command::TogglePause => "pause",
+            Command::SpeedUp => "speed_up",
+            Command::SpeedDown => "speed_down",
+            Command::StepTick => "step",
+            Command::ToggleTurbo => "turbo",
+            Command::CursorUp => "cursor_up",
+            Command::CursorDown => "cursor_down",
+            Command::CursorLeft => "cursor_left",
+            Command::CursorRight => "cursor_right",
+            Command::CursorUpFast => "cursor_up_fast",
+            Command::CursorDownFast => "cursor_down_fast",
+            Command::CursorLeftFast => "cursor_left_fast",
+            Command::CursorRightFast => "cursor_right_fast",
+            Command::CycleOrc => "next_orc",
+            Command::DropFood => "drop_food",
+            Command::ToggleDawnNews => "toggle_news",
+            Command::GotoCampfire => "goto_campfire",
+            Command::GotoMeatRack => "goto_meat_rack",
+            Command::GotoSelectedOrc => "goto_selected_orc",
+            Command::CycleTheme => "next_theme",
+            Command::BuildHut => "build_hut",
+            Command::BuildWall => "build_wall",
+            Command::BuildWallLine => "build_wall_line",
+            Command::BuildGate => "build_gate",
+            Command::BuildWell => "build_well",
+            Command::BuildWatchtower => "build_watchtower",
+            Command::BuildStorage => "build_storage",
+            Command::BuildWorkbench => "build_workbench",
+            Command::BuildBridge => "build_bridge",
+            Command::BuildPen => "build_pen",
+            Command::TogglePerf => "toggle_perf",
+            Command::ToggleMinimap => "minimap",
+            Command::ToggleTooltip => "tooltip",
+            Command::ToggleGlyphs => "glyphs",
+            Command::ToggleLegend => "legend",
+            Command::ToggleHelp => "help",
+            Command::Console => "console",
+            Command::OrderMove => "order_move",
+            Command::OrderHunt => "order_hunt",
+            Command::RenameOrc => "rename",
+            Command::NameClan => "name_clan",
+            Command::DismissNotice => "dismiss",
+            Command::ToggleSidebar => "sidebar",
+            Command::ToggleLog => "log",
+            Command::WidenSidebar => "sidebar_wider",
+            Command::NarrowSidebar => "sidebar_narrower",
+            Command::GrowLog => "log_taller",
+            Command::ShrinkLog => "log_shorter",
+            Command::CycleZoom => "zoom",
+            Command::ToggleAmbience => "toggle_ambience",
+            Command::CycleView => "next_view",
+            Command::CancelActivity => "cancel_activity",
+            Command::RecallAll => "recall_all",
+            Command::Evacuate => "evacuate",
+            Command::CampfireMenu => "campfire",
+            Command::ToggleBestiary => "bestiary",
+            Command::ToggleGraphs => "graphs",
+            Command::Export => "export",
+            Command::CycleDesignation => "designate",
+            Command::MarkTile => "mark",
+            Command::ScrollLogBack => "log_back",
+            Command::ScrollLogForward => "log_forward",
+            Command::PickEvent => "log_pick",
+            Command::FilterGeneral => "filter_general",
+            Command::FilterBirths => "filter_births",
+            Command::FilterDeaths => "filter_deaths",
+            Command::FilterHunts => "filter_hunts",
+            Command::FilterFood => "filter_food",
+            Command::FilterAlerts => "filter_alerts",
+            Command::FilterOmens => "filter_omens",
+            Command::FilterSocial => "filter_social",
+            Command::FilterCombat => "filter_combat",
+        }
+    }
+
+    /// What the command does, for the help screen
+    pub fn help(&self) -> &'static str {
+        match self {
+            Command::Quit => "Quit",
+            Command::Menu => "Pause menu: new world, settings, save, load",
+            Command::TogglePause => "Pause or resume",
+            Command::SpeedUp => "Speed up",
+            Command::SpeedDown => "Slow down",
+            Command::StepTick => "Move on one tick while paused",
+            Command::ToggleTurbo => "Run as fast as it'll go, or back to the speed set",
+            Command::CursorUp => "Move the cursor up",
+            Command::CursorDown => "Move the cursor down",
+            Command::CursorLeft => "Move the cursor left",
+            Command::CursorRight => "Move the cursor right",
+            Command::CursorUpFast => "Move the cursor 10 tiles up",
+            Command::CursorDownFast => "Move the cursor 10 tiles down",
+            Command::CursorLeftFast => "Move the cursor 10 tiles left",
+            Command::CursorRightFast => "Move the cursor 10 tiles right",
+            Command::CycleOrc => "Select the next orc",
+            Command::DropFood => "Drop food at the cursor",
+            Command::ToggleDawnNews => "Toggle the news at dawn",
+            Command::GotoCampfire => "Cursor to the campfire",
+            Command::GotoMeatRack => "Cursor to the meat rack",
+            Command::GotoSelectedOrc => "Cursor to the selected orc",
+            Command::CycleTheme => "Next color theme",
+            Command::BuildHut => "Mark out a hut",
+            Command::BuildWall => "Mark out a wall",
+            Command::BuildWallLine => "Start or finish a line of wall",
+            Command::BuildGate => "Mark out a gate",
+            Command::BuildWell => "Mark out a well",
+            Command::BuildWatchtower => "Mark out a watchtower",
+            Command::BuildStorage => "Mark out a storage hut",
+            Command::BuildWorkbench => "Mark out a workbench",
+            Command::BuildBridge => "Mark out a bridge",
+            Command::BuildPen => "Mark out a pen",
+            Command::TogglePerf => "Performance overlay",
+            Command::ToggleMinimap => "Minimap",
+            Command::ToggleTooltip => "Box describing the tile under the cursor",
+            Command::ToggleGlyphs => "Unicode glyphs or plain ASCII",
+            Command::ToggleLegend => "Legend to the marks on the map",
+            Command::OrderMove => "Send the selected orc to a tile",
+            Command::OrderHunt => "Send the selected orc after the game under the cursor",
+            Command::RenameOrc => "Rename the selected orc",
+            Command::NameClan => "Give the clan a name",
+            Command::DismissNotice => "Take down the banner at the top of the map",
+            Command::ToggleSidebar => "Hide or show the sidebar",
+            Command::ToggleLog => "Hide or show the event log",
+            Command::WidenSidebar => "Widen the sidebar",
+            Command::NarrowSidebar => "Narrow the sidebar",
+            Command::GrowLog => "Make the event log taller",
+            Command::ShrinkLog => "Make the event log shorter",
+            Command::CycleZoom => "Zoom the map out, then back in",
+            Command::ToggleAmbience => "Drifting leaves, sparks and rain",
+            Command::CycleView => "What the orcs on the map are colored by",
+            Command::CancelActivity => "Make the selected orc stop and decide again",
+            Command::RecallAll => "Recall every orc to camp",
+            Command::Evacuate => "Evacuate the clan to the cursor, or call it home",
+            Command::CampfireMenu => "Ventures to send a party on",
+            Command::ToggleBestiary => "Bestiary",
+            Command::ToggleGraphs => "Graphs of how the clan has fared",
+            Command::Export => "Export the event log and daily statistics to CSV",
+            Command::CycleDesignation => "Mark trees to chop or bushes to gather, or stop",
+            Command::MarkTile => "Mark the tile, or open the selected orc's details",
+            Command::ScrollLogBack => "Scroll the event log back",
+            Command::ScrollLogForward => "Scroll the event log forward",
+            Command::PickEvent => "Pick out an event in the log to jump to where it happened",
+            Command::FilterGeneral => "Hide or show general events",
+            Command::FilterBirths => "Hide or show births",
+            Command::FilterDeaths => "Hide or show deaths",
+            Command::FilterHunts => "Hide or show hunting",
+            Command::FilterFood => "Hide or show food events",
+            Command::FilterAlerts => "Hide or show alerts",
+            Command::FilterOmens => "Hide or show omens",
+            Command::FilterSocial => "Hide or show social events",
+            Command::FilterCombat => "Hide or show combat",
+            Command::ToggleHelp => "This help",
+            Command::Console => "Debug command: spawn, set, teleport, reveal, kill",
+        }
+    }
+}
+
+/// One key press together with the modifiers held down
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Chord {
+    code: KeyCode,
+    mods: KeyModifiers,
+}
+
+impl Chord {
+    fn new(code: KeyCode, mods: KeyModifiers) -> Chord {
+        // Shift is already part of a typed character ('G', '+'), and which
+        // characters need it depends on the keyboard layout, so ignore it there
+        let mods = match code {
+            KeyCode::Char(_) => mods - KeyModifiers::SHIFT,
+            _ => mods,
+        };
+        Chord { code, mods: mods & (KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT) }
+    }
+
+    pub fn from_event(event: KeyEvent) -> Chord {
+        Chord::new(event.code, event.modifiers)
+    }
+
+    /// Parse a chord like `q`, `space`, `ctrl+c` or `shift+left`
+    pub fn parse(spec: &str) -> Result<Chord, String> {
+        let mut mods = KeyModifiers::NONE;
+        let mut parts: Vec<&str> = spec.split('+').collect();
+        // A trailing empty part means the key itself is '+', as in "ctrl++"
+        if spec.ends_with('+') {
+            parts.pop();
+            if let Some(last) = parts.last_mut() {
+                *last = "+";
+            }
+        }
+        let (key, modifiers) = parts.split_last().ok_or_else(|| format!("empty key '{}'", spec))?;
+        for m in modifiers {
+            mods |= match m.to_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                other => return Err(format!("unknown modifier '{}' in '{}'", other, spec)),
+            };
+        }
+
+        let code = match key.to_lowercase().as_str() {
+            "space" => KeyCode::Char(' '),
+            "tab" => KeyCode::Tab,
+            "enter" => KeyCode::Enter,
+            "esc" => KeyCode::Esc,
+            "backspace" => KeyCode::Backspace,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            lower => {
+                let mut chars = key.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) if mods.contains(KeyModifiers::SHIFT) => KeyCode::Char(c.to_ascii_uppercase()),
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => match lower.strip_prefix('f').and_then(|n| n.parse().ok()) {
+                        Some(n @ 1..=12) => KeyCode::F(n),
+                        _ => return Err(format!("unknown key '{}'", key)),
+                    },
+                }
+            }
+        };
+        Ok(Chord::new(code, mods))
+    }
+
+    pub fn describe(&self) -> String {
+        let mut out = String::new();
+        if self.mods.contains(KeyModifiers::CONTROL) {
+            out.push_str("Ctrl+");
+        }
+        if self.mods.contains(KeyModifiers::ALT) {
+            out.push_str("Alt+");
+        }
+        if self.mods.contains(KeyModifiers::SHIFT) {
+            out.push_str("Shift+");
+        }
+        match self.code {
+            KeyCode::Char(' ') => out.push_str("Space"),
+            KeyCode::Char(c) => out.push(c),
+            KeyCode::F(n) => out.push_str(&format!("F{}", n)),
+            other => out.push_str(&format!("{:?}", other)),
+        }
+        out
+    }
+}
+
+/// Parse a space-separated key sequence like `g c`
+pub fn parse_sequence(spec: &str) -> Result<Vec<Chord>, String> {
+    let chords = spec.split_whitespace().map(Chord::parse).collect::<Result<Vec<_>, _>>()?;
+    if chords.is_empty() {
+        return Err("empty key binding".to_string());
+    }
+    Ok(chords)
+}
+
+enum Lookup {
+    Command(Command),
+    Prefix,
+    NoMatch,
+}
+
+pub struct Keymap {
+    bindings: Vec<(Vec<Chord>, Command)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let defaults = [
+            ("q", Command::Quit),
+            ("ctrl+c", Command::Quit),
+            ("esc", Command::Menu),
+            ("space", Command::TogglePause),
+            ("+", Command::SpeedUp),
+            ("=", Command::SpeedUp),
+            ("-", Command::SpeedDown),
+            (".", Command::StepTick),
+            (">", Command::ToggleTurbo),
+            ("up", Command::CursorUp),
+            ("down", Command::CursorDown),
+            ("left", Command::CursorLeft),
+            ("right", Command::CursorRight),
+            ("shift+up", Command::CursorUpFast),
+            ("shift+down", Command::CursorDownFast),
+            ("shift+left", Command::CursorLeftFast),
+            ("shift+right", Command::CursorRightFast),
+            ("tab", Command::CycleOrc),
+            ("f", Command::DropFood),
+            ("n", Command::ToggleDawnNews),
+            ("g c", Command::GotoCampfire),
+            ("g r", Command::GotoMeatRack),
+            ("g o", Command::GotoSelectedOrc),
+            ("t", Command::CycleTheme),
+            ("b h", Command::BuildHut),
+            ("b w", Command::BuildWall),
+            ("b l", Command::BuildWallLine),
+            ("b g", Command::BuildGate),
+            ("b e", Command::BuildWell),
+            ("b t", Command::BuildWatchtower),
+            ("b s", Command::BuildStorage),
+            ("b c", Command::BuildWorkbench),
+            ("b b", Command::BuildBridge),
+            ("b p", Command::BuildPen),
+            ("f3", Command::TogglePerf),
+            ("m", Command::ToggleMinimap),
+            ("i", Command::ToggleTooltip),
+            ("A", Command::ToggleGlyphs),
+            ("M", Command::ToggleLegend),
+            ("?", Command::ToggleHelp),
+            (":", Command::Console),
+            ("o m", Command::OrderMove),
+            ("o h", Command::OrderHunt),
+            ("r", Command::RenameOrc),
+            ("N", Command::NameClan),
+            ("backspace", Command::DismissNotice),
+            ("S", Command::ToggleSidebar),
+            ("L", Command::ToggleLog),
+            ("]", Command::WidenSidebar),
+            ("[", Command::NarrowSidebar),
+            ("}", Command::GrowLog),
+            ("{", Command::ShrinkLog),
+            ("z", Command::CycleZoom),
+            ("a", Command::ToggleAmbience),
+            ("v", Command::CycleView),
+            ("x", Command::CancelActivity),
+            ("R", Command::RecallAll),
+            ("E", Command::Evacuate),
+            ("c", Command::CampfireMenu),
+            ("B", Command::ToggleBestiary),
+            ("G", Command::ToggleGraphs),
+            ("X", Command::Export),
+            ("d", Command::CycleDesignation),
+            ("enter", Command::MarkTile),
+            ("pageup", Command::ScrollLogBack),
+            ("pagedown", Command::ScrollLogForward),
+            ("l p", Command::PickEvent),
+            ("l g", Command::FilterGeneral),
+            ("l b", Command::FilterBirths),
+            ("l d", Command::FilterDeaths),
+            ("l h", Command::FilterHunts),
+            ("l f", Command::FilterFood),
+            ("l a", Command::FilterAlerts),
+            ("l o", Command::FilterOmens),
+            ("l s", Command::FilterSocial),
+            ("l c", Command::FilterCombat),
+        ];
+        Keymap {
+            bindings: defaults
+                .iter()
+                .map(|&(spec, command)| (parse_sequence(spec).expect("default key binding"), command))
+                .collect(),
+        }
+    }
+}
+
+impl Keymap {
+    /// The default bindings with the `[keys]` overrides from the config file
+    /// on top, given as command names and key specs
+    pub fn from_config(keys: &[(String, String)]) -> Result<Keymap, String> {
+        let mut keymap = Keymap::default();
+        for (name, spec) in keys {
+            let Some(&command) = Command::ALL.iter().find(|c| c.name() == name) else {
+                return Err(format!("unknown setting 'keys.{}'", name));
+            };
+            let sequence = parse_sequence(spec).map_err(|e| format!("keys.{}: {}", name, e))?;
+            keymap.rebind(command, sequence)?;
+        }
+        Ok(keymap)
+    }
+
+    /// Replace every binding of `command` with `keys`
+    pub fn rebind(&mut self, command: Command, keys: Vec<Chord>) -> Result<(), String> {
+        self.bindings.retain(|(seq, c)| *c != command && *seq != keys);
+        // Neither sequence could ever finish if one were a prefix of the other
+        if let Some((_, other)) = self.bindings.iter().find(|(seq, _)| seq.starts_with(&keys) || keys.starts_with(seq)) {
+            return Err(format!("{} clashes with the binding for {}", command.name(), other.name()));
+        }
+        self.bindings.push((keys, command));
+        Ok(())
+    }
+
+    /// How to trigger `command`, for the help screen
+    pub fn describe(&self, command: Command) -> String {
+        match self.bindings.iter().find(|(_, c)| *c == command) {
+            Some((seq, _)) => seq.iter().map(|c| c.describe()).collect::<Vec<_>>().join(" "),
+            None => "-".to_string(),
+        }
+    }
+
+    fn lookup(&self, typed: &[Chord]) -> Lookup {
+        let mut prefix = false;
+        for (seq, command) in &self.bindings {
+            if seq == typed {
+                return Lookup::Command(*command);
+            }
+            prefix |= seq.starts_with(typed);
+        }
+        if prefix { Lookup::Prefix } else { Lookup::NoMatch }
+    }
+}
+
+/// Tracks a partly typed key sequence between key presses
+#[derive(Default)]
+pub struct InputState {
+    pub keymap: Keymap,
+    pending: Vec<Chord>,
+}
+
+impl InputState {
+    /// Feed one key press; returns a command once a full binding has been typed
+    pub fn feed(&mut self, event: KeyEvent) -> Option<Command> {
+        let chord = Chord::from_event(event);
+        if chord.code == KeyCode::Esc && !self.pending.is_empty() {
+            self.pending.clear();
+            return None;
+        }
+
+        self.pending.push(chord);
+        match self.keymap.lookup(&self.pending) {
+            Lookup::Command(command) => {
+                self.pending.clear();
+                Some(command)
+            }
+            Lookup::Prefix => None,
+            Lookup::NoMatch => {
+                // A stray key abandons the sequence and is tried on its own
+                let retry = self.pending.len() > 1;
+                self.pending.clear();
+                if retry { self.feed(event) } else { None }
+            }
+        }
+    }
+
+    /// The keys typed so far of an unfinished sequence
+    pub fn pending(&self) -> String {
+        self.pending.iter().map(|c| c.describe()).collect::<Vec<_>>().join(" ")
+    }
+}
@@ -0,0 +1,64 @@
+//! How long the raw meat on the rack has been hanging there.
+
+use std::collections::VecDeque;
+
+/// Days raw meat keeps on the rack
+pub const SPOIL_DAYS: usize = 3;
+
+/// Units of meat each storage hut keeps from spoiling
+pub const CELLAR_SPACE: u32 = 10;
+
+pub struct Larder {
+    /// Meat put away in storage huts, which never goes off
+    cellared: u32,
+    /// Meat on the rack by the day it was hung up, oldest first
+    days: VecDeque<u32>,
+}
+
+impl Larder {
+    pub fn new(meat: u32) -> Self {
+        Larder { cellared: 0, days: VecDeque::from([meat]) }
+    }
+
+    pub fn store(&mut self) {
+        match self.days.back_mut() {
+            Some(today) => *today += 1,
+            None => self.days.push_back(1),
+        }
+    }
+
+    /// Take the oldest meat, starting with the cellar
+    pub fn take(&mut self) {
+        if self.cellared > 0 {
+            self.cellared -= 1;
+        } else if let Some(oldest) = self.days.iter_mut().find(|n| **n > 0) {
+            *oldest -= 1;
+        }
+    }
+
+    pub fn cellared(&self) -> u32 {
+        self.cellared
+    }
+
+    /// Let all the meat on the rack go off, leaving what's cellared. Returns
+    /// how much that was.
+    pub fn spoil_rack(&mut self) -> u32 {
+        let spoiled = self.days.iter().sum();
+        self.days.clear();
+        spoiled
+    }
+
+    /// Start a new day with room for `cellar_space` units in storage. Meat that
+    /// has been out too long goes into the cellar if it fits. Returns how much
+    /// didn't and has spoiled.
+    pub fn age(&mut self, cellar_space: u32) -> u32 {
+        self.days.push_back(0);
+        if self.days.len() <= SPOIL_DAYS {
+            return 0;
+        }
+        let expired = self.days.pop_front().unwrap_or(0);
+        let kept = expired.min(cellar_space.saturating_sub(self.cellared));
+        self.cellared += kept;
+        expired - kept
+    }
+}
@@ -0,0 +1,80 @@
+//! The orc village simulation, apart from any frontend.
+//!
+//! Everything that makes the world go lives here: the map and what grows on
+//! it ([`world`]), the orcs and how they decide what to do ([`orc`], [`ai`]),
+//! the wildlife ([`animal`]), and [`sim::Sim`], which holds the lot and moves
+//! it on a tick at a time with [`sim::Sim::tick`]. The `orcs` binary is a
+//! terminal frontend built on top of it, and nothing here knows about the
+//! screen, so a GUI, a web renderer or an integration test can drive a world
+//! just the same:
+//!
+//! ```no_run
+//! use orcs::config::WorldConfig;
+//! use orcs::scenario::Scenario;
+//! use orcs::sim::Sim;
+//!
+//! let mut sim = Sim::with_seed(42, Scenario::Sandbox, &WorldConfig::default());
+//! while sim.tick < 1000 && !sim.paused {
+//!     sim.tick();
+//! }
+//! let living = sim.orcs.iter().filter(|o| o.alive).count();
+//! println!("{} orcs alive on day {}", living, orcs::calendar::day_number(sim.tick, sim.world.day_length));
+//! ```
+//!
+//! A world is fully determined by its seed, its scenario and the settings
+//! in [`config::Config`], and what happens in it is told in its
+//! [`event::EventLog`]. For a frontend that would rather not reach into the
+//! world's state, [`gym::observe`] sums it up as a plain [`gym::Observation`]
+//! that also writes itself out as JSON, [`gym::apply`] carries out anything a
+//! player could do, and [`simulation::Simulation`] shares a game between
+//! threads, one stepping it while others watch.
+
+pub mod ai;
+pub mod alarm;
+pub mod animal;
+pub mod bestiary;
+pub mod building;
+pub mod burial;
+pub mod calendar;
+pub mod carcass;
+pub mod combat;
+pub mod commands;
+pub mod config;
+pub mod custom;
+pub mod designation;
+pub mod diary;
+pub mod emote;
+pub mod evacuation;
+pub mod event;
+pub mod expedition;
+pub mod export;
+pub mod gym;
+pub mod healing;
+pub mod herd;
+pub mod husbandry;
+pub mod json;
+pub mod larder;
+pub mod light;
+pub mod markers;
+pub mod metrics;
+pub mod notify;
+pub mod nursery;
+pub mod orc;
+pub mod pack;
+pub mod pathfinding;
+pub mod projectile;
+pub mod raid;
+pub mod rival;
+pub mod scenario;
+pub mod script;
+pub mod serve;
+pub mod sim;
+pub mod simulation;
+pub mod spatial;
+pub mod spirits;
+pub mod stats;
+pub mod territory;
+pub mod tool;
+pub mod toml;
+pub mod watch;
+pub mod world;
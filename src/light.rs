@@ -0,0 +1,38 @@
+//! Light after dark.
+
+use std::ops::RangeInclusive;
+
+use crate::orc::Orc;
+use crate::world::World;
+
+/// How far the light of a burning campfire, and of a torch, reaches
+pub const FIRE_LIGHT: usize = 8;
+pub const TORCH_LIGHT: usize = 3;
+/// Ticks a torch burns, and the fuel it takes off the campfire to light
+pub const TORCH_TICKS: u32 = 25;
+pub const TORCH_FUEL: u32 = 5;
+/// Chance each tick that an orc making its way through the dark strays off
+/// it, and how many steps it wanders before finding it again
+pub const STRAY_CHANCE: f64 = 0.03;
+pub const STRAY_STEPS: RangeInclusive<usize> = 1..=3;
+
+/// Where the light falls at night
+pub struct Lights {
+    fire: Option<(usize, usize)>,
+    torches: Vec<(usize, usize)>,
+}
+
+impl Lights {
+    /// The light thrown by the campfire, if it's burning, and every torch carried about
+    pub fn of(world: &World, orcs: &[Orc]) -> Lights {
+        Lights {
+            fire: world.fire_lit().then_some(world.campfire_pos),
+            torches: orcs.iter().filter(|o| o.alive && o.torch > 0).map(|o| (o.x, o.y)).collect(),
+        }
+    }
+
+    pub fn lit(&self, x: usize, y: usize) -> bool {
+        let within = |(lx, ly): (usize, usize), reach: usize| x.abs_diff(lx).max(y.abs_diff(ly)) <= reach;
+        self.fire.is_some_and(|f| within(f, FIRE_LIGHT)) || self.torches.iter().any(|&t| within(t, TORCH_LIGHT))
+    }
+}
@@ -1,84 +1,737 @@
-mod animal;
+mod ambience;
 mod app;
-mod event;
-mod orc;
-mod pathfinding;
+mod cli;
+mod header;
+mod headless;
+mod help;
+mod input;
+mod minimap;
+mod mouse;
+mod menu;
+mod recording;
+mod save;
+mod scheduler;
+mod perf;
 mod render;
-mod world;
+mod status;
+mod term;
+mod theme;
+mod tooltip;
+mod tutorial;
+mod view;
+mod zoom;
 
+use std::fs;
 use std::io;
+use std::path::Path;
 use std::time::{Duration, Instant};
 
-use crossterm::event::{self as ct_event, Event as CtEvent, KeyCode, KeyEventKind};
+use crossterm::event::{self as ct_event, DisableMouseCapture, EnableMouseCapture, Event as CtEvent, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use crossterm::execute;
 use crossterm::terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode};
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 
-use app::App;
+// The simulation comes from the library; the frontend's modules reach it
+// through these as if it were their own
+use orcs::{alarm, animal, building, calendar, commands, config, custom, designation, diary, emote, event, expedition, export};
+use orcs::{gym, json, markers, metrics, nursery, orc, pack, scenario, script, serve, sim, spatial, territory, toml, tool, world};
+
+use app::{App, Screen};
+use building::Building;
+use cli::Options;
+use config::Config;
+use custom::Custom;
+use event::{Archive, EventCategory};
+use gym::{Action, StateDump};
+use input::{Command, Keymap};
+use metrics::Metrics;
+use recording::{Header, Input, Player, Press, Recorder, Recording};
+use scheduler::Scheduler;
+use script::Hook;
+use serve::Server;
+use sim::Sim;
+use theme::Theme;
+use tutorial::Tutorial;
+
+/// How often playback checks for recorded keys that have come due
+const PLAYBACK_POLL: Duration = Duration::from_millis(10);
+/// Longest wait between frames while the game runs, about thirty a second,
+/// to keep the afterimages fading and the ambient particles moving
+const FRAME: Duration = Duration::from_millis(33);
+/// Ticks run between one frame and the next in turbo
+const TURBO_TICKS: u32 = 50;
+/// Longest wait for a key while the world stands still
+const IDLE: Duration = Duration::from_millis(250);
+/// Lines PageUp and PageDown move the help screen
+const HELP_PAGE: i32 = 10;
+/// The file the pause menu saves to and loads from, in the config folder
+const SAVE_FILE: &str = "save.orcrec";
 
 fn main() -> io::Result<()> {
+    let opts = match Options::parse(std::env::args().skip(1)) {
+        Ok(opts) => opts,
+        Err(msg) => {
+            eprintln!("{}", msg);
+            std::process::exit(2);
+        }
+    };
+    if opts.show_help {
+        println!("{}", cli::USAGE);
+        return Ok(());
+    }
+    let recording = opts.play_path.as_deref().map(|path| {
+        Recording::load(path).unwrap_or_else(|msg| {
+            eprintln!("{}", msg);
+            std::process::exit(2);
+        })
+    });
+    // Keep the text of the config file so a recording can carry it along
+    let config_src = match (&recording, &opts.config_path) {
+        (Some(recording), _) => recording.header.config.clone(),
+        (None, Some(path)) => fs::read_to_string(path).unwrap_or_else(|e| {
+            eprintln!("{}: {}", path.display(), e);
+            std::process::exit(2);
+        }),
+        (None, None) => String::new(),
+    };
+    let mut config = Config::parse(&config_src).and_then(|config| Keymap::from_config(&config.keys).map(|_| config)).unwrap_or_else(|msg| {
+        let origin = match (&opts.play_path, &opts.config_path) {
+            (Some(path), _) | (None, Some(path)) => path.display().to_string(),
+            (None, None) => "config".to_string(),
+        };
+        eprintln!("{}: {}", origin, msg);
+        std::process::exit(2);
+    });
+    if let Some(dir) = config::dir(opts.config_path.as_deref()) {
+        config.hooks = script::load_dir(&dir.join("scripts")).unwrap_or_else(|msg| {
+            eprintln!("{}", msg);
+            std::process::exit(2);
+        });
+    }
+    let custom = opts.scenario_path.as_deref().map(|path| {
+        Custom::load(path).unwrap_or_else(|msg| {
+            eprintln!("{}", msg);
+            std::process::exit(2);
+        })
+    });
+    let archive = opts.archive_path.as_ref().map(|path| {
+        Archive::create(path).unwrap_or_else(|e| {
+            eprintln!("{}: {}", path.display(), e);
+            std::process::exit(2);
+        })
+    });
+    let dump = opts.dump.as_ref().map(|dump| {
+        StateDump::create(&dump.path, dump.every).unwrap_or_else(|e| {
+            eprintln!("{}: {}", dump.path.display(), e);
+            std::process::exit(2);
+        })
+    });
+    let metrics = opts.metrics.map(|addr| {
+        Metrics::serve(addr).unwrap_or_else(|e| {
+            eprintln!("{}: {}", addr, e);
+            std::process::exit(2);
+        })
+    });
+    if opts.gym {
+        return gym::run_stdio(opts.seed.unwrap_or_else(rand::random), config, archive, dump);
+    }
+    if let Some(ticks) = opts.ticks {
+        let run = headless::Run {
+            seed: opts.seed.or(custom.as_ref().and_then(|c| c.seed)).unwrap_or_else(rand::random),
+            ticks,
+            scenario: opts.scenario,
+            custom,
+            stress: opts.stress,
+            stats_path: opts.stats_path,
+            metrics,
+        };
+        return headless::run(run, config, archive, dump);
+    }
+    let mut themes = Theme::built_in();
+    if let Some(dir) = config::dir(opts.config_path.as_deref()) {
+        themes.extend(theme::load_dir(&dir.join("themes")).unwrap_or_else(|msg| {
+            eprintln!("{}", msg);
+            std::process::exit(2);
+        }));
+    }
+    if let Some(name) = &config.theme
+        && !themes.iter().any(|t| t.name.eq_ignore_ascii_case(name))
+    {
+        eprintln!("unknown theme '{}'", name);
+        std::process::exit(2);
+    }
+
+    let server = opts.serve.map(|addr| {
+        Server::start(addr).unwrap_or_else(|e| {
+            eprintln!("{}: {}", addr, e);
+            std::process::exit(2);
+        })
+    });
+
     // Setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let result = run(&mut terminal);
+    let result = run(&mut terminal, opts, config, Sources { config: config_src, custom }, themes, recording, Outputs { archive, dump, server, metrics });
 
     // Restore terminal
     disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    execute!(terminal.backend_mut(), DisableMouseCapture, LeaveAlternateScreen)?;
     terminal.show_cursor()?;
 
     result
 }
 
-fn run(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> io::Result<()> {
-    let mut app = App::new();
-    let mut last_tick = Instant::now();
+/// Files the game is set up from, read before the terminal is taken over
+struct Sources {
+    /// Text of the config file, for a recording to carry along
+    config: String,
+    /// The scenario file to start from, if any
+    custom: Option<Custom>,
+}
+
+/// Files the game writes to as it goes, opened before the terminal is taken over
+struct Outputs {
+    archive: Option<Archive>,
+    dump: Option<StateDump>,
+    server: Option<Server>,
+    metrics: Option<Metrics>,
+}
+
+fn run(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    mut opts: Options,
+    config: Config,
+    sources: Sources,
+    themes: Vec<Theme>,
+    recording: Option<Recording>,
+    outputs: Outputs,
+) -> io::Result<()> {
+    let mut player = None;
+    let mut custom = sources.custom;
+    if let Some(recording) = recording {
+        let header = recording.header;
+        opts.seed = Some(header.seed);
+        opts.stress = header.stress;
+        opts.glyphs = opts.glyphs.or(header.glyphs);
+        opts.colors = opts.colors.or(header.colors);
+        opts.tutorial = header.tutorial;
+        opts.scenario = header.scenario;
+        custom = header.custom;
+        player = Some(Player::new(recording.presses));
+    }
+    if opts.tutorial {
+        opts.seed = opts.seed.or(Some(tutorial::TUTORIAL_SEED));
+    }
+    let header = Header {
+        seed: opts.seed.or(custom.as_ref().and_then(|c| c.seed)).unwrap_or_else(rand::random),
+        stress: opts.stress,
+        glyphs: opts.glyphs,
+        colors: opts.colors,
+        tutorial: opts.tutorial,
+        scenario: custom.as_ref().map_or(opts.scenario, |c| c.scenario),
+        config: sources.config,
+        custom,
+    };
+    let mut app = start(&header, config, &themes);
+    app.sim.news_path = opts.news_path;
+    if let Some(archive) = outputs.archive {
+        app.sim.event_log.archive_to(archive)?;
+    }
+    app.sim.dump = outputs.dump;
+    let server = outputs.server;
+    let metrics = outputs.metrics;
+    let mut recorder = match &opts.record_path {
+        Some(path) => {
+            let header = Header { glyphs: Some(app.caps.glyphs), colors: Some(app.caps.colors), ..header.clone() };
+            Some(Recorder::create(path, &header)?)
+        }
+        None => None,
+    };
+    // What the player has done to this world so far, to save it by
+    let mut session = Recording::new(header);
+    let save_path = config::dir(opts.config_path.as_deref()).unwrap_or_default().join(SAVE_FILE);
+    let started = Instant::now();
+    let mut scheduler = Scheduler::new();
 
     loop {
+        app.advance_tutorial();
+        let running = app.screen == Screen::Playing && !app.sim.paused;
+        scheduler.catch_up(app.speed, running && !app.turbo);
+
         // Render
+        app.tick_progress = scheduler.progress();
+        let drawing = Instant::now();
         terminal.draw(|frame| render::render(frame, &mut app))?;
+        app.perf.record_frame(drawing.elapsed());
+        if let Some(metrics) = &metrics {
+            metrics.record_frame(drawing.elapsed());
+        }
 
         // Handle input with timeout
-        let tick_rate = Duration::from_millis(app.tick_interval_ms());
-        let timeout = tick_rate
-            .checked_sub(last_tick.elapsed())
-            .unwrap_or(Duration::ZERO);
+        let mut timeout = if running { scheduler.until_due(app.speed).min(FRAME) } else { IDLE };
+        if player.is_some() {
+            // Wake up often enough to hand over recorded keys on time
+            timeout = timeout.min(PLAYBACK_POLL);
+        }
+        if running && app.turbo {
+            timeout = Duration::ZERO;
+        }
 
         if ct_event::poll(timeout)? {
-            if let CtEvent::Key(key) = ct_event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    match key.code {
-                        KeyCode::Char('q') => {
-                            app.should_quit = true;
+            match ct_event::read()? {
+                CtEvent::Key(key) if key.kind == KeyEventKind::Press => {
+                    if player.is_some() {
+                        // The recording is in charge; Ctrl+C still gets out
+                        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                            return Ok(());
+                        }
+                    } else {
+                        if let Some(recorder) = &mut recorder {
+                            recorder.key(app.sim.tick, key)?;
+                        }
+                        // Only what's done to the world itself goes in a save, not the menu
+                        let (playing, tick) = (app.screen == Screen::Playing, app.sim.tick);
+                        let chosen = press(&mut app, key);
+                        if playing && app.screen == Screen::Playing {
+                            session.presses.push(Press { ms: started.elapsed().as_millis() as u64, tick, input: Input::Key(key) });
+                        }
+                        if let Some(item) = chosen {
+                            choose(&mut app, &mut session, item, &themes, &save_path);
+                        }
+                    }
+                }
+                CtEvent::Mouse(event) if player.is_none() && app.screen == Screen::Playing => {
+                    if let Some(gesture) = app.mouse.read(event, (app.camera_x, app.camera_y), app.zoom.scale()) {
+                        if let Some(recorder) = &mut recorder {
+                            recorder.mouse(app.sim.tick, gesture)?;
                         }
-                        KeyCode::Char(' ') => app.toggle_pause(),
-                        KeyCode::Char('+') | KeyCode::Char('=') => app.speed_up(),
-                        KeyCode::Char('-') => app.speed_down(),
-                        KeyCode::Up => app.move_cursor(0, -1),
-                        KeyCode::Down => app.move_cursor(0, 1),
-                        KeyCode::Left => app.move_cursor(-1, 0),
-                        KeyCode::Right => app.move_cursor(1, 0),
-                        KeyCode::Tab => app.cycle_selected_orc(),
-                        KeyCode::Char('f') => app.drop_food(),
-                        _ => {}
+                        session.presses.push(Press { ms: started.elapsed().as_millis() as u64, tick: app.sim.tick, input: Input::Mouse(gesture) });
+                        app.gesture(gesture);
                     }
                 }
+                _ => {}
             }
         }
 
+        if let Some(player) = &mut player {
+            play(&mut app, player, false, &mut session, &themes, &save_path);
+        }
+        // Once the recording runs out (say it ended in a crash), the keyboard takes over
+        if player.as_ref().is_some_and(Player::finished) {
+            player = None;
+        }
+
         if app.should_quit {
             return Ok(());
         }
 
-        // Tick simulation
-        if last_tick.elapsed() >= tick_rate {
+        // Viewers' orders go in the recording and the save like keys do;
+        // while a recording plays, it alone is in charge
+        if let Some(server) = server.as_ref().filter(|_| player.is_none()) {
+            for order in take_orders(&mut app, server) {
+                if let Some(recorder) = &mut recorder {
+                    recorder.order(app.sim.tick, &order)?;
+                }
+                session.presses.push(Press { ms: started.elapsed().as_millis() as u64, tick: app.sim.tick, input: Input::Order(order) });
+            }
+        }
+
+        // Tick simulation, unless paused or the pause menu is up: as many
+        // ticks as have come due, but no more than fit in a frame, or in turbo
+        // a set number of them flat out. A recording's keys go in between
+        // ticks so that each lands on the tick it was made on.
+        let bursting = Instant::now();
+        let mut ran = 0;
+        while app.screen == Screen::Playing && !app.sim.paused && !app.should_quit {
+            let due = if app.turbo { ran < TURBO_TICKS } else { scheduler.take() };
+            if !due {
+                break;
+            }
+            if let Some(player) = &mut player {
+                play(&mut app, player, true, &mut session, &themes, &save_path);
+            }
+            let ticking = Instant::now();
             app.tick();
-            last_tick = Instant::now();
+            if let Some(metrics) = &metrics {
+                metrics.record_tick(&app.sim, ticking.elapsed());
+            }
+            if let Some(server) = server.as_ref().filter(|s| s.watched()) {
+                server.broadcast(&gym::observe(&app.sim).to_json());
+            }
+            ran += 1;
+            if !app.turbo && bursting.elapsed() >= FRAME {
+                scheduler.forgive();
+                break;
+            }
+        }
+    }
+}
+
+/// Carry out the actions the WebSocket viewers have sent, answering any
+/// that can't be read, and return the text of each one carried out
+fn take_orders(app: &mut App, server: &Server) -> Vec<String> {
+    let mut taken = Vec::new();
+    for (viewer, message) in server.commands() {
+        let actions: Result<Vec<Action>, String> = message.split(';').map(Action::parse).collect();
+        match actions {
+            Ok(actions) => {
+                actions.iter().for_each(|action| gym::apply(&mut app.sim, action));
+                taken.extend(message.split(';').map(|order| order.split_whitespace().collect::<Vec<_>>().join(" ")));
+            }
+            Err(e) => server.reply(viewer, &json::Object::new().str("error", &e).finish()),
+        }
+    }
+    taken
+}
+
+/// Feed `app` the keys and gestures of a recording that have come due,
+/// `tick_due` if the next tick is about to run
+fn play(app: &mut App, player: &mut Player, tick_due: bool, session: &mut Recording, themes: &[Theme], save_path: &Path) {
+    while let Some(input) = player.next(app.sim.tick, tick_due) {
+        match input {
+            Input::Key(key) => {
+                if let Some(item) = press(app, key) {
+                    choose(app, session, item, themes, save_path);
+                }
+            }
+            Input::Mouse(gesture) => app.gesture(gesture),
+            Input::Order(order) => order_from(app, &order),
+        }
+    }
+}
+
+/// Carry out a viewer's order played back from a recording or a save
+fn order_from(app: &mut App, order: &str) {
+    if let Ok(action) = Action::parse(order) {
+        gym::apply(&mut app.sim, &action);
+    }
+}
+
+/// A world set up from the seed and settings in `header`, with `config`
+/// read from the config file it keeps
+fn start(header: &Header, config: Config, themes: &[Theme]) -> App {
+    let sim = match &header.custom {
+        Some(custom) => Sim::with_custom(header.seed, custom, &config.world),
+        None => Sim::with_seed(header.seed, header.scenario, &config.world),
+    };
+    let mut app = App::new(sim);
+    if header.tutorial {
+        app.tutorial = Some(Tutorial::default());
+    }
+    app.sim.ai = config.ai;
+    // Checked when the config was first read
+    app.input.keymap = Keymap::from_config(&config.keys).unwrap_or_default();
+    app.sim.limits = config.limits;
+    app.sim.needs = config.needs;
+    app.sim.childcare = config.childcare;
+    app.sim.hooks = config.hooks;
+    app.sim.notices.responses = config.notify;
+    if let Some(stress) = header.stress {
+        app.sim.populate(stress);
+        app.show_perf = true;
+    }
+    app.themes = themes.to_vec();
+    if let Some(name) = &config.theme {
+        app.select_theme(name);
+    }
+    if let Some(glyphs) = header.glyphs {
+        app.caps.glyphs = glyphs;
+    }
+    if let Some(colors) = header.colors {
+        app.caps.colors = colors;
+    }
+    app
+}
+
+/// A saved world, played back from the start as fast as it'll go up to the
+/// tick it was saved on, with `hooks` from the scripts in place
+fn restore(save: &Recording, themes: &[Theme], hooks: &[Hook]) -> Result<App, String> {
+    let saved_at = save.saved_at.ok_or("a recording, not a save")?;
+    let config = Config { hooks: hooks.to_vec(), ..Config::parse(&save.header.config)? };
+    let mut app = start(&save.header, config, themes);
+    let mut presses = save.presses.iter().peekable();
+    loop {
+        app.advance_tutorial();
+        while let Some(next) = presses.next_if(|p| p.tick <= app.sim.tick) {
+            match &next.input {
+                Input::Key(key) => {
+                    press(&mut app, *key);
+                }
+                Input::Mouse(gesture) => app.gesture(*gesture),
+                Input::Order(order) => order_from(&mut app, order),
+            }
+        }
+        // Paused with nothing left to unpause it, the world won't get any further
+        if app.sim.tick >= saved_at || (app.sim.paused && presses.peek().is_none_or(|p| p.tick > app.sim.tick)) {
+            return Ok(app);
+        }
+        app.tick();
+    }
+}
+
+/// Carry out what was taken from the pause menu: start a new world with the
+/// same settings, save this one or load the last save. The new world keeps
+/// the theme and glyphs picked and the scripts, and the news file, event
+/// archive and state dump carry on into it.
+fn choose(app: &mut App, session: &mut Recording, item: menu::Item, themes: &[Theme], save_path: &Path) {
+    let next = match item {
+        menu::Item::NewWorld => {
+            let header = Header { seed: app.sim.next_seed(), tutorial: false, ..session.header.clone() };
+            let config = Config::parse(&header.config).unwrap_or_default();
+            let next = start(&header, config, themes);
+            *session = Recording::new(header);
+            next
+        }
+        menu::Item::Save => {
+            let saved = save_path.parent().map_or(Ok(()), fs::create_dir_all).and_then(|_| session.save(save_path, app.sim.tick));
+            app.menu_note = Some(match saved {
+                Ok(()) => format!("Saved to {}", save_path.display()),
+                Err(e) => format!("Couldn't save: {}", e),
+            });
+            return;
+        }
+        menu::Item::Load => match Recording::load(save_path).and_then(|save| Ok((restore(&save, themes, &app.sim.hooks)?, save))) {
+            Ok((next, save)) => {
+                *session = Recording { saved_at: None, ..save };
+                next
+            }
+            Err(msg) => {
+                app.menu_note = Some(format!("Couldn't load {}", msg));
+                return;
+            }
+        },
+        _ => return,
+    };
+    let mut next = next;
+    let theme = app.theme().name.clone();
+    next.select_theme(&theme);
+    next.caps = app.caps;
+    next.sim.news_path = app.sim.news_path.take();
+    next.sim.hooks = std::mem::take(&mut app.sim.hooks);
+    next.sim.dump = app.sim.dump.take();
+    if let Some(archive) = app.sim.event_log.take_archive() {
+        // Nothing to be done about an archive that won't take; the game goes on without it
+        let _ = next.sim.event_log.archive_to(archive);
+    }
+    *app = next;
+}
+
+/// Hand a key press to the app: Enter or Esc closes a tutorial lesson, the
+/// food prompt takes the amount to drop while it's up, the `:` prompt a
+/// debug command while it's open, the arrows and Page
+/// keys scroll the help screen while it's up, Esc closes it or the
+/// bestiary, a number picks from the campfire menu while that's up, the
+/// pause menu takes every key while it's up, and anything else goes through
+/// the keymap. Returns what was taken from the pause menu, if it's for the
+/// main loop to carry out.
+fn press(app: &mut App, key: KeyEvent) -> Option<menu::Item> {
+    if app.screen != Screen::Playing {
+        return menu::press(app, key);
+    }
+    if matches!(key.code, KeyCode::Enter | KeyCode::Esc) && app.dismiss_lesson() {
+        return None;
+    }
+    if let Some(typed) = &mut app.food_prompt {
+        match key.code {
+            KeyCode::Char(c) if c.is_ascii_digit() => app.type_food_amount(c),
+            KeyCode::Backspace => {
+                typed.pop();
+            }
+            KeyCode::Enter => app.confirm_food_prompt(),
+            KeyCode::Esc => app.food_prompt = None,
+            _ => {}
+        }
+        return None;
+    }
+    if let Some((_, typed)) = &mut app.naming {
+        match key.code {
+            KeyCode::Char(c) => app.type_name(c),
+            KeyCode::Backspace => {
+                typed.pop();
+            }
+            KeyCode::Enter => app.confirm_naming(),
+            KeyCode::Esc => app.naming = None,
+            _ => {}
+        }
+        return None;
+    }
+    if let Some(typed) = &mut app.console {
+        match key.code {
+            KeyCode::Char(c) => typed.push(c),
+            KeyCode::Backspace => {
+                typed.pop();
+            }
+            KeyCode::Enter => app.run_console(),
+            KeyCode::Esc => app.console = None,
+            _ => {}
+        }
+        return None;
+    }
+    if app.show_bestiary && key.code == KeyCode::Esc {
+        app.show_bestiary = false;
+        return None;
+    }
+    if app.show_help {
+        let scroll = match key.code {
+            KeyCode::Up => -1,
+            KeyCode::Down => 1,
+            KeyCode::PageUp => -HELP_PAGE,
+            KeyCode::PageDown => HELP_PAGE,
+            KeyCode::Esc => {
+                app.toggle_help();
+                return None;
+            }
+            _ => 0,
+        };
+        if scroll != 0 {
+            app.scroll_help(scroll);
+            return None;
+        }
+    }
+    if app.show_graphs && key.code == KeyCode::Esc {
+        app.show_graphs = false;
+        return None;
+    }
+    if app.inspecting && key.code == KeyCode::Esc {
+        app.inspecting = false;
+        return None;
+    }
+    if app.log_pick.is_some() {
+        let page = app.log_height.saturating_sub(2) as i32;
+        let step = match key.code {
+            KeyCode::Up => 1,
+            KeyCode::Down => -1,
+            KeyCode::PageUp => page,
+            KeyCode::PageDown => -page,
+            KeyCode::Enter => {
+                app.jump_to_pick();
+                return None;
+            }
+            KeyCode::Esc => {
+                app.log_pick = None;
+                return None;
+            }
+            _ => 0,
+        };
+        if step != 0 {
+            app.move_log_pick(step);
+            return None;
+        }
+    }
+    if app.ordering && key.code == KeyCode::Esc {
+        app.ordering = false;
+        return None;
+    }
+    if app.campfire_menu {
+        if let KeyCode::Char(c) = key.code
+            && let Some(n) = c.to_digit(10)
+        {
+            app.choose_venture(n as usize);
+            return None;
+        }
+        if key.code == KeyCode::Esc {
+            app.campfire_menu = false;
+            return None;
+        }
+    }
+    if let Some(command) = app.input.feed(key) {
+        apply(app, command);
+    }
+    None
+}
+
+fn apply(app: &mut App, command: Command) {
+    match command {
+        Command::Quit => app.should_quit = true,
+        Command::Menu => app.open_menu(),
+        Command::TogglePause => app.toggle_pause(),
+        Command::SpeedUp => app.speed_up(),
+        Command::SpeedDown => app.speed_down(),
+        Command::StepTick => app.step(),
+        Command::ToggleTurbo => app.toggle_turbo(),
+        Command::CursorUp => app.move_cursor(0, -1),
+        Command::CursorDown => app.move_cursor(0, 1),
+        Command::CursorLeft => app.move_cursor(-1, 0),
+        Command::CursorRight => app.move_cursor(1, 0),
+        Command::CursorUpFast => app.move_cursor(0, -10),
+        Command::CursorDownFast => app.move_cursor(0, 10),
+        Command::CursorLeftFast => app.move_cursor(-10, 0),
+        Command::CursorRightFast => app.move_cursor(10, 0),
+        Command::CycleOrc => app.cycle_selected_orc(),
+        Command::DropFood => app.open_food_prompt(),
+        Command::ToggleDawnNews => app.toggle_dawn_news(),
+        Command::TogglePerf => app.toggle_perf(),
+        Command::ToggleMinimap => app.toggle_minimap(),
+        Command::ToggleTooltip => app.toggle_tooltip(),
+        Command::ToggleGlyphs => app.toggle_glyphs(),
+        Command::ToggleLegend => app.toggle_legend(),
+        Command::ToggleHelp => app.toggle_help(),
+        Command::Console => app.open_console(),
+        Command::OrderMove => app.start_move_order(),
+        Command::OrderHunt => app.order_hunt(),
+        Command::RenameOrc => app.open_rename(),
+        Command::NameClan => app.open_clan_naming(),
+        Command::DismissNotice => app.sim.dismiss_notice(),
+        Command::ToggleSidebar => app.toggle_sidebar(),
+        Command::ToggleLog => app.toggle_log(),
+        Command::WidenSidebar => app.resize_sidebar(true),
+        Command::NarrowSidebar => app.resize_sidebar(false),
+        Command::GrowLog => app.resize_log(true),
+        Command::ShrinkLog => app.resize_log(false),
+        Command::CycleZoom => app.cycle_zoom(),
+        Command::ToggleAmbience => app.ambience.toggle(),
+        Command::CycleView => app.cycle_view(),
+        Command::CancelActivity => app.cancel_selected(),
+        Command::RecallAll => app.sim.recall_all(),
+        Command::Evacuate => app.order_evacuation(),
+        Command::CampfireMenu => app.toggle_campfire_menu(),
+        Command::ToggleBestiary => app.toggle_bestiary(),
+        Command::ToggleGraphs => app.toggle_graphs(),
+        Command::Export => app.export(),
+        Command::CycleDesignation => app.cycle_designation(),
+        Command::MarkTile => app.mark_tile(),
+        Command::ScrollLogBack => app.page_log(true),
+        Command::ScrollLogForward => app.page_log(false),
+        Command::PickEvent => app.start_log_pick(),
+        Command::FilterGeneral => app.toggle_log_filter(EventCategory::General),
+        Command::FilterBirths => app.toggle_log_filter(EventCategory::Birth),
+        Command::FilterDeaths => app.toggle_log_filter(EventCategory::Death),
+        Command::FilterHunts => app.toggle_log_filter(EventCategory::Hunt),
+        Command::FilterFood => app.toggle_log_filter(EventCategory::Food),
+        Command::FilterAlerts => app.toggle_log_filter(EventCategory::Alert),
+        Command::FilterOmens => app.toggle_log_filter(EventCategory::Spirit),
+        Command::FilterSocial => app.toggle_log_filter(EventCategory::Social),
+        Command::FilterCombat => app.toggle_log_filter(EventCategory::Combat),
+        Command::CycleTheme => app.cycle_theme(),
+        Command::BuildHut => app.designate(Building::Hut),
+        Command::BuildWall => app.designate(Building::Wall),
+        Command::BuildWallLine => app.wall_line(),
+        Command::BuildGate => app.designate(Building::Gate),
+        Command::BuildWell => app.designate(Building::Well),
+        Command::BuildWatchtower => app.designate(Building::Watchtower),
+        Command::BuildStorage => app.designate(Building::Storage),
+        Command::BuildWorkbench => app.designate(Building::Workbench),
+        Command::BuildBridge => app.designate(Building::Bridge),
+        Command::BuildPen => app.designate(Building::Pen),
+        Command::GotoCampfire => {
+            let (x, y) = app.sim.world.campfire_pos;
+            app.move_cursor_to(x, y);
+        }
+        Command::GotoMeatRack => {
+            if let Some((x, y)) = app.sim.world.meat_rack_pos() {
+                app.move_cursor_to(x, y);
+            }
+        }
+        Command::GotoSelectedOrc => {
+            if let Some(orc) = app.selected_orc.and_then(|i| app.sim.orcs.get(i)) {
+                let (x, y) = (orc.x, orc.y);
+                app.move_cursor_to(x, y);
+            }
         }
     }
 }
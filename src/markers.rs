@@ -0,0 +1,91 @@
+//! Marks left on the map where something happened worth remembering.
+
+use crate::calendar;
+
+/// Meat a kill has to leave for it to count as big game
+pub const BIG_GAME: u32 = 2;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Mark {
+    Death,
+    Raid,
+    Hunt,
+}
+
+impl Mark {
+    pub const ALL: [Mark; 3] = [Mark::Death, Mark::Raid, Mark::Hunt];
+
+    pub fn symbol(&self) -> char {
+        match self {
+            Mark::Death => '☠',
+            Mark::Raid => '⚐',
+            Mark::Hunt => '✦',
+        }
+    }
+
+    /// What the mark stands for, for the legend
+    pub fn meaning(&self) -> &'static str {
+        match self {
+            Mark::Death => "where an orc died",
+            Mark::Raid => "where a raider fell",
+            Mark::Hunt => "the last big hunt",
+        }
+    }
+
+    /// Marks of this kind kept at once
+    fn limit(&self) -> usize {
+        match self {
+            Mark::Death => 40,
+            Mark::Raid => 12,
+            Mark::Hunt => 1,
+        }
+    }
+}
+
+pub struct Marker {
+    pub mark: Mark,
+    pub x: usize,
+    pub y: usize,
+    pub tick: u64,
+    /// What happened there, e.g. "Grok died"
+    pub note: String,
+}
+
+impl Marker {
+    pub fn describe(&self, day_length: u64) -> String {
+        format!("{}, day {}", self.note, calendar::day_number(self.tick, day_length))
+    }
+}
+
+/// Every mark on the map, oldest first
+#[derive(Default)]
+pub struct Markers {
+    list: Vec<Marker>,
+}
+
+impl Markers {
+    /// Mark (x, y) with what happened there at `tick`, in place of any mark
+    /// already there and the oldest of its kind if there are too many
+    pub fn note(&mut self, mark: Mark, x: usize, y: usize, tick: u64, note: String) {
+        self.list.retain(|m| (m.x, m.y) != (x, y));
+        if self.count(mark) == mark.limit()
+            && let Some(oldest) = self.list.iter().position(|m| m.mark == mark)
+        {
+            self.list.remove(oldest);
+        }
+        self.list.push(Marker { mark, x, y, tick, note });
+    }
+
+    pub fn at(&self, x: usize, y: usize) -> Option<&Marker> {
+        self.list.iter().find(|m| (m.x, m.y) == (x, y))
+    }
+
+    pub fn count(&self, mark: Mark) -> usize {
+        self.list.iter().filter(|m| m.mark == mark).count()
+    }
+
+    /// The latest `count` marks, latest first
+    pub fn latest(&self, count: usize) -> impl Iterator<Item = &Marker> {
+        self.list.iter().rev().take(count)
+    }
+}
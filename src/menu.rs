@@ -0,0 +1,106 @@
+//! The pause menu, brought up with Esc.
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::app::{App, Screen};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Item {
+    Resume,
+    NewWorld,
+    Settings,
+    Save,
+    Load,
+    Quit,
+}
+
+impl Item {
+    pub const ALL: [Item; 6] = [Item::Resume, Item::NewWorld, Item::Settings, Item::Save, Item::Load, Item::Quit];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Item::Resume => "Resume",
+            Item::NewWorld => "New world",
+            Item::Settings => "Settings",
+            Item::Save => "Save",
+            Item::Load => "Load",
+            Item::Quit => "Quit",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Setting {
+    Speed,
+    Theme,
+    Glyphs,
+}
+
+impl Setting {
+    pub const ALL: [Setting; 3] = [Setting::Speed, Setting::Theme, Setting::Glyphs];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Setting::Speed => "Speed",
+            Setting::Theme => "Theme",
+            Setting::Glyphs => "Glyphs",
+        }
+    }
+
+    /// How the setting stands
+    pub fn value(&self, app: &App) -> String {
+        match self {
+            Setting::Speed => app.speed.name(),
+            Setting::Theme => app.theme().name.clone(),
+            Setting::Glyphs => app.caps.glyphs.name().to_string(),
+        }
+    }
+
+    /// Step the setting on to the next value, or back to the one before
+    fn change(&self, app: &mut App, forward: bool) {
+        match (self, forward) {
+            (Setting::Speed, true) => app.speed_up(),
+            (Setting::Speed, false) => app.speed_down(),
+            (Setting::Theme, true) => app.cycle_theme(),
+            (Setting::Theme, false) => app.cycle_theme_back(),
+            (Setting::Glyphs, _) => app.toggle_glyphs(),
+        }
+    }
+}
+
+/// Work the menu with a key press. Returns the item taken if it's one for the
+/// main loop to carry out: a new world, a save or a load.
+pub fn press(app: &mut App, key: KeyEvent) -> Option<Item> {
+    match app.screen {
+        Screen::Playing => {}
+        Screen::Menu(choice) => match key.code {
+            KeyCode::Up => app.screen = Screen::Menu(step(choice, Item::ALL.len(), false)),
+            KeyCode::Down => app.screen = Screen::Menu(step(choice, Item::ALL.len(), true)),
+            KeyCode::Esc => app.screen = Screen::Playing,
+            KeyCode::Enter => match Item::ALL[choice] {
+                Item::Resume => app.screen = Screen::Playing,
+                Item::Settings => app.screen = Screen::Settings(0),
+                Item::Quit => app.should_quit = true,
+                item => return Some(item),
+            },
+            _ => {}
+        },
+        Screen::Settings(choice) => match key.code {
+            KeyCode::Up => app.screen = Screen::Settings(step(choice, Setting::ALL.len(), false)),
+            KeyCode::Down => app.screen = Screen::Settings(step(choice, Setting::ALL.len(), true)),
+            KeyCode::Left => Setting::ALL[choice].change(app, false),
+            KeyCode::Right => Setting::ALL[choice].change(app, true),
+            KeyCode::Esc | KeyCode::Enter => {
+                let settings = Item::ALL.iter().position(|&i| i == Item::Settings).unwrap_or(0);
+                app.screen = Screen::Menu(settings);
+            }
+            _ => {}
+        },
+    }
+    None
+}
+
+/// The next of `count` items round from `choice`, or the one before
+fn step(choice: usize, count: usize, forward: bool) -> usize {
+    if forward { (choice + 1) % count } else { (choice + count - 1) % count }
+}
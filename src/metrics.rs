@@ -0,0 +1,149 @@
+//! Keeping an eye on a long run from outside, with Prometheus.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use crate::sim::Sim;
+
+/// Longest request read before giving up on it
+const MAX_REQUEST: usize = 8 * 1024;
+/// Longest wait for a request to come in
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The latest readings, shared with the thread answering scrapes
+#[derive(Clone, Default)]
+struct Readings {
+    tick: u64,
+    ticks: u64,
+    tick_seconds: f64,
+    slowest_tick: f64,
+    frames: u64,
+    frame_seconds: f64,
+    path_searches: u64,
+    path_nodes: u64,
+    paths_deferred: u64,
+    orcs: usize,
+    animals: usize,
+    goblins: usize,
+    events: u64,
+}
+
+pub struct Metrics {
+    readings: Arc<Mutex<Readings>>,
+}
+
+impl Metrics {
+    /// Start answering scrapes on `addr`, on a thread of its own
+    pub fn serve(addr: SocketAddr) -> io::Result<Metrics> {
+        let listener = TcpListener::bind(addr)?;
+        let readings = Arc::new(Mutex::new(Readings::default()));
+        let shared = Arc::clone(&readings);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                // A scrape that goes wrong is the scraper's to retry
+                let _ = answer(stream, &shared);
+            }
+        });
+        Ok(Metrics { readings })
+    }
+
+    fn readings(&self) -> MutexGuard<'_, Readings> {
+        self.readings.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Count in a tick of `sim` that took `elapsed` to run
+    pub fn record_tick(&self, sim: &Sim, elapsed: Duration) {
+        let budget = &sim.world.path_budget;
+        let seconds = elapsed.as_secs_f64();
+        let mut readings = self.readings();
+        readings.tick = sim.tick;
+        readings.ticks += 1;
+        readings.tick_seconds += seconds;
+        readings.slowest_tick = readings.slowest_tick.max(seconds);
+        readings.path_searches += budget.searches() as u64;
+        readings.path_nodes += budget.nodes() as u64;
+        readings.paths_deferred += budget.deferred() as u64;
+        readings.orcs = sim.orcs.iter().filter(|o| o.alive).count();
+        readings.animals = sim.animals.iter().filter(|a| a.alive).count();
+        readings.goblins = sim.raid.as_ref().map_or(0, |raid| raid.goblins.iter().filter(|g| g.health > 0.0).count());
+        readings.events = sim.event_log.total;
+    }
+
+    /// Count in a frame that took `elapsed` to draw
+    pub fn record_frame(&self, elapsed: Duration) {
+        let mut readings = self.readings();
+        readings.frames += 1;
+        readings.frame_seconds += elapsed.as_secs_f64();
+    }
+}
+
+/// Read a request and send back the metrics, or a 404 for anything else
+fn answer(stream: TcpStream, readings: &Mutex<Readings>) -> io::Result<()> {
+    // One scrape at a time is answered, so one that stalls mustn't hold up the rest
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    let mut request = String::new();
+    let mut read = 0;
+    loop {
+        let mut line = String::new();
+        let n = reader.read_line(&mut line)?;
+        read += n;
+        if n == 0 || read > MAX_REQUEST {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "request cut short or too long"));
+        }
+        if line.trim_end().is_empty() {
+            break;
+        }
+        if request.is_empty() {
+            request = line;
+        }
+    }
+    let mut words = request.split_whitespace();
+    let (status, body) = match (words.next(), words.next()) {
+        (Some("GET"), Some("/metrics")) => {
+            let readings = readings.lock().unwrap_or_else(PoisonError::into_inner).clone();
+            ("200 OK", exposition(&readings))
+        }
+        _ => ("404 Not Found", "Try /metrics\n".to_string()),
+    };
+    write!(
+        writer,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body,
+    )
+}
+
+/// The readings in Prometheus's text format
+fn exposition(r: &Readings) -> String {
+    let metrics: [(&str, &str, &str, String); 13] = [
+        ("orcs_tick", "gauge", "The tick the world is on", r.tick.to_string()),
+        ("orcs_tick_duration_seconds_sum", "summary", "Time spent simulating ticks", r.tick_seconds.to_string()),
+        ("orcs_tick_duration_seconds_count", "", "", r.ticks.to_string()),
+        ("orcs_slowest_tick_seconds", "gauge", "The longest a single tick has taken", r.slowest_tick.to_string()),
+        ("orcs_frame_duration_seconds_sum", "summary", "Time spent drawing frames", r.frame_seconds.to_string()),
+        ("orcs_frame_duration_seconds_count", "", "", r.frames.to_string()),
+        ("orcs_path_searches_total", "counter", "A* searches run", r.path_searches.to_string()),
+        ("orcs_path_nodes_total", "counter", "Nodes expanded by those searches", r.path_nodes.to_string()),
+        ("orcs_paths_deferred_total", "counter", "Searches put off because a tick's budget ran out", r.paths_deferred.to_string()),
+        ("orcs_population", "gauge", "Living orcs", r.orcs.to_string()),
+        ("orcs_animals", "gauge", "Living animals, wild and tame", r.animals.to_string()),
+        ("orcs_goblins", "gauge", "Goblins of a raid still on their feet", r.goblins.to_string()),
+        ("orcs_events_total", "counter", "Events logged", r.events.to_string()),
+    ];
+    let mut out = String::new();
+    for (name, kind, help, value) in metrics {
+        // A summary's `_count` goes under the `# TYPE` line of its `_sum`
+        if !kind.is_empty() {
+            let family = name.strip_suffix("_sum").unwrap_or(name);
+            out.push_str(&format!("# HELP {} {}.\n# TYPE {} {}\n", family, help, family, kind));
+        }
+        out.push_str(&format!("{} {}\n", name, value));
+    }
+    out
+}
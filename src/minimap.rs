@@ -0,0 +1,53 @@
+//! The whole world shrunk into a box over the corner of the map.
+
+use crate::world::{MAP_HEIGHT, MAP_WIDTH, Terrain, World};
+
+/// Tiles along each side of the block a cell stands for
+pub const BLOCK: usize = 10;
+/// Cells across and down
+pub const COLUMNS: usize = MAP_WIDTH.div_ceil(BLOCK);
+pub const ROWS: usize = MAP_HEIGHT.div_ceil(BLOCK);
+
+/// Percentage of a block that has to be water, or trees, for it to count as such
+const WATER_SHARE: usize = 8;
+const FOREST_SHARE: usize = 15;
+
+/// The lie of the land in a block
+#[derive(Clone, Copy, PartialEq)]
+pub enum Ground {
+    Open,
+    Forest,
+    Water,
+}
+
+/// The lie of the land in the block at cell (column, row)
+pub fn ground(world: &World, column: usize, row: usize) -> Ground {
+    let (mut trees, mut water, mut tiles) = (0, 0, 0);
+    for y in row * BLOCK..((row + 1) * BLOCK).min(MAP_HEIGHT) {
+        for x in column * BLOCK..((column + 1) * BLOCK).min(MAP_WIDTH) {
+            match world.get(x, y) {
+                Terrain::Water => water += 1,
+                Terrain::Tree | Terrain::FruitTree | Terrain::BareFruitTree => trees += 1,
+                _ => {}
+            }
+            tiles += 1;
+        }
+    }
+    if water * 100 >= tiles * WATER_SHARE {
+        Ground::Water
+    } else if trees * 100 >= tiles * FOREST_SHARE {
+        Ground::Forest
+    } else {
+        Ground::Open
+    }
+}
+
+/// The cell the tile at (x, y) falls in
+pub fn cell(x: usize, y: usize) -> (usize, usize) {
+    (x / BLOCK, y / BLOCK)
+}
+
+/// The tile in the middle of cell (column, row)
+pub fn center(column: usize, row: usize) -> (usize, usize) {
+    ((column * BLOCK + BLOCK / 2).min(MAP_WIDTH - 1), (row * BLOCK + BLOCK / 2).min(MAP_HEIGHT - 1))
+}
@@ -0,0 +1,94 @@
+//! The mouse.
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Margin, Position, Rect};
+
+use crate::minimap;
+
+/// Events the log moves by for each notch of the scroll wheel
+const SCROLL_STEP: i32 = 3;
+
+/// What the player did with the mouse, in terms of the map
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Gesture {
+    /// Clicked the tile at (x, y)
+    Click { x: usize, y: usize },
+    /// Dragged the map this many tiles right and down
+    Pan { dx: i32, dy: i32 },
+    /// Scrolled the event log this many events further back, or forward if negative
+    Scroll(i32),
+}
+
+impl Gesture {
+    /// How it's written in a session recording
+    pub fn encode(&self) -> String {
+        match self {
+            Gesture::Click { x, y } => format!("click {} {}", x, y),
+            Gesture::Pan { dx, dy } => format!("pan {} {}", dx, dy),
+            Gesture::Scroll(n) => format!("scroll {}", n),
+        }
+    }
+
+    pub fn decode(fields: &[&str]) -> Option<Gesture> {
+        match fields {
+            ["click", x, y] => Some(Gesture::Click { x: x.parse().ok()?, y: y.parse().ok()? }),
+            ["pan", dx, dy] => Some(Gesture::Pan { dx: dx.parse().ok()?, dy: dy.parse().ok()? }),
+            ["scroll", n] => Some(Gesture::Scroll(n.parse().ok()?)),
+            _ => None,
+        }
+    }
+}
+
+/// Where the map, the event log and the minimap's cells were last drawn, and
+/// the drag under way
+#[derive(Default)]
+pub struct Mouse {
+    map: Rect,
+    log: Rect,
+    minimap: Option<Rect>,
+    /// Screen cell the mouse was last at while a button is held down on the map
+    held: Option<(u16, u16)>,
+}
+
+impl Mouse {
+    /// Note where the map and the event log have been drawn, borders and
+    /// all, and the minimap's cells if it's up
+    pub fn place(&mut self, map: Rect, log: Rect, minimap: Option<Rect>) {
+        self.map = map;
+        self.log = log;
+        self.minimap = minimap;
+    }
+
+    /// Make sense of a mouse event, given the map tile at the top left of the
+    /// view and the tiles each character stands for along a side
+    pub fn read(&mut self, event: MouseEvent, camera: (usize, usize), scale: usize) -> Option<Gesture> {
+        let at = Position::new(event.column, event.row);
+        match event.kind {
+            MouseEventKind::Down(MouseButton::Left) if self.minimap.is_some_and(|m| m.contains(at)) => {
+                let cells = self.minimap?;
+                let (x, y) = minimap::center((event.column - cells.x) as usize, (event.row - cells.y) as usize);
+                Some(Gesture::Click { x, y })
+            }
+            MouseEventKind::Down(MouseButton::Left) if self.map.inner(Margin::new(1, 1)).contains(at) => {
+                self.held = Some((event.column, event.row));
+                let x = camera.0 + (event.column - self.map.x - 1) as usize * scale;
+                let y = camera.1 + (event.row - self.map.y - 1) as usize * scale;
+                Some(Gesture::Click { x, y })
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                let (column, row) = self.held?;
+                self.held = Some((event.column, event.row));
+                let (dx, dy) = (event.column as i32 - column as i32, event.row as i32 - row as i32);
+                let (dx, dy) = (dx * scale as i32, dy * scale as i32);
+                ((dx, dy) != (0, 0)).then_some(Gesture::Pan { dx, dy })
+            }
+            MouseEventKind::Up(_) => {
+                self.held = None;
+                None
+            }
+            MouseEventKind::ScrollUp if self.log.contains(at) => Some(Gesture::Scroll(SCROLL_STEP)),
+            MouseEventKind::ScrollDown if self.log.contains(at) => Some(Gesture::Scroll(-SCROLL_STEP)),
+            _ => None,
+        }
+    }
+}
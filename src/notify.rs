@@ -0,0 +1,138 @@
+//! Banners for what the player mustn't miss.
+
+use std::collections::VecDeque;
+
+/// Ticks a banner stays up if not dismissed, half a day
+fn shown_for(day_length: u64) -> u64 {
+    day_length / 2
+}
+/// Most banners queued up at once; the oldest go first
+const MAX_QUEUED: usize = 10;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Kind {
+    Death,
+    Raid,
+    Stores,
+    Fire,
+}
+
+impl Kind {
+    pub const ALL: [Kind; 4] = [Kind::Death, Kind::Raid, Kind::Stores, Kind::Fire];
+
+    /// What the banner says it's about
+    pub fn title(&self) -> &'static str {
+        match self {
+            Kind::Death => "A death in the clan",
+            Kind::Raid => "Goblins!",
+            Kind::Stores => "Out of food",
+            Kind::Fire => "Fire out",
+        }
+    }
+
+    /// Its name in the config file
+    pub fn name(&self) -> &'static str {
+        match self {
+            Kind::Death => "death",
+            Kind::Raid => "raid",
+            Kind::Stores => "stores",
+            Kind::Fire => "fire",
+        }
+    }
+}
+
+/// How a kind of happening is brought to the player's notice
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Response {
+    /// Only in the event log
+    Off,
+    #[default]
+    Banner,
+    /// A banner, with the game paused on it
+    Pause,
+}
+
+impl Response {
+    pub const ALL: [Response; 3] = [Response::Off, Response::Banner, Response::Pause];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Response::Off => "off",
+            Response::Banner => "banner",
+            Response::Pause => "pause",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Response> {
+        Response::ALL.into_iter().find(|r| r.name() == name)
+    }
+}
+
+/// The response set for each kind, in the order of `Kind::ALL`
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Responses([Response; 4]);
+
+impl Responses {
+    pub fn get(&self, kind: Kind) -> Response {
+        self.0[kind as usize]
+    }
+
+    pub fn set(&mut self, kind: Kind, response: Response) {
+        self.0[kind as usize] = response;
+    }
+}
+
+pub struct Notice {
+    pub kind: Kind,
+    pub message: String,
+    pub tick: u64,
+}
+
+#[derive(Default)]
+pub struct Notices {
+    pub responses: Responses,
+    queue: VecDeque<Notice>, // the one showing first
+    shown: u64, // tick the one showing went up
+}
+
+impl Notices {
+    /// Put up a banner for `message`, unless the player has turned `kind`
+    /// off or the same one is already waiting. Returns whether the game
+    /// should pause for it.
+    pub fn raise(&mut self, kind: Kind, message: String, tick: u64) -> bool {
+        let response = self.responses.get(kind);
+        if response == Response::Off || self.queue.iter().any(|n| n.message == message) {
+            return false;
+        }
+        if self.queue.is_empty() {
+            self.shown = tick;
+        } else if self.queue.len() == MAX_QUEUED {
+            self.queue.pop_front();
+        }
+        self.queue.push_back(Notice { kind, message, tick });
+        response == Response::Pause
+    }
+
+    /// The banner showing, if any
+    pub fn showing(&self) -> Option<&Notice> {
+        self.queue.front()
+    }
+
+    /// Banners waiting behind the one showing
+    pub fn waiting(&self) -> usize {
+        self.queue.len().saturating_sub(1)
+    }
+
+    /// Take down the banner showing, bringing up the next
+    pub fn dismiss(&mut self, tick: u64) {
+        self.queue.pop_front();
+        self.shown = tick;
+    }
+
+    /// Take down the banner showing once it's been up long enough
+    pub fn expire(&mut self, tick: u64, day_length: u64) {
+        if !self.queue.is_empty() && tick >= self.shown + shown_for(day_length) {
+            self.dismiss(tick);
+        }
+    }
+}
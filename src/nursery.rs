@@ -0,0 +1,59 @@
+//! Babies born to the clan, and who looks after them.
+
+use crate::orc::Orc;
+
+/// Days a newborn needs carrying and feeding
+pub const INFANCY_DAYS: u64 = 5;
+/// Years before an orc can have children of its own
+pub const ADULT_YEARS: u64 = 16;
+/// A baby cries to be fed once its hunger or thirst gets this high
+pub const FEED_AT: f32 = 30.0;
+/// Hunger and thirst a carer takes off a baby each tick of nursing
+pub const NURSE_RATE: f32 = 5.0;
+/// What a tick of nursing takes out of the carer
+pub const NURSE_COST: f32 = 0.5;
+
+/// Who in the clan looks after the babies
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Childcare {
+    /// Only the baby's mother
+    Mother,
+    /// Only the baby's father
+    Father,
+    /// Whichever parent is closer
+    #[default]
+    Parents,
+    /// Any grown orc, whoever has the fewest babies to carry already
+    Anyone,
+}
+
+impl Childcare {
+    pub const ALL: [Childcare; 4] = [Childcare::Mother, Childcare::Father, Childcare::Parents, Childcare::Anyone];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Childcare::Mother => "mother",
+            Childcare::Father => "father",
+            Childcare::Parents => "parents",
+            Childcare::Anyone => "anyone",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Childcare> {
+        Childcare::ALL.into_iter().find(|c| c.name() == name)
+    }
+
+    /// Whether `carer` may look after `baby` under this policy. A foundling
+    /// with no parents in the clan is anyone's to raise.
+    pub fn allows(&self, carer: &Orc, baby: &Orc) -> bool {
+        let Some((mother, father)) = &baby.parents else {
+            return true;
+        };
+        match self {
+            Childcare::Mother => carer.name == *mother,
+            Childcare::Father => carer.name == *father,
+            Childcare::Parents => carer.name == *mother || carer.name == *father,
+            Childcare::Anyone => true,
+        }
+    }
+}
@@ -1,9 +1,23 @@
 use rand::Rng;
 
-use crate::animal::Animal;
-use crate::event::EventLog;
+use crate::ai;
+use crate::animal::{Animal, AnimalKind, Behaviour, WOLF_RESPITE};
+use crate::bestiary::Deed;
+use crate::building::{Building, Material};
+use crate::calendar::{self, DayPhase, Season};
+use crate::combat::{self, Blow, Combatant, Weapon};
+use crate::config::{AiConfig, NeedRates};
+use crate::diary::Diary;
+use crate::emote::EmoteKind;
+use crate::event::{EventCategory, EventLog, Severity};
+use crate::healing::{self, DRESSED_HEALING, REST_HEALING, SLEEP_HEALING};
+use crate::husbandry;
+use crate::light;
+use crate::nursery::{ADULT_YEARS, INFANCY_DAYS};
 use crate::pathfinding;
-use crate::world::{MAP_HEIGHT, MAP_WIDTH, Terrain, World};
+use crate::spirits;
+use crate::tool::{Tool, ToolKind};
+use crate::world::{BARREL_CAPACITY, BUCKET, MAP_HEIGHT, MAP_WIDTH, Terrain, World};
 
 const ORC_NAMES: &[&str] = &[
     "Grok", "Thrak", "Murg", "Zug", "Brak", "Gor", "Krag", "Drog", "Narg", "Skul",
@@ -11,27 +25,295 @@ const ORC_NAMES: &[&str] = &[
     "Thog", "Grim", "Uzk", "Ragz", "Lurk", "Bonk", "Drak", "Gurn", "Tusk", "Mok",
 ];
 
+/// Orcs within this many tiles (Chebyshev) of the campfire are kept warm by it
+pub const CAMPFIRE_WARMTH_RADIUS: usize = 4;
+/// Hunger or thirst from which an orc's health starts wasting away
+pub const WASTING: f32 = 95.0;
+/// Warmth at or below which an orc starts freezing
+pub const FREEZING: f32 = 10.0;
+/// Morale lost each tick of a night spent without the campfire
+const DARK_CAMP_GLOOM: f32 = 0.2;
+/// Ticks spent at the fire to turn one raw meat into a cooked meal
+const COOK_TICKS: u32 = 6;
+/// Chance that eating raw meat makes an orc sick
+const RAW_MEAT_SICKNESS_CHANCE: f64 = 0.15;
+/// Skill gained every time an orc picks a bush
+const FORAGE_PRACTICE: f32 = 2.0;
+/// Below this skill an orc may pick a bad berry
+const NOVICE_FORAGER: f32 = 30.0;
+/// Extra berries an orc can carry back for the stockpile
+pub const BERRY_POUCH: u32 = 3;
+/// Hunger a ripe fruit takes off straight away, on top of the meal itself
+const FRUIT_NUTRITION: f32 = 10.0;
+/// Chance that a wild mushroom is poisonous
+const TOADSTOOL_CHANCE: f64 = 0.25;
+/// Forage skill needed to tell a toadstool from a good mushroom
+const MUSHROOM_EXPERT: f32 = 60.0;
+/// Labour an orc puts into a building site each tick
+const BUILD_RATE: u32 = 1;
+/// Condition a structure regains per tick of repairs
+const REPAIR_RATE: u32 = 40;
+/// How much more likely a lunge with a spear is to bring the quarry down
+const SPEAR_BONUS: f64 = 0.25;
+/// Share of a boar's charges a spear fends off
+const SPEAR_GUARD: f64 = 0.5;
+/// Skill gained with every kill
+const HUNT_PRACTICE: f32 = 3.0;
+/// Furthest a hunter throws its spear, the chance a throw from two tiles off
+/// lands, how much of that each tile further takes off, and how much a
+/// practised hunter's skill adds
+const THROW_RANGE: usize = 5;
+const THROW_AIM: f64 = 0.6;
+const THROW_FALLOFF: f64 = 0.1;
+const THROW_SKILL: f64 = 0.3;
+/// Extra berries a basket brings back from a marked bush
+const BASKET_BONUS: u32 = 2;
+/// Without an axe, wood has to be scrounged from deadwood this many times slower
+const DEADWOOD_SLOWDOWN: u32 = 3;
+/// Energy regained per tick of sleep, out in the open and in a hut
+const SLEEP_RECOVERY: f32 = 3.0;
+const HUT_SLEEP_RECOVERY: f32 = 4.5;
+/// Chance for a new orc to be a night owl
+const NIGHT_OWL_CHANCE: f64 = 0.15;
+/// Chances for a new orc to be brave, or else timid
+const BRAVE_CHANCE: f64 = 0.15;
+const TIMID_CHANCE: f64 = 0.15;
+/// Health below which an orc runs from a fight rather than see it through,
+/// and the lower mark a brave orc holds out to
+const FLEE_HEALTH: f32 = 50.0;
+const BRAVE_FLEE_HEALTH: f32 = 20.0;
+/// Morale below which an orc has less stomach for a fight than it would
+const DISHEARTENED: f32 = 25.0;
+/// Odds of landing a blow in a fight, and the damage done, bare-handed and
+/// with a spear
+const FISTS: Weapon = Weapon { chance: 0.5, damage: 6.0..12.0 };
+const SPEAR: Weapon = Weapon { chance: 0.75, damage: 12.0..18.0 };
+/// What an orc running for the campfire tells anyone who asks
+const FLEEING: &str = "Fleeing a wolf";
+/// Range of ages, in years, of the orcs who found the clan
+const FOUNDER_AGE: std::ops::Range<u64> = 16..40;
+/// Years after which an orc counts among the clan's elders
+const ELDER_YEARS: u64 = 45;
+
+/// Personality traits that bend an orc's routine
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Trait {
+    NightOwl,
+    Brave,
+    Timid,
+}
+
+impl Trait {
+    pub fn name(&self) -> &str {
+        match self {
+            Trait::NightOwl => "Night owl",
+            Trait::Brave => "Brave",
+            Trait::Timid => "Timid",
+        }
+    }
+
+    /// Short form for the cramped sidebar
+    pub fn tag(&self) -> &str {
+        match self {
+            Trait::NightOwl => "owl",
+            Trait::Brave => "brave",
+            Trait::Timid => "timid",
+        }
+    }
+
+    pub fn roll(rng: &mut impl Rng) -> Vec<Trait> {
+        let mut traits = Vec::new();
+        if rng.gen_bool(NIGHT_OWL_CHANCE) {
+            traits.push(Trait::NightOwl);
+        }
+        if rng.gen_bool(BRAVE_CHANCE) {
+            traits.push(Trait::Brave);
+        } else if rng.gen_bool(TIMID_CHANCE) {
+            traits.push(Trait::Timid);
+        }
+        traits
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Sex {
+    Female,
+    Male,
+}
+
+impl Sex {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Sex::Female => "female",
+            Sex::Male => "male",
+        }
+    }
+}
+
+/// Which standing orders from the player an orc takes on, and which chores
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Job {
+    Woodcutter,
+    Forager,
+    WaterCarrier, // keeps the barrel by the campfire topped up
+    Herder, // brings in boars and tends the livestock in the pens
+    Guard, // keeps watch over the camp and raises the alarm
+    Healer, // picks herbs and dresses the clan's wounds
+}
+
+impl Job {
+    pub const ALL: [Job; 6] = [Job::Woodcutter, Job::Forager, Job::WaterCarrier, Job::Herder, Job::Guard, Job::Healer];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Job::Woodcutter => "woodcutter",
+            Job::Forager => "forager",
+            Job::WaterCarrier => "water carrier",
+            Job::Herder => "herder",
+            Job::Guard => "guard",
+            Job::Healer => "healer",
+        }
+    }
+}
+
+/// Why an orc is walking somewhere, which decides what it does on arrival
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Purpose {
+    Drink,
+    Eat,
+    Sleep,
+    Camp,
+    Wander,
+    Harvest,
+    /// Sent there by the player, to stand about once there
+    Ordered,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Activity {
     Idle,
-    GoingTo { x: usize, y: usize, reason: String },
+    GoingTo { x: usize, y: usize, purpose: Purpose, reason: &'static str },
     Eating,
     Sleeping,
     Drinking,
     Hunting { target_idx: usize },
     CarryingMeat,
+    Warming,
+    Cooking { progress: u32 },
+    DeliveringFood { patient: String, x: usize, y: usize, has_food: bool },
+    Hauling { x: usize, y: usize },
+    /// Going to pick up the spear it threw, lying at (x, y)
+    Retrieving { x: usize, y: usize },
+    FetchingWater { x: usize, y: usize, full: bool },
+    Gathering { material: Material, x: usize, y: usize, site: (usize, usize), progress: u32 },
+    Supplying { material: Material, x: usize, y: usize },
+    Constructing { x: usize, y: usize },
+    Crafting { x: usize, y: usize },
+    Repairing { x: usize, y: usize },
+    Fighting,
+    /// Going after the goblin raider or wolf at (x, y)
+    Defending { x: usize, y: usize },
+    /// On guard duty, manning the watchtower at (x, y) or making for the post
+    /// there on the round
+    Guarding { x: usize, y: usize, tower: bool },
+    Marching { x: usize, y: usize },
+    Sheltering { x: usize, y: usize },
+    /// Out with a party the chief sent on a venture
+    Expedition { x: usize, y: usize },
+    Nursing,
+    /// Carrying a captive boar to the pen tile at (x, y)
+    Penning { x: usize, y: usize },
+    /// Fetching a meal from the rack, then taking it to the livestock around (x, y)
+    Feeding { x: usize, y: usize, has_feed: bool },
+    /// Going to slaughter a tame animal in the pens around (x, y)
+    Butchering { x: usize, y: usize },
+    /// Going to pick the herbs at (x, y)
+    PickingHerbs { x: usize, y: usize },
+    /// Taking herbs to dress a wounded clanmate's wounds
+    Tending { patient: String, x: usize, y: usize },
+    /// Going for the body of the orc called `name` at (x, y), or carrying it
+    /// to the grave plot there
+    Burying { name: String, x: usize, y: usize, carrying: bool },
+    /// A baby, carried about by its carer or left lying where it was
+    Infant { carried: bool },
 }
 
 impl Activity {
     pub fn label(&self) -> &str {
         match self {
             Activity::Idle => "Idling",
-            Activity::GoingTo { reason, .. } => reason.as_str(),
+            Activity::GoingTo { reason, .. } => reason,
             Activity::Eating => "Eating",
             Activity::Sleeping => "Sleeping",
             Activity::Drinking => "Drinking",
             Activity::Hunting { .. } => "Hunting",
-            Activity::CarryingMeat => "Carrying meat",
+            Activity::CarryingMeat => "Carrying food",
+            Activity::Warming => "Warming by the fire",
+            Activity::Cooking { .. } => "Cooking",
+            Activity::DeliveringFood { .. } => "Delivering food",
+            Activity::Hauling { .. } => "Hauling meat",
+            Activity::Retrieving { .. } => "Fetching a thrown spear",
+            Activity::FetchingWater { full: false, .. } => "Fetching water",
+            Activity::FetchingWater { full: true, .. } => "Carrying water",
+            Activity::Gathering { material: Material::Wood, .. } => "Chopping wood",
+            Activity::Gathering { material: Material::Stone, .. } => "Breaking stone",
+            Activity::Supplying { material: Material::Wood, .. } => "Carrying wood",
+            Activity::Supplying { material: Material::Stone, .. } => "Carrying stone",
+            Activity::Constructing { .. } => "Building",
+            Activity::Crafting { .. } => "Making tools",
+            Activity::Repairing { .. } => "Repairing",
+            Activity::Fighting => "Fighting a wolf",
+            Activity::Defending { .. } => "Defending the camp",
+            Activity::Guarding { tower: true, .. } => "Keeping watch from the tower",
+            Activity::Guarding { tower: false, .. } => "Walking the rounds",
+            Activity::Marching { .. } => "On the move with the clan",
+            Activity::Sheltering { .. } => "Sheltering",
+            Activity::Expedition { .. } => "Away on an expedition",
+            Activity::Nursing => "Feeding a baby",
+            Activity::Penning { .. } => "Bringing in a boar",
+            Activity::Feeding { has_feed: false, .. } => "Fetching feed",
+            Activity::Feeding { has_feed: true, .. } => "Feeding the livestock",
+            Activity::Butchering { .. } => "Butchering livestock",
+            Activity::PickingHerbs { .. } => "Picking herbs",
+            Activity::Tending { .. } => "Tending the wounded",
+            Activity::Burying { carrying: false, .. } => "Fetching the dead",
+            Activity::Burying { carrying: true, .. } => "Carrying the dead to the graveyard",
+            Activity::Infant { carried: true } => "Carried",
+            Activity::Infant { carried: false } => "Crying, left alone",
+        }
+    }
+}
+
+/// What an orc died of
+#[derive(Clone, Copy, PartialEq)]
+pub enum Cause {
+    Thirst,
+    Hunger,
+    Cold,
+    Exhaustion,
+    Sickness,
+    Beast(AnimalKind),
+    Goblin,
+    Rival,
+    /// Crushed as a building came down on it
+    Collapse,
+    /// Struck down from the debug prompt
+    Struck,
+}
+
+impl Cause {
+    pub fn name(&self) -> String {
+        match self {
+            Cause::Thirst => "thirst".to_string(),
+            Cause::Hunger => "hunger".to_string(),
+            Cause::Cold => "cold".to_string(),
+            Cause::Exhaustion => "exhaustion".to_string(),
+            Cause::Sickness => "sickness".to_string(),
+            Cause::Beast(kind) => kind.name().to_lowercase(),
+            Cause::Goblin => "goblins".to_string(),
+            Cause::Rival => "rivals".to_string(),
+            Cause::Collapse => "collapse".to_string(),
+            Cause::Struck => "struck down".to_string(),
         }
     }
 }
@@ -40,17 +322,74 @@ pub struct Orc {
     pub name: String,
     pub x: usize,
     pub y: usize,
+    pub was: (usize, usize), // where it stood before the latest tick, for smoothing the map
     pub hunger: f32,
     pub energy: f32,
     pub thirst: f32,
+    pub warmth: f32,
     pub health: f32,
+    pub morale: f32,
+    pub sick_ticks: u32,
+    pub wounds: f32, // harm from blows and falls still to heal, which health can't come back past
+    pub dressed_ticks: u32, // ticks left of a healer's dressing speeding the wounds along
+    pub herbs: u32, // healing herbs a healer carries
+    pub torch: u32, // ticks left of the torch it's carrying through the night
+    pub in_dark: bool, // out beyond any light at night, as App last worked out
+    pub forage_skill: f32, // 0-100, improves with practice
+    pub hunt_skill: f32, // 0-100, takes the sting out of a boar's charge
+    pub berries: u32, // gathered for the stockpile
+    pub tools: Vec<Tool>, // at most one of each kind
+    pub traits: Vec<Trait>,
+    pub job: Job,
+    pub sex: Sex,
+    pub birth_day: i64, // calendar day of birth; the founders were born before day 1
+    pub parents: Option<(String, String)>, // mother and father, for orcs born into the clan
+    pub carer: Option<String>, // who is carrying this baby about
     pub alive: bool,
     pub death_tick: Option<u64>,
+    pub died_of: Option<Cause>,
     pub activity: Activity,
     idle_ticks: u32,
     pub carrying_food: bool,
+    pub awaiting_food: bool, // too weak to travel, a clanmate is bringing food
+    pub hut: Option<(usize, usize)>, // the hut this orc sleeps in
+    pub emote: Option<EmoteKind>, // picked up by App and shown above the orc
+    pub threw: Option<(usize, usize)>, // where it threw its spear this tick, picked up by App to draw the throw
+    thrown: Option<(Tool, usize, usize)>, // its spear, lying where the throw left it
+    pub diary: Diary, // what it's been through lately, for the inspection screen
     path: Vec<(usize, usize)>, // A* computed waypoints
     path_step: usize,
+    path_deferred: bool, // planning was put off for lack of pathfinding budget
+    groping: bool, // spent its last step in the dark feeling the way
+}
+
+impl Combatant for Orc {
+    fn health(&self) -> f32 {
+        self.health
+    }
+
+    fn max_health(&self) -> f32 {
+        100.0
+    }
+
+    fn hurt(&mut self, damage: f32, tick: u64) {
+        self.health = (self.health - damage).max(0.0);
+        self.wounds = (self.wounds + damage).min(100.0);
+        self.emote = Some(EmoteKind::Alarm);
+        if self.health <= 0.0 {
+            self.alive = false;
+            self.death_tick = Some(tick);
+        }
+    }
+
+    /// Practice at the hunt takes the sting out of a blow
+    fn guard(&self) -> f32 {
+        1.0 - self.hunt_skill / 200.0
+    }
+
+    fn breaks(&self) -> bool {
+        !self.stands_ground()
+    }
 }
 
 impl Orc {
@@ -59,17 +398,45 @@ impl Orc {
             name,
             x,
             y,
+            was: (x, y),
             hunger: 20.0,
             energy: 80.0,
             thirst: 10.0,
+            warmth: 100.0,
             health: 100.0,
+            morale: 50.0,
+            sick_ticks: 0,
+            wounds: 0.0,
+            dressed_ticks: 0,
+            herbs: 0,
+            torch: 0,
+            in_dark: false,
+            forage_skill: 10.0,
+            hunt_skill: 0.0,
+            berries: 0,
+            tools: Vec::new(),
+            traits: Vec::new(),
+            job: Job::Woodcutter,
+            sex: Sex::Female,
+            birth_day: 1,
+            parents: None,
+            carer: None,
             alive: true,
             death_tick: None,
+            died_of: None,
             activity: Activity::Idle,
             idle_ticks: 0,
             carrying_food: false,
+            awaiting_food: false,
+            hut: None,
+            emote: None,
+            threw: None,
+            thrown: None,
+            diary: Diary::default(),
             path: Vec::new(),
             path_step: 0,
+            path_deferred: false,
+            groping: false,
         }
     }
 
@@ -77,19 +444,32 @@ impl Orc {
         let mut used_names: Vec<String> = Vec::new();
         let mut orcs = Vec::new();
 
-        for _ in 0..count {
+        for i in 0..count {
             let name = pick_name(rng, &used_names);
             used_names.push(name.clone());
 
             let (cx, cy) = world.campfire_pos;
-            loop {
-                let x = cx.saturating_sub(3) + rng.gen_range(0..7);
-                let y = cy.saturating_sub(3) + rng.gen_range(0..7);
-                if x < MAP_WIDTH && y < MAP_HEIGHT && world.is_walkable(x, y) {
-                    if !orcs.iter().any(|o: &Orc| o.x == x && o.y == y) {
-                        orcs.push(Orc::new(name, x, y));
-                        break;
-                    }
+            // Big clans spill out of the camp clearing
+            let mut spread = 3;
+            for attempt in 1.. {
+                if attempt % 50 == 0 {
+                    spread += 1;
+                }
+                let x = cx.saturating_sub(spread) + rng.gen_range(0..spread * 2 + 1);
+                let y = cy.saturating_sub(spread) + rng.gen_range(0..spread * 2 + 1);
+                if x < MAP_WIDTH && y < MAP_HEIGHT && world.is_walkable(x, y)
+                    && !orcs.iter().any(|o: &Orc| o.x == x && o.y == y)
+                {
+                    let mut orc = Orc::new(name, x, y);
+                    orc.traits = Trait::roll(rng);
+                    orc.job = Job::ALL[i % Job::ALL.len()];
+                    orc.sex = if i % 2 == 0 { Sex::Female } else { Sex::Male };
+                    orc.forage_skill = rng.gen_range(10.0..70.0);
+                    orc.hunt_skill = rng.gen_range(0.0..50.0);
+                    let age = rng.gen_range(FOUNDER_AGE) * calendar::YEAR_LENGTH + rng.gen_range(0..calendar::YEAR_LENGTH);
+                    orc.birth_day = 1 - age as i64;
+                    orcs.push(orc);
+                    break;
                 }
             }
         }
@@ -98,134 +478,338 @@ impl Orc {
     }
 
     /// Compute and store an A* path to the target
-    fn plan_path(&mut self, tx: usize, ty: usize, world: &World, allow_tree: bool) {
-        if let Some(p) = pathfinding::find_path(world, self.x, self.y, tx, ty, allow_tree) {
-            self.path = p;
-            self.path_step = 0;
-        } else {
-            // No path found — clear and rely on fallback
-            self.path.clear();
-            self.path_step = 0;
+    pub fn plan_path(&mut self, tx: usize, ty: usize, world: &World, allow_tree: bool) {
+        self.path.clear();
+        self.path_step = 0;
+        // Out of pathfinding budget this tick: set off directly and plan on a later tick
+        self.path_deferred = world.path_budget.exhausted();
+        if self.path_deferred {
+            world.path_budget.defer();
+            return;
+        }
+        match pathfinding::find_path(world, self.x, self.y, tx, ty, allow_tree) {
+            Some(p) => self.path = p,
+            // Cut short by the budget rather than finding no way through
+            None if world.path_budget.exhausted() => {
+                world.path_budget.defer();
+                self.path_deferred = true;
+            }
+            // No path found — rely on fallback
+            None => self.emote = Some(EmoteKind::Confused),
         }
     }
 
-    /// Follow the stored A* path. Returns true if moved, false if path exhausted.
+    /// Follow the stored A* path, at half pace in the dark. Returns true if
+    /// still on the way, false if path exhausted.
     fn follow_path(&mut self) -> bool {
-        if self.path_step < self.path.len() {
+        if self.path_step >= self.path.len() {
+            return false;
+        }
+        self.groping = self.in_dark && !self.groping;
+        if !self.groping {
             let (nx, ny) = self.path[self.path_step];
             self.x = nx;
             self.y = ny;
             self.path_step += 1;
-            true
-        } else {
-            false
         }
+        true
+    }
+
+    /// Pick the spear it threw back up once it's beside where it fell
+    fn pick_up_spear(&mut self) {
+        let Some((_, x, y)) = self.thrown else {
+            return;
+        };
+        if self.x.abs_diff(x).max(self.y.abs_diff(y)) <= 1
+            && !self.has_tool(ToolKind::Spear)
+            && let Some((spear, _, _)) = self.thrown.take()
+        {
+            self.tools.push(spear);
+        }
+    }
+
+    /// Light a torch at the campfire before heading out into the night, and
+    /// let it burn down, putting it out come morning
+    fn tend_torch(&mut self, world: &mut World, is_night: bool, log: &mut EventLog, tick: u64) {
+        if !is_night {
+            self.torch = 0;
+            return;
+        }
+        if self.torch > 0 {
+            self.torch -= 1;
+            if self.torch == 0 {
+                log.log_at(tick, EventCategory::General, format!("{}'s torch burns out", self.name), Severity::Info, (self.x, self.y));
+            }
+            return;
+        }
+        let (cx, cy) = world.campfire_pos;
+        let from_fire = |(x, y): (usize, usize)| x.abs_diff(cx).max(y.abs_diff(cy));
+        let Some(&goal) = self.path.last() else {
+            return;
+        };
+        if self.path_step >= self.path.len()
+            || from_fire((self.x, self.y)) > light::FIRE_LIGHT
+            || from_fire(goal) <= light::FIRE_LIGHT
+            || world.fire_fuel <= light::TORCH_FUEL
+        {
+            return;
+        }
+        world.fire_fuel -= light::TORCH_FUEL;
+        self.torch = light::TORCH_TICKS;
+        log.log_at(tick, EventCategory::General, format!("{} lights a torch at the fire", self.name), Severity::Info, (self.x, self.y));
+    }
+
+    /// Lose the way in the dark, wandering a few steps off it before finding it again
+    fn stray(&mut self, world: &World, rng: &mut impl Rng, log: &mut EventLog, tick: u64) {
+        let mut detour = Vec::new();
+        let (mut x, mut y) = (self.x, self.y);
+        for _ in 0..rng.gen_range(light::STRAY_STEPS) {
+            let nx = (x as i32 + rng.gen_range(-1..=1)).clamp(0, MAP_WIDTH as i32 - 1) as usize;
+            let ny = (y as i32 + rng.gen_range(-1..=1)).clamp(0, MAP_HEIGHT as i32 - 1) as usize;
+            if (nx, ny) == (x, y) || !world.is_walkable(nx, ny) || world.cuts_corner(x, y, nx, ny, Terrain::walkable) {
+                break;
+            }
+            detour.push((nx, ny));
+            (x, y) = (nx, ny);
+        }
+        if detour.is_empty() {
+            return;
+        }
+        // Out and back again the same way
+        let back: Vec<(usize, usize)> = detour.iter().rev().skip(1).copied().chain([(self.x, self.y)]).collect();
+        detour.extend(back);
+        self.path.splice(self.path_step..self.path_step, detour);
+        self.emote = Some(EmoteKind::Confused);
+        log.log_at(tick, EventCategory::General, format!("{} loses the way in the dark", self.name), Severity::Info, (self.x, self.y));
     }
 
     /// Set a GoingTo activity and compute the path
-    fn go_to(&mut self, x: usize, y: usize, reason: String, world: &World) {
+    pub fn go_to(&mut self, x: usize, y: usize, purpose: Purpose, reason: &'static str, world: &World) {
         let allow_tree = matches!(world.get(x, y), Terrain::Tree | Terrain::Bush);
         self.plan_path(x, y, world, allow_tree);
-        self.activity = Activity::GoingTo { x, y, reason };
+        self.activity = Activity::GoingTo { x, y, purpose, reason };
     }
 
-    pub fn update(
-        &mut self,
-        world: &mut World,
-        animals: &mut Vec<Animal>,
-        rng: &mut impl Rng,
-        log: &mut EventLog,
-        tick: u64,
-        is_night: bool,
-    ) {
+    /// A tick's wear on the orc's needs: hungrier and thirstier, and more
+    /// tired unless it's asleep
+    pub fn wear(&mut self, world: &World, rates: &NeedRates, tick: u64) {
         if !self.alive {
             return;
         }
-
-        // Update needs
-        let hunger_rate = if is_night { 0.3 } else { 0.5 };
-        let energy_drain = if is_night { 0.8 } else { 0.4 };
-        let thirst_rate = 0.6;
+        let is_night = DayPhase::of(tick, world.day_length) == DayPhase::Night;
+        let hunger_rate = if is_night { rates.night_hunger } else { rates.hunger };
+        let energy_drain = if is_night { rates.night_fatigue } else { rates.fatigue };
 
         self.hunger = (self.hunger + hunger_rate).clamp(0.0, 100.0);
-        self.thirst = (self.thirst + thirst_rate).clamp(0.0, 100.0);
+        self.thirst = (self.thirst + rates.thirst).clamp(0.0, 100.0);
 
         match &self.activity {
+            // A baby dozes in its carer's arms
+            Activity::Infant { .. } => {}
             Activity::Sleeping => {
-                self.energy = (self.energy + 3.0).clamp(0.0, 100.0);
+                let recovery = if self.sheltered(world) { HUT_SLEEP_RECOVERY } else { SLEEP_RECOVERY };
+                self.energy = (self.energy + recovery).clamp(0.0, 100.0);
             }
             _ => {
                 self.energy = (self.energy - energy_drain).clamp(0.0, 100.0);
             }
         }
+    }
+
+    pub fn update(
+        &mut self,
+        world: &mut World,
+        animals: &mut [Animal],
+        rng: &mut impl Rng,
+        log: &mut EventLog,
+        tick: u64,
+        config: &AiConfig,
+    ) {
+        if !self.alive {
+            return;
+        }
+        let phase = DayPhase::of(tick, world.day_length);
+        let is_night = phase == DayPhase::Night;
+
+        // Warmth: the campfire heats nearby orcs, huts keep out the night air, which chills everyone else
+        let season = Season::of(tick, world.day_length);
+        let was_warm = self.warmth >= 20.0;
+        let warmth_delta = if self.near_campfire(world) && world.fire_lit() {
+            4.0
+        } else if self.sheltered(world) {
+            1.0
+        } else if is_night && self.near_campfire(world) {
+            // The embers of a dead fire still take the edge off
+            -season.night_chill() / 2.0
+        } else if is_night {
+            -season.night_chill()
+        } else {
+            season.day_warmth()
+        };
+        self.warmth = (self.warmth + warmth_delta).clamp(0.0, 100.0);
+        if was_warm && self.warmth < 20.0 {
+            log.log_at(tick, EventCategory::Alert, format!("{} is shivering with cold", self.name), Severity::Warning, (self.x, self.y));
+        }
 
         // Health system
         let mut health_delta = 0.0f32;
-        if self.hunger >= 95.0 {
+        if self.hunger >= WASTING {
             health_delta -= 2.0;
         }
-        if self.thirst >= 95.0 {
+        if self.thirst >= WASTING {
             health_delta -= 3.0;
         }
         if self.energy <= 5.0 {
             health_delta -= 1.0;
         }
-        if self.hunger < 50.0 && self.thirst < 50.0 && self.energy > 30.0 {
+        if self.warmth <= FREEZING {
+            health_delta -= 1.5; // hypothermia
+        }
+        if self.sick_ticks > 0 {
+            health_delta -= 0.5;
+            self.sick_ticks -= 1;
+            if self.sick_ticks == 0 {
+                log.log_at(tick, EventCategory::General, format!("{} feels better", self.name), Severity::Info, (self.x, self.y));
+            }
+        } else if self.hunger < 50.0 && self.thirst < 50.0 && self.energy > 30.0 && self.warmth > 30.0 {
             health_delta += 0.5;
         }
-        self.health = (self.health + health_delta).clamp(0.0, 100.0);
+        // Health only comes back as far as the orc's wounds let it
+        let ceiling = (100.0 - self.wounds).max(self.health);
+        self.health = (self.health + health_delta).clamp(0.0, ceiling);
+        self.heal_wounds(log, tick);
+
+        // Morale slowly settles back to neutral, but a night without the fire is a grim one
+        self.morale += (50.0 - self.morale) * 0.01;
+        if is_night && !world.fire_lit() {
+            self.morale = (self.morale - DARK_CAMP_GLOOM).max(0.0);
+        }
 
         // Death check
         if self.health <= 0.0 {
             self.alive = false;
             self.death_tick = Some(tick);
-            log.log(tick, format!("{} has died!", self.name), ratatui::style::Color::Red);
+            self.died_of = Some(self.wasting_cause());
+            log.log_at(tick, EventCategory::Death, format!("{} has died!", self.name), Severity::Danger, (self.x, self.y));
             return;
         }
 
+        // Take a torch out into the night, or stumble about in the dark without one
+        self.tend_torch(world, is_night, log, tick);
+        self.pick_up_spear();
+        if self.in_dark && self.path_step < self.path.len() && rng.gen_bool(light::STRAY_CHANCE) {
+            self.stray(world, rng, log, tick);
+        }
+
         // AI decision-making
         match &self.activity {
             Activity::Sleeping => {
-                if self.energy >= 90.0 {
-                    log.log(tick, format!("{} woke up, feeling rested", self.name), ratatui::style::Color::Cyan);
+                if self.is_bedtime(phase) {
+                    // Sleep through the night unless hunger or thirst gets serious
+                    if self.has_pressing_need(config) {
+                        log.log_at(tick, EventCategory::General, format!("{} wakes in the night, unable to sleep", self.name), Severity::Info, (self.x, self.y));
+                        self.activity = Activity::Idle;
+                    }
+                } else if self.energy >= 90.0 {
+                    log.log_at(tick, EventCategory::General, format!("{} woke up, feeling rested", self.name), Severity::Info, (self.x, self.y));
                     self.activity = Activity::Idle;
                 }
             }
             Activity::Eating => {
                 self.hunger = (self.hunger - 15.0).clamp(0.0, 100.0);
                 if self.hunger <= 10.0 {
-                    log.log(tick, format!("{} finished eating", self.name), ratatui::style::Color::Cyan);
+                    log.log_at(tick, EventCategory::General, format!("{} finished eating", self.name), Severity::Info, (self.x, self.y));
                     self.activity = Activity::Idle;
                 }
             }
             Activity::Drinking => {
                 self.thirst = (self.thirst - 20.0).clamp(0.0, 100.0);
                 if self.thirst <= 5.0 {
-                    log.log(tick, format!("{} finished drinking", self.name), ratatui::style::Color::Cyan);
+                    log.log_at(tick, EventCategory::General, format!("{} finished drinking", self.name), Severity::Info, (self.x, self.y));
                     self.activity = Activity::Idle;
                 }
             }
             Activity::Hunting { target_idx } => {
                 let idx = *target_idx;
-                if idx < animals.len() && animals[idx].alive {
+                if idx < animals.len() && animals[idx].alive && animals[idx].kind.is_game() && animals[idx].tame.is_none() {
                     let (ax, ay) = (animals[idx].x, animals[idx].y);
                     let dist = self.x.abs_diff(ax) + self.y.abs_diff(ay);
-                    if dist <= 1 {
-                        animals[idx].kill(world, log, tick);
-                        log.log(tick, format!("{} caught a {}!", self.name, animals[idx].kind.name()), ratatui::style::Color::Green);
-                        if self.hunger > 50.0 {
-                            self.activity = Activity::Eating;
+                    let armed = dist <= 1 && self.has_tool(ToolKind::Spear);
+                    let catch_chance = animals[idx].kind.catch_chance(phase) + if armed { SPEAR_BONUS } else { 0.0 };
+                    // Quarry that fights back may gore the hunter closing in. A spear
+                    // fends off some charges, and practice takes the sting out of the rest.
+                    if dist <= 1
+                        && let Some(tusks) = animals[idx].tusks(phase)
+                        && !(armed && rng.gen_bool(SPEAR_GUARD))
+                    {
+                        let kind = animals[idx].kind.name();
+                        let blow = combat::attack(&animals[idx], &tusks, self, rng, tick);
+                        if blow != Blow::Missed {
+                            animals[idx].behaviour = Behaviour::Fighting;
+                        }
+                        match blow {
+                            Blow::Missed => {}
+                            Blow::Killed => {
+                                self.died_of = Some(Cause::Beast(animals[idx].kind));
+                                log.log_at(tick, EventCategory::Death, format!("{} is gored to death by a {}!", self.name, kind), Severity::Danger, (self.x, self.y));
+                                return;
+                            }
+                            Blow::Landed(_) | Blow::Routed(_) => {
+                                log.log_at(tick, EventCategory::Combat, format!("A {} gores {} ({:.0} health left)", kind, self.name, self.health), Severity::Danger, (self.x, self.y));
+                            }
+                        }
+                        if let Blow::Routed(_) = blow {
+                            log.log_at(tick, EventCategory::Hunt, format!("{} gives up on the {} and limps off", self.name, kind), Severity::Info, (self.x, self.y));
+                            self.path.clear();
+                            self.activity = Activity::Idle;
+                            return;
+                        }
+                    }
+                    if dist <= 1
+                        && self.job == Job::Herder
+                        && !self.has_pressing_need(config)
+                        && animals[idx].kind.stats().tameable
+                        && let Some((px, py)) = husbandry::free_pen(world, animals)
+                    {
+                        if rng.gen_bool(catch_chance.min(1.0)) {
+                            // Room in the pens: a herder brings the quarry in alive
+                            animals[idx].tame = Some(0.0);
+                            animals[idx].carried_by = Some(self.name.clone());
+                            self.hunt_skill = (self.hunt_skill + HUNT_PRACTICE).min(100.0);
+                            log.log_at(tick, EventCategory::Hunt, format!("{} wrestles a {} down and ties it up to take back alive", self.name, animals[idx].kind.name().to_lowercase()), Severity::Info, (self.x, self.y));
+                            self.plan_path(px, py, world, false);
+                            self.activity = Activity::Penning { x: px, y: py };
                         } else {
-                            self.carrying_food = true;
-                            self.activity = Activity::CarryingMeat;
-                            if world.get(ax, ay) == Terrain::Food {
-                                world.set(ax, ay, Terrain::Grass);
+                            self.lose_quarry(&mut animals[idx], world, log, tick);
+                        }
+                    } else if dist <= 1 {
+                        // The lunge is a blow with whatever the hunter has to hand
+                        let lunge = Weapon { chance: catch_chance.min(1.0), damage: self.weapon().damage.clone() };
+                        match combat::attack(&*self, &lunge, &mut animals[idx], rng, tick) {
+                            Blow::Missed => self.lose_quarry(&mut animals[idx], world, log, tick),
+                            Blow::Landed(_) | Blow::Routed(_) => {
+                                let kind = animals[idx].kind.name().to_lowercase();
+                                log.log_at(tick, EventCategory::Hunt, format!("{} wounds a {} but it breaks away", self.name, kind), Severity::Info, (self.x, self.y));
+                                animals[idx].flee_from(self.x, self.y, world);
+                                self.path.clear();
                             }
-                            // Plan path to meat rack
-                            if let Some((mx, my)) = world.meat_rack_pos() {
-                                self.plan_path(mx, my, world, false);
+                            Blow::Killed => {
+                                let carcass = animals[idx].kill(world, log, tick);
+                                self.hunt_skill = (self.hunt_skill + HUNT_PRACTICE).min(100.0);
+                                log.log_at(tick, EventCategory::Hunt, format!("{} caught a {}!", self.name, animals[idx].kind.name()), Severity::Good, (self.x, self.y));
+                                // Eat or carry one unit; the rest of the carcass waits for haulers
+                                if !carcass.is_some_and(|(cx, cy)| world.butcher(cx, cy)) {
+                                    self.activity = Activity::Idle;
+                                } else if self.hunger > 50.0 {
+                                    self.eat_raw_meat(rng, log, tick);
+                                    self.activity = Activity::Eating;
+                                } else {
+                                    self.pick_up_meat(world);
+                                }
                             }
                         }
+                    } else if dist <= THROW_RANGE && self.job != Job::Herder && self.has_tool(ToolKind::Spear) {
+                        self.throw_spear(&mut animals[idx], dist, world, rng, log, tick);
                     } else {
                         // Recompute path to moving target every few steps
                         if self.path.is_empty() || self.path_step >= self.path.len() {
@@ -236,6 +820,9 @@ impl Orc {
                             self.move_toward_greedy(ax, ay, world, rng);
                         }
                     }
+                    if armed {
+                        self.wear_tool(ToolKind::Spear, log, tick);
+                    }
                 } else {
                     self.activity = Activity::Idle;
                 }
@@ -244,9 +831,17 @@ impl Orc {
                 if let Some((mx, my)) = world.meat_rack_pos() {
                     let dist = self.x.abs_diff(mx) + self.y.abs_diff(my);
                     if dist <= 1 {
-                        world.food_stockpile += 1;
-                        self.carrying_food = false;
-                        log.log(tick, format!("{} stored meat (stockpile: {})", self.name, world.food_stockpile), ratatui::style::Color::Rgb(180, 120, 60));
+                        if self.carrying_food {
+                            world.store_meat();
+                            self.carrying_food = false;
+                            log.log_at(tick, EventCategory::Food, format!("{} stored meat (stockpile: {})", self.name, world.food_stockpile), Severity::Info, (self.x, self.y));
+                        }
+                        if self.berries > 0 {
+                            world.berry_stockpile += self.berries;
+                            world.wear_rack();
+                            self.berries = 0;
+                            log.log_at(tick, EventCategory::Food, format!("{} stored berries (berries: {})", self.name, world.berry_stockpile), Severity::Info, (self.x, self.y));
+                        }
                         self.activity = Activity::Idle;
                     } else if !self.follow_path() {
                         self.move_toward_greedy(mx, my, world, rng);
@@ -256,176 +851,1252 @@ impl Orc {
                     self.activity = Activity::Idle;
                 }
             }
-            Activity::GoingTo { x, y, .. } => {
+            Activity::Retrieving { x, y } => {
+                let (tx, ty) = (*x, *y);
+                if self.thrown.is_none() {
+                    self.activity = Activity::Idle;
+                } else if !self.follow_path() && !self.move_toward_greedy(tx, ty, world, rng) {
+                    self.emote = Some(EmoteKind::Confused);
+                    self.activity = Activity::Idle;
+                }
+            }
+            Activity::Hauling { x, y } => {
                 let (tx, ty) = (*x, *y);
                 if self.x == tx && self.y == ty {
-                    self.arrive_at_destination(world, log, tick);
+                    if world.take_meat_at(tx, ty) {
+                        self.pick_up_meat(world);
+                    } else {
+                        // Someone else got to the pile first
+                        self.activity = Activity::Idle;
+                    }
                 } else if !self.follow_path() {
-                    // Path exhausted or failed — fallback to greedy
                     self.move_toward_greedy(tx, ty, world, rng);
                 }
             }
-            Activity::Idle => {
-                self.decide_action(world, animals, rng, log, tick, is_night);
+            Activity::FetchingWater { x, y, full } => {
+                let (tx, ty, full) = (*x, *y, *full);
+                if self.x != tx || self.y != ty {
+                    if !self.follow_path() && !self.move_toward_greedy(tx, ty, world, rng) {
+                        self.emote = Some(EmoteKind::Confused);
+                        self.activity = Activity::Idle;
+                    }
+                } else if !full && self.is_adjacent_to_water(world) {
+                    log.log_at(tick, EventCategory::General, format!("{} fills a bucket", self.name), Severity::Info, (self.x, self.y));
+                    match world.barrel_pos() {
+                        Some((bx, by)) => {
+                            self.plan_path(bx, by, world, false);
+                            self.activity = Activity::FetchingWater { x: bx, y: by, full: true };
+                        }
+                        None => self.activity = Activity::Idle,
+                    }
+                } else if full && world.get(tx, ty) == Terrain::WaterBarrel {
+                    world.water_barrel = (world.water_barrel + BUCKET).min(BARREL_CAPACITY);
+                    log.log_at(tick, EventCategory::General, format!("{} tops up the water barrel ({}/{})", self.name, world.water_barrel, BARREL_CAPACITY), Severity::Info, (self.x, self.y));
+                    self.activity = Activity::Idle;
+                } else {
+                    self.activity = Activity::Idle;
+                }
             }
-        }
-    }
-
-    fn arrive_at_destination(&mut self, world: &mut World, log: &mut EventLog, tick: u64) {
-        let terrain = world.get(self.x, self.y);
-
-        if terrain == Terrain::Bush {
-            log.log(tick, format!("{} found berries and starts eating", self.name), ratatui::style::Color::Green);
-            world.deplete_bush(self.x, self.y, tick);
-            self.activity = Activity::Eating;
-        } else if terrain == Terrain::Food {
-            log.log(tick, format!("{} found food and starts eating", self.name), ratatui::style::Color::Green);
-            world.set(self.x, self.y, Terrain::Grass);
-            self.activity = Activity::Eating;
-        } else if terrain == Terrain::Tree {
-            log.log(tick, format!("{} forages from a tree", self.name), ratatui::style::Color::Green);
-            self.activity = Activity::Eating;
-        } else if terrain == Terrain::MeatRack && world.food_stockpile > 0 {
-            world.food_stockpile -= 1;
-            log.log(tick, format!("{} takes food from stockpile (left: {})", self.name, world.food_stockpile), ratatui::style::Color::Rgb(180, 120, 60));
-            self.activity = Activity::Eating;
-        } else if self.is_adjacent_to_water(world) {
-            log.log(tick, format!("{} drinks water", self.name), ratatui::style::Color::Rgb(65, 105, 225));
-            self.activity = Activity::Drinking;
-        } else {
-            log.log(tick, format!("{} lies down to sleep by the fire", self.name), ratatui::style::Color::Blue);
-            self.activity = Activity::Sleeping;
-        }
-    }
-
-    fn decide_action(
-        &mut self,
-        world: &mut World,
-        animals: &[Animal],
-        rng: &mut impl Rng,
-        log: &mut EventLog,
-        tick: u64,
-        _is_night: bool,
-    ) {
-        let (cx, cy) = world.campfire_pos;
-
-        // Priority 1: Health critical
-        if self.health < 20.0 {
-            if self.thirst > self.hunger && self.thirst > (100.0 - self.energy) {
-                if let Some((wx, wy)) = world.find_water_adjacent(self.x, self.y) {
-                    log.log(tick, format!("{} desperately needs water!", self.name), ratatui::style::Color::Red);
-                    self.go_to(wx, wy, "Desperate for water".to_string(), world);
-                    return;
+            Activity::GoingTo { x, y, purpose, .. } => {
+                let (tx, ty, purpose) = (*x, *y, *purpose);
+                if self.x == tx && self.y == ty {
+                    self.arrive_at_destination(purpose, world, rng, log, tick, phase);
+                } else if !self.follow_path() && !self.move_toward_greedy(tx, ty, world, rng) {
+                    // Boxed in with no way forward: give up and think again
+                    self.emote = Some(EmoteKind::Confused);
+                    self.activity = Activity::Idle;
                 }
-            } else if self.hunger > (100.0 - self.energy) {
-                if let Some(target) = self.find_food_target(world, animals) {
-                    log.log(tick, format!("{} desperately needs food!", self.name), ratatui::style::Color::Red);
-                    self.set_activity_with_path(target, world);
-                    return;
+            }
+            Activity::Gathering { material, x, y, site, progress } => {
+                let (material, rx, ry, site, progress) = (*material, *x, *y, *site, *progress);
+                if self.x.abs_diff(rx).max(self.y.abs_diff(ry)) > 1 {
+                    if !self.follow_path() {
+                        self.move_toward_greedy(rx, ry, world, rng);
+                    }
+                } else if world.get(rx, ry) != material.source() {
+                    // Someone else got to it first
+                    self.activity = Activity::Idle;
+                } else if progress + 1 >= self.gather_ticks(material) {
+                    // Without an axe the tree is left standing
+                    let felled = material == Material::Wood && self.has_tool(ToolKind::Axe);
+                    if felled || material == Material::Stone {
+                        world.set(rx, ry, Terrain::Grass);
+                    }
+                    let verb = match material {
+                        Material::Wood if felled => "fells a tree",
+                        Material::Wood => "gathers deadwood",
+                        Material::Stone => "breaks up a rock",
+                    };
+                    log.log_at(tick, EventCategory::General, format!("{} {} for {}", self.name, verb, material.name()), Severity::Info, (self.x, self.y));
+                    if felled {
+                        world.spirits.offend(spirits::FELL_OFFENCE);
+                        self.wear_tool(ToolKind::Axe, log, tick);
+                    }
+                    self.plan_path(site.0, site.1, world, false);
+                    self.activity = Activity::Supplying { material, x: site.0, y: site.1 };
+                } else {
+                    self.activity = Activity::Gathering { material, x: rx, y: ry, site, progress: progress + 1 };
                 }
-            } else {
-                let (sx, sy) = self.find_spot_near(cx, cy, world, rng);
-                log.log(tick, format!("{} desperately needs rest!", self.name), ratatui::style::Color::Red);
-                self.go_to(sx, sy, "Desperate for sleep".to_string(), world);
-                return;
             }
-        }
-
-        // Priority 2: Thirst
-        if self.thirst > 60.0 {
-            if let Some((wx, wy)) = world.find_water_adjacent(self.x, self.y) {
-                log.log(tick, format!("{} is thirsty, heading to water", self.name), ratatui::style::Color::Yellow);
-                self.go_to(wx, wy, "Going to drink".to_string(), world);
-                return;
+            Activity::Supplying { material, x, y } => {
+                let (material, sx, sy) = (*material, *x, *y);
+                if self.x.abs_diff(sx).max(self.y.abs_diff(sy)) > 1 {
+                    if !self.follow_path() {
+                        self.move_toward_greedy(sx, sy, world, rng);
+                    }
+                } else {
+                    // The load is simply dropped if the site is gone
+                    if let Some(site) = world.site_mut(sx, sy) {
+                        site.deliver(material);
+                        log.log_at(tick, EventCategory::General, format!("{} brings {} to the {} site ({})", self.name, material.name(), site.kind.name(), site.describe()), Severity::Info, (self.x, self.y));
+                    } else if let Some(craft) = world.craft_mut(sx, sy) {
+                        craft.deliver(material);
+                        log.log_at(tick, EventCategory::General, format!("{} brings {} to the workbench ({})", self.name, material.name(), craft.describe()), Severity::Info, (self.x, self.y));
+                    } else if world.meat_rack_pos() == Some((sx, sy)) {
+                        let stock = world.store(material);
+                        log.log_at(tick, EventCategory::General, format!("{} stacks {} by the rack ({}: {})", self.name, material.name(), material.name(), stock), Severity::Info, (self.x, self.y));
+                    } else if world.campfire_pos == (sx, sy) && material == Material::Wood {
+                        if world.is_broken(sx, sy) {
+                            log.log_at(tick, EventCategory::General, format!("{} finds the hearth in pieces and drops the log", self.name), Severity::Info, (self.x, self.y));
+                        } else if world.feed_fire() {
+                            log.log_at(tick, EventCategory::General, format!("{} gets the campfire going again", self.name), Severity::Info, (self.x, self.y));
+                        } else {
+                            log.log_at(tick, EventCategory::General, format!("{} puts a log on the fire", self.name), Severity::Info, (self.x, self.y));
+                        }
+                    }
+                    self.activity = Activity::Idle;
+                }
             }
-        }
-
-        // Priority 3: Hunger
-        if self.hunger > 70.0 {
-            if let Some(target) = self.find_food_target(world, animals) {
-                log.log(tick, format!("{} is hungry, looking for food", self.name), ratatui::style::Color::Yellow);
-                self.set_activity_with_path(target, world);
-                return;
+            Activity::Constructing { x, y } => {
+                let (sx, sy) = (*x, *y);
+                if self.needs_a_break(config) {
+                    self.activity = Activity::Idle;
+                } else if self.x.abs_diff(sx).max(self.y.abs_diff(sy)) > 1 {
+                    if !self.follow_path() {
+                        self.move_toward_greedy(sx, sy, world, rng);
+                    }
+                } else if let Some(site) = world.site_mut(sx, sy) {
+                    site.work += BUILD_RATE;
+                    if site.work >= site.kind.work()
+                        && let Some(kind) = world.complete_site(sx, sy)
+                    {
+                        log.log_at(tick, EventCategory::General, format!("{} finishes building a {} at ({}, {})!", self.name, kind.name(), sx, sy), Severity::Good, (self.x, self.y));
+                        self.morale = (self.morale + 5.0).clamp(0.0, 100.0);
+                        self.activity = Activity::Idle;
+                    }
+                } else {
+                    self.activity = Activity::Idle;
+                }
             }
-        }
-
-        // Priority 4: Sleep
-        if self.energy < 20.0 {
-            let (sx, sy) = self.find_spot_near(cx, cy, world, rng);
-            log.log(tick, format!("{} is exhausted, heading to campfire", self.name), ratatui::style::Color::Yellow);
-            self.go_to(sx, sy, "Going to sleep".to_string(), world);
-            return;
-        }
-
-        // Priority 5: Carrying meat
-        if self.carrying_food {
-            self.activity = Activity::CarryingMeat;
-            if let Some((mx, my)) = world.meat_rack_pos() {
-                self.plan_path(mx, my, world, false);
+            Activity::Crafting { x, y } => {
+                let (bx, by) = (*x, *y);
+                if self.needs_a_break(config) {
+                    self.activity = Activity::Idle;
+                } else if self.x != bx || self.y != by {
+                    if !self.follow_path() {
+                        self.move_toward_greedy(bx, by, world, rng);
+                    }
+                } else if let Some(craft) = world.craft_mut(bx, by) {
+                    craft.work += 1;
+                    if craft.work >= craft.kind.work()
+                        && let Some(kind) = world.complete_craft(bx, by)
+                    {
+                        log.log_at(tick, EventCategory::General, format!("{} makes {} and hangs it on the tool rack", self.name, kind.with_article()), Severity::Good, (self.x, self.y));
+                        self.activity = Activity::Idle;
+                    }
+                } else {
+                    self.activity = Activity::Idle;
+                }
             }
-            return;
-        }
-
-        // Priority 6: Wander
-        self.idle_ticks += 1;
-        if self.idle_ticks > 3 {
-            self.idle_ticks = 0;
-            let max_dist: i32 = 30;
-            let nx = (self.x as i32 + rng.gen_range(-4..=4))
-                .clamp(cx as i32 - max_dist, cx as i32 + max_dist)
-                .clamp(0, MAP_WIDTH as i32 - 1) as usize;
-            let ny = (self.y as i32 + rng.gen_range(-4..=4))
-                .clamp(cy as i32 - max_dist, cy as i32 + max_dist)
-                .clamp(0, MAP_HEIGHT as i32 - 1) as usize;
-            if world.is_walkable(nx, ny) {
-                self.go_to(nx, ny, "Wandering".to_string(), world);
+            Activity::Repairing { x, y } => {
+                let (sx, sy) = (*x, *y);
+                if self.needs_a_break(config) {
+                    self.activity = Activity::Idle;
+                } else if self.x.abs_diff(sx).max(self.y.abs_diff(sy)) > 1 {
+                    if !self.follow_path() {
+                        self.move_toward_greedy(sx, sy, world, rng);
+                    }
+                } else {
+                    match world.repair(sx, sy, REPAIR_RATE) {
+                        Some(true) => {
+                            log.log_at(tick, EventCategory::General, format!("{} has the {} as good as new", self.name, world.structure_name(sx, sy)), Severity::Info, (self.x, self.y));
+                            self.activity = Activity::Idle;
+                        }
+                        Some(false) => {}
+                        // It came down before anyone got to it
+                        None => self.activity = Activity::Idle,
+                    }
+                }
             }
-        }
-    }
-
-    /// Set an activity that may be GoingTo or Hunting, computing path if needed
-    fn set_activity_with_path(&mut self, activity: Activity, world: &World) {
-        match &activity {
-            Activity::GoingTo { x, y, .. } => {
+            Activity::Fighting => {
+                // Stand and trade blows until the wolf is dead or gone
+            }
+            Activity::Defending { x, y } => {
+                // Close in on the threat; the blows are traded once within reach
                 let (tx, ty) = (*x, *y);
-                let allow_tree = matches!(world.get(tx, ty), Terrain::Tree | Terrain::Bush);
-                self.plan_path(tx, ty, world, allow_tree);
+                if self.x.abs_diff(tx).max(self.y.abs_diff(ty)) > 1 && !self.follow_path() {
+                    self.move_toward_greedy(tx, ty, world, rng);
+                }
             }
-            Activity::Hunting { .. } => {
-                // Hunting paths are recomputed dynamically since the target moves
-                self.path.clear();
-                self.path_step = 0;
+            Activity::PickingHerbs { x, y } => {
+                let (tx, ty) = (*x, *y);
+                if self.x.abs_diff(tx).max(self.y.abs_diff(ty)) > 1 {
+                    if !self.follow_path() {
+                        self.move_toward_greedy(tx, ty, world, rng);
+                    }
+                } else {
+                    // Someone or something may have beaten the healer to it
+                    if world.get(tx, ty) == Terrain::Herb && self.herbs < healing::HERB_POUCH {
+                        world.set(tx, ty, Terrain::Grass);
+                        self.herbs += 1;
+                        log.log_at(tick, EventCategory::General, format!("{} picks healing herbs ({} carried)", self.name, self.herbs), Severity::Info, (self.x, self.y));
+                    }
+                    self.activity = Activity::Idle;
+                }
             }
-            _ => {}
-        }
-        self.activity = activity;
-    }
-
-    fn find_food_target(&self, world: &World, animals: &[Animal]) -> Option<Activity> {
-        if world.food_stockpile > 0 {
-            if let Some((mx, my)) = world.meat_rack_pos() {
-                return Some(Activity::GoingTo {
-                    x: mx, y: my,
-                    reason: "Going to stockpile".to_string(),
-                });
+            Activity::Burying { name, x, y, carrying } => {
+                let (tx, ty, carrying) = (*x, *y, *carrying);
+                let name = name.clone();
+                if self.x.abs_diff(tx).max(self.y.abs_diff(ty)) > 1 {
+                    if !self.follow_path() {
+                        self.move_toward_greedy(tx, ty, world, rng);
+                    }
+                } else if !carrying {
+                    let Some(corpse) = world.take_corpse(&name) else {
+                        // Someone else got to the body first
+                        self.activity = Activity::Idle;
+                        return;
+                    };
+                    if let Some((gx, gy)) = world.grave_plot() {
+                        log.log_at(tick, EventCategory::General, format!("{} lifts {}'s body to carry it to the graveyard", self.name, name), Severity::Info, (self.x, self.y));
+                        self.plan_path(gx, gy, world, false);
+                        self.activity = Activity::Burying { name, x: gx, y: gy, carrying: true };
+                    } else {
+                        world.corpses.push(corpse);
+                        self.activity = Activity::Idle;
+                    }
+                } else if world.get(tx, ty) == Terrain::Grass && world.corpse_at(tx, ty).is_none() {
+                    log.log_at(tick, EventCategory::General, format!("{} buries {} in the graveyard", self.name, name), Severity::Info, (self.x, self.y));
+                    world.dig_grave(tx, ty, name, calendar::day_number(tick, world.day_length));
+                    self.activity = Activity::Idle;
+                } else if let Some((gx, gy)) = world.grave_plot() {
+                    // The plot was taken meanwhile; on to the next
+                    self.plan_path(gx, gy, world, false);
+                    self.activity = Activity::Burying { name, x: gx, y: gy, carrying: true };
+                } else {
+                    world.lay_corpse(name, self.x, self.y);
+                    self.activity = Activity::Idle;
+                }
+            }
+            Activity::Tending { x, y, .. } => {
+                // Walk to the patient; App dresses the wounds once adjacent
+                let (px, py) = (*x, *y);
+                if self.x.abs_diff(px).max(self.y.abs_diff(py)) > 1 && !self.follow_path() {
+                    self.move_toward_greedy(px, py, world, rng);
+                }
+            }
+            Activity::Guarding { x, y, tower } => {
+                let (tx, ty, tower) = (*x, *y, *tower);
+                let at_post = self.x.abs_diff(tx).max(self.y.abs_diff(ty)) <= 1;
+                let tower_gone = tower && world.get(tx, ty) != Terrain::Built(Building::Watchtower);
+                if self.needs_a_break(config) || self.is_bedtime(phase) || tower_gone || (at_post && !tower) {
+                    // Off duty, or on to the next post of the round
+                    self.activity = Activity::Idle;
+                } else if !at_post && !self.follow_path() {
+                    self.move_toward_greedy(tx, ty, world, rng);
+                }
+            }
+            Activity::Marching { x, y } => {
+                // Keep up with the clan unless hunger or thirst gets serious
+                let (tx, ty) = (*x, *y);
+                if self.has_pressing_need(config) {
+                    self.activity = Activity::Idle;
+                    let mut ctx = ai::Ctx { world, animals, rng, log, tick, phase, config };
+                    ai::clan_tree().run(self, &mut ctx);
+                } else if self.x.abs_diff(tx).max(self.y.abs_diff(ty)) > 1 && !self.follow_path() {
+                    self.move_toward_greedy(tx, ty, world, rng);
+                }
+            }
+            Activity::Sheltering { x, y } => {
+                // Wait it out at the rally point, sleeping rough there at night
+                let (tx, ty) = (*x, *y);
+                let tired = self.is_bedtime(phase) || self.energy < config.exhaustion;
+                if self.x.abs_diff(tx).max(self.y.abs_diff(ty)) > 1 {
+                    if !self.follow_path() {
+                        self.move_toward_greedy(tx, ty, world, rng);
+                    }
+                } else if tired {
+                    self.turn_in(log, tick);
+                } else if self.needs_a_break(config) {
+                    self.activity = Activity::Idle;
+                    let mut ctx = ai::Ctx { world, animals, rng, log, tick, phase, config };
+                    ai::clan_tree().run(self, &mut ctx);
+                }
+            }
+            Activity::Expedition { x, y } => {
+                // Keep with the party, breaking off for food or water and sleeping rough at night
+                let (tx, ty) = (*x, *y);
+                if self.needs_a_break(config) {
+                    self.activity = Activity::Idle;
+                    let mut ctx = ai::Ctx { world, animals, rng, log, tick, phase, config };
+                    ai::clan_tree().run(self, &mut ctx);
+                } else if self.x.abs_diff(tx).max(self.y.abs_diff(ty)) > 1 {
+                    if !self.follow_path() {
+                        self.move_toward_greedy(tx, ty, world, rng);
+                    }
+                } else if self.is_bedtime(phase) || self.energy < config.exhaustion {
+                    self.turn_in(log, tick);
+                }
+            }
+            Activity::Penning { x, y } => {
+                let (px, py) = (*x, *y);
+                let Some(captive) = animals.iter_mut().find(|a| a.alive && a.carried_by.as_ref() == Some(&self.name)) else {
+                    // It broke loose
+                    self.activity = Activity::Idle;
+                    return;
+                };
+                if self.has_pressing_need(config) {
+                    // Too parched or starved to keep hold of it
+                    self.activity = Activity::Idle;
+                } else if self.x != px || self.y != py {
+                    if !self.follow_path() && !self.move_toward_greedy(px, py, world, rng) {
+                        self.emote = Some(EmoteKind::Confused);
+                    }
+                    captive.x = self.x;
+                    captive.y = self.y;
+                } else {
+                    (captive.x, captive.y) = (px, py);
+                    captive.carried_by = None;
+                    log.log_at(tick, EventCategory::Food, format!("{} turns a captive {} loose in the pen", self.name, captive.kind.name().to_lowercase()), Severity::Info, (self.x, self.y));
+                    self.activity = Activity::Idle;
+                }
+            }
+            Activity::Feeding { x, y, has_feed } => {
+                let (px, py, has_feed) = (*x, *y, *has_feed);
+                if !has_feed {
+                    // First leg: take a meal off the rack
+                    let Some((mx, my)) = world.meat_rack_pos() else {
+                        self.activity = Activity::Idle;
+                        return;
+                    };
+                    if self.x.abs_diff(mx) + self.y.abs_diff(my) <= 1 {
+                        if !world.take_meal() {
+                            log.log_at(tick, EventCategory::General, format!("{} finds nothing on the rack for the livestock", self.name), Severity::Warning, (self.x, self.y));
+                            self.activity = Activity::Idle;
+                            return;
+                        }
+                        self.plan_path(px, py, world, false);
+                        self.activity = Activity::Feeding { x: px, y: py, has_feed: true };
+                    } else if !self.follow_path() {
+                        self.move_toward_greedy(mx, my, world, rng);
+                    }
+                } else if self.x.abs_diff(px).max(self.y.abs_diff(py)) > 1 {
+                    // Second leg: out to the pens
+                    if !self.follow_path() {
+                        self.move_toward_greedy(px, py, world, rng);
+                    }
+                } else {
+                    let hungriest = animals.iter_mut()
+                        .filter(|a| husbandry::is_livestock(a) && a.carried_by.is_none() && self.x.abs_diff(a.x).max(self.y.abs_diff(a.y)) <= husbandry::PEN_REACH)
+                        .max_by(|a, b| a.hunger.total_cmp(&b.hunger));
+                    match hungriest {
+                        Some(animal) => {
+                            let kind = animal.kind.name().to_lowercase();
+                            let tame = animal.tame.unwrap_or(0.0);
+                            animal.hunger = 0.0;
+                            animal.tame = Some((tame + husbandry::TAME_PER_FEED).min(husbandry::TAME));
+                            if tame < husbandry::TAME && tame + husbandry::TAME_PER_FEED >= husbandry::TAME {
+                                world.record(animal.kind, Deed::Tamed);
+                                log.log_at(tick, EventCategory::Food, format!("{} has tamed a penned {}", self.name, kind), Severity::Info, (self.x, self.y));
+                            } else {
+                                log.log_at(tick, EventCategory::General, format!("{} feeds a penned {}", self.name, kind), Severity::Info, (self.x, self.y));
+                            }
+                        }
+                        // The pens are empty now; the meal goes back to the rack
+                        None => self.carrying_food = true,
+                    }
+                    self.activity = Activity::Idle;
+                }
+            }
+            Activity::Butchering { x, y } => {
+                let (px, py) = (*x, *y);
+                if self.x.abs_diff(px).max(self.y.abs_diff(py)) > 1 {
+                    if !self.follow_path() {
+                        self.move_toward_greedy(px, py, world, rng);
+                    }
+                } else {
+                    let fattest = animals.iter_mut()
+                        .filter(|a| husbandry::is_livestock(a) && a.tame.is_some_and(|t| t >= husbandry::TAME) && self.x.abs_diff(a.x).max(self.y.abs_diff(a.y)) <= husbandry::PEN_REACH)
+                        .min_by(|a, b| a.hunger.total_cmp(&b.hunger));
+                    if let Some(animal) = fattest {
+                        animal.alive = false;
+                        world.record(animal.kind, Deed::Killed);
+                        let meat = animal.kind.meat_yield();
+                        log.log_at(tick, EventCategory::Food, format!("{} butchers a penned {} ({} meat)", self.name, animal.kind.name().to_lowercase(), meat), Severity::Info, (self.x, self.y));
+                        // One piece goes straight to the rack, the rest waits for haulers
+                        if meat > 1 {
+                            self.lay_down_meat(meat - 1, world);
+                        }
+                        self.pick_up_meat(world);
+                    } else {
+                        self.activity = Activity::Idle;
+                    }
+                }
+            }
+            // The clan sees to babies and their carers
+            Activity::Infant { .. } => {}
+            Activity::Nursing => {
+                if self.has_pressing_need(config) {
+                    self.activity = Activity::Idle;
+                }
+            }
+            Activity::Warming => {
+                // Stay by the fire until dawn unless a pressing need comes up
+                if phase.is_daylight() || self.needs_a_break(config) {
+                    self.activity = Activity::Idle;
+                } else if self.is_bedtime(phase) {
+                    self.turn_in(log, tick);
+                } else if !self.keeps_schedule() && self.warmth >= 90.0 {
+                    // Night owls only stop by the fire long enough to warm up
+                    self.activity = Activity::Idle;
+                }
+            }
+            Activity::Cooking { progress } => {
+                let progress = *progress;
+                let (cx, cy) = world.campfire_pos;
+                if self.x.abs_diff(cx).max(self.y.abs_diff(cy)) > 2 {
+                    if !self.follow_path() {
+                        self.move_toward_greedy(cx, cy, world, rng);
+                    }
+                } else if progress == 0 && world.food_stockpile == 0 {
+                    // Someone else got to the raw meat first
+                    self.activity = Activity::Idle;
+                } else {
+                    if progress == 0 {
+                        world.take_meat();
+                        log.log_at(tick, EventCategory::General, format!("{} puts some meat over the fire", self.name), Severity::Info, (self.x, self.y));
+                    }
+                    if progress + 1 >= COOK_TICKS {
+                        world.cooked_stockpile += 1;
+                        log.log_at(tick, EventCategory::Food, format!("{} cooked a meal (cooked: {})", self.name, world.cooked_stockpile), Severity::Info, (self.x, self.y));
+                        self.activity = Activity::Idle;
+                    } else {
+                        self.activity = Activity::Cooking { progress: progress + 1 };
+                    }
+                }
+            }
+            Activity::DeliveringFood { x, y, has_food, .. } => {
+                let (px, py, has_food) = (*x, *y, *has_food);
+                if !has_food {
+                    // First leg: pick up a meal from the meat rack
+                    if let Some((mx, my)) = world.meat_rack_pos() {
+                        if self.x.abs_diff(mx) + self.y.abs_diff(my) <= 1 {
+                            if !world.take_meal() {
+                                log.log_at(tick, EventCategory::General, format!("{} finds the stockpile empty", self.name), Severity::Warning, (self.x, self.y));
+                                self.activity = Activity::Idle;
+                                return;
+                            }
+                            if let Activity::DeliveringFood { has_food, .. } = &mut self.activity {
+                                *has_food = true;
+                            }
+                            self.plan_path(px, py, world, false);
+                        } else if !self.follow_path() {
+                            self.move_toward_greedy(mx, my, world, rng);
+                        }
+                    } else {
+                        self.activity = Activity::Idle;
+                    }
+                } else if self.x.abs_diff(px).max(self.y.abs_diff(py)) > 1 && !self.follow_path() {
+                    // Second leg: walk to the patient; App hands the food over once adjacent
+                    self.move_toward_greedy(px, py, world, rng);
+                }
+            }
+            Activity::Idle => {
+                let mut ctx = ai::Ctx { world, animals, rng, log, tick, phase, config };
+                ai::clan_tree().run(self, &mut ctx);
             }
         }
+    }
+
+    /// Starving and too weak to fetch food on their own
+    pub fn needs_feeding(&self) -> bool {
+        self.alive && self.health < 30.0 && self.hunger > 75.0 && self.hunger >= self.thirst
+    }
+
+    /// Healthy enough to run an errand for a clanmate
+    pub fn can_help(&self) -> bool {
+        self.alive
+            && self.health > 60.0
+            && self.hunger < 60.0
+            && self.thirst < 60.0
+            && matches!(self.activity, Activity::Idle | Activity::Warming)
+    }
+
+    /// Whether it's up and about, marking the ground it's on as the clan's
+    pub fn leaves_scent(&self) -> bool {
+        self.alive && !matches!(self.activity, Activity::Sleeping | Activity::Infant { .. })
+    }
+
+    /// Needs that should pull an orc away from what it's doing
+    pub fn needs_a_break(&self, config: &AiConfig) -> bool {
+        self.thirst > config.thirst || self.hunger > config.hunger || self.energy < config.exhaustion
+    }
 
-        let bush = world.find_nearest(self.x, self.y, Terrain::Bush);
-        let food = world.find_nearest(self.x, self.y, Terrain::Food);
+    /// Stop whatever this orc is doing and put down anything it was carrying,
+    /// leaving it idle to decide afresh on its next turn
+    pub fn cancel(&mut self, world: &mut World, log: &mut EventLog, tick: u64) {
+        match std::mem::replace(&mut self.activity, Activity::Idle) {
+            // A meal being carried to a patient goes down with any other food
+            Activity::DeliveringFood { has_food: true, .. } | Activity::Feeding { has_feed: true, .. } => self.carrying_food = true,
+            Activity::Cooking { progress } if progress > 0 => {
+                // The meat is taken off the fire and hung back on the rack
+                world.store_meat();
+            }
+            Activity::Supplying { material, .. } => {
+                log.log_at(tick, EventCategory::General, format!("{} drops the {}", self.name, material.name()), Severity::Info, (self.x, self.y));
+            }
+            Activity::FetchingWater { full: true, .. } => {
+                log.log_at(tick, EventCategory::General, format!("{} pours out the bucket", self.name), Severity::Info, (self.x, self.y));
+            }
+            Activity::Burying { name, carrying: true, .. } => {
+                log.log_at(tick, EventCategory::General, format!("{} sets {}'s body down", self.name, name), Severity::Info, (self.x, self.y));
+                world.lay_corpse(name, self.x, self.y);
+            }
+            _ => {}
+        }
+        if self.carrying_food {
+            self.carrying_food = false;
+            self.put_down_meat(world, log, tick);
+        }
+        self.path.clear();
+        self.path_step = 0;
+        self.path_deferred = false;
+        self.idle_ticks = 0;
+    }
+
+    /// Leave a unit of meat on open ground at or next to the orc for haulers to collect
+    fn put_down_meat(&self, world: &mut World, log: &mut EventLog, tick: u64) {
+        if self.lay_down_meat(1, world) {
+            log.log_at(tick, EventCategory::Food, format!("{} sets the meat down on the ground", self.name), Severity::Info, (self.x, self.y));
+        } else {
+            log.log_at(tick, EventCategory::Food, format!("{} has nowhere to set the meat down and it spoils", self.name), Severity::Warning, (self.x, self.y));
+        }
+    }
+
+    /// Leave `units` of meat in one pile on open ground at or next to the orc.
+    /// Returns false if there was nowhere to put it.
+    fn lay_down_meat(&self, units: u32, world: &mut World) -> bool {
+        let spots = [(0i32, 0i32), (1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (-1, -1), (1, -1), (-1, 1)];
+        spots.iter().any(|&(dx, dy)| {
+            let x = (self.x as i32 + dx).clamp(0, MAP_WIDTH as i32 - 1) as usize;
+            let y = (self.y as i32 + dy).clamp(0, MAP_HEIGHT as i32 - 1) as usize;
+            world.add_food(x, y, units)
+        })
+    }
+
+    /// Walk to a marked bush to pick its berries for the stockpile
+    pub fn start_harvest(&mut self, x: usize, y: usize, world: &World) {
+        let harvest = Activity::GoingTo { x, y, purpose: Purpose::Harvest, reason: "Going to pick berries" };
+        self.set_activity_with_path(harvest, world);
+    }
+
+    /// Fetch one unit of `material` from the tree or rock at (x, y) for a building site,
+    /// or for the store by the meat rack
+    pub fn start_gathering(&mut self, material: Material, x: usize, y: usize, site: (usize, usize), world: &World) {
+        let (tx, ty) = if world.is_walkable(x, y) {
+            (x, y)
+        } else {
+            world.walkable_neighbor(x, y, self.x, self.y).unwrap_or((x, y))
+        };
+        self.plan_path(tx, ty, world, false);
+        self.activity = Activity::Gathering { material, x, y, site, progress: 0 };
+    }
+
+    /// Carry a log from the woodpile straight to the campfire
+    pub fn start_stoking(&mut self, world: &World) {
+        let (cx, cy) = world.campfire_pos;
+        self.start_delivering(Material::Wood, cx, cy, world);
+    }
+
+    /// Carry a unit of `material` from the store by the rack to (x, y)
+    pub fn start_delivering(&mut self, material: Material, x: usize, y: usize, world: &World) {
+        self.plan_path(x, y, world, false);
+        self.activity = Activity::Supplying { material, x, y };
+    }
+
+    /// Go and make the tool ordered at the workbench at (x, y)
+    pub fn start_crafting(&mut self, x: usize, y: usize, world: &World) {
+        self.plan_path(x, y, world, false);
+        self.activity = Activity::Crafting { x, y };
+    }
+
+    pub fn has_tool(&self, kind: ToolKind) -> bool {
+        self.tools.iter().any(|t| t.kind == kind)
+    }
+
+    /// Make sure this orc has a `kind` of tool, taking one down from the rack
+    /// if need be. Returns whether it has one now.
+    pub fn take_tool(&mut self, kind: ToolKind, world: &mut World, log: &mut EventLog, tick: u64) -> bool {
+        if self.has_tool(kind) {
+            return true;
+        }
+        if !world.tool_rack.take(kind) {
+            return false;
+        }
+        self.tools.push(Tool::new(kind));
+        log.log_at(tick, EventCategory::General, format!("{} takes {} from the tool rack", self.name, kind.with_article()), Severity::Info, (self.x, self.y));
+        true
+    }
+
+    /// Use a tool once, throwing it away when it breaks
+    fn wear_tool(&mut self, kind: ToolKind, log: &mut EventLog, tick: u64) {
+        let Some(i) = self.tools.iter().position(|t| t.kind == kind) else {
+            return;
+        };
+        self.tools[i].uses_left = self.tools[i].uses_left.saturating_sub(1);
+        if self.tools[i].uses_left == 0 {
+            self.tools.remove(i);
+            log.log_at(tick, EventCategory::General, format!("{}'s {} breaks", self.name, kind.name()), Severity::Warning, (self.x, self.y));
+        }
+    }
+
+    /// Whether this orc fights whatever goes for it rather than run. An orc
+    /// in low spirits has the nerve of one a step more timid than it is.
+    pub fn stands_ground(&self) -> bool {
+        let courage = if self.has_trait(Trait::Brave) {
+            2
+        } else if self.has_trait(Trait::Timid) {
+            0
+        } else {
+            1
+        };
+        match courage - i32::from(self.morale < DISHEARTENED) {
+            2 => self.health >= BRAVE_FLEE_HEALTH,
+            1 => self.health >= FLEE_HEALTH,
+            _ => false,
+        }
+    }
+
+    /// Run for the safety of the campfire, which wolves won't come near
+    pub fn flee_to_camp(&mut self, world: &World, log: &mut EventLog, tick: u64) {
+        if matches!(self.activity, Activity::GoingTo { reason: FLEEING, .. }) {
+            return;
+        }
+        log.log_at(tick, EventCategory::Alert, format!("{} runs for the campfire", self.name), Severity::Danger, (self.x, self.y));
+        self.emote = Some(EmoteKind::Alarm);
+        let (cx, cy) = world.campfire_pos;
+        self.go_to(cx, cy, Purpose::Camp, FLEEING, world);
+    }
+
+    /// Hit back at the wolf at its throat, killing it or driving it off if the
+    /// blow is hard enough
+    pub fn strike(&mut self, wolf: &mut Animal, world: &mut World, rng: &mut impl Rng, log: &mut EventLog, tick: u64) {
+        self.activity = Activity::Fighting;
+        self.path.clear();
+        if matches!(self.set_upon(wolf, world, rng, log, tick), Blow::Routed(_) | Blow::Killed) {
+            self.activity = Activity::Idle;
+        }
+    }
+
+    /// Swing at the wolf within reach, killing it or driving it off if the
+    /// blow is hard enough
+    pub fn set_upon(&mut self, wolf: &mut Animal, world: &mut World, rng: &mut impl Rng, log: &mut EventLog, tick: u64) -> Blow {
+        let blow = self.fight(wolf, rng, log, tick);
+        match blow {
+            Blow::Missed => {}
+            Blow::Landed(_) => {
+                log.log_at(tick, EventCategory::Combat, format!("{} lands a blow on the wolf", self.name), Severity::Info, (self.x, self.y));
+            }
+            Blow::Routed(_) => {
+                wolf.rests_until = tick + WOLF_RESPITE;
+                log.log_at(tick, EventCategory::Combat, format!("{} drives the wolf off, and it slinks away wounded", self.name), Severity::Good, (self.x, self.y));
+            }
+            Blow::Killed => {
+                world.lay_carcass(wolf.x, wolf.y, wolf.kind, wolf.kind.meat_yield(), tick);
+                world.record(wolf.kind, Deed::Killed);
+                self.morale = (self.morale + 15.0).clamp(0.0, 100.0);
+                log.log_at(tick, EventCategory::Combat, format!("{} kills the wolf!", self.name), Severity::Good, (self.x, self.y));
+            }
+        }
+        blow
+    }
+
+    /// What this orc fights with: a spear if it has one, or else its fists
+    fn weapon(&self) -> &'static Weapon {
+        if self.has_tool(ToolKind::Spear) { &SPEAR } else { &FISTS }
+    }
+
+    /// Swing at `foe`, wearing down the spear if the blow lands
+    pub fn fight(&mut self, foe: &mut impl Combatant, rng: &mut impl Rng, log: &mut EventLog, tick: u64) -> Blow {
+        let blow = combat::attack(&*self, self.weapon(), foe, rng, tick);
+        if blow != Blow::Missed {
+            self.wear_tool(ToolKind::Spear, log, tick);
+        }
+        blow
+    }
+
+    /// Lose the quarry at arm's length, which bolts with the hunter still on its heels
+    /// Hurl the spear at `quarry`, `dist` tiles off: the nearer it is and the
+    /// better the hunter, the likelier the throw lands. A missed throw sends
+    /// the quarry bolting. The spear is left lying where the quarry stood, in
+    /// its carcass if the throw brought it down, and the hunter goes to get
+    /// it; a spear worn through shatters and is gone.
+    fn throw_spear(&mut self, quarry: &mut Animal, dist: usize, world: &mut World, rng: &mut impl Rng, log: &mut EventLog, tick: u64) {
+        let Some(i) = self.tools.iter().position(|t| t.kind == ToolKind::Spear) else {
+            return;
+        };
+        let mut spear = self.tools.remove(i);
+        spear.uses_left = spear.uses_left.saturating_sub(1);
+        let chance = THROW_AIM - dist.saturating_sub(2) as f64 * THROW_FALLOFF + self.hunt_skill as f64 / 100.0 * THROW_SKILL;
+        let throw = Weapon { chance, damage: SPEAR.damage.clone() };
+        let (sx, sy) = (quarry.x, quarry.y);
+        let kind = quarry.kind.name().to_lowercase();
+        self.threw = Some((sx, sy));
+        self.path.clear();
+        let blow = combat::attack(&*self, &throw, quarry, rng, tick);
+        let carcass = match blow {
+            Blow::Killed => {
+                self.hunt_skill = (self.hunt_skill + HUNT_PRACTICE).min(100.0);
+                log.log_at(tick, EventCategory::Hunt, format!("{} brings down a {} with a thrown spear!", self.name, kind), Severity::Good, (self.x, self.y));
+                quarry.kill(world, log, tick)
+            }
+            Blow::Landed(_) | Blow::Routed(_) => {
+                log.log_at(tick, EventCategory::Hunt, format!("{}'s spear wounds a {} but it breaks away", self.name, kind), Severity::Info, (self.x, self.y));
+                quarry.flee_from(self.x, self.y, world);
+                None
+            }
+            Blow::Missed => {
+                log.log_at(tick, EventCategory::Hunt, format!("{}'s spear flies wide and the {} bolts", self.name, kind), Severity::Info, (self.x, self.y));
+                quarry.flee_from(self.x, self.y, world);
+                None
+            }
+        };
+        if spear.uses_left == 0 {
+            log.log_at(tick, EventCategory::General, format!("{}'s spear shatters", self.name), Severity::Warning, (self.x, self.y));
+        } else {
+            self.thrown = Some((spear, sx, sy));
+        }
+        self.activity = match carcass {
+            Some((cx, cy)) => Activity::Hauling { x: cx, y: cy },
+            None if self.thrown.is_some() => Activity::Retrieving { x: sx, y: sy },
+            None => Activity::Idle,
+        };
+        if self.activity != Activity::Idle {
+            self.plan_path(sx, sy, world, false);
+        }
+    }
+
+    fn lose_quarry(&mut self, quarry: &mut Animal, world: &World, log: &mut EventLog, tick: u64) {
+        log.log_at(tick, EventCategory::Hunt, format!("{} lunges at a {} but it gets away", self.name, quarry.kind.name()), Severity::Info, (self.x, self.y));
+        quarry.flee_from(self.x, self.y, world);
+        self.path.clear();
+    }
+
+    /// Ticks this orc takes to fell a tree or break a rock
+    fn gather_ticks(&self, material: Material) -> u32 {
+        if material == Material::Wood && !self.has_tool(ToolKind::Axe) {
+            material.gather_ticks() * DEADWOOD_SLOWDOWN
+        } else {
+            material.gather_ticks()
+        }
+    }
+
+    /// Go and put in work on a fully stocked site
+    pub fn start_repairing(&mut self, x: usize, y: usize, world: &World) {
+        self.plan_path(x, y, world, false);
+        self.activity = Activity::Repairing { x, y };
+    }
+
+    /// Go after the goblin or wolf at (x, y), planning a way there afresh only once
+    /// it has moved well away from where the last plan led
+    pub fn defend(&mut self, x: usize, y: usize, world: &World) {
+        let stale = self.path.last().is_none_or(|&(px, py)| px.abs_diff(x).max(py.abs_diff(y)) > 2);
+        if stale || !matches!(self.activity, Activity::Defending { .. }) {
+            self.plan_path(x, y, world, false);
+        }
+        self.activity = Activity::Defending { x, y };
+    }
+
+    /// Go on watch at the watchtower at (x, y), or at the post there on the round
+    pub fn stand_guard(&mut self, x: usize, y: usize, tower: bool, world: &World) {
+        let (sx, sy) = if world.is_walkable(x, y) { (x, y) } else { world.walkable_neighbor(x, y, self.x, self.y).unwrap_or((x, y)) };
+        self.plan_path(sx, sy, world, false);
+        self.activity = Activity::Guarding { x, y, tower };
+    }
+
+    /// Make for a stop on the clan's march
+    pub fn march_to(&mut self, x: usize, y: usize, world: &World) {
+        self.plan_path(x, y, world, false);
+        self.activity = Activity::Marching { x, y };
+    }
+
+    /// Wait at the rally point with the rest of the clan
+    pub fn shelter_at(&mut self, x: usize, y: usize, world: &World) {
+        self.plan_path(x, y, world, false);
+        self.activity = Activity::Sheltering { x, y };
+    }
+
+    /// Make for where the party is headed
+    pub fn venture_to(&mut self, x: usize, y: usize, world: &World) {
+        self.plan_path(x, y, world, false);
+        self.activity = Activity::Expedition { x, y };
+    }
+
+    pub fn start_construction(&mut self, x: usize, y: usize, world: &World) {
+        self.plan_path(x, y, world, false);
+        self.activity = Activity::Constructing { x, y };
+    }
+
+    /// Whether this orc is fetching or carrying `material` for the site at (x, y)
+    pub fn supplies(&self, x: usize, y: usize, material: Material) -> bool {
+        match self.activity {
+            Activity::Gathering { material: m, site, .. } => m == material && site == (x, y),
+            Activity::Supplying { material: m, x: sx, y: sy } => m == material && (sx, sy) == (x, y),
+            _ => false,
+        }
+    }
+
+    /// Walk to a pile of meat on the ground to bring one unit back to the rack
+    pub fn start_haul(&mut self, x: usize, y: usize, world: &World) {
+        self.plan_path(x, y, world, false);
+        self.activity = Activity::Hauling { x, y };
+    }
+
+    /// Take a bucket to the pond or well at (x, y) to fill the camp's water barrel
+    pub fn start_fetching_water(&mut self, x: usize, y: usize, world: &World) {
+        self.plan_path(x, y, world, false);
+        self.activity = Activity::FetchingWater { x, y, full: false };
+    }
+
+    /// Shoulder one unit of meat (all an orc can carry) and head for the rack
+    fn pick_up_meat(&mut self, world: &World) {
+        self.carrying_food = true;
+        self.head_to_rack(world);
+    }
+
+    /// Take whatever the orc is carrying to the rack
+    pub fn head_to_rack(&mut self, world: &World) {
+        self.activity = Activity::CarryingMeat;
+        if let Some((mx, my)) = world.meat_rack_pos() {
+            self.plan_path(mx, my, world, false);
+        }
+    }
+
+    /// Head to the meat rack for a meal to feed the livestock around (x, y)
+    pub fn start_feeding(&mut self, x: usize, y: usize, world: &World) {
+        if let Some((mx, my)) = world.meat_rack_pos() {
+            self.plan_path(mx, my, world, false);
+        }
+        self.activity = Activity::Feeding { x, y, has_feed: false };
+    }
+
+    /// Go to slaughter a tame animal in the pens around (x, y)
+    pub fn start_butchering(&mut self, x: usize, y: usize, world: &World) {
+        self.plan_path(x, y, world, false);
+        self.activity = Activity::Butchering { x, y };
+    }
+
+    /// Go for the body of the orc called `name` at (x, y), to bury it
+    pub fn start_burial(&mut self, name: String, x: usize, y: usize, world: &World) {
+        self.plan_path(x, y, world, false);
+        self.activity = Activity::Burying { name, x, y, carrying: false };
+    }
+
+    /// Go to pick the herbs at (x, y)
+    pub fn pick_herbs(&mut self, x: usize, y: usize, world: &World) {
+        self.plan_path(x, y, world, false);
+        self.activity = Activity::PickingHerbs { x, y };
+    }
+
+    /// Take herbs to a wounded clanmate at (x, y) to dress their wounds
+    pub fn start_tending(&mut self, patient: String, x: usize, y: usize, world: &World) {
+        self.plan_path(x, y, world, false);
+        self.activity = Activity::Tending { patient, x, y };
+    }
+
+    /// Head to the meat rack to fetch a meal for a weak clanmate
+    pub fn start_food_delivery(&mut self, patient: String, x: usize, y: usize, world: &World) {
+        if let Some((mx, my)) = world.meat_rack_pos() {
+            self.plan_path(mx, my, world, false);
+        }
+        self.activity = Activity::DeliveringFood { patient, x, y, has_food: false };
+    }
+
+    /// Which of its needs wore its health away, the one taking most first
+    fn wasting_cause(&self) -> Cause {
+        if self.thirst >= WASTING {
+            Cause::Thirst
+        } else if self.hunger >= WASTING {
+            Cause::Hunger
+        } else if self.warmth <= FREEZING {
+            Cause::Cold
+        } else if self.energy <= 5.0 {
+            Cause::Exhaustion
+        } else {
+            Cause::Sickness
+        }
+    }
+
+    /// Know a clanmate who has been renamed from `old` by its new name
+    pub fn renamed(&mut self, old: &str, new: &str) {
+        let rename = |name: &mut String| {
+            if *name == old {
+                *name = new.to_string();
+            }
+        };
+        if let Some((mother, father)) = &mut self.parents {
+            rename(mother);
+            rename(father);
+        }
+        if let Some(carer) = &mut self.carer {
+            rename(carer);
+        }
+        if let Activity::DeliveringFood { patient, .. } | Activity::Tending { patient, .. } = &mut self.activity {
+            rename(patient);
+        }
+    }
+
+    /// Its needs as the diary reads them, in the order of `diary::NEEDS`
+    pub fn needs(&self) -> [f32; 6] {
+        [self.health, self.hunger, self.energy, self.thirst, self.warmth, self.morale]
+    }
+
+    /// The need at `need` in `diary::NEEDS`, to change
+    pub fn need_mut(&mut self, need: usize) -> &mut f32 {
+        match need {
+            0 => &mut self.health,
+            1 => &mut self.hunger,
+            2 => &mut self.energy,
+            3 => &mut self.thirst,
+            4 => &mut self.warmth,
+            _ => &mut self.morale,
+        }
+    }
+
+    /// The waypoints still ahead of it on the way it's going
+    pub fn path_ahead(&self) -> &[(usize, usize)] {
+        self.path.get(self.path_step..).unwrap_or_default()
+    }
+
+    /// Whole days lived as of `tick`
+    pub fn age_days(&self, tick: u64, day_length: u64) -> u64 {
+        (calendar::day_number(tick, day_length) as i64 - self.birth_day).max(0) as u64
+    }
+
+    /// Whether the day containing `tick` is one of this orc's birthdays
+    pub fn is_birthday(&self, tick: u64, day_length: u64) -> bool {
+        let age = self.age_days(tick, day_length);
+        age > 0 && age.is_multiple_of(calendar::YEAR_LENGTH)
+    }
+
+    /// Whether this orc was born into the clan and is still a babe in arms
+    pub fn is_infant(&self, tick: u64, day_length: u64) -> bool {
+        self.parents.is_some() && self.age_days(tick, day_length) < INFANCY_DAYS
+    }
+
+    /// Whether this orc is old enough to have children
+    pub fn is_adult(&self, tick: u64, day_length: u64) -> bool {
+        self.age_days(tick, day_length) >= ADULT_YEARS * calendar::YEAR_LENGTH
+    }
+
+    /// Let wounds knit a little while resting, faster while they're dressed
+    fn heal_wounds(&mut self, log: &mut EventLog, tick: u64) {
+        let rest = match self.activity {
+            Activity::Sleeping => SLEEP_HEALING,
+            Activity::Idle | Activity::Warming | Activity::Sheltering { .. } => REST_HEALING,
+            _ => 0.0,
+        };
+        let dressing = if self.dressed_ticks > 0 { DRESSED_HEALING } else { 1.0 };
+        self.dressed_ticks = self.dressed_ticks.saturating_sub(1);
+        if self.wounds <= 0.0 || rest == 0.0 {
+            return;
+        }
+        self.wounds = (self.wounds - rest * dressing).max(0.0);
+        if self.wounds == 0.0 {
+            log.log_at(tick, EventCategory::General, format!("{}'s wounds have healed", self.name), Severity::Info, (self.x, self.y));
+        }
+    }
+
+    /// Whether this orc is old enough to be one of the clan's elders
+    pub fn is_elder(&self, tick: u64, day_length: u64) -> bool {
+        self.age_days(tick, day_length) >= ELDER_YEARS * calendar::YEAR_LENGTH
+    }
+
+    pub fn has_trait(&self, t: Trait) -> bool {
+        self.traits.contains(&t)
+    }
+
+    /// Whether this orc follows the clan's sleep schedule
+    pub fn keeps_schedule(&self) -> bool {
+        !self.has_trait(Trait::NightOwl)
+    }
+
+    pub fn is_bedtime(&self, phase: DayPhase) -> bool {
+        phase == DayPhase::Night && self.keeps_schedule()
+    }
+
+    /// Needs serious enough to interrupt a night's sleep
+    pub fn has_pressing_need(&self, config: &AiConfig) -> bool {
+        self.thirst >= config.wake_thirst || self.hunger >= config.wake_hunger
+    }
+
+    pub fn turn_in(&mut self, log: &mut EventLog, tick: u64) {
+        log.log_at(tick, EventCategory::General, format!("{} turns in for the night", self.name), Severity::Info, (self.x, self.y));
+        self.emote = Some(EmoteKind::Sleepy);
+        self.activity = Activity::Sleeping;
+    }
+
+    /// Raw meat fills the belly but may turn it
+    fn eat_raw_meat(&mut self, rng: &mut impl Rng, log: &mut EventLog, tick: u64) {
+        if self.sick_ticks == 0 && rng.gen_bool(RAW_MEAT_SICKNESS_CHANCE) {
+            self.sick_ticks = 40;
+            self.morale = (self.morale - 10.0).clamp(0.0, 100.0);
+            log.log_at(tick, EventCategory::Alert, format!("{} feels sick from raw meat", self.name), Severity::Danger, (self.x, self.y));
+        }
+    }
+
+    /// Pick over the bush the orc is standing on, getting a little better at it.
+    /// Returns the skill it picked with, or None if it came away empty-handed.
+    fn pick_bush(&mut self, world: &mut World, rng: &mut impl Rng, log: &mut EventLog, tick: u64) -> Option<f32> {
+        let skill = self.forage_skill;
+        self.forage_skill = (skill + FORAGE_PRACTICE).min(100.0);
+        world.deplete_bush(self.x, self.y, tick);
+        self.wear_tool(ToolKind::Basket, log, tick);
+
+        // Novices trample the bush and can come away with nothing
+        let miss_chance = ((50.0 - skill) / 100.0).clamp(0.0, 1.0) as f64;
+        if rng.gen_bool(miss_chance) {
+            log.log_at(tick, EventCategory::General, format!("{} searches a bush but finds nothing ripe", self.name), Severity::Warning, (self.x, self.y));
+            self.emote = Some(EmoteKind::Confused);
+            self.activity = Activity::Idle;
+            return None;
+        }
+        Some(skill)
+    }
+
+    /// Pick a bush; how well it goes depends on how practised the orc is
+    fn forage_bush(&mut self, world: &mut World, rng: &mut impl Rng, log: &mut EventLog, tick: u64) {
+        let Some(skill) = self.pick_bush(world, rng, log, tick) else {
+            return;
+        };
+
+        log.log_at(tick, EventCategory::General, format!("{} found berries and starts eating", self.name), Severity::Good, (self.x, self.y));
+        self.activity = Activity::Eating;
+        if skill < NOVICE_FORAGER && self.sick_ticks == 0 && rng.gen_bool(0.2) {
+            self.sick_ticks = 15;
+            log.log_at(tick, EventCategory::Alert, format!("{} ate a bad berry and feels queasy", self.name), Severity::Danger, (self.x, self.y));
+        }
+
+        // Skilled foragers pocket extra berries for the stockpile
+        let gathered = spare_berries(skill).min(BERRY_POUCH - self.berries);
+        if gathered > 0 {
+            self.berries += gathered;
+            log.log_at(tick, EventCategory::Food, format!("{} pockets extra berries for the stockpile ({}/{})", self.name, self.berries, BERRY_POUCH), Severity::Info, (self.x, self.y));
+        }
+    }
+
+    /// Pick a marked bush for the stockpile rather than to eat
+    fn harvest_bush(&mut self, world: &mut World, rng: &mut impl Rng, log: &mut EventLog, tick: u64) {
+        if world.get(self.x, self.y) != Terrain::Bush {
+            // Someone else got here first
+            self.activity = Activity::Idle;
+            return;
+        }
+        let Some(skill) = self.pick_bush(world, rng, log, tick) else {
+            return;
+        };
+        let basket = if self.has_tool(ToolKind::Basket) { BASKET_BONUS } else { 0 };
+        let picked = (1 + spare_berries(skill) + basket).min(BERRY_POUCH.saturating_sub(self.berries));
+        self.berries += picked;
+        log.log_at(tick, EventCategory::Food, format!("{} picks berries for the stockpile ({}/{})", self.name, self.berries, BERRY_POUCH), Severity::Info, (self.x, self.y));
+        self.head_to_rack(world);
+    }
+
+    /// Act on whatever the orc set out to do once it reaches its destination
+    fn arrive_at_destination(&mut self, purpose: Purpose, world: &mut World, rng: &mut impl Rng, log: &mut EventLog, tick: u64, phase: DayPhase) {
+        match purpose {
+            Purpose::Drink if self.is_adjacent_to_water(world) => {
+                log.log_at(tick, EventCategory::General, format!("{} drinks water", self.name), Severity::Info, (self.x, self.y));
+                self.activity = Activity::Drinking;
+            }
+            Purpose::Drink if world.get(self.x, self.y) == Terrain::WaterBarrel && world.water_barrel > 0 => {
+                world.water_barrel -= 1;
+                log.log_at(tick, EventCategory::General, format!("{} drinks from the water barrel", self.name), Severity::Info, (self.x, self.y));
+                self.activity = Activity::Drinking;
+            }
+            Purpose::Eat => self.eat_here(world, rng, log, tick),
+            Purpose::Harvest => self.harvest_bush(world, rng, log, tick),
+            Purpose::Sleep | Purpose::Camp if self.is_bedtime(phase) => self.turn_in(log, tick),
+            Purpose::Sleep => self.lie_down(log, tick),
+            Purpose::Camp if self.energy < 50.0 => self.lie_down(log, tick),
+            Purpose::Camp if !phase.is_daylight() && self.near_campfire(world) => {
+                log.log_at(tick, EventCategory::General, format!("{} sits down by the fire", self.name), Severity::Info, (self.x, self.y));
+                self.activity = Activity::Warming;
+            }
+            _ => self.activity = Activity::Idle,
+        }
+    }
+
+    /// Eat from whatever food source the orc is standing on
+    fn eat_here(&mut self, world: &mut World, rng: &mut impl Rng, log: &mut EventLog, tick: u64) {
+        let terrain = world.get(self.x, self.y);
+
+        if world.take_food(self.x, self.y) {
+            log.log_at(tick, EventCategory::General, format!("{} found food and starts eating", self.name), Severity::Good, (self.x, self.y));
+            self.activity = Activity::Eating;
+        } else if terrain == Terrain::Bush {
+            self.forage_bush(world, rng, log, tick);
+        } else if terrain == Terrain::Carcass && world.butcher(self.x, self.y) {
+            log.log_at(tick, EventCategory::General, format!("{} cuts meat off a carcass and starts eating", self.name), Severity::Good, (self.x, self.y));
+            self.activity = Activity::Eating;
+        } else if terrain == Terrain::FruitTree {
+            world.set(self.x, self.y, Terrain::BareFruitTree);
+            self.hunger = (self.hunger - FRUIT_NUTRITION).clamp(0.0, 100.0);
+            self.morale = (self.morale + 5.0).clamp(0.0, 100.0);
+            log.log_at(tick, EventCategory::General, format!("{} picks ripe fruit and starts eating", self.name), Severity::Info, (self.x, self.y));
+            self.activity = Activity::Eating;
+        } else if terrain == Terrain::Mushroom {
+            self.eat_mushroom(world, rng, log, tick);
+        } else if terrain == Terrain::Tree {
+            log.log_at(tick, EventCategory::General, format!("{} forages from a tree", self.name), Severity::Good, (self.x, self.y));
+            self.activity = Activity::Eating;
+        } else if terrain == Terrain::MeatRack && world.cooked_stockpile > 0 {
+            world.cooked_stockpile -= 1;
+            world.wear_rack();
+            self.hunger = (self.hunger - 20.0).clamp(0.0, 100.0); // cooked meals go further
+            self.morale = (self.morale + 10.0).clamp(0.0, 100.0);
+            log.log_at(tick, EventCategory::Food, format!("{} enjoys a cooked meal (left: {})", self.name, world.cooked_stockpile), Severity::Info, (self.x, self.y));
+            self.activity = Activity::Eating;
+        } else if terrain == Terrain::MeatRack && world.berry_stockpile > 0 {
+            world.berry_stockpile -= 1;
+            world.wear_rack();
+            log.log_at(tick, EventCategory::Food, format!("{} eats berries from the stockpile (left: {})", self.name, world.berry_stockpile), Severity::Info, (self.x, self.y));
+            self.activity = Activity::Eating;
+        } else if terrain == Terrain::MeatRack && world.take_meat() {
+            log.log_at(tick, EventCategory::Food, format!("{} takes food from stockpile (left: {})", self.name, world.food_stockpile), Severity::Info, (self.x, self.y));
+            self.eat_raw_meat(rng, log, tick);
+            self.activity = Activity::Eating;
+        } else {
+            // Someone else got here first
+            self.activity = Activity::Idle;
+        }
+    }
+
+    /// Some wild mushrooms are toadstools, which only a practised forager can spot
+    fn eat_mushroom(&mut self, world: &mut World, rng: &mut impl Rng, log: &mut EventLog, tick: u64) {
+        world.set(self.x, self.y, Terrain::Grass);
+        let skill = self.forage_skill;
+        self.forage_skill = (skill + FORAGE_PRACTICE).min(100.0);
+        if !rng.gen_bool(TOADSTOOL_CHANCE) {
+            log.log_at(tick, EventCategory::General, format!("{} finds mushrooms and starts eating", self.name), Severity::Info, (self.x, self.y));
+            self.activity = Activity::Eating;
+        } else if skill >= MUSHROOM_EXPERT {
+            log.log_at(tick, EventCategory::General, format!("{} spots a toadstool and throws it away", self.name), Severity::Warning, (self.x, self.y));
+            self.activity = Activity::Idle;
+        } else {
+            self.sick_ticks = self.sick_ticks.max(60);
+            self.health = (self.health - 15.0).clamp(0.0, 100.0);
+            self.morale = (self.morale - 10.0).clamp(0.0, 100.0);
+            log.log_at(tick, EventCategory::Alert, format!("{} ate a poisonous mushroom!", self.name), Severity::Danger, (self.x, self.y));
+            self.activity = Activity::Eating;
+        }
+    }
+
+    /// Go to bed in this orc's hut
+    pub fn head_to_hut(&mut self, world: &World) -> bool {
+        let Some((hx, hy)) = self.hut else {
+            return false;
+        };
+        self.go_to(hx, hy, Purpose::Sleep, "Going to bed", world);
+        true
+    }
+
+    /// Standing inside a hut, out of the weather
+    pub fn sheltered(&self, world: &World) -> bool {
+        world.get(self.x, self.y) == Terrain::Built(Building::Hut)
+    }
+
+    pub fn in_own_hut(&self) -> bool {
+        self.hut == Some((self.x, self.y))
+    }
+
+    fn lie_down(&mut self, log: &mut EventLog, tick: u64) {
+        let place = if self.in_own_hut() { "in the hut" } else { "by the fire" };
+        log.log_at(tick, EventCategory::General, format!("{} lies down to sleep {}", self.name, place), Severity::Info, (self.x, self.y));
+        self.emote = Some(EmoteKind::Sleepy);
+        self.activity = Activity::Sleeping;
+    }
+
+    /// Amble around near camp, picking a new spot every few idle ticks
+    pub fn wander(&mut self, world: &World, rng: &mut impl Rng) {
+        let (cx, cy) = world.campfire_pos;
+        self.idle_ticks += 1;
+        if self.idle_ticks > 3 {
+            self.idle_ticks = 0;
+            let max_dist: i32 = 30;
+            let nx = (self.x as i32 + rng.gen_range(-4..=4))
+                .clamp(cx as i32 - max_dist, cx as i32 + max_dist)
+                .clamp(0, MAP_WIDTH as i32 - 1) as usize;
+            let ny = (self.y as i32 + rng.gen_range(-4..=4))
+                .clamp(cy as i32 - max_dist, cy as i32 + max_dist)
+                .clamp(0, MAP_HEIGHT as i32 - 1) as usize;
+            if world.is_walkable(nx, ny) {
+                self.go_to(nx, ny, Purpose::Wander, "Wandering", world);
+            }
+        }
+    }
+
+    /// Set an activity that may be GoingTo or Hunting, computing path if needed
+    pub fn set_activity_with_path(&mut self, activity: Activity, world: &World) {
+        match &activity {
+            Activity::GoingTo { x, y, .. } => {
+                let (tx, ty) = (*x, *y);
+                let allow_tree = matches!(world.get(tx, ty), Terrain::Tree | Terrain::Bush);
+                self.plan_path(tx, ty, world, allow_tree);
+            }
+            Activity::Hunting { .. } => {
+                // Hunting paths are recomputed dynamically since the target moves
+                self.path.clear();
+                self.path_step = 0;
+            }
+            _ => {}
+        }
+        self.activity = activity;
+    }
+
+    pub fn find_food_target(&self, world: &World, animals: &[Animal], phase: DayPhase) -> Option<Activity> {
+        if world.stockpiled_meals() > 0
+            && let Some((mx, my)) = world.meat_rack_pos()
+        {
+            return Some(Activity::GoingTo {
+                x: mx, y: my,
+                purpose: Purpose::Eat,
+                reason: "Going to stockpile",
+            });
+        }
+
+        let bush = world.find_nearest_unclaimed(self.x, self.y, Terrain::Bush);
+        let food = world.nearest_unclaimed_food(self.x, self.y);
+        let carcass = world.find_nearest_unclaimed(self.x, self.y, Terrain::Carcass);
         let tree = world.find_nearest(self.x, self.y, Terrain::Tree);
+        let fruit = world.find_nearest_unclaimed(self.x, self.y, Terrain::FruitTree);
+        let mushroom = world.find_nearest_unclaimed(self.x, self.y, Terrain::Mushroom);
 
         let mut best: Option<(usize, usize, usize)> = None;
-        for target in [bush, food, tree].iter().flatten() {
+        for target in [bush, food, carcass, tree, fruit, mushroom].iter().flatten() {
             let dist = self.x.abs_diff(target.0) + self.y.abs_diff(target.1);
             if best.is_none() || dist < best.unwrap().2 {
                 best = Some((target.0, target.1, dist));
             }
         }
 
+        // Bedded animals are easier prey, so an alert one has to be much closer to be worth it
         let nearest_animal = animals.iter().enumerate()
-            .filter(|(_, a)| a.alive)
-            .min_by_key(|(_, a)| self.x.abs_diff(a.x) + self.y.abs_diff(a.y));
+            .filter(|(_, a)| a.alive && !a.claimed && a.kind.is_game() && a.tame.is_none())
+            .min_by_key(|(_, a)| {
+                let alert_penalty = if a.kind.is_active(phase) { 8 } else { 0 };
+                self.x.abs_diff(a.x) + self.y.abs_diff(a.y) + alert_penalty
+            });
 
         if let Some((idx, animal)) = nearest_animal {
             let animal_dist = self.x.abs_diff(animal.x) + self.y.abs_diff(animal.y);
@@ -436,21 +2107,37 @@ impl Orc {
 
         best.map(|(x, y, _)| Activity::GoingTo {
             x, y,
-            reason: "Looking for food".to_string(),
+            purpose: Purpose::Eat,
+            reason: "Looking for food",
         })
     }
 
+    pub fn near_campfire(&self, world: &World) -> bool {
+        let (cx, cy) = world.campfire_pos;
+        self.x.abs_diff(cx).max(self.y.abs_diff(cy)) <= CAMPFIRE_WARMTH_RADIUS
+    }
+
     fn is_adjacent_to_water(&self, world: &World) -> bool {
         let neighbors = [(0i32, 1i32), (0, -1), (1, 0), (-1, 0)];
         neighbors.iter().any(|&(dx, dy)| {
             let nx = (self.x as i32 + dx).clamp(0, MAP_WIDTH as i32 - 1) as usize;
             let ny = (self.y as i32 + dy).clamp(0, MAP_HEIGHT as i32 - 1) as usize;
-            world.get(nx, ny) == Terrain::Water
+            world.get(nx, ny).holds_water()
         })
     }
 
-    /// Greedy fallback when A* path is unavailable or exhausted
-    fn move_toward_greedy(&mut self, tx: usize, ty: usize, world: &World, rng: &mut impl Rng) {
+    /// Greedy fallback when A* path is unavailable or exhausted. Returns false
+    /// if every step toward the target is blocked.
+    fn move_toward_greedy(&mut self, tx: usize, ty: usize, world: &World, rng: &mut impl Rng) -> bool {
+        // Catch up on planning that was put off, if there is budget for it now
+        if self.path_deferred {
+            let allow_tree = matches!(world.get(tx, ty), Terrain::Tree | Terrain::Bush);
+            self.plan_path(tx, ty, world, allow_tree);
+            if self.follow_path() {
+                return true;
+            }
+        }
+
         let dx = (tx as i32 - self.x as i32).signum();
         let dy = (ty as i32 - self.y as i32).signum();
 
@@ -466,15 +2153,16 @@ impl Orc {
             }
             let nx = (self.x as i32 + cdx).clamp(0, MAP_WIDTH as i32 - 1) as usize;
             let ny = (self.y as i32 + cdy).clamp(0, MAP_HEIGHT as i32 - 1) as usize;
-            if world.is_walkable(nx, ny) || world.get(nx, ny) == Terrain::Tree {
+            if (world.is_walkable(nx, ny) || world.get(nx, ny) == Terrain::Tree) && !world.cuts_corner(self.x, self.y, nx, ny, Terrain::walkable) {
                 self.x = nx;
                 self.y = ny;
-                return;
+                return true;
             }
         }
+        false
     }
 
-    fn find_spot_near(&self, cx: usize, cy: usize, world: &World, rng: &mut impl Rng) -> (usize, usize) {
+    pub fn find_spot_near(&self, cx: usize, cy: usize, world: &World, rng: &mut impl Rng) -> (usize, usize) {
         for _ in 0..20 {
             let x = (cx as i32 + rng.gen_range(-2..=2)).clamp(0, MAP_WIDTH as i32 - 1) as usize;
             let y = (cy as i32 + rng.gen_range(-2..=2)).clamp(0, MAP_HEIGHT as i32 - 1) as usize;
@@ -486,16 +2174,62 @@ impl Orc {
     }
 }
 
+/// Tile or prey this orc is currently heading for, if it is a food source or
+/// material that no other orc should also pursue.
+fn claimable_target(activity: &Activity, world: &World) -> Option<Claim> {
+    match activity {
+        Activity::GoingTo { x, y, purpose: Purpose::Eat, .. }
+            if world.food_at(*x, *y) > 0 || matches!(world.get(*x, *y), Terrain::Bush | Terrain::Carcass | Terrain::FruitTree | Terrain::Mushroom) =>
+        {
+            Some(Claim::Tile(*x, *y))
+        }
+        Activity::GoingTo { x, y, purpose: Purpose::Harvest, .. } => Some(Claim::Tile(*x, *y)),
+        Activity::Hunting { target_idx } => Some(Claim::Animal(*target_idx)),
+        Activity::Cooking { .. } => Some(Claim::Tile(world.campfire_pos.0, world.campfire_pos.1)),
+        Activity::Gathering { x, y, .. } => Some(Claim::Tile(*x, *y)),
+        Activity::PickingHerbs { x, y } => Some(Claim::Tile(*x, *y)),
+        Activity::Burying { x, y, carrying: true, .. } => Some(Claim::Tile(*x, *y)),
+        _ => None,
+    }
+}
+
+enum Claim {
+    Tile(usize, usize),
+    Animal(usize),
+}
+
+/// Reserve a target so other orcs pick something else
+pub fn claim_target(activity: &Activity, world: &mut World, animals: &mut [Animal]) {
+    match claimable_target(activity, world) {
+        Some(Claim::Tile(x, y)) => {
+            world.claimed_tiles.insert((x, y));
+        }
+        Some(Claim::Animal(idx)) => {
+            if let Some(animal) = animals.get_mut(idx) {
+                animal.claimed = true;
+            }
+        }
+        None => {}
+    }
+}
+
+/// Berries a forager of this skill can pick beyond what it needs
+fn spare_berries(skill: f32) -> u32 {
+    if skill >= 90.0 { 2 } else if skill >= 60.0 { 1 } else { 0 }
+}
+
 pub fn pick_name(rng: &mut impl Rng, existing: &[String]) -> String {
     let available: Vec<&&str> = ORC_NAMES.iter().filter(|n| !existing.iter().any(|e| e == **n)).collect();
     if available.is_empty() {
         let prefix = ["Gr", "Th", "Kr", "Br", "Dr", "Sk", "Zn", "Gl"];
         let suffix = ["ok", "ag", "ug", "ak", "im", "oz", "ur", "ash"];
-        format!(
+        let name = format!(
             "{}{}",
             prefix[rng.gen_range(0..prefix.len())],
             suffix[rng.gen_range(0..suffix.len())]
-        )
+        );
+        // Names identify orcs, so a big clan starts numbering repeats
+        if existing.contains(&name) { format!("{} {}", name, existing.len() + 1) } else { name }
     } else {
         available[rng.gen_range(0..available.len())].to_string()
     }
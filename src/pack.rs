@@ -0,0 +1,41 @@
+//! Wolves run in packs that hunt together.
+
+use std::ops::RangeInclusive;
+
+use crate::animal::Animal;
+
+/// Wolves in a newly spawned pack
+pub const PACK_SIZE: RangeInclusive<usize> = 2..=3;
+/// How many tiles nearer a quarry counts for each point (out of 100) of
+/// hunger, thirst or harm wearing it down
+const WEAKNESS_WEIGHT: f32 = 0.05;
+
+#[derive(Default)]
+pub struct Packs {
+    next_id: u32,
+}
+
+impl Packs {
+    /// Start a new pack, and return its id
+    pub fn form(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+}
+
+/// The wolf leading pack `id`: the healthiest of those still alive, the
+/// first of them on a tie
+pub fn leader(animals: &[Animal], id: u32) -> Option<usize> {
+    animals.iter()
+        .enumerate()
+        .filter(|(_, a)| a.alive && a.pack == Some(id))
+        .max_by(|(i, a), (j, b)| a.health.total_cmp(&b.health).then(j.cmp(i)))
+        .map(|(i, _)| i)
+}
+
+/// How a quarry `dist` tiles off and worn down by `weakness` (0-100) ranks
+/// with the leader; the lowest is the one it goes for
+pub fn rank(dist: usize, weakness: f32) -> f32 {
+    dist as f32 - weakness * WEAKNESS_WEIGHT
+}
@@ -1,8 +1,166 @@
 use std::collections::BinaryHeap;
 use std::cmp::Ordering;
+use std::sync::atomic::{self, AtomicU32, AtomicUsize};
+use std::sync::{Mutex, PoisonError};
 
+use crate::animal;
 use crate::world::{MAP_HEIGHT, MAP_WIDTH, Terrain, World};
 
+/// Most nodes a single search may expand before giving up on the target
+const MAX_SEARCH: usize = 5000;
+
+/// Who a path is for, which decides the ground it may cross
+#[derive(Clone, Copy)]
+enum Traveller {
+    /// An orc, who may also be let onto tree tiles (for foraging)
+    Orc { allow_tree: bool },
+    /// An animal, which keeps off anything built, and for a wolf, out of the
+    /// light of a burning campfire
+    Wildlife { shuns_fire: bool },
+}
+
+impl Traveller {
+    fn passable(&self, world: &World, x: usize, y: usize) -> bool {
+        match self {
+            Traveller::Orc { allow_tree } => world.is_walkable(x, y) || (*allow_tree && world.get(x, y) == Terrain::Tree),
+            Traveller::Wildlife { shuns_fire } => {
+                world.is_open_to_wildlife(x, y) && !(*shuns_fire && world.fire_lit() && animal::near_fire(world, x, y))
+            }
+        }
+    }
+
+    fn corner_open(&self) -> fn(&Terrain) -> bool {
+        match self {
+            Traveller::Orc { .. } => Terrain::walkable,
+            Traveller::Wildlife { .. } => Terrain::open_to_wildlife,
+        }
+    }
+}
+
+/// Nodes A* may expand per tick, shared by every search that tick, so a
+/// crowd of orcs replanning at once can't stall the simulation. Searches
+/// over budget are put off until a later tick. The counts are atomics, not
+/// cells, so the world can be shared between threads.
+pub struct PathBudget {
+    remaining: AtomicUsize,
+    searches: AtomicU32,
+    nodes: AtomicUsize,
+    deferred: AtomicU32,
+}
+
+impl Default for PathBudget {
+    fn default() -> Self {
+        PathBudget {
+            remaining: AtomicUsize::new(usize::MAX),
+            searches: AtomicU32::new(0),
+            nodes: AtomicUsize::new(0),
+            deferred: AtomicU32::new(0),
+        }
+    }
+}
+
+// Only ever touched by the thread ticking the world, so the order of updates doesn't matter
+const RELAXED: atomic::Ordering = atomic::Ordering::Relaxed;
+
+impl PathBudget {
+    /// Start a new tick with `nodes` to spend
+    pub fn reset(&self, nodes: usize) {
+        self.remaining.store(nodes, RELAXED);
+        self.searches.store(0, RELAXED);
+        self.nodes.store(0, RELAXED);
+        self.deferred.store(0, RELAXED);
+    }
+
+    /// Whether this tick's nodes have all been spent
+    pub fn exhausted(&self) -> bool {
+        self.remaining.load(RELAXED) == 0
+    }
+
+    pub fn defer(&self) {
+        self.deferred.fetch_add(1, RELAXED);
+    }
+
+    fn spend(&self, nodes: usize) {
+        let remaining = self.remaining.load(RELAXED);
+        self.remaining.store(remaining.saturating_sub(nodes), RELAXED);
+        self.searches.fetch_add(1, RELAXED);
+        self.nodes.fetch_add(nodes, RELAXED);
+    }
+
+    /// Searches run this tick
+    pub fn searches(&self) -> u32 {
+        self.searches.load(RELAXED)
+    }
+
+    /// Nodes expanded this tick
+    pub fn nodes(&self) -> usize {
+        self.nodes.load(RELAXED)
+    }
+
+    /// Searches put off this tick
+    pub fn deferred(&self) -> u32 {
+        self.deferred.load(RELAXED)
+    }
+}
+
+/// The scratch arrays A* works in, kept on the world and used again by
+/// every search rather than allocated afresh for each. A tile's entries only
+/// count if they were written in the current search's generation, so
+/// starting a search is a matter of moving the generation on, not clearing
+/// the map. Behind a mutex for the same reason the budget is atomic.
+#[derive(Default)]
+pub struct PathfindingContext {
+    scratch: Mutex<Scratch>,
+}
+
+/// Tiles are indexed `y * MAP_WIDTH + x`
+#[derive(Default)]
+struct Scratch {
+    generation: u32,
+    /// The generation each tile's cost and step back were last set in
+    reached: Vec<u32>,
+    /// The generation each tile was last expanded in
+    closed: Vec<u32>,
+    g_cost: Vec<usize>,
+    came_from: Vec<(usize, usize)>,
+    open: BinaryHeap<Node>,
+}
+
+impl Scratch {
+    /// Make ready for a new search, forgetting the last
+    fn begin(&mut self) {
+        if self.reached.is_empty() {
+            self.reached = vec![0; MAP_WIDTH * MAP_HEIGHT];
+            self.closed = vec![0; MAP_WIDTH * MAP_HEIGHT];
+            self.g_cost = vec![usize::MAX; MAP_WIDTH * MAP_HEIGHT];
+            self.came_from = vec![(0, 0); MAP_WIDTH * MAP_HEIGHT];
+        }
+        self.generation = self.generation.wrapping_add(1);
+        // Once in four billion searches the count comes round, and a stamp
+        // from long ago could pass for this search's
+        if self.generation == 0 {
+            self.reached.fill(0);
+            self.closed.fill(0);
+            self.generation = 1;
+        }
+        self.open.clear();
+    }
+
+    fn cost(&self, i: usize) -> usize {
+        if self.reached[i] == self.generation { self.g_cost[i] } else { usize::MAX }
+    }
+
+    fn reach(&mut self, i: usize, cost: usize, from: (usize, usize)) {
+        self.reached[i] = self.generation;
+        self.g_cost[i] = cost;
+        self.came_from[i] = from;
+    }
+
+    fn is_closed(&self, i: usize) -> bool {
+        self.closed[i] == self.generation
+    }
+}
+
 #[derive(Clone, Eq, PartialEq)]
 struct Node {
     x: usize,
@@ -26,7 +184,8 @@ impl PartialOrd for Node {
 /// A* pathfinding from (sx, sy) to (gx, gy).
 /// Returns a list of (x, y) waypoints excluding the start, including the goal.
 /// `allow_tree` lets orcs walk onto tree tiles (for foraging).
-/// Max search limit prevents lag on unreachable targets.
+/// Max search limit prevents lag on unreachable targets; nodes expanded are
+/// charged to the world's `PathBudget`.
 pub fn find_path(
     world: &World,
     sx: usize,
@@ -38,16 +197,87 @@ pub fn find_path(
     if sx == gx && sy == gy {
         return Some(vec![]);
     }
+    let budget = &world.path_budget;
+    let (path, searched) = search(world, sx, sy, gx, gy, Traveller::Orc { allow_tree }, MAX_SEARCH.min(budget.remaining.load(RELAXED)));
+    budget.spend(searched);
+    path
+}
 
-    let max_search = 5000; // limit to prevent lag on huge maps
-    let mut visited = vec![vec![false; MAP_WIDTH]; MAP_HEIGHT];
-    let mut came_from = vec![vec![(0usize, 0usize); MAP_WIDTH]; MAP_HEIGHT];
-    let mut g_cost = vec![vec![usize::MAX; MAP_WIDTH]; MAP_HEIGHT];
+/// A* for an animal, over ground open to wildlife, giving up after
+/// `max_search` nodes; a hunting animal's quarry is never far, so there's no
+/// point searching as widely as an orc does. Charged to the same `PathBudget`.
+pub fn find_wildlife_path(
+    world: &World,
+    sx: usize,
+    sy: usize,
+    gx: usize,
+    gy: usize,
+    shuns_fire: bool,
+    max_search: usize,
+) -> Option<Vec<(usize, usize)>> {
+    if sx == gx && sy == gy {
+        return Some(vec![]);
+    }
+    let budget = &world.path_budget;
+    let (path, searched) = search(world, sx, sy, gx, gy, Traveller::Wildlife { shuns_fire }, max_search.min(budget.remaining.load(RELAXED)));
+    budget.spend(searched);
+    path
+}
+
+/// The way somebody from outside the clan is walking, goblin or rival orc,
+/// kept from one tick to the next and planned afresh for each new goal
+#[derive(Default)]
+pub struct Route {
+    path: Vec<(usize, usize)>,
+    /// Where the path was planned to, found or not
+    planned_for: Option<(usize, usize)>,
+}
 
-    let mut open = BinaryHeap::new();
+impl Route {
+    /// The next step from (x, y) towards (tx, ty), along a path planned once
+    /// for each new goal, and straight at it when there was no path to be
+    /// had. Planning waits for a tick with pathfinding budget to spare.
+    pub fn step(&mut self, (x, y): (usize, usize), (tx, ty): (usize, usize), world: &World) -> (usize, usize) {
+        if self.planned_for != Some((tx, ty)) {
+            if world.path_budget.exhausted() {
+                world.path_budget.defer();
+            } else {
+                self.path = find_path(world, x, y, tx, ty, false).unwrap_or_default();
+                self.planned_for = Some((tx, ty));
+            }
+        }
+        if !self.path.is_empty() {
+            return self.path.remove(0);
+        }
+        let (dx, dy) = ((tx as i32 - x as i32).signum(), (ty as i32 - y as i32).signum());
+        for (sx, sy) in [(dx, dy), (dx, 0), (0, dy)] {
+            let (nx, ny) = ((x as i32 + sx) as usize, (y as i32 + sy) as usize);
+            if (sx, sy) != (0, 0) && world.is_walkable(nx, ny) && !world.cuts_corner(x, y, nx, ny, Terrain::walkable) {
+                return (nx, ny);
+            }
+        }
+        (x, y)
+    }
+}
 
-    g_cost[sy][sx] = 0;
-    open.push(Node {
+/// Search in the world's scratch arrays, returning the path if there is one
+/// and the nodes expanded
+fn search(
+    world: &World,
+    sx: usize,
+    sy: usize,
+    gx: usize,
+    gy: usize,
+    traveller: Traveller,
+    max_search: usize,
+) -> (Option<Vec<(usize, usize)>>, usize) {
+    let idx = |x: usize, y: usize| y * MAP_WIDTH + x;
+    let mut scratch = world.path_context.scratch.lock().unwrap_or_else(PoisonError::into_inner);
+    let scratch = &mut *scratch;
+    scratch.begin();
+
+    scratch.reach(idx(sx, sy), 0, (sx, sy));
+    scratch.open.push(Node {
         x: sx,
         y: sy,
         cost: 0,
@@ -56,19 +286,19 @@ pub fn find_path(
 
     let mut searched = 0;
 
-    while let Some(current) = open.pop() {
+    while let Some(current) = scratch.open.pop() {
         if current.x == gx && current.y == gy {
-            return Some(reconstruct_path(&came_from, sx, sy, gx, gy));
+            return (Some(reconstruct_path(&scratch.came_from, sx, sy, gx, gy)), searched);
         }
 
-        if visited[current.y][current.x] {
+        if scratch.is_closed(idx(current.x, current.y)) {
             continue;
         }
-        visited[current.y][current.x] = true;
+        scratch.closed[idx(current.x, current.y)] = scratch.generation;
 
         searched += 1;
         if searched > max_search {
-            return None;
+            return (None, searched);
         }
 
         // 8-directional neighbors
@@ -87,28 +317,26 @@ pub fn find_path(
             let nx = nx as usize;
             let ny = ny as usize;
 
-            if visited[ny][nx] {
+            if scratch.is_closed(idx(nx, ny)) {
                 continue;
             }
 
             // Check walkability (goal tile is always allowed)
             let is_goal = nx == gx && ny == gy;
-            if !is_goal {
-                let terrain = world.get(nx, ny);
-                let passable = world.is_walkable(nx, ny) || (allow_tree && terrain == Terrain::Tree);
-                if !passable {
-                    continue;
-                }
+            if !is_goal && !traveller.passable(world, nx, ny) {
+                continue;
+            }
+            if world.cuts_corner(current.x, current.y, nx, ny, traveller.corner_open()) {
+                continue;
             }
 
             // Diagonal movement costs more
             let move_cost = if dx != 0 && dy != 0 { 14 } else { 10 };
             let new_cost = current.cost + move_cost;
 
-            if new_cost < g_cost[ny][nx] {
-                g_cost[ny][nx] = new_cost;
-                came_from[ny][nx] = (current.x, current.y);
-                open.push(Node {
+            if new_cost < scratch.cost(idx(nx, ny)) {
+                scratch.reach(idx(nx, ny), new_cost, (current.x, current.y));
+                scratch.open.push(Node {
                     x: nx,
                     y: ny,
                     cost: new_cost,
@@ -118,7 +346,7 @@ pub fn find_path(
         }
     }
 
-    None // no path found
+    (None, searched) // no path found
 }
 
 fn heuristic(x: usize, y: usize, gx: usize, gy: usize) -> usize {
@@ -131,7 +359,7 @@ fn heuristic(x: usize, y: usize, gx: usize, gy: usize) -> usize {
 }
 
 fn reconstruct_path(
-    came_from: &[Vec<(usize, usize)>],
+    came_from: &[(usize, usize)],
     sx: usize,
     sy: usize,
     gx: usize,
@@ -143,7 +371,7 @@ fn reconstruct_path(
 
     while cx != sx || cy != sy {
         path.push((cx, cy));
-        let (px, py) = came_from[cy][cx];
+        let (px, py) = came_from[cy * MAP_WIDTH + cx];
         cx = px;
         cy = py;
     }
@@ -0,0 +1,35 @@
+//! Timings and counters for the performance HUD.
+
+use std::time::Duration;
+
+/// Weight given to the newest sample when smoothing timings
+const SMOOTHING: f64 = 0.1;
+
+#[derive(Default)]
+pub struct PerfStats {
+    /// Milliseconds spent simulating one tick
+    pub tick_ms: f64,
+    /// Milliseconds spent drawing one frame
+    pub frame_ms: f64,
+    /// A* searches run last tick
+    pub path_searches: u32,
+    /// Nodes those searches expanded
+    pub path_nodes: usize,
+    /// Searches skipped last tick because the budget ran out
+    pub paths_deferred: u32,
+}
+
+impl PerfStats {
+    pub fn record_tick(&mut self, elapsed: Duration) {
+        self.tick_ms = smooth(self.tick_ms, elapsed);
+    }
+
+    pub fn record_frame(&mut self, elapsed: Duration) {
+        self.frame_ms = smooth(self.frame_ms, elapsed);
+    }
+}
+
+fn smooth(average: f64, sample: Duration) -> f64 {
+    let ms = sample.as_secs_f64() * 1000.0;
+    if average == 0.0 { ms } else { average + (ms - average) * SMOOTHING }
+}
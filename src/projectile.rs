@@ -0,0 +1,59 @@
+//! Spears in flight, drawn over the map.
+
+/// Ticks a thrown spear takes to reach where it was aimed
+const FLIGHT_TICKS: u64 = 3;
+
+struct Projectile {
+    from: (usize, usize),
+    to: (usize, usize),
+    launched: u64,
+}
+
+impl Projectile {
+    /// Where it is at `tick`, and the glyph that shows which way it's flying
+    fn at(&self, tick: u64) -> (usize, usize, char) {
+        let (fx, fy) = (self.from.0 as i64, self.from.1 as i64);
+        let (dx, dy) = (self.to.0 as i64 - fx, self.to.1 as i64 - fy);
+        let done = (tick - self.launched + 1).min(FLIGHT_TICKS) as i64;
+        let x = fx + dx * done / FLIGHT_TICKS as i64;
+        let y = fy + dy * done / FLIGHT_TICKS as i64;
+        let glyph = match (dx.signum(), dy.signum()) {
+            (_, 0) => '-',
+            (0, _) => '|',
+            (sx, sy) if sx == sy => '\\',
+            _ => '/',
+        };
+        (x as usize, y as usize, glyph)
+    }
+}
+
+pub struct Projectiles {
+    flying: Vec<Projectile>,
+}
+
+impl Default for Projectiles {
+    fn default() -> Self {
+        Projectiles::new()
+    }
+}
+
+impl Projectiles {
+    pub fn new() -> Self {
+        Projectiles { flying: Vec::new() }
+    }
+
+    /// Send a spear from (x, y) towards `to`
+    pub fn launch(&mut self, x: usize, y: usize, to: (usize, usize), tick: u64) {
+        self.flying.push(Projectile { from: (x, y), to, launched: tick });
+    }
+
+    /// Drop those that have landed by `tick`
+    pub fn prune(&mut self, tick: u64) {
+        self.flying.retain(|p| tick < p.launched + FLIGHT_TICKS);
+    }
+
+    /// Where every spear still in the air is at `tick`, with its glyph
+    pub fn in_flight(&self, tick: u64) -> impl Iterator<Item = (usize, usize, char)> + '_ {
+        self.flying.iter().map(move |p| p.at(tick))
+    }
+}
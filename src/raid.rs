@@ -0,0 +1,176 @@
+//! Goblin bands that raid the clan's stores.
+
+use std::ops::RangeInclusive;
+
+use rand::Rng;
+
+use crate::combat::{Combatant, Weapon};
+use crate::pathfinding::Route;
+use crate::world::{MAP_HEIGHT, MAP_WIDTH, World};
+
+/// Days the clan is left in peace before the first raid, and after each one
+pub const GRACE_DAYS: u64 = 3;
+pub fn respite(day_length: u64) -> u64 {
+    GRACE_DAYS * day_length
+}
+/// Chance of a raid each dusk, to which every day survived and every meal in
+/// the stores adds a little, up to the most there can be
+const RAID_CHANCE: f64 = 0.02;
+const CHANCE_PER_DAY: f64 = 0.005;
+const CHANCE_PER_MEAL: f64 = 0.005;
+const MAX_CHANCE: f64 = 0.3;
+/// Goblins in a band, one more for every so many days survived and meals stored
+const BAND_SIZE: RangeInclusive<usize> = 2..=5;
+const DAYS_PER_GOBLIN: u64 = 15;
+const MEALS_PER_GOBLIN: u32 = 20;
+/// How far along the edge of the map from the camp a band turns up
+const EDGE_SPREAD: usize = 20;
+/// A goblin's health when unhurt, and below which it drops its loot and runs
+pub const GOBLIN_HEALTH: f32 = 20.0;
+const GOBLIN_FLEE: f32 = 10.0;
+/// What a goblin fights with
+pub const CLUB: Weapon = Weapon { chance: 0.4, damage: 4.0..8.0 };
+/// Meals a goblin carries off the rack
+pub const LOOT: u32 = 2;
+
+/// Chance of a raid at dusk, once the clan has lasted `days` with `meals` in the stores
+pub fn chance(days: u64, meals: u32) -> f64 {
+    (RAID_CHANCE + days as f64 * CHANCE_PER_DAY + meals as f64 * CHANCE_PER_MEAL).min(MAX_CHANCE)
+}
+
+/// Goblins in a band sent after a clan that has lasted `days` with `meals` in the stores
+pub fn band_size(days: u64, meals: u32) -> usize {
+    (BAND_SIZE.start() + (days / DAYS_PER_GOBLIN) as usize + (meals / MEALS_PER_GOBLIN) as usize).min(*BAND_SIZE.end())
+}
+
+pub struct Goblin {
+    pub x: usize,
+    pub y: usize,
+    pub health: f32,
+    /// Meals it has grabbed off the rack
+    pub loot: u32,
+    /// Running back off the map, with its loot or without
+    pub fleeing: bool,
+    route: Route,
+}
+
+impl Goblin {
+    fn new(x: usize, y: usize) -> Self {
+        Goblin { x, y, health: GOBLIN_HEALTH, loot: 0, fleeing: false, route: Route::default() }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health <= 0.0
+    }
+
+    /// Whether it's hurt badly enough to give up
+    pub fn is_beaten(&self) -> bool {
+        self.health < GOBLIN_FLEE
+    }
+
+    /// Take a step towards (tx, ty)
+    pub fn advance(&mut self, tx: usize, ty: usize, world: &World) {
+        (self.x, self.y) = self.route.step((self.x, self.y), (tx, ty), world);
+    }
+}
+
+impl Combatant for Goblin {
+    fn health(&self) -> f32 {
+        self.health
+    }
+
+    fn max_health(&self) -> f32 {
+        GOBLIN_HEALTH
+    }
+
+    fn hurt(&mut self, damage: f32, _tick: u64) {
+        self.health -= damage;
+    }
+
+    fn breaks(&self) -> bool {
+        self.is_beaten()
+    }
+}
+
+pub struct Raid {
+    pub goblins: Vec<Goblin>,
+    /// Where the band came onto the map, and runs back to
+    pub edge: (usize, usize),
+    from: Edge,
+    /// Goblins the band set out with
+    pub size: usize,
+    pub killed: usize,
+    /// Meals carried off the map so far
+    pub stolen: u32,
+    /// Whether the band has reached the campfire and set about smashing it
+    pub smashing: bool,
+}
+
+impl Raid {
+    /// Bring a band of `size` goblins onto the edge of the map nearest the
+    /// camp, if there's open ground for them there
+    pub fn arrive(size: usize, world: &World, rng: &mut impl Rng) -> Option<Raid> {
+        let (cx, cy) = world.campfire_pos;
+        let mut along = |c: usize, len: usize| (c + rng.gen_range(0..=EDGE_SPREAD * 2)).saturating_sub(EDGE_SPREAD).min(len - 1);
+        let from = nearest_edge(cx, cy);
+        let (ex, ey) = match from {
+            Edge::North => (along(cx, MAP_WIDTH), 0),
+            Edge::South => (along(cx, MAP_WIDTH), MAP_HEIGHT - 1),
+            Edge::West => (0, along(cy, MAP_HEIGHT)),
+            Edge::East => (MAP_WIDTH - 1, along(cy, MAP_HEIGHT)),
+        };
+        let mut goblins = Vec::new();
+        for _ in 0..size * 10 {
+            if goblins.len() == size {
+                break;
+            }
+            let x = (ex + rng.gen_range(0..=4)).saturating_sub(2).min(MAP_WIDTH - 1);
+            let y = (ey + rng.gen_range(0..=4)).saturating_sub(2).min(MAP_HEIGHT - 1);
+            if world.is_walkable(x, y) {
+                goblins.push(Goblin::new(x, y));
+            }
+        }
+        let first = goblins.first()?;
+        let edge = (first.x, first.y);
+        Some(Raid { size: goblins.len(), goblins, edge, from, killed: 0, stolen: 0, smashing: false })
+    }
+
+    /// Whether the band has lost half its number and given up
+    pub fn is_routed(&self) -> bool {
+        self.killed * 2 >= self.size
+    }
+
+    pub fn goblin_at(&self, x: usize, y: usize) -> Option<&Goblin> {
+        self.goblins.iter().find(|g| g.x == x && g.y == y)
+    }
+
+    /// Which way the band came from, for telling the clan
+    pub fn direction(&self) -> &'static str {
+        match self.from {
+            Edge::North => "north",
+            Edge::South => "south",
+            Edge::West => "west",
+            Edge::East => "east",
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self.goblins.len() {
+            1 => "Raid: 1 goblin".to_string(),
+            n => format!("Raid: {} goblins", n),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Edge {
+    North,
+    South,
+    West,
+    East,
+}
+
+fn nearest_edge(x: usize, y: usize) -> Edge {
+    let distances = [(y, Edge::North), (MAP_HEIGHT - 1 - y, Edge::South), (x, Edge::West), (MAP_WIDTH - 1 - x, Edge::East)];
+    distances.into_iter().min_by_key(|(d, _)| *d).map(|(_, edge)| edge).unwrap_or(Edge::North)
+}
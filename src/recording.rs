@@ -0,0 +1,290 @@
+//! Session recordings for reproducing bugs, made with `--record` and played back with `--play`.
+
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::custom::Custom;
+use crate::mouse::Gesture;
+use crate::save;
+use crate::scenario::Scenario;
+use crate::sim::Stress;
+use crate::term::{ColorDepth, GlyphMode};
+
+const MAGIC: &str = "orcrec 1";
+
+/// Keys with a name of their own; anything else is written as `char:<n>` or `f<n>`
+pub const NAMED_KEYS: [(KeyCode, &str); 15] = [
+    (KeyCode::Backspace, "backspace"),
+    (KeyCode::Enter, "enter"),
+    (KeyCode::Left, "left"),
+    (KeyCode::Right, "right"),
+    (KeyCode::Up, "up"),
+    (KeyCode::Down, "down"),
+    (KeyCode::Home, "home"),
+    (KeyCode::End, "end"),
+    (KeyCode::PageUp, "pageup"),
+    (KeyCode::PageDown, "pagedown"),
+    (KeyCode::Tab, "tab"),
+    (KeyCode::BackTab, "backtab"),
+    (KeyCode::Delete, "delete"),
+    (KeyCode::Insert, "insert"),
+    (KeyCode::Esc, "esc"),
+];
+
+/// Everything a session starts from
+#[derive(Clone, Default)]
+pub struct Header {
+    pub seed: u64,
+    pub stress: Option<Stress>,
+    pub glyphs: Option<GlyphMode>,
+    pub colors: Option<ColorDepth>,
+    pub tutorial: bool,
+    pub scenario: Scenario,
+    /// Text of the config file, empty if there was none
+    pub config: String,
+    /// The scenario file played from, if any
+    pub custom: Option<Custom>,
+}
+
+/// Something the player did
+#[derive(Clone)]
+pub enum Input {
+    Key(KeyEvent),
+    Mouse(Gesture),
+    /// An action a WebSocket viewer sent, in the text form `gym::Action::parse` reads
+    Order(String),
+}
+
+pub struct Press {
+    pub ms: u64,
+    pub tick: u64,
+    pub input: Input,
+}
+
+fn encode_key(code: KeyCode) -> Option<String> {
+    match code {
+        KeyCode::Char(c) => Some(format!("char:{}", c as u32)),
+        KeyCode::F(n) => Some(format!("f{}", n)),
+        other => NAMED_KEYS.iter().find(|(k, _)| *k == other).map(|(_, name)| name.to_string()),
+    }
+}
+
+fn decode_key(s: &str) -> Option<KeyCode> {
+    if let Some(n) = s.strip_prefix("char:") {
+        return n.parse().ok().and_then(char::from_u32).map(KeyCode::Char);
+    }
+    if let Some(named) = NAMED_KEYS.iter().find(|(_, name)| *name == s) {
+        return Some(named.0);
+    }
+    s.strip_prefix('f').and_then(|n| n.parse().ok()).map(KeyCode::F)
+}
+
+pub fn write_header(file: &mut impl Write, header: &Header) -> io::Result<()> {
+    writeln!(file, "{}", MAGIC)?;
+    writeln!(file, "seed {}", header.seed)?;
+    if let Some(stress) = header.stress {
+        writeln!(file, "stress {} {}", stress.orcs, stress.animals)?;
+    }
+    if let Some(glyphs) = header.glyphs {
+        writeln!(file, "glyphs {}", glyphs.name())?;
+    }
+    if let Some(colors) = header.colors {
+        writeln!(file, "colors {}", colors.name())?;
+    }
+    if header.tutorial {
+        writeln!(file, "tutorial")?;
+    }
+    if header.scenario != Scenario::Sandbox {
+        writeln!(file, "scenario {}", header.scenario.name())?;
+    }
+    for line in header.config.lines() {
+        writeln!(file, "config {}", line)?;
+    }
+    if let Some(custom) = &header.custom {
+        for line in custom.source.lines() {
+            writeln!(file, "custom {}", line)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_press(file: &mut impl Write, press: &Press) -> io::Result<()> {
+    match &press.input {
+        Input::Key(event) => match encode_key(event.code) {
+            Some(code) => writeln!(file, "key {} {} {} {}", press.ms, press.tick, code, event.modifiers.bits()),
+            None => Ok(()),
+        },
+        Input::Mouse(gesture) => writeln!(file, "mouse {} {} {}", press.ms, press.tick, gesture.encode()),
+        Input::Order(order) => writeln!(file, "order {} {} {}", press.ms, press.tick, order),
+    }
+}
+
+/// Appends a session to its recording as it is played
+pub struct Recorder {
+    file: File,
+    started: Instant,
+}
+
+impl Recorder {
+    pub fn create(path: &Path, header: &Header) -> io::Result<Recorder> {
+        let mut file = File::create(path)?;
+        write_header(&mut file, header)?;
+        file.flush()?;
+        Ok(Recorder { file, started: Instant::now() })
+    }
+
+    /// Write down a key press as it is handed to the app, flushing straight
+    /// away so that the recording survives a crash
+    pub fn key(&mut self, tick: u64, event: KeyEvent) -> io::Result<()> {
+        let ms = self.started.elapsed().as_millis() as u64;
+        write_press(&mut self.file, &Press { ms, tick, input: Input::Key(event) })?;
+        self.file.flush()
+    }
+
+    /// Write down a mouse gesture as it is handed to the app
+    pub fn mouse(&mut self, tick: u64, gesture: Gesture) -> io::Result<()> {
+        let ms = self.started.elapsed().as_millis() as u64;
+        write_press(&mut self.file, &Press { ms, tick, input: Input::Mouse(gesture) })?;
+        self.file.flush()
+    }
+
+    /// Write down an action a viewer sent as it is carried out
+    pub fn order(&mut self, tick: u64, order: &str) -> io::Result<()> {
+        let ms = self.started.elapsed().as_millis() as u64;
+        write_press(&mut self.file, &Press { ms, tick, input: Input::Order(order.to_string()) })?;
+        self.file.flush()
+    }
+}
+
+pub struct Recording {
+    pub header: Header,
+    pub presses: Vec<Press>,
+    /// Tick the session was saved on, if it's a save
+    pub saved_at: Option<u64>,
+}
+
+impl Recording {
+    /// A session about to start from `header`, with nothing done in it yet
+    pub fn new(header: Header) -> Recording {
+        Recording { header, presses: Vec::new(), saved_at: None }
+    }
+
+    /// Write the session down as a save made at `tick`
+    pub fn save(&self, path: &Path, tick: u64) -> io::Result<()> {
+        fs::write(path, save::encode(self, tick))
+    }
+
+    /// Read a recording, or a save in any version of its format
+    pub fn load(path: &Path) -> Result<Recording, String> {
+        let bytes = fs::read(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        save::decode(&bytes).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn parse(src: &str) -> Result<Recording, String> {
+        let mut lines = src.lines().enumerate();
+        if lines.next().map(|(_, l)| l) != Some(MAGIC) {
+            return Err("not a session recording".to_string());
+        }
+        let mut header = Header::default();
+        let mut presses = Vec::new();
+        let mut saved_at = None;
+        let mut config = Vec::new();
+        let mut custom = Vec::new();
+        for (i, line) in lines {
+            let err = |what: &str| format!("line {}: {}", i + 1, what);
+            let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+            match kind {
+                "seed" => header.seed = rest.parse().map_err(|_| err("bad seed"))?,
+                "stress" => {
+                    let counts: Vec<usize> = rest.split_whitespace().filter_map(|n| n.parse().ok()).collect();
+                    let [orcs, animals] = counts[..] else {
+                        return Err(err("stress needs two counts"));
+                    };
+                    header.stress = Some(Stress { orcs, animals });
+                }
+                "glyphs" => header.glyphs = Some(GlyphMode::parse(rest).ok_or_else(|| err("unknown glyph mode"))?),
+                "colors" => header.colors = Some(ColorDepth::parse(rest).ok_or_else(|| err("unknown color depth"))?),
+                "tutorial" => header.tutorial = true,
+                "scenario" => header.scenario = Scenario::parse(rest).ok_or_else(|| err("unknown scenario"))?,
+                "config" => config.push(rest),
+                "custom" => custom.push(rest),
+                "key" => {
+                    let fields: Vec<&str> = rest.split_whitespace().collect();
+                    let [ms, tick, code, mods] = fields[..] else {
+                        return Err(err("key needs a time, a tick, a key and modifiers"));
+                    };
+                    let code = decode_key(code).ok_or_else(|| err("unknown key"))?;
+                    let mods = mods.parse().map(KeyModifiers::from_bits_truncate).map_err(|_| err("bad modifiers"))?;
+                    presses.push(Press {
+                        ms: ms.parse().map_err(|_| err("bad time"))?,
+                        tick: tick.parse().map_err(|_| err("bad tick"))?,
+                        input: Input::Key(KeyEvent::new(code, mods)),
+                    });
+                }
+                "mouse" => {
+                    let fields: Vec<&str> = rest.split_whitespace().collect();
+                    let [ms, tick, gesture @ ..] = &fields[..] else {
+                        return Err(err("mouse needs a time, a tick and a gesture"));
+                    };
+                    presses.push(Press {
+                        ms: ms.parse().map_err(|_| err("bad time"))?,
+                        tick: tick.parse().map_err(|_| err("bad tick"))?,
+                        input: Input::Mouse(Gesture::decode(gesture).ok_or_else(|| err("unknown gesture"))?),
+                    });
+                }
+                "order" => {
+                    let fields: Vec<&str> = rest.split_whitespace().collect();
+                    let [ms, tick, order @ ..] = &fields[..] else {
+                        return Err(err("order needs a time, a tick and an action"));
+                    };
+                    presses.push(Press {
+                        ms: ms.parse().map_err(|_| err("bad time"))?,
+                        tick: tick.parse().map_err(|_| err("bad tick"))?,
+                        input: Input::Order(order.join(" ")),
+                    });
+                }
+                "saved" => saved_at = Some(rest.parse().map_err(|_| err("bad tick"))?),
+                "" => {}
+                other => return Err(err(&format!("unknown entry '{}'", other))),
+            }
+        }
+        header.config = config.join("\n");
+        if !custom.is_empty() {
+            header.custom = Some(Custom::parse(&custom.join("\n")).map_err(|e| format!("scenario file: {}", e))?);
+        }
+        Ok(Recording { header, presses, saved_at })
+    }
+}
+
+/// Hands recorded keys, gestures and orders back to the app once their moment comes round
+pub struct Player {
+    presses: VecDeque<Press>,
+    started: Instant,
+}
+
+impl Player {
+    pub fn new(presses: Vec<Press>) -> Player {
+        Player { presses: presses.into(), started: Instant::now() }
+    }
+
+    /// The next key, gesture or order to feed in, if it is due. One is never fed
+    /// before the tick it was recorded on, and always before the app moves
+    /// past that tick; in between it waits for the time it was made.
+    pub fn next(&mut self, tick: u64, tick_due: bool) -> Option<Input> {
+        let next = self.presses.front()?;
+        let on_time = self.started.elapsed().as_millis() >= next.ms as u128;
+        if next.tick <= tick && (on_time || tick_due) {
+            return self.presses.pop_front().map(|p| p.input);
+        }
+        None
+    }
+
+    pub fn finished(&self) -> bool {
+        self.presses.is_empty()
+    }
+}
@@ -1,71 +1,349 @@
+use std::collections::{HashMap, HashSet};
+
 use ratatui::Frame;
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Direction, Layout, Margin, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, BorderType, Borders, List, ListItem, Paragraph};
+use ratatui::symbols::Marker;
+use ratatui::widgets::{Axis, Block, BorderType, Borders, Chart, Clear, Dataset, GraphType, List, ListItem, Padding, Paragraph, Sparkline, Wrap};
 
-use crate::app::App;
+use crate::alarm;
+use crate::ambience::Mote;
+use crate::animal::Animal;
+use crate::app::{App, Naming, Screen};
+use crate::calendar::{self, DayPhase};
+use crate::diary::NEEDS;
+use crate::expedition::Venture;
+use crate::header;
+use crate::help;
+use crate::input::Command;
+use crate::markers::Mark;
+use crate::menu::{Item, Setting};
+use crate::minimap::{self, Ground};
 use crate::orc::Activity;
-use crate::world::{MAP_HEIGHT, MAP_WIDTH};
+use crate::pack;
+use crate::sim::MAX_DROP;
+use crate::spatial::Entity;
+use crate::status;
+use crate::term::{ColorDepth, GlyphMode, TermCaps};
+use crate::territory::{Clan, Hold};
+use crate::theme::Theme;
+use crate::tooltip;
+use crate::view::ViewMode;
+use crate::world::{MAP_HEIGHT, MAP_WIDTH, Terrain};
+use crate::zoom::{self, Zoom};
+
+/// Marks listed under the legend, the latest first
+const LEGEND_LATEST: usize = 3;
+/// Share of a tick an afterimage takes to fade away
+const AFTERIMAGE: f32 = 0.6;
 
 pub fn render(frame: &mut Frame, app: &mut App) {
+    let screen = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Min(10),
+            Constraint::Length(1),
+        ])
+        .split(frame.area());
+
     let main_chunks = Layout::default()
         .direction(Direction::Horizontal)
         .constraints([
             Constraint::Min(20),
-            Constraint::Length(32),
+            Constraint::Length(if app.show_sidebar { app.sidebar_width } else { 0 }),
         ])
-        .split(frame.area());
+        .split(screen[0]);
 
     let left_chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Min(10),
-            Constraint::Length(10),
+            Constraint::Length(if app.show_log { app.log_height } else { 0 }),
         ])
         .split(main_chunks[0]);
 
+    let minimap = minimap_area(app, left_chunks[0]);
+    app.mouse.place(left_chunks[0], left_chunks[1], minimap.map(|area| area.inner(Margin::new(1, 1))));
     render_map(frame, app, left_chunks[0]);
-    render_event_log(frame, app, left_chunks[1]);
-    render_sidebar(frame, app, main_chunks[1]);
+    if app.show_log {
+        render_event_log(frame, app, left_chunks[1]);
+    }
+    if app.show_sidebar {
+        render_sidebar(frame, app, main_chunks[1]);
+    }
+    frame.render_widget(Paragraph::new(status::line(app)), screen[1]);
+    if app.show_perf {
+        render_perf(frame, app, left_chunks[0]);
+    }
+    if let Some(area) = minimap {
+        render_minimap(frame, app, area);
+    }
+    render_banner(frame, app, left_chunks[0]);
+    render_legend(frame, app, left_chunks[0]);
+    render_tooltip(frame, app, left_chunks[0]);
+    render_campfire_menu(frame, app, left_chunks[0]);
+    render_bestiary(frame, app, left_chunks[0]);
+    render_graphs(frame, app, left_chunks[0]);
+    render_inspector(frame, app, left_chunks[0]);
+    render_food_prompt(frame, app, left_chunks[0]);
+    render_naming(frame, app, left_chunks[0]);
+    render_lesson(frame, app, left_chunks[0]);
+    render_help(frame, app, screen[0]);
+    render_menu(frame, app, screen[0]);
+
+    adapt_to_terminal(frame.buffer_mut(), app.caps);
+}
+
+/// Rewrite the finished frame for terminals without Unicode glyphs or true color
+fn adapt_to_terminal(buf: &mut Buffer, caps: TermCaps) {
+    if caps.glyphs == GlyphMode::Unicode && caps.colors == ColorDepth::TrueColor {
+        return;
+    }
+    for cell in buf.content.iter_mut() {
+        if caps.glyphs == GlyphMode::Ascii {
+            let mut chars = cell.symbol().chars();
+            if let (Some(c), None) = (chars.next(), chars.next())
+                && !c.is_ascii()
+            {
+                cell.set_char(ascii_glyph(c));
+            }
+        }
+        cell.fg = match cell.fg {
+            Color::Rgb(..) => visible(degrade_color(cell.fg, caps.colors)),
+            fg => fg,
+        };
+        cell.bg = degrade_color(cell.bg, caps.colors);
+    }
+}
+
+/// A dark shade of text that came out as plain black, which would vanish
+/// against the terminal's background, as the darkest gray that still shows
+/// instead; it's mostly tiles dimmed at night that land there
+fn visible(color: Color) -> Color {
+    match color {
+        Color::Black => Color::DarkGray,
+        Color::Indexed(16) => Color::Indexed(236),
+        _ => color,
+    }
+}
+
+/// Plain-ASCII stand-ins for every glyph the UI draws
+fn ascii_glyph(c: char) -> char {
+    match c {
+        // Terrain
+        '·' => '.',
+        '♣' => 'T',
+        '◆' => '#',
+        '≈' => '~',
+        '♨' => '*',
+        '⚘' => '%',
+        '✕' => 'x',
+        '✿' => '"',
+        '♠' => 'Y',
+        '∩' => 'm',
+        '⚕' => 'h',
+        '✝' => '+',
+        '⌸' => '=',
+        '○' => 'u',
+        'Ш' => 'E',
+        '◌' => ':',
+        // Marks
+        '☠' => 'D',
+        '⚐' => 'R',
+        '✦' => 'K',
+        // Buildings
+        '⌂' => '^',
+        '█' => '#',
+        '▥' => 'H',
+        'Θ' => 'O',
+        '♜' => '!',
+        '▤' => 'S',
+        'π' => 'n',
+        '═' => '=',
+        '⊞' => 'P',
+        '⚑' => 'F',
+        // Creatures
+        '☻' => '@',
+        '◎' => 'o',
+        '⚔' => '&',
+        '•' => '`',
+        '†' => '+',
+        'δ' => 'd',
+        'β' => 'b',
+        'ω' => 'w',
+        'ρ' => 'r',
+        'γ' => 'g',
+        'ν' => 'v',
+        // Emotes and UI
+        '♥' => '<',
+        '◷' | '◶' | '◵' | '◴' => 'o',
+        '☀' => '*',
+        '☂' => '/',
+        '❄' => '*',
+        '⚠' => '!',
+        '▣' => 'X',
+        '◈' => 'X',
+        '▁' | '▂' => '_',
+        '▃' | '▄' => '-',
+        '▅' | '▆' => '=',
+        '▇' => '#',
+        '▓' => '#',
+        '▒' => '+',
+        '░' => '-',
+        '─' => '-',
+        '│' => '|',
+        '╭' | '╮' | '╰' | '╯' | '┌' | '┐' | '└' | '┘' => '+',
+        '━' => '=',
+        '┃' => '|',
+        '┏' | '┓' | '┗' | '┛' => '+',
+        _ => '?',
+    }
+}
+
+fn degrade_color(color: Color, depth: ColorDepth) -> Color {
+    match (color, depth) {
+        (Color::Rgb(r, g, b), ColorDepth::Ansi256) => Color::Indexed(rgb_to_256(r, g, b)),
+        (Color::Rgb(r, g, b), ColorDepth::Ansi16) => nearest_ansi16(r, g, b),
+        _ => color,
+    }
+}
+
+fn rgb_to_256(r: u8, g: u8, b: u8) -> u8 {
+    if r == g && g == b {
+        // Grayscale ramp 232..=255, with the cube's black and white at the ends
+        return match r {
+            0..8 => 16,
+            249.. => 231,
+            _ => 232 + ((r as u16 - 8) * 24 / 241) as u8,
+        };
+    }
+    let level = |v: u8| ((v as u16 * 5 + 127) / 255) as u8;
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+/// The 16 terminal colors and what they look like, more or less
+const ANSI16: [(Color, (i32, i32, i32)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (128, 0, 0)),
+    (Color::Green, (0, 128, 0)),
+    (Color::Yellow, (128, 128, 0)),
+    (Color::Blue, (0, 0, 128)),
+    (Color::Magenta, (128, 0, 128)),
+    (Color::Cyan, (0, 128, 128)),
+    (Color::Gray, (192, 192, 192)),
+    (Color::DarkGray, (128, 128, 128)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (0, 0, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+fn nearest_ansi16(r: u8, g: u8, b: u8) -> Color {
+    let (r, g, b) = (r as i32, g as i32, b as i32);
+    ANSI16
+        .iter()
+        .min_by_key(|(_, (pr, pg, pb))| (r - pr).pow(2) + (g - pg).pow(2) + (b - pb).pow(2))
+        .map(|(color, _)| *color)
+        .unwrap()
+}
+
+/// `from` shaded `t` of the way towards `to`, from 0 to 1
+fn blend(from: Color, to: Color, t: f32) -> Color {
+    match (rgb(from), rgb(to)) {
+        (Some((r1, g1, b1)), Some((r2, g2, b2))) => {
+            let mix = |a: i32, b: i32| (a as f32 + (b - a) as f32 * t) as u8;
+            Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+        }
+        _ if t < 0.5 => from,
+        _ => to,
+    }
+}
+
+/// A color's red, green and blue, if it's one with a known look
+fn rgb(color: Color) -> Option<(i32, i32, i32)> {
+    match color {
+        Color::Rgb(r, g, b) => Some((r as i32, g as i32, b as i32)),
+        _ => ANSI16.iter().find(|(c, _)| *c == color).map(|&(_, rgb)| rgb),
+    }
 }
 
 fn render_map(frame: &mut Frame, app: &mut App, area: Rect) {
-    let night_dim = app.is_night();
+    let phase = app.sim.phase();
+    // At night only the tiles lit by a burning campfire or a torch keep their color
+    let fire_lit = app.sim.world.fire_lit();
+    let lights = app.sim.lights();
 
     let vw = (area.width.saturating_sub(2)) as usize;
     let vh = (area.height.saturating_sub(2)) as usize;
 
     app.update_camera(vw, vh);
-
+    if app.zoom != Zoom::Full {
+        let lines = zoomed_lines(app, vw, vh);
+        frame.render_widget(Paragraph::new(lines).block(map_block(app)), area);
+        return;
+    }
     let cam_x = app.camera_x;
     let cam_y = app.camera_y;
+    let view = Rect { x: cam_x as u16, y: cam_y as u16, width: vw.min(MAP_WIDTH - cam_x) as u16, height: vh.min(MAP_HEIGHT - cam_y) as u16 };
+    app.ambience.update(&app.sim.world, app.sim.tick, app.sim.paused, view);
+    let particles = app.ambience.layer(view);
+    let theme = app.theme();
+    let spears: Vec<(usize, usize, char)> = app.sim.projectiles.in_flight(app.sim.tick).collect();
+    // The way the selected orc is going, and where it ends
+    let path: HashSet<(usize, usize)> = selected_path(app).iter().copied().collect();
+    let destination = selected_path(app).last().copied();
+    // Under the needs view, a letter beside each orc for its worst need
+    let need_marks: HashMap<(usize, usize), (char, Color)> = app.sim.orcs.iter()
+        .filter(|o| o.alive)
+        .filter_map(|o| app.view.mark(o, theme).map(|mark| ((o.x + 1, o.y), mark)))
+        .collect();
+    // Orcs and animals that moved this tick leave an afterimage on the tile
+    // they left, fading into the ground as the next tick comes on
+    let moved_orcs = app.sim.orcs.iter()
+        .filter(|o| o.alive && o.was != (o.x, o.y))
+        .map(|o| (o.was, '☻', app.view.color(o, theme).unwrap_or(theme.orc)));
+    let moved_animals = app.sim.animals.iter()
+        .filter(|a| a.alive && a.was != (a.x, a.y))
+        .map(|a| (a.was, a.kind.symbol(), theme.creature(a.kind)));
+    let afterimages: HashMap<(usize, usize), (char, Color)> = moved_orcs.chain(moved_animals)
+        .map(|(at, glyph, color)| (at, (glyph, color)))
+        .collect();
 
     let mut lines: Vec<Line> = Vec::new();
     for y in cam_y..(cam_y + vh).min(MAP_HEIGHT) {
         let mut spans: Vec<Span> = Vec::new();
         for x in cam_x..(cam_x + vw).min(MAP_WIDTH) {
-            // Check if an orc is here
-            if let Some((idx, orc)) = app.orcs.iter().enumerate().find(|(_, o)| o.x == x && o.y == y) {
+            // An orc is drawn over anything else on its tile, then an animal
+            let here = app.sim.entities_at(x, y).next();
+            if let Some(Entity::Orc(idx)) = here {
+                let orc = &app.sim.orcs[idx];
                 if !orc.alive {
                     // Dead orc tombstone
-                    spans.push(Span::styled("†", Style::default().fg(Color::DarkGray)));
+                    spans.push(Span::styled("†", Style::default().fg(theme.tombstone)));
                 } else {
                     let orc_char = match &orc.activity {
                         Activity::Sleeping => '◎',
                         Activity::Hunting { .. } => '⚔',
                         Activity::CarryingMeat => '☻',
+                        Activity::Infant { .. } => '•',
                         _ => '☻',
                     };
                     let selected = app.selected_orc == Some(idx);
-                    let color = if orc.health < 30.0 {
-                        Color::Red
+                    let color = if let Some(color) = app.view.color(orc, theme) {
+                        color
+                    } else if orc.health < 30.0 {
+                        theme.orc_hurt
                     } else if selected {
-                        Color::White
+                        theme.orc_selected
                     } else if orc.carrying_food {
-                        Color::Rgb(180, 120, 60)
+                        theme.orc_carrying
                     } else {
-                        Color::LightGreen
+                        theme.orc
                     };
                     let style = if selected {
                         Style::default().fg(color).add_modifier(Modifier::BOLD | Modifier::REVERSED)
@@ -74,133 +352,1046 @@ fn render_map(frame: &mut Frame, app: &mut App, area: Rect) {
                     };
                     spans.push(Span::styled(orc_char.to_string(), style));
                 }
-            } else if let Some(animal) = app.animals.iter().find(|a| a.alive && a.x == x && a.y == y) {
-                // Render animal
-                let mut color = animal.kind.color();
-                if night_dim {
-                    color = dim_color(color);
-                }
+            } else if let Some(rival) = app.sim.rivals.member_at(x, y) {
+                let color = if rival.beaten { theme.orc_hurt } else { theme.rival };
+                spans.push(Span::styled("☻", Style::default().fg(color).add_modifier(Modifier::BOLD)));
+            } else if let Some(goblin) = app.sim.raid.as_ref().and_then(|r| r.goblin_at(x, y)) {
+                let color = if goblin.is_beaten() { theme.orc_hurt } else { theme.goblin };
+                spans.push(Span::styled("g", Style::default().fg(color).add_modifier(Modifier::BOLD)));
+            } else if let Some(&(_, _, glyph)) = spears.iter().find(|s| (s.0, s.1) == (x, y)) {
+                spans.push(Span::styled(glyph.to_string(), Style::default().fg(theme.spear).add_modifier(Modifier::BOLD)));
+            } else if let Some(emote) = app.sim.emotes.at(x, y) {
                 spans.push(Span::styled(
-                    animal.kind.symbol().to_string(),
-                    Style::default().fg(color),
+                    emote.kind.symbol().to_string(),
+                    Style::default().fg(theme.emote(emote.kind)).add_modifier(Modifier::BOLD),
                 ));
+            } else if app.sim.world.corpse_at(x, y).is_some() {
+                spans.push(Span::styled("†", Style::default().fg(theme.tombstone)));
+            } else if let Some(Entity::Animal(i)) = here {
+                let animal = &app.sim.animals[i];
+                // Render animal
+                let mut color = theme.creature(animal.kind);
+                if app.sim.is_night() && !lights.lit(x, y) {
+                    color = theme.dim(color);
+                }
+                // Bedded-down animals are drawn faint
+                let mut style = Style::default().fg(color);
+                if !animal.kind.is_active(phase) {
+                    style = style.add_modifier(Modifier::DIM);
+                }
+                spans.push(Span::styled(animal.kind.symbol().to_string(), style));
             } else if app.cursor_x == x && app.cursor_y == y {
                 spans.push(Span::styled(
                     "▣",
-                    Style::default().fg(Color::White).add_modifier(Modifier::REVERSED),
+                    Style::default().fg(theme.cursor).add_modifier(Modifier::REVERSED),
                 ));
+            } else if let Some(&(letter, color)) = need_marks.get(&(x, y)) {
+                spans.push(Span::styled(letter.to_string(), Style::default().fg(color).add_modifier(Modifier::BOLD)));
+            } else if let Some(&(glyph, color)) = afterimages.get(&(x, y))
+                && app.tick_progress < AFTERIMAGE
+            {
+                let mut color = blend(color, theme.terrain(app.sim.world.get(x, y)), app.tick_progress / AFTERIMAGE);
+                if app.sim.is_night() && !lights.lit(x, y) {
+                    color = theme.dim(color);
+                }
+                spans.push(Span::styled(glyph.to_string(), Style::default().fg(color).add_modifier(Modifier::DIM)));
+            } else if let Some((glyph, mote)) = particles[(y - cam_y) * view.width as usize + x - cam_x] {
+                let color = match mote {
+                    Mote::Spark => theme.campfire,
+                    Mote::Leaf if app.sim.is_night() && !lights.lit(x, y) => theme.dim(theme.tree),
+                    Mote::Leaf => theme.tree,
+                    Mote::Rain if app.sim.is_night() && !lights.lit(x, y) => theme.dim(theme.water),
+                    Mote::Rain => theme.water,
+                };
+                spans.push(Span::styled(glyph.to_string(), Style::default().fg(color)));
+            } else if app.sim.evacuation.as_ref().is_some_and(|e| e.rally == (x, y)) {
+                spans.push(Span::styled("⚑", Style::default().fg(theme.accent_bright).add_modifier(Modifier::BOLD)));
+            } else if destination == Some((x, y)) {
+                spans.push(Span::styled("◈", Style::default().fg(theme.orc_selected).add_modifier(Modifier::BOLD)));
             } else {
-                let terrain = app.world.get(x, y);
-                let mut color = terrain.color();
-                if night_dim {
-                    color = dim_color(color);
+                // Food dropped on the ground lies on top of whatever is there
+                let terrain = app.sim.world.get(x, y);
+                let (symbol, mut color) = if app.sim.world.food_at(x, y) > 0 {
+                    ('⚘', theme.food)
+                } else if terrain == Terrain::Campfire && !fire_lit {
+                    (terrain.symbol(), theme.muted)
+                } else if app.sim.rivals.camp == (x, y) {
+                    (Terrain::Campfire.symbol(), theme.rival)
+                } else if terrain == Terrain::Grass
+                    && let Some(marker) = app.sim.world.markers.at(x, y)
+                {
+                    (marker.mark.symbol(), theme.mark(marker.mark))
+                } else {
+                    (terrain.symbol(), theme.terrain(terrain))
+                };
+                if app.sim.is_night() && !lights.lit(x, y) {
+                    color = theme.dim(color);
                 }
-                spans.push(Span::styled(
-                    terrain.symbol().to_string(),
-                    Style::default().fg(color),
-                ));
+                let mut style = Style::default().fg(color);
+                if app.sim.world.designations.contains_key(&(x, y)) {
+                    style = style.bg(theme.designation);
+                } else if path.contains(&(x, y)) {
+                    style = style.bg(theme.path);
+                } else if app.view == ViewMode::Territory {
+                    match app.sim.territory.hold(x, y) {
+                        Hold::Open => {}
+                        Hold::Held(Clan::Ours) => style = style.bg(theme.territory_ours),
+                        Hold::Held(Clan::Rivals) => style = style.bg(theme.territory_rivals),
+                        Hold::Contested => style = style.bg(theme.territory_contested),
+                    }
+                } else if app.sim.is_night() && lights.lit(x, y) {
+                    style = style.bg(theme.firelight);
+                }
+                spans.push(Span::styled(symbol.to_string(), style));
             }
         }
         lines.push(Line::from(spans));
     }
 
-    let time_label = if app.is_night() { "Night" } else { "Day" };
-    let day_num = app.tick / 100 + 1;
-    let alive_count = app.orcs.iter().filter(|o| o.alive).count();
-    let title = format!(
-        " Orc Village | Day {} ({}) | Pop: {} | Meat: {} | Speed: {}x {} | ({},{}) ",
-        day_num,
-        time_label,
-        alive_count,
-        app.world.food_stockpile,
-        app.speed,
-        if app.paused { "[PAUSED]" } else { "" },
-        app.cursor_x,
-        app.cursor_y,
+    let map_widget = Paragraph::new(lines).block(map_block(app));
+    frame.render_widget(map_widget, area);
+}
+
+/// The map's border flashes while the clan is on alert, and dims at night
+/// The map's border, with the header along the top
+fn map_block(app: &App) -> Block<'static> {
+    Block::default()
+        .title(header::line(app))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(map_border(app)))
+}
+
+/// The map zoomed out, a character for each block of tiles, `vw` by `vh` of them
+fn zoomed_lines(app: &App, vw: usize, vh: usize) -> Vec<Line<'static>> {
+    let theme = app.theme();
+    let scale = app.zoom.scale();
+    let fire_lit = app.sim.world.fire_lit();
+    let lights = app.sim.lights();
+    let path: HashSet<(usize, usize)> = selected_path(app).iter().copied().collect();
+
+    let mut lines = Vec::new();
+    for top in (app.camera_y..(app.camera_y + vh * scale).min(MAP_HEIGHT)).step_by(scale) {
+        let mut spans = Vec::new();
+        for left in (app.camera_x..(app.camera_x + vw * scale).min(MAP_WIDTH)).step_by(scale) {
+            let tiles = zoom::block(left, top, scale);
+            // The selected orc before any other, and the living before the dead
+            let orcs: Vec<usize> = tiles
+                .clone()
+                .flat_map(|(x, y)| app.sim.entities_at(x, y))
+                .filter_map(|entity| match entity {
+                    Entity::Orc(i) => Some(i),
+                    _ => None,
+                })
+                .collect();
+            let orc = orcs
+                .iter()
+                .find(|&&i| app.selected_orc == Some(i))
+                .or_else(|| orcs.iter().find(|&&i| app.sim.orcs[i].alive))
+                .or(orcs.first())
+                .map(|&i| (i, &app.sim.orcs[i]));
+            let animal = tiles.clone().flat_map(|(x, y)| app.sim.entities_at(x, y)).find_map(|entity| match entity {
+                Entity::Animal(i) => Some(&app.sim.animals[i]),
+                _ => None,
+            });
+            let lit = tiles.clone().any(|(x, y)| lights.lit(x, y));
+
+            let span = if let Some((i, orc)) = orc {
+                if !orc.alive {
+                    Span::styled("†", Style::default().fg(theme.tombstone))
+                } else if app.selected_orc == Some(i) {
+                    Span::styled("☻", Style::default().fg(theme.orc_selected).add_modifier(Modifier::BOLD | Modifier::REVERSED))
+                } else {
+                    let color = app.view.color(orc, theme).unwrap_or(if orc.health < 30.0 { theme.orc_hurt } else { theme.orc });
+                    Span::styled("☻", Style::default().fg(color).add_modifier(Modifier::BOLD))
+                }
+            } else if tiles.clone().any(|(x, y)| app.sim.rivals.member_at(x, y).is_some()) {
+                Span::styled("☻", Style::default().fg(theme.rival).add_modifier(Modifier::BOLD))
+            } else if tiles.clone().any(|(x, y)| app.sim.raid.as_ref().is_some_and(|r| r.goblin_at(x, y).is_some())) {
+                Span::styled("g", Style::default().fg(theme.goblin).add_modifier(Modifier::BOLD))
+            } else if let Some(animal) = animal {
+                let mut color = theme.creature(animal.kind);
+                if app.sim.is_night() && !lit {
+                    color = theme.dim(color);
+                }
+                Span::styled(animal.kind.symbol().to_string(), Style::default().fg(color))
+            } else if tiles.clone().any(|tile| tile == (app.cursor_x, app.cursor_y)) {
+                Span::styled("▣", Style::default().fg(theme.cursor).add_modifier(Modifier::REVERSED))
+            } else {
+                let terrain = zoom::ground(&app.sim.world, left, top, scale);
+                let mut color = if terrain == Terrain::Campfire && !fire_lit { theme.muted } else { theme.terrain(terrain) };
+                if app.sim.is_night() && !lit {
+                    color = theme.dim(color);
+                }
+                let mut style = Style::default().fg(color);
+                if tiles.clone().any(|tile| app.sim.world.designations.contains_key(&tile)) {
+                    style = style.bg(theme.designation);
+                } else if tiles.clone().any(|tile| path.contains(&tile)) {
+                    style = style.bg(theme.path);
+                } else if app.sim.is_night() && lit {
+                    style = style.bg(theme.firelight);
+                }
+                Span::styled(terrain.symbol().to_string(), style)
+            };
+            spans.push(span);
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+fn map_border(app: &App) -> Color {
+    let theme = app.theme();
+    if app.sim.alarm.is_raised() && (app.sim.tick / alarm::FLASH_TICKS).is_multiple_of(2) {
+        theme.border_alert
+    } else if app.sim.is_night() {
+        theme.border_night
+    } else {
+        theme.border
+    }
+}
+
+/// Where the simulation is spending its time, drawn over the top right of the map
+fn render_perf(frame: &mut Frame, app: &App, map: Rect) {
+    let theme = app.theme();
+    let perf = &app.perf;
+    let width = 36.min(map.width);
+    let area = Rect { x: map.x + map.width - width, y: map.y, width, height: 6.min(map.height) };
+
+    // Flag anything that is falling behind or has hit its cap
+    let budget_ms = app.speed.interval().as_secs_f64() * 1000.0;
+    let tick_color = if perf.tick_ms > budget_ms { theme.bad } else if perf.tick_ms > budget_ms / 2.0 { theme.warn } else { theme.good };
+    let path_color = if perf.paths_deferred > 0 { theme.warn } else { theme.good };
+    let alive = app.sim.orcs.iter().filter(|o| o.alive).count();
+    let at_cap = alive >= app.sim.limits.max_clan || app.sim.animals.len() >= app.sim.limits.max_animals;
+    let crowd_color = if at_cap { theme.warn } else { theme.good };
+
+    let lines = vec![
+        Line::styled(format!(" Tick  {:.2} ms of {:.0} ms", perf.tick_ms, budget_ms), Style::default().fg(tick_color)),
+        Line::styled(format!(" Frame {:.2} ms", perf.frame_ms), Style::default().fg(theme.muted)),
+        Line::styled(
+            format!(" Paths {} ({} nodes) {} late", perf.path_searches, perf.path_nodes, perf.paths_deferred),
+            Style::default().fg(path_color),
+        ),
+        Line::styled(
+            format!(" Orcs {}/{} Animals {}/{}", alive, app.sim.limits.max_clan, app.sim.animals.len(), app.sim.limits.max_animals),
+            Style::default().fg(crowd_color),
+        ),
+    ];
+    let hud = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Performance ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.muted)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(hud, area);
+}
+
+/// The tutorial lesson on screen, if any, in a box over the middle of the map
+fn render_lesson(frame: &mut Frame, app: &App, map: Rect) {
+    let Some(tutorial) = &app.tutorial else {
+        return;
+    };
+    let (Some((number, lesson)), Some(text)) = (tutorial.showing(), tutorial.text(app)) else {
+        return;
+    };
+    let theme = app.theme();
+
+    // Size the box to the wrapped text: borders, a blank line and the footer
+    let width = 64.min(map.width);
+    let inner = width.saturating_sub(4).max(1) as usize;
+    let rows: usize = text.lines().map(|l| l.chars().count().div_ceil(inner).max(1)).sum();
+    let height = (rows as u16 + 4).min(map.height);
+    let area = Rect { x: map.x + (map.width - width) / 2, y: map.y + (map.height - height) / 2, width, height };
+
+    let mut lines: Vec<Line> = text.lines().map(|l| Line::raw(l.to_string())).collect();
+    lines.push(Line::raw(""));
+    lines.push(Line::styled("Enter: continue", Style::default().fg(theme.muted)));
+    let popup = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .title(format!(" Tutorial {}/{}: {} ", number, tutorial.lessons(), lesson.title))
+            .title_style(Style::default().fg(theme.heading).add_modifier(Modifier::BOLD))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent))
+            .padding(Padding::horizontal(1)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// The ventures on offer at the campfire, when called up, in a box over the middle of the map
+fn render_campfire_menu(frame: &mut Frame, app: &App, map: Rect) {
+    if !app.campfire_menu {
+        return;
+    }
+    let theme = app.theme();
+    let mut lines: Vec<Line> = Venture::ALL.iter().enumerate().map(|(i, &venture)| {
+        match app.sim.venture_barred(venture) {
+            None => Line::from(vec![
+                Span::styled(format!("{} ", i + 1), Style::default().fg(theme.accent_bright).add_modifier(Modifier::BOLD)),
+                Span::raw(venture.describe()),
+            ]),
+            Some(reason) => Line::styled(format!("{} {} ({})", i + 1, venture.describe(), reason.to_lowercase()), Style::default().fg(theme.muted)),
+        }
+    }).collect();
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(format!("1-{}: choose  Esc: close", Venture::ALL.len()), Style::default().fg(theme.muted)));
+
+    let width = 64.min(map.width);
+    let height = (lines.len() as u16 + 2).min(map.height);
+    let area = Rect { x: map.x + (map.width - width) / 2, y: map.y + (map.height - height) / 2, width, height };
+    let popup = Paragraph::new(lines).wrap(Wrap { trim: true }).block(
+        Block::default()
+            .title(" Around the campfire ")
+            .title_style(Style::default().fg(theme.heading).add_modifier(Modifier::BOLD))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent))
+            .padding(Padding::horizontal(1)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// A page for each kind of animal, when called up, in a box over the middle
+/// of the map. Kinds not yet sighted are left blank, and ways not yet seen
+/// are marked with a question mark.
+/// Where the minimap goes over the bottom right corner of the map, if it's
+/// up and there's room for it
+fn minimap_area(app: &App, map: Rect) -> Option<Rect> {
+    let (width, height) = (minimap::COLUMNS as u16 + 2, minimap::ROWS as u16 + 2);
+    if !app.show_minimap || map.width < width + 2 || map.height < height + 2 {
+        return None;
+    }
+    Some(Rect { x: map.x + map.width - width - 1, y: map.y + map.height - height - 1, width, height })
+}
+
+/// The whole world in miniature, shaded by the lie of the land, with the
+/// campfires and orcs picked out and the part of the map in view framed
+fn render_minimap(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let (left, top, width, height) = app.in_view();
+    let (l, t) = minimap::cell(left, top);
+    let (r, b) = minimap::cell(left + width.saturating_sub(1), top + height.saturating_sub(1));
+    let camp = minimap::cell(app.sim.world.campfire_pos.0, app.sim.world.campfire_pos.1);
+    let rival_camp = minimap::cell(app.sim.rivals.camp.0, app.sim.rivals.camp.1);
+    let orcs: Vec<(usize, usize)> = app.sim.orcs.iter().filter(|o| o.alive).map(|o| minimap::cell(o.x, o.y)).collect();
+
+    let mut lines = Vec::new();
+    for row in 0..minimap::ROWS {
+        let mut spans = Vec::new();
+        for column in 0..minimap::COLUMNS {
+            let ground = match minimap::ground(&app.sim.world, column, row) {
+                Ground::Open => Style::default(),
+                Ground::Forest => Style::default().bg(theme.dim(theme.tree)),
+                Ground::Water => Style::default().bg(theme.dim(theme.water)),
+            };
+            let framed = (l..=r).contains(&column) && (t..=b).contains(&row);
+            let (glyph, fg) = if (column, row) == camp {
+                (Terrain::Campfire.symbol(), theme.campfire)
+            } else if (column, row) == rival_camp {
+                (Terrain::Campfire.symbol(), theme.rival)
+            } else if orcs.contains(&(column, row)) {
+                ('☻', theme.orc)
+            } else if framed && (column == l || column == r || row == t || row == b) {
+                let edge = match (column == l, column == r, row == t, row == b) {
+                    (true, _, true, _) => '┌',
+                    (_, true, true, _) => '┐',
+                    (true, _, _, true) => '└',
+                    (_, true, _, true) => '┘',
+                    (_, _, true, _) | (_, _, _, true) => '─',
+                    _ => '│',
+                };
+                (edge, theme.cursor)
+            } else {
+                (' ', theme.cursor)
+            };
+            spans.push(Span::styled(glyph.to_string(), ground.fg(fg).add_modifier(Modifier::BOLD)));
+        }
+        lines.push(Line::from(spans));
+    }
+
+    let minimap = Paragraph::new(lines).block(
+        Block::default()
+            .title(" World ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.muted)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(minimap, area);
+}
+
+/// What each mark on the map stands for, and the latest few left, in a box
+/// over the top left corner of the map
+fn render_legend(frame: &mut Frame, app: &App, map: Rect) {
+    if !app.show_legend {
+        return;
+    }
+    let theme = app.theme();
+    let markers = &app.sim.world.markers;
+    let mut lines: Vec<Line> = Mark::ALL
+        .iter()
+        .map(|&mark| {
+            Line::from(vec![
+                Span::styled(format!("{} ", mark.symbol()), Style::default().fg(theme.mark(mark)).add_modifier(Modifier::BOLD)),
+                Span::raw(format!("{} ({})", mark.meaning(), markers.count(mark))),
+            ])
+        })
+        .collect();
+    let latest: Vec<Line> = markers
+        .latest(LEGEND_LATEST)
+        .map(|m| Line::styled(format!("{} {}", m.mark.symbol(), m.describe(app.sim.world.day_length)), Style::default().fg(theme.muted)))
+        .collect();
+    if !latest.is_empty() {
+        lines.push(Line::raw(""));
+        lines.extend(latest);
+    }
+
+    let inner = map.inner(Margin::new(1, 1));
+    let width = (lines.iter().map(|l| l.width()).max().unwrap_or(0) as u16 + 4).min(inner.width);
+    let height = (lines.len() as u16 + 2).min(inner.height);
+    let area = Rect { x: inner.x, y: inner.y, width, height };
+    let legend = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Marks ")
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.muted))
+            .padding(Padding::horizontal(1)),
     );
+    frame.render_widget(Clear, area);
+    frame.render_widget(legend, area);
+}
 
+/// What the cursor is over, in a box just below and to the right of it, or
+/// to whichever side of it there's room
+fn render_tooltip(frame: &mut Frame, app: &App, map: Rect) {
+    if !app.show_tooltip {
+        return;
+    }
+    let theme = app.theme();
+    let lines = tooltip::describe(app, app.cursor_x, app.cursor_y);
+    let scale = app.zoom.scale();
+    let inner = map.inner(Margin::new(1, 1));
+    let column = inner.x + ((app.cursor_x - app.camera_x) / scale) as u16;
+    let row = inner.y + ((app.cursor_y - app.camera_y) / scale) as u16;
+    let width = (lines.iter().map(|l| l.chars().count()).max().unwrap_or(0) as u16 + 4).min(inner.width);
+    let height = (lines.len() as u16 + 2).min(inner.height);
+    let x = if column + 1 + width <= inner.right() { column + 1 } else { column.saturating_sub(width).max(inner.x) };
+    let y = if row + 1 + height <= inner.bottom() { row + 1 } else { row.saturating_sub(height).max(inner.y) };
+    let area = Rect { x, y, width, height };
+
+    let lines: Vec<Line> = lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, text)| match i {
+            0 => Line::styled(text, Style::default().fg(theme.heading)),
+            _ => Line::raw(text),
+        })
+        .collect();
+    let tip = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.muted))
+            .padding(Padding::horizontal(1)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(tip, area);
+}
+
+/// The pause menu, or its settings page, in a box over the middle of the screen
+fn render_menu(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let entry = |text: String, picked: bool| {
+        let style = if picked {
+            Style::default().fg(theme.accent_bright).add_modifier(Modifier::REVERSED | Modifier::BOLD)
+        } else {
+            Style::default()
+        };
+        Line::styled(text, style)
+    };
+    let (title, mut lines, keys) = match app.screen {
+        Screen::Playing => return,
+        Screen::Menu(choice) => {
+            let lines: Vec<Line> = Item::ALL.iter().enumerate().map(|(i, item)| entry(format!(" {} ", item.label()), i == choice)).collect();
+            (" Paused ", lines, "Up/Down: pick  Enter: take  Esc: resume")
+        }
+        Screen::Settings(choice) => {
+            let lines: Vec<Line> = Setting::ALL
+                .iter()
+                .enumerate()
+                .map(|(i, setting)| entry(format!(" {:<7} < {} > ", setting.label(), setting.value(app)), i == choice))
+                .collect();
+            (" Settings ", lines, "Up/Down: pick  Left/Right: change  Esc: back")
+        }
+    };
+    if let Some(note) = &app.menu_note {
+        lines.push(Line::raw(""));
+        lines.push(Line::styled(note.clone(), Style::default().fg(theme.heading)));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled(keys, Style::default().fg(theme.muted)));
+
+    let width = (lines.iter().map(|l| l.width()).max().unwrap_or(0) as u16 + 4).min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let area = Rect { x: area.x + (area.width - width) / 2, y: area.y + (area.height - height) / 2, width, height };
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .title_style(Style::default().fg(theme.heading).add_modifier(Modifier::BOLD))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent))
+            .padding(Padding::horizontal(1)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// Every key, glyph and rule, over everything but the status bar, scrolled
+/// as far down as the player has taken it
+fn render_help(frame: &mut Frame, app: &App, area: Rect) {
+    if !app.show_help {
+        return;
+    }
+    let theme = app.theme();
+    let lines = help::lines(app);
+    let width = 90.min(area.width);
+    let area = Rect { x: area.x + (area.width - width) / 2, width, ..area };
+    let popup = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((app.help_scroll, 0))
+        .block(
+            Block::default()
+                .title(" Help ")
+                .title_style(Style::default().fg(theme.heading).add_modifier(Modifier::BOLD))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.accent))
+                .padding(Padding::horizontal(1)),
+        );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+fn render_bestiary(frame: &mut Frame, app: &App, map: Rect) {
+    if !app.show_bestiary {
+        return;
+    }
+    let theme = app.theme();
+    let mut lines = Vec::new();
+    for page in app.sim.bestiary.pages() {
+        let ways = page.kind.stats().ways;
+        if page.seen == 0 {
+            lines.push(Line::styled(format!("? {:<7} not yet sighted", "???"), Style::default().fg(theme.muted)));
+            lines.push(Line::styled(format!("  ways 0/{}", ways.len()), Style::default().fg(theme.muted)));
+            continue;
+        }
+        let heading = if page.complete() { theme.good } else { theme.heading };
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", page.kind.symbol()), Style::default().fg(theme.creature(page.kind))),
+            Span::styled(format!("{:<7}", page.kind.name()), Style::default().fg(heading).add_modifier(Modifier::BOLD)),
+            Span::raw(format!(" seen {}  killed {}  tamed {}", page.seen, page.killed, page.tamed)),
+        ]));
+        let witnessed: Vec<&str> = ways.iter().map(|way| if page.witnessed.contains(way) { way.name() } else { "?" }).collect();
+        lines.push(Line::styled(
+            format!("  ways {}/{}: {}", page.witnessed.len(), ways.len(), witnessed.join(", ")),
+            Style::default().fg(theme.muted),
+        ));
+    }
+    lines.push(Line::raw(""));
+    lines.push(Line::styled("Esc: close", Style::default().fg(theme.muted)));
+
+    let width = 72.min(map.width);
+    let height = (lines.len() as u16 + 2).min(map.height);
+    let area = Rect { x: map.x + (map.width - width) / 2, y: map.y + (map.height - height) / 2, width, height };
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .title(" Bestiary ")
+            .title_style(Style::default().fg(theme.heading).add_modifier(Modifier::BOLD))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent))
+            .padding(Padding::horizontal(1)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// How much food to drop at the cursor, while asking, in a box over the middle of the map
+/// How the clan has fared day by day, over the map
+fn render_graphs(frame: &mut Frame, app: &App, map: Rect) {
+    if !app.show_graphs {
+        return;
+    }
+    let theme = app.theme();
+    let days = &app.sim.stats.history;
+    let width = 100.min(map.width);
+    let height = 30.min(map.height);
+    let area = Rect { x: map.x + (map.width - width) / 2, y: map.y + (map.height - height) / 2, width, height };
     let block = Block::default()
-        .title(title)
+        .title(format!(" The clan over {} days ", days.len()))
+        .title_style(Style::default().fg(theme.heading).add_modifier(Modifier::BOLD))
         .borders(Borders::ALL)
         .border_type(BorderType::Rounded)
-        .border_style(Style::default().fg(if app.is_night() { Color::DarkGray } else { Color::White }));
+        .border_style(Style::default().fg(theme.accent))
+        .padding(Padding::horizontal(1));
+    let inner = block.inner(area);
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    let close = Line::styled("Esc: close", Style::default().fg(theme.muted));
+    if days.is_empty() {
+        frame.render_widget(Paragraph::new(vec![Line::raw("No day has ended yet."), Line::raw(""), close]), inner);
+        return;
+    }
 
-    let map_widget = Paragraph::new(lines).block(block);
-    frame.render_widget(map_widget, area);
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(6), Constraint::Length(3), Constraint::Length(3), Constraint::Length(3), Constraint::Length(1)])
+        .split(inner);
+    let charts = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[0]);
+    // Braille dots draw a finer line, where the terminal has them
+    let marker = if app.caps.glyphs == GlyphMode::Ascii { Marker::Block } else { Marker::Braille };
+    let population: Vec<(f64, f64)> = days.iter().map(|d| (d.day as f64, d.population as f64)).collect();
+    let meat: Vec<(f64, f64)> = days.iter().map(|d| (d.day as f64, d.food_end as f64)).collect();
+    frame.render_widget(line_chart("Population", &population, theme.accent, marker, theme), charts[0]);
+    frame.render_widget(line_chart("Meat on the rack", &meat, theme.food, marker, theme), charts[1]);
+
+    let tallies = [
+        ("Births", days.iter().map(|d| d.births as u64).collect::<Vec<_>>(), theme.good),
+        ("Deaths", days.iter().map(|d| d.deaths as u64).collect(), theme.bad),
+        ("Hunts", days.iter().map(|d| d.hunts as u64).collect(), theme.accent),
+    ];
+    for (row, (name, values, color)) in rows[1..4].iter().zip(tallies) {
+        // As many of the latest days as there's room for
+        let shown = &values[values.len().saturating_sub(row.width.saturating_sub(2) as usize)..];
+        let sparkline = Sparkline::default()
+            .block(
+                Block::default()
+                    .title(format!(" {} a day, {} in all ", name, values.iter().sum::<u64>()))
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(theme.muted)),
+            )
+            .data(shown)
+            .style(Style::default().fg(color));
+        frame.render_widget(sparkline, *row);
+    }
+    frame.render_widget(Paragraph::new(close), rows[4]);
+}
+
+/// A line through `points`, day against value, scaled to fit
+fn line_chart<'a>(title: &str, points: &'a [(f64, f64)], color: Color, marker: Marker, theme: &Theme) -> Chart<'a> {
+    let first = points.first().map_or(1.0, |p| p.0);
+    let last = points.last().map_or(1.0, |p| p.0).max(first + 1.0);
+    let top = (points.iter().map(|p| p.1).fold(1.0, f64::max) * 1.1).ceil();
+    let latest = points.last().map_or(0.0, |p| p.1);
+    let dataset = Dataset::default().marker(marker).graph_type(GraphType::Line).style(Style::default().fg(color)).data(points);
+    Chart::new(vec![dataset])
+        .block(
+            Block::default()
+                .title(format!(" {} ({:.0}) ", title, latest))
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme.muted)),
+        )
+        .x_axis(
+            Axis::default()
+                .bounds([first, last])
+                .labels(vec![format!("day {}", first), format!("day {}", last)])
+                .style(Style::default().fg(theme.muted)),
+        )
+        .y_axis(
+            Axis::default()
+                .bounds([0.0, top])
+                .labels(vec!["0".to_string(), format!("{:.0}", top)])
+                .style(Style::default().fg(theme.muted)),
+        )
+}
+
+/// The waypoints still ahead of the selected orc, if it's alive and going anywhere
+fn selected_path(app: &App) -> &[(usize, usize)] {
+    app.selected_orc.and_then(|i| app.sim.orcs.get(i)).filter(|o| o.alive).map(|o| o.path_ahead()).unwrap_or_default()
+}
+
+/// Everything there is to know about the selected orc, over the map
+fn render_inspector(frame: &mut Frame, app: &App, map: Rect) {
+    let Some(orc) = app.selected_orc.filter(|_| app.inspecting).and_then(|i| app.sim.orcs.get(i)) else {
+        return;
+    };
+    let theme = app.theme();
+    let width = 100.min(map.width);
+    let height = 32.min(map.height);
+    let area = Rect { x: map.x + (map.width - width) / 2, y: map.y + (map.height - height) / 2, width, height };
+    let block = Block::default()
+        .title(format!(" {} - {} ", orc.name, orc.activity.label()))
+        .title_style(Style::default().fg(theme.heading).add_modifier(Modifier::BOLD))
+        .borders(Borders::ALL)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(theme.accent))
+        .padding(Padding::horizontal(1));
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(block.inner(area));
+    let heading = |text: &str| Line::styled(text.to_string(), Style::default().fg(theme.heading).add_modifier(Modifier::BOLD));
+    let muted = |text: String| Line::styled(text, Style::default().fg(theme.muted));
+
+    // Needs and how they've gone, then the orc itself
+    let mut left = vec![heading("Needs, last three days")];
+    let colors = [theme.good, theme.food, theme.energy, theme.water_bar, theme.warmth, theme.morale];
+    let readings = (columns[0].width as usize).saturating_sub(13);
+    for (i, need) in NEEDS.iter().enumerate() {
+        left.push(Line::from(vec![
+            Span::raw(format!("{:<7} ", need)),
+            Span::styled(sparkline(orc.diary.history(i, readings), 100.0), Style::default().fg(colors[i])),
+            Span::styled(format!(" {:.0}", orc.needs()[i]), Style::default().fg(colors[i])),
+        ]));
+    }
+    left.push(Line::raw(""));
+    left.push(heading("About"));
+    left.push(Line::raw(format!(
+        "{}, {}, {}",
+        calendar::describe_age(orc.age_days(app.sim.tick, app.sim.world.day_length)),
+        orc.sex.name(),
+        orc.job.name()
+    )));
+    let traits: Vec<&str> = orc.traits.iter().map(|t| t.name()).collect();
+    left.push(Line::from(vec![
+        Span::raw("Traits "),
+        Span::styled(if traits.is_empty() { "none".to_string() } else { traits.join(", ") }, Style::default().fg(theme.traits)),
+    ]));
+    for (skill, value) in [("Forage", orc.forage_skill), ("Hunt", orc.hunt_skill)] {
+        left.push(Line::from(vec![
+            Span::raw(format!("{:<7}", skill)),
+            Span::styled(bar(value, 100.0, 10), Style::default().fg(theme.accent)),
+            Span::raw(format!(" {:.0}", value)),
+        ]));
+    }
+    let tools: Vec<String> = orc.tools.iter().map(|t| format!("{} ({} uses left)", t.kind.name(), t.uses_left)).collect();
+    left.push(Line::from(vec![
+        Span::raw("Tools "),
+        Span::styled(if tools.is_empty() { "none".to_string() } else { tools.join(", ") }, Style::default().fg(theme.tool_rack)),
+    ]));
+    if orc.wounds >= 1.0 {
+        let dressed = if orc.dressed_ticks > 0 { ", dressed" } else { "" };
+        left.push(Line::styled(format!("Wounds {:.0}{}", orc.wounds, dressed), Style::default().fg(theme.sick)));
+    }
+    if orc.sick_ticks > 0 {
+        left.push(Line::styled("Sick", Style::default().fg(theme.sick)));
+    }
+    left.push(Line::raw(""));
+    left.push(muted("Esc: close".to_string()));
+
+    // Where it's going, what it's been about, and its kin
+    let mut right = vec![heading("Way ahead")];
+    let path = orc.path_ahead();
+    let (cols, rows) = (columns[1].width as usize, 9);
+    let west = orc.x.saturating_sub(cols / 2).min(MAP_WIDTH.saturating_sub(cols));
+    let north = orc.y.saturating_sub(rows / 2).min(MAP_HEIGHT.saturating_sub(rows));
+    for y in north..(north + rows).min(MAP_HEIGHT) {
+        let spans: Vec<Span> = (west..(west + cols).min(MAP_WIDTH))
+            .map(|x| {
+                if (x, y) == (orc.x, orc.y) {
+                    Span::styled("☻", Style::default().fg(theme.orc_selected).add_modifier(Modifier::BOLD))
+                } else if path.last() == Some(&(x, y)) {
+                    Span::styled("◈", Style::default().fg(theme.orc_selected).add_modifier(Modifier::BOLD))
+                } else {
+                    let terrain = app.sim.world.get(x, y);
+                    let style = Style::default().fg(theme.dim(theme.terrain(terrain)));
+                    let style = if path.contains(&(x, y)) { style.bg(theme.path) } else { style };
+                    Span::styled(terrain.symbol().to_string(), style)
+                }
+            })
+            .collect();
+        right.push(Line::from(spans));
+    }
+    right.push(match path.last() {
+        Some((x, y)) => muted(format!("Making for {},{}, {} steps to go", x, y, path.len())),
+        None => muted("Not going anywhere".to_string()),
+    });
+    right.push(Line::raw(""));
+    right.push(heading("Lately"));
+    for (tick, doing) in orc.diary.doings().take(6) {
+        right.push(Line::from(vec![
+            Span::styled(format!("Day {} {:<9}", calendar::day_number(*tick, app.sim.world.day_length), DayPhase::of(*tick, app.sim.world.day_length).name()), Style::default().fg(theme.muted)),
+            Span::raw(doing.clone()),
+        ]));
+    }
+    right.push(Line::raw(""));
+    right.push(heading("Family"));
+    let names = |pick: &dyn Fn(&crate::orc::Orc) -> bool| -> Vec<&str> {
+        app.sim.orcs.iter().filter(|o| o.alive && o.name != orc.name && pick(o)).map(|o| o.name.as_str()).collect()
+    };
+    right.push(Line::raw(match &orc.parents {
+        Some((mother, father)) => format!("Child of {} and {}", mother, father),
+        None => "One of the clan's founders".to_string(),
+    }));
+    let children = names(&|o| o.parents.as_ref().is_some_and(|(m, f)| *m == orc.name || *f == orc.name));
+    if !children.is_empty() {
+        right.push(Line::raw(format!("Children: {}", children.join(", "))));
+    }
+    if let Some(carer) = &orc.carer {
+        right.push(Line::raw(format!("Carried about by {}", carer)));
+    }
+    let carried = names(&|o| o.carer.as_ref() == Some(&orc.name));
+    if !carried.is_empty() {
+        right.push(Line::raw(format!("Carrying {}", carried.join(", "))));
+    }
+    if orc.hut.is_some() {
+        let mates = names(&|o| o.hut == orc.hut);
+        if !mates.is_empty() {
+            right.push(Line::raw(format!("Shares a hut with {}", mates.join(", "))));
+        }
+    }
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(block, area);
+    frame.render_widget(Paragraph::new(left).wrap(Wrap { trim: true }), columns[0]);
+    frame.render_widget(Paragraph::new(right).wrap(Wrap { trim: false }), columns[1]);
+}
+
+fn render_food_prompt(frame: &mut Frame, app: &App, map: Rect) {
+    let Some(typed) = &app.food_prompt else {
+        return;
+    };
+    let theme = app.theme();
+    let lines = vec![
+        Line::from(vec![
+            Span::raw("How much? "),
+            Span::styled(format!("{}_", typed), Style::default().fg(theme.accent_bright).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::raw(""),
+        Line::styled(format!("1-{}, Enter: drop  Esc: cancel", MAX_DROP), Style::default().fg(theme.muted)),
+    ];
+
+    let width = 40.min(map.width);
+    let height = (lines.len() as u16 + 2).min(map.height);
+    let area = Rect { x: map.x + (map.width - width) / 2, y: map.y + (map.height - height) / 2, width, height };
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .title(format!(" Drop food at ({},{}) ", app.cursor_x, app.cursor_y))
+            .title_style(Style::default().fg(theme.heading).add_modifier(Modifier::BOLD))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent))
+            .padding(Padding::horizontal(1)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
+}
+
+/// The notice showing, in a banner across the top of the map
+fn render_banner(frame: &mut Frame, app: &App, map: Rect) {
+    let Some(notice) = app.sim.notices.showing() else {
+        return;
+    };
+    let theme = app.theme();
+    let mut footer = format!(" {}: dismiss ", app.input.keymap.describe(Command::DismissNotice));
+    if app.sim.notices.waiting() > 0 {
+        footer = format!(" {} more |{}", app.sim.notices.waiting(), footer);
+    }
+    let area = Rect { x: map.x + 2, y: map.y + 1, width: map.width.saturating_sub(4), height: 3.min(map.height.saturating_sub(1)) };
+    let text = Line::from(vec![
+        Span::styled(format!("[{}] ", notice.tick), Style::default().fg(theme.muted)),
+        Span::styled(notice.message.as_str(), Style::default().fg(theme.bad).add_modifier(Modifier::BOLD)),
+    ]);
+    let banner = Paragraph::new(text).block(
+        Block::default()
+            .title(format!(" {} ", notice.kind.title()))
+            .title_bottom(Line::from(footer).right_aligned())
+            .title_style(Style::default().fg(theme.bad).add_modifier(Modifier::BOLD))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Thick)
+            .border_style(Style::default().fg(theme.bad))
+            .padding(Padding::horizontal(1)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(banner, area);
+}
+
+fn render_naming(frame: &mut Frame, app: &App, map: Rect) {
+    let Some((naming, typed)) = &app.naming else {
+        return;
+    };
+    let theme = app.theme();
+    let (title, hint) = match naming {
+        Naming::Orc(name) => (format!(" Rename {} ", name), "Enter: rename  Esc: cancel"),
+        Naming::Clan => (" Name the clan ".to_string(), "Enter: name it (empty for none)  Esc: cancel"),
+    };
+    let lines = vec![
+        Line::from(vec![
+            Span::raw("Name: "),
+            Span::styled(format!("{}_", typed), Style::default().fg(theme.accent_bright).add_modifier(Modifier::BOLD)),
+        ]),
+        Line::raw(""),
+        Line::styled(hint, Style::default().fg(theme.muted)),
+    ];
+
+    let width = 50.min(map.width);
+    let height = (lines.len() as u16 + 2).min(map.height);
+    let area = Rect { x: map.x + (map.width - width) / 2, y: map.y + (map.height - height) / 2, width, height };
+    let popup = Paragraph::new(lines).block(
+        Block::default()
+            .title(title)
+            .title_style(Style::default().fg(theme.heading).add_modifier(Modifier::BOLD))
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.accent))
+            .padding(Padding::horizontal(1)),
+    );
+    frame.render_widget(Clear, area);
+    frame.render_widget(popup, area);
 }
 
 fn render_event_log(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
     let height = area.height.saturating_sub(2) as usize;
-    let events = app.event_log.recent(height);
+    let mut back = app.log_scroll.min(app.sim.event_log.held().saturating_sub(height));
+    // Events keep coming in while one is picked out; keep it in view
+    if let Some(number) = app.log_pick
+        && height > 0
+    {
+        let since = app.sim.event_log.shown_since(number);
+        back = back.clamp((since + 1).saturating_sub(height), since);
+    }
+    let events = app.sim.event_log.recent_before(height, back);
+    let picked = app.picked_event();
 
     let items: Vec<ListItem> = events
-        .iter()
+        .into_iter()
         .map(|e| {
-            ListItem::new(Line::from(vec![
+            let item = ListItem::new(Line::from(vec![
                 Span::styled(
                     format!("[{:>4}] ", e.tick),
-                    Style::default().fg(Color::DarkGray),
+                    Style::default().fg(theme.muted),
                 ),
-                Span::styled(&e.message, Style::default().fg(e.color)),
-            ]))
+                Span::styled(&e.message, theme.severity(e.severity).map_or_else(Style::default, |color| Style::default().fg(color))),
+            ]));
+            if picked.is_some_and(|p| std::ptr::eq(p, e)) { item.style(Style::default().add_modifier(Modifier::REVERSED)) } else { item }
         })
         .collect();
 
     let list = List::new(items).block(
         Block::default()
-            .title(" Events ")
+            .title(log_title(app, back))
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(Style::default().fg(theme.muted)),
     );
     frame.render_widget(list, area);
 }
 
+/// How far back the log is scrolled and what it's hiding
+fn log_title(app: &App, back: usize) -> String {
+    let mut title = " Events".to_string();
+    if back > 0 {
+        title.push_str(&format!(" ({} back)", back));
+    }
+    let hidden: Vec<&str> = app.sim.event_log.hidden().iter().map(|c| c.name()).collect();
+    if !hidden.is_empty() {
+        title.push_str(&format!(" [hiding {}]", hidden.join(", ")));
+    }
+    if app.log_pick.is_some() {
+        match app.picked_event().and_then(|e| e.at) {
+            Some((x, y)) => title.push_str(&format!(" - picked: at ({}, {})", x, y)),
+            None => title.push_str(" - picked: nowhere in particular"),
+        }
+    }
+    title.push(' ');
+    title
+}
+
 fn render_sidebar(frame: &mut Frame, app: &App, area: Rect) {
+    let theme = app.theme();
+    let animal = app.animal_at_cursor();
     let chunks = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(10), Constraint::Length(9)])
+        .constraints([Constraint::Min(10), Constraint::Length(if animal.is_some() { 7 } else { 0 }), Constraint::Length(3)])
         .split(area);
 
     // Orc details
     let mut items: Vec<ListItem> = Vec::new();
-    for (i, orc) in app.orcs.iter().enumerate() {
+    for (i, orc) in app.sim.orcs.iter().enumerate() {
         if !orc.alive {
             items.push(ListItem::new(Line::from(vec![
                 Span::styled("  ", Style::default()),
-                Span::styled(&orc.name, Style::default().fg(Color::DarkGray)),
-                Span::styled(" (Dead)", Style::default().fg(Color::Red)),
+                Span::styled(&orc.name, Style::default().fg(theme.muted)),
+                Span::styled(" (Dead)", Style::default().fg(theme.bad)),
             ])));
             continue;
         }
 
         let selected = app.selected_orc == Some(i);
         let name_style = if selected {
-            Style::default().fg(Color::LightGreen).add_modifier(Modifier::BOLD)
+            Style::default().fg(theme.accent_bright).add_modifier(Modifier::BOLD)
         } else {
-            Style::default().fg(Color::Green)
+            Style::default().fg(theme.accent)
         };
 
         let health_bar = bar(orc.health, 100.0, 6);
         let hunger_bar = bar(orc.hunger, 100.0, 6);
         let energy_bar = bar(orc.energy, 100.0, 6);
         let thirst_bar = bar(orc.thirst, 100.0, 6);
+        let warmth_bar = bar(orc.warmth, 100.0, 6);
+        let morale_bar = bar(orc.morale, 100.0, 6);
+        let forage_bar = bar(orc.forage_skill, 100.0, 6);
+        let hunt_bar = bar(orc.hunt_skill, 100.0, 6);
+        let birthday = if orc.is_birthday(app.sim.tick, app.sim.world.day_length) { " - birthday!" } else { "" };
 
-        let health_color = if orc.health < 30.0 { Color::Red } else if orc.health < 60.0 { Color::Yellow } else { Color::Green };
-        let hunger_color = if orc.hunger > 70.0 { Color::Red } else if orc.hunger > 40.0 { Color::Yellow } else { Color::Green };
-        let energy_color = if orc.energy < 20.0 { Color::Red } else if orc.energy < 50.0 { Color::Yellow } else { Color::Cyan };
-        let thirst_color = if orc.thirst > 70.0 { Color::Red } else if orc.thirst > 40.0 { Color::Yellow } else { Color::Rgb(65, 105, 225) };
+        let health_color = if orc.health < 30.0 { theme.bad } else if orc.health < 60.0 { theme.warn } else { theme.good };
+        let hunger_color = if orc.hunger > 70.0 { theme.bad } else if orc.hunger > 40.0 { theme.warn } else { theme.good };
+        let energy_color = if orc.energy < 20.0 { theme.bad } else if orc.energy < 50.0 { theme.warn } else { theme.energy };
+        let thirst_color = if orc.thirst > 70.0 { theme.bad } else if orc.thirst > 40.0 { theme.warn } else { theme.water_bar };
+        let warmth_color = if orc.warmth < 20.0 { theme.bad } else if orc.warmth < 50.0 { theme.warn } else { theme.warmth };
+        let morale_color = if orc.morale < 25.0 { theme.bad } else if orc.morale < 45.0 { theme.warn } else { theme.morale };
 
         items.push(ListItem::new(vec![
             Line::from(vec![
                 Span::styled(if selected { "> " } else { "  " }, name_style),
                 Span::styled(&orc.name, name_style),
-                Span::styled(format!(" ({})", orc.activity.label()), Style::default().fg(Color::DarkGray)),
+                Span::styled(
+                    orc.traits.iter().map(|t| format!(" [{}]", t.tag())).collect::<String>(),
+                    Style::default().fg(theme.traits),
+                ),
+                Span::styled(format!(" ({})", orc.activity.label()), Style::default().fg(theme.muted)),
+                Span::styled(if orc.sick_ticks > 0 { " sick" } else { "" }, Style::default().fg(theme.sick)),
+            ]),
+            Line::from(vec![
+                Span::raw("   Age "),
+                Span::styled(calendar::describe_age(orc.age_days(app.sim.tick, app.sim.world.day_length)), Style::default().fg(theme.muted)),
+                Span::styled(birthday, Style::default().fg(theme.morale)),
+                Span::styled(format!(" {}", orc.sex.name()), Style::default().fg(theme.muted)),
+                Span::styled(format!(" {}", orc.job.name()), Style::default().fg(theme.muted)),
+                Span::styled(
+                    orc.tools.iter().map(|t| format!(" {}", t.kind.name())).collect::<String>(),
+                    Style::default().fg(theme.tool_rack),
+                ),
+                Span::styled(
+                    orc.hut.map(|(x, y)| format!(" hut {},{}", x, y)).unwrap_or_default(),
+                    Style::default().fg(theme.building),
+                ),
             ]),
             Line::from(vec![
                 Span::raw("   HP "),
                 Span::styled(health_bar, Style::default().fg(health_color)),
                 Span::styled(format!(" {:.0}", orc.health), Style::default().fg(health_color)),
+                Span::styled(
+                    match (orc.wounds >= 1.0, orc.dressed_ticks > 0) {
+                        (true, true) => format!(" wounds {:.0}, dressed", orc.wounds),
+                        (true, false) => format!(" wounds {:.0}", orc.wounds),
+                        (false, _) => String::new(),
+                    },
+                    Style::default().fg(theme.sick),
+                ),
             ]),
             Line::from(vec![
                 Span::raw("   Hun"),
@@ -217,6 +1408,34 @@ fn render_sidebar(frame: &mut Frame, app: &App, area: Rect) {
                 Span::styled(thirst_bar, Style::default().fg(thirst_color)),
                 Span::styled(format!(" {:.0}", orc.thirst), Style::default().fg(thirst_color)),
             ]),
+            Line::from(vec![
+                Span::raw("   Wrm"),
+                Span::styled(warmth_bar, Style::default().fg(warmth_color)),
+                Span::styled(format!(" {:.0}", orc.warmth), Style::default().fg(warmth_color)),
+            ]),
+            Line::from(vec![
+                Span::raw("   Mor"),
+                Span::styled(morale_bar, Style::default().fg(morale_color)),
+                Span::styled(format!(" {:.0}", orc.morale), Style::default().fg(morale_color)),
+            ]),
+            Line::from(vec![
+                Span::raw("   Frg"),
+                Span::styled(forage_bar, Style::default().fg(theme.forage)),
+                Span::styled(format!(" {:.0}", orc.forage_skill), Style::default().fg(theme.forage)),
+                Span::styled(
+                    if orc.berries > 0 { format!(" +{} berries", orc.berries) } else { String::new() },
+                    Style::default().fg(theme.muted),
+                ),
+                Span::styled(
+                    if orc.herbs > 0 { format!(" +{} herbs", orc.herbs) } else { String::new() },
+                    Style::default().fg(theme.herb),
+                ),
+            ]),
+            Line::from(vec![
+                Span::raw("   Hnt"),
+                Span::styled(hunt_bar, Style::default().fg(theme.forage)),
+                Span::styled(format!(" {:.0}", orc.hunt_skill), Style::default().fg(theme.forage)),
+            ]),
             Line::raw(""),
         ]));
     }
@@ -226,27 +1445,95 @@ fn render_sidebar(frame: &mut Frame, app: &App, area: Rect) {
             .title(" Clan ")
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::Green)),
+            .border_style(Style::default().fg(theme.accent)),
     );
     frame.render_widget(orc_list, chunks[0]);
 
-    // Help
-    let help_text = vec![
-        Line::styled(" Controls:", Style::default().fg(Color::White).add_modifier(Modifier::BOLD)),
-        Line::styled(" Space  Pause/Resume", Style::default().fg(Color::DarkGray)),
-        Line::styled(" +/-    Speed up/down", Style::default().fg(Color::DarkGray)),
-        Line::styled(" Arrows Move cursor", Style::default().fg(Color::DarkGray)),
-        Line::styled(" Tab    Select orc", Style::default().fg(Color::DarkGray)),
-        Line::styled(" f      Drop food", Style::default().fg(Color::DarkGray)),
-        Line::styled(" q      Quit", Style::default().fg(Color::DarkGray)),
+    if let Some(animal) = animal {
+        render_animal(frame, app, animal, chunks[1]);
+    }
+
+    // Everything else is on the help screen, under whatever key opens it
+    let help = Paragraph::new(Line::styled(
+        format!(" {}  Help: keys, map, rules", app.input.keymap.describe(Command::ToggleHelp)),
+        Style::default().fg(theme.muted),
+    ))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(theme.muted)),
+    );
+    frame.render_widget(help, chunks[2]);
+}
+
+/// What the animal under the cursor is doing and how it's faring, and the
+/// herd, pack or pen it belongs to
+fn render_animal(frame: &mut Frame, app: &App, animal: &Animal, area: Rect) {
+    let theme = app.theme();
+    let kind = animal.kind.name().to_lowercase();
+    let need = |label: &str, value: f32| {
+        let color = if value > 70.0 { theme.bad } else if value > 40.0 { theme.warn } else { theme.good };
+        Line::from(vec![
+            Span::raw(format!(" {}", label)),
+            Span::styled(bar(value, 100.0, 6), Style::default().fg(color)),
+            Span::styled(format!(" {:.0}", value), Style::default().fg(color)),
+        ])
+    };
+    let full = animal.kind.stats().health;
+    let health_color = if animal.health < full * 0.3 { theme.bad } else if animal.health < full * 0.6 { theme.warn } else { theme.good };
+    let mut doing = animal.behaviour.name().to_string();
+    if app.sim.tick < animal.rests_until {
+        doing.push_str(", sated");
+    }
+
+    let kin = |same: &dyn Fn(&Animal) -> bool| app.sim.animals.iter().filter(|a| a.alive && same(a)).count();
+    let belongs = if let Some(herd) = animal.herd {
+        format!(" Herd of {} {}", kin(&|a| a.herd == Some(herd)), kind)
+    } else if let Some(id) = animal.pack {
+        let leads = pack::leader(&app.sim.animals, id).is_some_and(|l| std::ptr::eq(&app.sim.animals[l], animal));
+        match kin(&|a| a.pack == Some(id)) {
+            1 => " Last of its pack".to_string(),
+            n => format!(" Pack of {} wolves{}", n, if leads { ", leading it" } else { "" }),
+        }
+    } else if let Some(tame) = animal.tame {
+        match &animal.carried_by {
+            Some(herder) => format!(" Carried by {}", herder),
+            None => format!(" Tameness {:.0}", tame),
+        }
+    } else {
+        " On its own".to_string()
+    };
+
+    let mut lines = vec![
+        Line::styled(format!(" {}", doing), Style::default().fg(theme.muted)),
+        Line::from(vec![
+            Span::raw(" HP "),
+            Span::styled(bar(animal.health, full, 6), Style::default().fg(health_color)),
+            Span::styled(format!(" {:.0}", animal.health), Style::default().fg(health_color)),
+        ]),
     ];
-    let help = Paragraph::new(help_text).block(
+    if animal.kind.is_game() {
+        lines.push(need("Hun", animal.hunger));
+        lines.push(need("H2O", animal.thirst));
+    }
+    lines.push(Line::styled(belongs, Style::default().fg(theme.muted)));
+
+    let panel = Paragraph::new(lines).block(
         Block::default()
+            .title(format!(" {} ({},{}) ", animal.kind.name(), animal.x, animal.y))
+            .title_style(Style::default().fg(theme.creature(animal.kind)).add_modifier(Modifier::BOLD))
             .borders(Borders::ALL)
             .border_type(BorderType::Rounded)
-            .border_style(Style::default().fg(Color::DarkGray)),
+            .border_style(Style::default().fg(theme.muted)),
     );
-    frame.render_widget(help, chunks[1]);
+    frame.render_widget(panel, area);
+}
+
+/// A line of block heights, one for each of `values` out of `max`
+fn sparkline(values: impl Iterator<Item = f32>, max: f32) -> String {
+    const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    values.map(|v| LEVELS[((v / max).clamp(0.0, 1.0) * 7.0).round() as usize]).collect()
 }
 
 fn bar(value: f32, max: f32, width: usize) -> String {
@@ -258,11 +1545,3 @@ fn bar(value: f32, max: f32, width: usize) -> String {
     let transition = if has_transition { "▒" } else { "" };
     format!("[{}{}{}]", "▓".repeat(filled), transition, "░".repeat(empty))
 }
-
-fn dim_color(color: Color) -> Color {
-    match color {
-        Color::Rgb(r, g, b) => Color::Rgb(r / 3, g / 3, b / 3),
-        Color::Gray => Color::DarkGray,
-        _ => Color::DarkGray,
-    }
-}
@@ -0,0 +1,177 @@
+//! The rival clan across the map.
+
+use std::ops::Range;
+
+use rand::Rng;
+
+use crate::combat::{Combatant, Weapon};
+use crate::orc;
+use crate::pathfinding::Route;
+use crate::world::{MAP_HEIGHT, MAP_WIDTH, Terrain, World};
+
+/// Orcs in the rival clan when it's at full strength
+pub const CLAN_SIZE: usize = 4;
+/// How far across the map from our campfire the rivals make camp
+const CAMP_DISTANCE: usize = 55;
+/// How far from their fire the rivals roam
+const RANGE: usize = 50;
+/// How close one of our orcs has to come for a rival to go for it
+pub const SIGHT: usize = 6;
+/// A rival's health when unhurt, below which it runs home, and how much it
+/// gets back each tick there
+pub const RIVAL_HEALTH: f32 = 40.0;
+const RIVAL_FLEE: f32 = 15.0;
+const MENDING: f32 = 0.2;
+/// What a rival fights with
+pub const AXE: Weapon = Weapon { chance: 0.5, damage: 4.0..9.0 };
+/// Ticks a rival lingers foraging at each spot it goes to
+const LINGER: Range<u64> = 20..60;
+/// Ticks the rivals take to replace one of their own who was killed
+pub fn newcomer(day_length: u64) -> u64 {
+    4 * day_length
+}
+
+pub struct Rival {
+    pub name: String,
+    pub x: usize,
+    pub y: usize,
+    pub health: f32,
+    /// Beaten, and going home to mend
+    pub beaten: bool,
+    /// Going after one of our orcs
+    pub hostile: bool,
+    /// Where it's foraging next, if it has somewhere in mind
+    goal: Option<(usize, usize)>,
+    /// Tick it's done foraging where it stands
+    lingers_until: u64,
+    route: Route,
+}
+
+impl Rival {
+    fn new(name: String, x: usize, y: usize) -> Self {
+        Rival { name, x, y, health: RIVAL_HEALTH, beaten: false, hostile: false, goal: None, lingers_until: 0, route: Route::default() }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health <= 0.0
+    }
+
+    /// Take a step towards (tx, ty)
+    pub fn advance(&mut self, tx: usize, ty: usize, world: &World) {
+        (self.x, self.y) = self.route.step((self.x, self.y), (tx, ty), world);
+    }
+
+    /// Go about the rival clan's business: foraging out from `camp` by day,
+    /// and going home at night or to mend once beaten
+    pub fn roam(&mut self, camp: (usize, usize), daylight: bool, world: &World, rng: &mut impl Rng, tick: u64) {
+        let home = self.x.abs_diff(camp.0).max(self.y.abs_diff(camp.1)) <= 2;
+        if self.beaten && home {
+            self.health = (self.health + MENDING).min(RIVAL_HEALTH);
+            self.beaten = self.health < RIVAL_HEALTH;
+        }
+        if self.beaten || !daylight {
+            self.goal = None;
+            if !home {
+                self.advance(camp.0, camp.1, world);
+            }
+            return;
+        }
+        match self.goal {
+            Some((gx, gy)) if (self.x, self.y) == (gx, gy) => {
+                self.goal = None;
+                self.lingers_until = tick + rng.gen_range(LINGER);
+            }
+            Some((gx, gy)) => self.advance(gx, gy, world),
+            None if tick >= self.lingers_until => {
+                let mut spread = |c: usize, len: usize| (c + rng.gen_range(0..=RANGE * 2)).saturating_sub(RANGE).min(len - 1);
+                let (x, y) = (spread(camp.0, MAP_WIDTH), spread(camp.1, MAP_HEIGHT));
+                if world.is_walkable(x, y) {
+                    self.goal = Some((x, y));
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+impl Combatant for Rival {
+    fn health(&self) -> f32 {
+        self.health
+    }
+
+    fn max_health(&self) -> f32 {
+        RIVAL_HEALTH
+    }
+
+    fn hurt(&mut self, damage: f32, _tick: u64) {
+        self.health -= damage;
+    }
+
+    fn breaks(&self) -> bool {
+        self.health < RIVAL_FLEE
+    }
+}
+
+pub struct RivalClan {
+    /// Where their campfire burns
+    pub camp: (usize, usize),
+    pub members: Vec<Rival>,
+    /// Tick the rivals last took in a newcomer, or lost one of their own
+    changed: u64,
+}
+
+impl RivalClan {
+    /// Make camp for the rivals across the map from ours, east or west,
+    /// taking care that none of them shares a name with one of `names`
+    pub fn settle(world: &World, names: &[String], rng: &mut impl Rng) -> RivalClan {
+        let (cx, cy) = world.campfire_pos;
+        let x = if rng.gen_bool(0.5) { cx + CAMP_DISTANCE } else { cx.saturating_sub(CAMP_DISTANCE) };
+        let y = (cy + rng.gen_range(0..=RANGE)).saturating_sub(RANGE / 2).min(MAP_HEIGHT - 1);
+        let camp = world.find_nearest(x.min(MAP_WIDTH - 1), y, Terrain::Grass).unwrap_or((x, y));
+        let mut clan = RivalClan { camp, members: Vec::new(), changed: 0 };
+        let mut taken = names.to_vec();
+        for _ in 0..CLAN_SIZE {
+            taken.push(clan.take_in(&taken, rng));
+        }
+        clan
+    }
+
+    /// Welcome a newcomer at the rivals' fire, named unlike any in `taken`,
+    /// and return its name
+    fn take_in(&mut self, taken: &[String], rng: &mut impl Rng) -> String {
+        let name = orc::pick_name(rng, taken);
+        self.members.push(Rival::new(name.clone(), self.camp.0, self.camp.1));
+        name
+    }
+
+    /// Take in a newcomer if the clan is short and it has been long enough
+    /// since the last change, returning its name
+    pub fn make_good(&mut self, names: &[String], rng: &mut impl Rng, tick: u64, day_length: u64) -> Option<String> {
+        if self.members.len() >= CLAN_SIZE || tick < self.changed + newcomer(day_length) {
+            return None;
+        }
+        let mut taken = names.to_vec();
+        taken.extend(self.members.iter().map(|r| r.name.clone()));
+        self.changed = tick;
+        Some(self.take_in(&taken, rng))
+    }
+
+    /// Strike off any rivals killed in a skirmish, returning their names
+    pub fn clear_dead(&mut self, tick: u64) -> Vec<String> {
+        let mut fallen = Vec::new();
+        self.members.retain(|r| {
+            if r.is_dead() {
+                fallen.push(r.name.clone());
+            }
+            !r.is_dead()
+        });
+        if !fallen.is_empty() {
+            self.changed = tick;
+        }
+        fallen
+    }
+
+    pub fn member_at(&self, x: usize, y: usize) -> Option<&Rival> {
+        self.members.iter().find(|r| r.x == x && r.y == y)
+    }
+}
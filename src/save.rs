@@ -0,0 +1,198 @@
+//! The pause menu's save, a replay of the session packed into a versioned binary form.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::mouse::Gesture;
+use crate::recording::{self, Input, NAMED_KEYS, Press, Recording};
+
+/// What every save since version 2 starts with
+pub const MAGIC: &[u8] = b"orcsave";
+/// The version saves are written in
+const VERSION: u8 = 2;
+
+/// What each input is, ahead of what follows it
+const CHAR: u8 = 0;
+const FUNCTION: u8 = 1;
+/// Named keys follow on from here, in the order of `NAMED_KEYS`
+const NAMED: u8 = 2;
+const CLICK: u8 = 100;
+const PAN: u8 = 101;
+const SCROLL: u8 = 102;
+const ORDER: u8 = 103;
+
+/// `session` as a save made at `tick`
+pub fn encode(session: &Recording, tick: u64) -> Vec<u8> {
+    let mut out = MAGIC.to_vec();
+    out.push(VERSION);
+    let mut header = Vec::new();
+    recording::write_header(&mut header, &session.header).expect("writing to memory can't fail");
+    put(&mut out, header.len() as u64);
+    out.extend(header);
+
+    let mut inputs = Vec::new();
+    let mut count = 0;
+    let (mut ms, mut at) = (0, 0);
+    for press in &session.presses {
+        let mut entry = Vec::new();
+        put_signed(&mut entry, press.ms as i64 - ms as i64);
+        put_signed(&mut entry, press.tick as i64 - at as i64);
+        match &press.input {
+            Input::Key(event) => {
+                // Keys a recording can't hold, such as media keys, are left out of a save too
+                let Some((tag, extra)) = code_tag(event.code) else {
+                    continue;
+                };
+                entry.push(tag);
+                if let Some(extra) = extra {
+                    put(&mut entry, extra);
+                }
+                entry.push(event.modifiers.bits());
+            }
+            Input::Mouse(Gesture::Click { x, y }) => {
+                entry.push(CLICK);
+                put(&mut entry, *x as u64);
+                put(&mut entry, *y as u64);
+            }
+            Input::Mouse(Gesture::Pan { dx, dy }) => {
+                entry.push(PAN);
+                put_signed(&mut entry, *dx as i64);
+                put_signed(&mut entry, *dy as i64);
+            }
+            Input::Mouse(Gesture::Scroll(n)) => {
+                entry.push(SCROLL);
+                put_signed(&mut entry, *n as i64);
+            }
+            Input::Order(order) => {
+                entry.push(ORDER);
+                put(&mut entry, order.len() as u64);
+                entry.extend(order.as_bytes());
+            }
+        }
+        inputs.extend(entry);
+        count += 1;
+        (ms, at) = (press.ms, press.tick);
+    }
+    put(&mut out, count);
+    out.extend(inputs);
+    put(&mut out, tick);
+    out
+}
+
+/// The tag a key is saved under, and the number that goes with it if any
+fn code_tag(code: KeyCode) -> Option<(u8, Option<u64>)> {
+    match code {
+        KeyCode::Char(c) => Some((CHAR, Some(c as u64))),
+        KeyCode::F(n) => Some((FUNCTION, Some(n as u64))),
+        other => NAMED_KEYS.iter().position(|(k, _)| *k == other).map(|i| (NAMED + i as u8, None)),
+    }
+}
+
+/// Read a save in whichever version of the format it was written in
+pub fn decode(bytes: &[u8]) -> Result<Recording, String> {
+    let Some(rest) = bytes.strip_prefix(MAGIC) else {
+        let text = std::str::from_utf8(bytes).map_err(|_| "not a save or a recording".to_string())?;
+        return Recording::parse(text);
+    };
+    let mut reader = Reader { bytes: rest };
+    match reader.byte()? {
+        2 => read_v2(&mut reader),
+        version if version > VERSION => Err(format!("saved by a newer version of the game (save format {}, this one reads up to {})", version, VERSION)),
+        version => Err(format!("unknown save format {}", version)),
+    }
+}
+
+fn read_v2(reader: &mut Reader) -> Result<Recording, String> {
+    let len = reader.number()? as usize;
+    let header = std::str::from_utf8(reader.take(len)?).map_err(|_| "damaged save: header isn't text".to_string())?;
+    let mut session = Recording::parse(header)?;
+    let count = reader.number()?;
+    let (mut ms, mut tick) = (0i64, 0i64);
+    for _ in 0..count {
+        ms += reader.signed()?;
+        tick += reader.signed()?;
+        let input = match reader.byte()? {
+            CHAR => {
+                let c = char::from_u32(reader.number()? as u32).ok_or("damaged save: bad character")?;
+                Input::Key(KeyEvent::new(KeyCode::Char(c), reader.modifiers()?))
+            }
+            FUNCTION => {
+                let n = reader.number()? as u8;
+                Input::Key(KeyEvent::new(KeyCode::F(n), reader.modifiers()?))
+            }
+            CLICK => Input::Mouse(Gesture::Click { x: reader.number()? as usize, y: reader.number()? as usize }),
+            PAN => Input::Mouse(Gesture::Pan { dx: reader.signed()? as i32, dy: reader.signed()? as i32 }),
+            SCROLL => Input::Mouse(Gesture::Scroll(reader.signed()? as i32)),
+            ORDER => {
+                let len = reader.number()? as usize;
+                let order = std::str::from_utf8(reader.take(len)?).map_err(|_| "damaged save: order isn't text".to_string())?;
+                Input::Order(order.to_string())
+            }
+            tag => {
+                let &(code, _) = NAMED_KEYS.get(tag.wrapping_sub(NAMED) as usize).ok_or("damaged save: unknown entry")?;
+                Input::Key(KeyEvent::new(code, reader.modifiers()?))
+            }
+        };
+        if ms < 0 || tick < 0 {
+            return Err("damaged save: time runs backwards".to_string());
+        }
+        session.presses.push(Press { ms: ms as u64, tick: tick as u64, input });
+    }
+    session.saved_at = Some(reader.number()?);
+    Ok(session)
+}
+
+/// Append `n` seven bits at a time, low bits first, with the top bit set
+/// on every byte but the last
+fn put(out: &mut Vec<u8>, mut n: u64) {
+    while n >= 0x80 {
+        out.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+/// Append `n` as `put` does, with small negative numbers kept small too
+fn put_signed(out: &mut Vec<u8>, n: i64) {
+    put(out, ((n << 1) ^ (n >> 63)) as u64);
+}
+
+/// Reads a save from the front, failing on anything cut short
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        if self.bytes.len() < n {
+            return Err("damaged save: cut short".to_string());
+        }
+        let (taken, rest) = self.bytes.split_at(n);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn byte(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn number(&mut self) -> Result<u64, String> {
+        let mut n = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            n |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(n);
+            }
+        }
+        Err("damaged save: number too long".to_string())
+    }
+
+    fn signed(&mut self) -> Result<i64, String> {
+        let n = self.number()?;
+        Ok((n >> 1) as i64 ^ -((n & 1) as i64))
+    }
+
+    fn modifiers(&mut self) -> Result<KeyModifiers, String> {
+        Ok(KeyModifiers::from_bits_truncate(self.byte()?))
+    }
+}
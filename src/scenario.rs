@@ -0,0 +1,88 @@
+//! Scenarios a game can be started in with `--scenario`.
+
+/// What kind of game to play
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Scenario {
+    #[default]
+    Sandbox,
+    Defense,
+}
+
+impl Scenario {
+    pub fn parse(s: &str) -> Option<Scenario> {
+        match s {
+            "sandbox" => Some(Scenario::Sandbox),
+            "defense" => Some(Scenario::Defense),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scenario::Sandbox => "sandbox",
+            Scenario::Defense => "defense",
+        }
+    }
+}
+
+/// Day the first wave comes, and days between one wave and the next
+const FIRST_WAVE: u64 = 3;
+const WAVE_DAYS: u64 = 3;
+/// Goblins in the first wave, how many more come with each wave after, and
+/// the most there can be in one
+const FIRST_BAND: usize = 3;
+const BAND_GROWTH: usize = 2;
+const MAX_BAND: usize = 20;
+/// Condition a goblin knocks off the hearth each tick it spends smashing it
+pub const SMASH: u32 = 10;
+/// Score for each day held, and for each goblin killed
+const DAY_SCORE: u64 = 10;
+const KILL_SCORE: u64 = 5;
+
+/// How the defense of the campfire is going
+pub struct Siege {
+    /// Waves sent so far
+    pub waves: u32,
+    /// Day the next wave comes
+    next_wave: u64,
+    /// Goblins killed so far
+    pub killed: usize,
+    /// Day the hearth was smashed, ending the run
+    pub fallen: Option<u64>,
+}
+
+impl Default for Siege {
+    fn default() -> Self {
+        Siege::new()
+    }
+}
+
+impl Siege {
+    pub fn new() -> Siege {
+        Siege { waves: 0, next_wave: FIRST_WAVE, killed: 0, fallen: None }
+    }
+
+    /// If a wave is due on `day` and the run isn't over, count it in and
+    /// return how many goblins it brings
+    pub fn call_wave(&mut self, day: u64) -> Option<usize> {
+        if self.fallen.is_some() || day < self.next_wave {
+            return None;
+        }
+        let size = (FIRST_BAND + self.waves as usize * BAND_GROWTH).min(MAX_BAND);
+        self.waves += 1;
+        self.next_wave = day + WAVE_DAYS;
+        Some(size)
+    }
+
+    /// The score for holding out until `day`, or until the hearth fell
+    pub fn score(&self, day: u64) -> u64 {
+        self.fallen.unwrap_or(day) * DAY_SCORE + self.killed as u64 * KILL_SCORE
+    }
+
+    pub fn label(&self, day: u64) -> String {
+        match self.fallen {
+            Some(fallen) => format!("Hearth fell day {}, score {}", fallen, self.score(day)),
+            None => format!("Wave {}, score {}", self.waves, self.score(day)),
+        }
+    }
+}
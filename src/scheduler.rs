@@ -0,0 +1,91 @@
+//! When the world ticks, apart from when the screen is drawn.
+
+use std::time::{Duration, Instant};
+
+/// Ticks a second at each speed the game can be set to, slowest first
+const SPEEDS: [f64; 9] = [0.5, 1.0, 2.0, 3.0, 5.0, 10.0, 20.0, 50.0, 100.0];
+
+/// One of the set speeds
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Speed(usize);
+
+impl Default for Speed {
+    fn default() -> Self {
+        Speed(1)
+    }
+}
+
+impl Speed {
+    pub fn ticks_per_second(self) -> f64 {
+        SPEEDS[self.0]
+    }
+
+    /// The time one tick takes
+    pub fn interval(self) -> Duration {
+        Duration::from_secs_f64(1.0 / self.ticks_per_second())
+    }
+
+    /// The next speed up, or this one if it's the fastest
+    pub fn faster(self) -> Speed {
+        Speed((self.0 + 1).min(SPEEDS.len() - 1))
+    }
+
+    /// The next speed down, or this one if it's the slowest
+    pub fn slower(self) -> Speed {
+        Speed(self.0.saturating_sub(1))
+    }
+
+    pub fn name(self) -> String {
+        format!("{}x", self.ticks_per_second())
+    }
+}
+
+pub struct Scheduler {
+    owed: f64, // ticks' worth of time gone by that haven't been run
+    last: Instant,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Scheduler { owed: 0.0, last: Instant::now() }
+    }
+
+    /// Count up the time gone by since last asked at `speed`. While the world
+    /// isn't `running`, paused or in the menu, no more ticks come due.
+    pub fn catch_up(&mut self, speed: Speed, running: bool) {
+        let now = Instant::now();
+        if running {
+            self.owed += now.duration_since(self.last).as_secs_f64() * speed.ticks_per_second();
+        }
+        self.last = now;
+    }
+
+    /// Whether a tick has come due
+    pub fn due(&self) -> bool {
+        self.owed >= 1.0
+    }
+
+    /// Run a tick if one is due, saying whether one was
+    pub fn take(&mut self) -> bool {
+        let due = self.due();
+        if due {
+            self.owed -= 1.0;
+        }
+        due
+    }
+
+    /// Let go of every tick still owed, keeping the way into the next
+    pub fn forgive(&mut self) {
+        self.owed = self.owed.fract();
+    }
+
+    /// How far the time gone by is into the next tick, from 0 to 1
+    pub fn progress(&self) -> f32 {
+        self.owed.min(1.0) as f32
+    }
+
+    /// The time until the next tick comes due at `speed`
+    pub fn until_due(&self, speed: Speed) -> Duration {
+        Duration::from_secs_f64((1.0 - self.owed).max(0.0) / speed.ticks_per_second())
+    }
+}
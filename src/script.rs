@@ -0,0 +1,228 @@
+//! Hooks read from the `*.hooks` files in the config's `scripts` folder.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::animal::AnimalKind;
+use crate::diary::NEEDS;
+use crate::event::Severity;
+
+/// What sets a hook off
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Trigger {
+    /// Every this many ticks
+    Tick(u64),
+    /// Once, at dawn on this day
+    Day(u64),
+    Death,
+    Birth,
+    Hunt,
+}
+
+/// A store by the rack a hook can give to or take from
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Store {
+    Meat,
+    Cooked,
+    Berries,
+    Wood,
+    Stone,
+    Water,
+}
+
+impl Store {
+    pub const ALL: [Store; 6] = [Store::Meat, Store::Cooked, Store::Berries, Store::Wood, Store::Stone, Store::Water];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Store::Meat => "meat",
+            Store::Cooked => "cooked",
+            Store::Berries => "berries",
+            Store::Wood => "wood",
+            Store::Stone => "stone",
+            Store::Water => "water",
+        }
+    }
+}
+
+/// Who a hook can bring onto the map
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Arrival {
+    /// Deer as a new herd, wolves as a new pack, anything else together
+    Animal(AnimalKind),
+    /// A goblin band, raiding the camp as any other would
+    Goblins,
+}
+
+impl Arrival {
+    /// `wolf`, `deer` and so on, or `goblin`
+    pub fn parse(name: &str) -> Option<Arrival> {
+        match name {
+            "goblin" | "goblins" => Some(Arrival::Goblins),
+            _ => AnimalKind::parse(name).map(Arrival::Animal),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Action {
+    Log(Severity, String),
+    Give(Store, u32),
+    Take(Store, u32),
+    /// Set the need at this index in `NEEDS`
+    Set(usize, f32),
+    /// Add to the need at this index in `NEEDS`
+    Add(usize, f32),
+    /// Bring this many of them in from the wilds
+    Arrive(Arrival, usize),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Hook {
+    pub trigger: Trigger,
+    /// How likely it is to go off each time it's set off
+    pub chance: f64,
+    pub actions: Vec<Action>,
+}
+
+/// Read the hooks in the text of a script
+pub fn parse(src: &str) -> Result<Vec<Hook>, String> {
+    let mut hooks: Vec<Hook> = Vec::new();
+    for (i, line) in src.lines().enumerate() {
+        let line = strip_comment(line);
+        if line.trim().is_empty() {
+            continue;
+        }
+        let fail = |msg: String| format!("line {}: {}", i + 1, msg);
+        if !line.starts_with([' ', '\t']) {
+            hooks.push(parse_header(line).map_err(fail)?);
+            continue;
+        }
+        let Some(hook) = hooks.last_mut() else {
+            return Err(fail("an action needs an `on ...` line above it".to_string()));
+        };
+        hook.actions.push(parse_action(line.trim()).map_err(fail)?);
+    }
+    Ok(hooks)
+}
+
+/// The line up to any `#` that isn't inside quotes
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, c) in line.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
+
+/// `on <tick|day <n>|death|birth|hunt> [every <ticks>] [chance <odds>]`
+fn parse_header(line: &str) -> Result<Hook, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let (event, mut rest) = match words.as_slice() {
+        ["on", event, rest @ ..] => (*event, rest),
+        _ => return Err(format!("expected `on tick`, `on day`, `on death`, `on birth` or `on hunt`, got `{}`", line.trim())),
+    };
+    let mut trigger = match event {
+        "tick" => Trigger::Tick(1),
+        "day" => {
+            let [day, tail @ ..] = rest else {
+                return Err("`day` needs a value".to_string());
+            };
+            rest = tail;
+            Trigger::Day(parse_day(day)?)
+        }
+        "death" => Trigger::Death,
+        "birth" => Trigger::Birth,
+        "hunt" => Trigger::Hunt,
+        _ => return Err(format!("unknown hook `{}`", event)),
+    };
+    let mut chance = 1.0;
+    while let [word, value, tail @ ..] = rest {
+        match *word {
+            "every" if trigger == Trigger::Tick(1) => {
+                let every = value.parse().ok().filter(|&n| n > 0).ok_or_else(|| format!("bad tick count: {}", value))?;
+                trigger = Trigger::Tick(every);
+            }
+            "chance" => {
+                chance = value.parse().ok().filter(|p| (0.0..=1.0).contains(p)).ok_or_else(|| format!("chance must be from 0 to 1, got {}", value))?;
+            }
+            _ => return Err(format!("unexpected `{}`", word)),
+        }
+        rest = tail;
+    }
+    if let [word] = rest {
+        return Err(format!("`{}` needs a value", word));
+    }
+    Ok(Hook { trigger, chance, actions: Vec::new() })
+}
+
+/// A day a hook can fire at the dawn of: any after the first, which has
+/// dawned before the world starts turning
+pub fn parse_day(value: &str) -> Result<u64, String> {
+    value.parse().ok().filter(|&day| day > 1).ok_or_else(|| format!("day must be 2 or later, got {}", value))
+}
+
+/// `log [severity] "<text>"`, `give|take <store> <n>`, `set|add <need> <value>`
+/// or `arrive <creature> [n]`
+fn parse_action(line: &str) -> Result<Action, String> {
+    if let Some(rest) = line.strip_prefix("log ") {
+        let rest = rest.trim();
+        let (severity, text) = match rest.split_once(' ') {
+            Some((word, text)) if !word.starts_with('"') => {
+                let severity = Severity::ALL.into_iter().find(|s| s.name() == word).ok_or_else(|| format!("unknown severity `{}`", word))?;
+                (severity, text.trim())
+            }
+            _ => (Severity::Info, rest),
+        };
+        let text = text.strip_prefix('"').and_then(|t| t.strip_suffix('"')).ok_or("the line to log goes in double quotes")?;
+        return Ok(Action::Log(severity, text.to_string()));
+    }
+    let words: Vec<&str> = line.split_whitespace().collect();
+    match words.as_slice() {
+        [verb @ ("give" | "take"), store, amount] => {
+            let store = Store::ALL.into_iter().find(|s| s.name() == *store).ok_or_else(|| format!("unknown store `{}`", store))?;
+            let amount = amount.parse().map_err(|_| format!("bad amount: {}", amount))?;
+            Ok(if *verb == "give" { Action::Give(store, amount) } else { Action::Take(store, amount) })
+        }
+        [verb @ ("set" | "add"), need, value] => {
+            let need = NEEDS.iter().position(|n| n.eq_ignore_ascii_case(need)).ok_or_else(|| format!("unknown need `{}`", need))?;
+            let value: f32 = value.parse().map_err(|_| format!("bad value: {}", value))?;
+            Ok(if *verb == "set" { Action::Set(need, value) } else { Action::Add(need, value) })
+        }
+        ["arrive", who, count @ ..] if count.len() <= 1 => {
+            let arrival = Arrival::parse(who).ok_or_else(|| format!("unknown creature `{}`", who))?;
+            let count = match count {
+                [n] => n.parse().ok().filter(|&n| n > 0).ok_or_else(|| format!("bad count: {}", n))?,
+                _ => 1,
+            };
+            Ok(Action::Arrive(arrival, count))
+        }
+        _ => Err(format!("unknown action `{}`", line)),
+    }
+}
+
+/// Every hook in the scripts in `dir`, taken file by file in order of name.
+/// A missing folder just means no scripts.
+pub fn load_dir(dir: &Path) -> Result<Vec<Hook>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {}", dir.display(), e)),
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "hooks"))
+        .collect();
+    paths.sort();
+    let mut hooks = Vec::new();
+    for path in paths {
+        let src = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        hooks.extend(parse(&src).map_err(|e| format!("{}: {}", path.display(), e))?);
+    }
+    Ok(hooks)
+}
@@ -0,0 +1,316 @@
+//! Watching a game from a browser or a dashboard, over WebSocket.
+
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender, TrySendError};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+/// Magic the handshake mixes into the client's key, as RFC 6455 sets out
+const HANDSHAKE_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+/// Messages queued up for a viewer before the latest are dropped
+const QUEUE: usize = 16;
+/// Longest message taken from a viewer
+const MAX_MESSAGE: u64 = 64 * 1024;
+/// Longest handshake request read before giving up on it
+const MAX_REQUEST: usize = 8 * 1024;
+/// Longest wait for a new connection's handshake
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+/// Most connections looked after at once, handshakes included
+const MAX_VIEWERS: usize = 32;
+/// Close code for a message of a kind the game doesn't take
+const UNSUPPORTED: u16 = 1003;
+/// Close code for a message longer than `MAX_MESSAGE`
+const TOO_BIG: u16 = 1009;
+
+const CONTINUATION: u8 = 0x0;
+const TEXT: u8 = 0x1;
+const CLOSE: u8 = 0x8;
+const PING: u8 = 0x9;
+const PONG: u8 = 0xA;
+
+/// A frame on its way out, shared between every viewer it's sent to
+type Frame = Arc<Vec<u8>>;
+
+pub struct Server {
+    viewers: Arc<Mutex<Vec<Viewer>>>,
+    commands: Receiver<(usize, String)>,
+}
+
+struct Viewer {
+    id: usize,
+    outbox: SyncSender<Frame>,
+}
+
+impl Server {
+    /// Start listening on `addr`, taking viewers on a thread of its own
+    pub fn start(addr: SocketAddr) -> io::Result<Server> {
+        let listener = TcpListener::bind(addr)?;
+        let viewers = Arc::new(Mutex::new(Vec::new()));
+        let (commands_in, commands) = mpsc::channel();
+        let joining = Arc::clone(&viewers);
+        let connected = Arc::new(AtomicUsize::new(0));
+        thread::spawn(move || {
+            for (id, stream) in listener.incoming().enumerate() {
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+                if connected.fetch_add(1, Ordering::Relaxed) >= MAX_VIEWERS {
+                    connected.fetch_sub(1, Ordering::Relaxed);
+                    let _ = stream.write_all(b"HTTP/1.1 503 Service Unavailable\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+                    continue;
+                }
+                let (viewers, commands_in, connected) = (Arc::clone(&joining), commands_in.clone(), Arc::clone(&connected));
+                thread::spawn(move || {
+                    // A viewer that goes wrong only loses its own connection
+                    let _ = welcome(id, stream, &viewers, commands_in);
+                    connected.fetch_sub(1, Ordering::Relaxed);
+                });
+            }
+        });
+        Ok(Server { viewers, commands })
+    }
+
+    fn viewers(&self) -> std::sync::MutexGuard<'_, Vec<Viewer>> {
+        self.viewers.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Whether anyone is connected to be sent anything
+    pub fn watched(&self) -> bool {
+        !self.viewers().is_empty()
+    }
+
+    /// Send `text` to every viewer, leaving out those too far behind to
+    /// take it and letting go of those who've gone
+    pub fn broadcast(&self, text: &str) {
+        let frame = Arc::new(frame(TEXT, text.as_bytes()));
+        self.viewers().retain(|viewer| match viewer.outbox.try_send(Arc::clone(&frame)) {
+            Ok(()) | Err(TrySendError::Full(_)) => true,
+            Err(TrySendError::Disconnected(_)) => false,
+        });
+    }
+
+    /// Send `text` to the viewer `id` alone
+    pub fn reply(&self, id: usize, text: &str) {
+        if let Some(viewer) = self.viewers().iter().find(|v| v.id == id) {
+            let _ = viewer.outbox.try_send(Arc::new(frame(TEXT, text.as_bytes())));
+        }
+    }
+
+    /// Messages the viewers have sent since last asked, with who sent each
+    pub fn commands(&self) -> Vec<(usize, String)> {
+        self.commands.try_iter().collect()
+    }
+}
+
+/// Shake hands with a new viewer, then pass on what it sends until it goes
+fn welcome(id: usize, stream: TcpStream, viewers: &Mutex<Vec<Viewer>>, commands: Sender<(usize, String)>) -> io::Result<()> {
+    stream.set_read_timeout(Some(HANDSHAKE_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    let key = read_handshake(&mut reader)?;
+    let Some(key) = key else {
+        writer.write_all(b"HTTP/1.1 400 Bad Request\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")?;
+        return Ok(());
+    };
+    let accept = base64(&sha1(format!("{}{}", key, HANDSHAKE_GUID).as_bytes()));
+    write!(writer, "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n", accept)?;
+    // A viewer may only ever watch, so once in it can be quiet as long as it likes
+    writer.set_read_timeout(None)?;
+
+    let (outbox, queue) = mpsc::sync_channel::<Frame>(QUEUE);
+    viewers.lock().unwrap_or_else(PoisonError::into_inner).push(Viewer { id, outbox: outbox.clone() });
+    thread::spawn(move || {
+        for frame in queue {
+            if writer.write_all(&frame).is_err() {
+                break;
+            }
+        }
+    });
+
+    // A message sent in fragments, put back together as they come
+    let mut message: Option<Vec<u8>> = None;
+    loop {
+        let (fin, opcode, payload) = read_frame(&mut reader)?;
+        match opcode {
+            TEXT | CONTINUATION => {
+                let so_far = match (opcode, message.take()) {
+                    (TEXT, None) => payload,
+                    (CONTINUATION, Some(mut so_far)) => {
+                        so_far.extend(payload);
+                        so_far
+                    }
+                    // A continuation of nothing, or a new message before the last was done
+                    _ => {
+                        let _ = outbox.try_send(Arc::new(frame(CLOSE, &UNSUPPORTED.to_be_bytes())));
+                        break;
+                    }
+                };
+                if so_far.len() as u64 > MAX_MESSAGE {
+                    let _ = outbox.try_send(Arc::new(frame(CLOSE, &TOO_BIG.to_be_bytes())));
+                    break;
+                }
+                if !fin {
+                    message = Some(so_far);
+                    continue;
+                }
+                let text = String::from_utf8_lossy(&so_far).into_owned();
+                if commands.send((id, text)).is_err() {
+                    break;
+                }
+            }
+            PING => {
+                let _ = outbox.try_send(Arc::new(frame(PONG, &payload)));
+            }
+            CLOSE => {
+                let _ = outbox.try_send(Arc::new(frame(CLOSE, &[])));
+                break;
+            }
+            _ => {}
+        }
+    }
+    viewers.lock().unwrap_or_else(PoisonError::into_inner).retain(|v| v.id != id);
+    Ok(())
+}
+
+/// Read the request opening a connection, returning its WebSocket key if it has one
+fn read_handshake(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut key = None;
+    let mut read = 0;
+    loop {
+        let mut line = String::new();
+        let n = reader.read_line(&mut line)?;
+        read += n;
+        if n == 0 || read > MAX_REQUEST {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "handshake cut short or too long"));
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            return Ok(key);
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.trim().eq_ignore_ascii_case("sec-websocket-key")
+        {
+            key = Some(value.trim().to_string());
+        }
+    }
+}
+
+/// Read one frame from a viewer, unmasking it: whether it ends a message,
+/// its opcode and payload
+fn read_frame(reader: &mut impl Read) -> io::Result<(bool, u8, Vec<u8>)> {
+    let mut head = [0; 2];
+    reader.read_exact(&mut head)?;
+    let fin = head[0] & 0x80 != 0;
+    let opcode = head[0] & 0x0F;
+    let masked = head[1] & 0x80 != 0;
+    let len = match head[1] & 0x7F {
+        126 => {
+            let mut len = [0; 2];
+            reader.read_exact(&mut len)?;
+            u16::from_be_bytes(len) as u64
+        }
+        127 => {
+            let mut len = [0; 8];
+            reader.read_exact(&mut len)?;
+            u64::from_be_bytes(len)
+        }
+        len => len as u64,
+    };
+    if len > MAX_MESSAGE {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "message too long"));
+    }
+    let mut mask = [0; 4];
+    if masked {
+        reader.read_exact(&mut mask)?;
+    }
+    let mut payload = vec![0; len as usize];
+    reader.read_exact(&mut payload)?;
+    for (i, byte) in payload.iter_mut().enumerate() {
+        *byte ^= mask[i % 4];
+    }
+    Ok((fin, opcode, payload))
+}
+
+/// A whole, unmasked frame carrying `payload`, as a server sends them
+fn frame(opcode: u8, payload: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x80 | opcode];
+    match payload.len() {
+        len @ 0..126 => out.push(len as u8),
+        len @ 126..=0xFFFF => {
+            out.push(126);
+            out.extend((len as u16).to_be_bytes());
+        }
+        len => {
+            out.push(127);
+            out.extend((len as u64).to_be_bytes());
+        }
+    }
+    out.extend_from_slice(payload);
+    out
+}
+
+/// The SHA-1 digest of `data`, which the handshake calls for
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend((data.len() as u64 * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, &word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..20 => ((b & c) | (!b & d), 0x5A827999),
+                20..40 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..60 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a.rotate_left(5).wrapping_add(f).wrapping_add(e).wrapping_add(k).wrapping_add(word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (h, v) in h.iter_mut().zip([a, b, c, d, e]) {
+            *h = h.wrapping_add(v);
+        }
+    }
+
+    let mut digest = [0; 20];
+    for (i, word) in h.iter().enumerate() {
+        digest[i * 4..i * 4 + 4].copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+/// `data` in standard, padded base64
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::new();
+    for chunk in data.chunks(3) {
+        let bytes = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
@@ -0,0 +1,2896 @@
+//! The world and everything in it, ticking along on its own.
+
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::PathBuf;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::alarm::{self, Alarm};
+use crate::animal::{self, Animal, AnimalKind, Behaviour, Lead, PROWL_RADIUS, WOLF_RESPITE};
+use crate::bestiary::{Bestiary, Deed};
+use crate::burial;
+use crate::building::{Building, Material};
+use crate::calendar::{self, DayPhase, Season};
+use crate::carcass::{self, CROW_CHANCE, CROW_DEPARTS, CROWS_PER_CARCASS, PECK_CHANCE, WOLF_FEED};
+use crate::combat::{self, Blow, Combatant};
+use crate::commands::{DebugCommand, Target};
+use crate::config::{AiConfig, Limits, NeedRates, WorldConfig};
+use crate::custom::Custom;
+use crate::designation::Designation;
+use crate::diary::NEEDS;
+use crate::emote::{EmoteKind, EmoteQueue};
+use crate::evacuation::{Evacuation, Reason, Stage};
+use crate::expedition::{self, Expedition, Venture};
+use crate::gym::StateDump;
+use crate::event::{EventCategory, EventLog, Severity};
+use crate::healing;
+use crate::herd::Herds;
+use crate::husbandry;
+use crate::light::Lights;
+use crate::markers::Mark;
+use crate::notify::{self, Notices};
+use crate::nursery::{Childcare, FEED_AT, NURSE_COST, NURSE_RATE};
+use crate::orc::{self, Activity, BERRY_POUCH, Cause, Job, Orc, Purpose, Sex, Trait};
+use crate::pack::{self, Packs};
+use crate::raid::{self, Raid};
+use crate::projectile::Projectiles;
+use crate::rival::{self, Rival, RivalClan};
+use crate::scenario::{self, Scenario, Siege};
+use crate::script::{Action, Arrival, Hook, Store, Trigger};
+use crate::spatial::{Entity, SpatialIndex};
+use crate::spirits::Mood;
+use crate::stats::StatsCollector;
+use crate::territory::{Clan, Territory};
+use crate::tool::{Craft, RACK_STOCK, ToolKind};
+use crate::watch::{self, Threat};
+use crate::world::{BARREL_CAPACITY, BUCKET, FIRE_MAX_FUEL, FUEL_PER_LOG, HUT_WEAR, MAP_HEIGHT, MAP_WIDTH, Terrain, World};
+
+/// Clanmates within this distance of a newborn react to the birth
+const BIRTH_NOTICE_RADIUS: usize = 8;
+/// Morale an orc gains on its birthday
+const BIRTHDAY_MORALE: f32 = 15.0;
+/// Idle orcs further than this from a meat pile aren't sent to haul it
+const HAUL_RADIUS: usize = 40;
+/// Idle orcs further than this from a building site aren't called over to help
+const BUILD_RADIUS: usize = 60;
+/// Orcs working on one stocked site at a time
+const BUILDERS_PER_SITE: usize = 2;
+/// Orcs come from up to this many tiles away to carry out a standing order
+const ORDER_RADIUS: usize = 60;
+/// An orc with no clanmate this close is alone as far as a wolf is concerned
+const LONE_RADIUS: usize = 3;
+/// This many wolves prowling this close to the campfire have overrun the camp
+const OVERRUN_WOLVES: usize = 2;
+const OVERRUN_RADIUS: usize = 12;
+/// With the stores bare, the clan is starving once its hunger averages this,
+/// and has eaten enough to go home once it's back under `FED_HUNGER`
+const FAMINE_HUNGER: f32 = 85.0;
+const FED_HUNGER: f32 = 40.0;
+/// How far from camp the clan looks for somewhere to rally
+const RALLY_DISTANCE: i32 = 30;
+/// A rally point the player picks must be at least this far from camp
+const MIN_RALLY_DISTANCE: usize = 15;
+/// Orcs this close to a stop on the march have caught up
+const STOP_RADIUS: usize = 3;
+/// Ticks the clan shelters at least, and at most, before heading home
+const MIN_SHELTER: u64 = 20;
+fn max_shelter(day_length: u64) -> u64 {
+    2 * day_length
+}
+/// Ticks after an evacuation ends before the clan flees on its own again
+fn evacuation_cooldown(day_length: u64) -> u64 {
+    2 * day_length
+}
+/// Game this close to a hunting party's site is there for it to take
+const HUNTING_GROUND: usize = 12;
+/// How far around its site a party gathers or forages
+const VENTURE_RADIUS: usize = 8;
+/// Berries each bush yields to a gathering party
+const BUSH_YIELD: u32 = 2;
+/// Most food each forager digs up on a winter forage that goes well
+const FORAGE_HAUL: u32 = 3;
+/// Morale a party gains from a venture that comes off, or loses from one that fails
+const VENTURE_MORALE: f32 = 10.0;
+/// Harm the cold does each forager on a forage that goes badly
+const FROSTBITE: std::ops::Range<f32> = 10.0..30.0;
+/// Most food the player can drop in one go
+pub const MAX_DROP: u32 = 20;
+
+/// Population for a `--stress` run
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stress {
+    pub orcs: usize,
+    pub animals: usize,
+}
+
+impl Default for Stress {
+    fn default() -> Self {
+        Stress { orcs: 100, animals: 200 }
+    }
+}
+
+pub struct Sim {
+    pub world: World,
+    pub orcs: Vec<Orc>,
+    pub animals: Vec<Animal>,
+    herds: Herds,
+    packs: Packs,
+    pub event_log: EventLog,
+    pub emotes: EmoteQueue,
+    pub projectiles: Projectiles,
+    pub stats: StatsCollector,
+    stats_mark: u64, // event_log.total already tallied into stats
+    /// Banners for what the player mustn't miss, queued to show one at a time
+    pub notices: Notices,
+    stores_empty: bool, // whether the food stores were bare last tick
+    pub news_path: Option<PathBuf>,
+    pub dump: Option<StateDump>, // snapshots of the game written out as it goes
+    pub dawn_news: bool,
+    pub tick: u64,
+    /// Whether the world stands still; it stops itself once the clan has
+    /// perished or lost its siege
+    pub paused: bool,
+    pub ai: AiConfig,
+    pub limits: Limits,
+    pub needs: NeedRates,
+    pub childcare: Childcare,
+    /// What the player's scripts have the world do, and when
+    pub hooks: Vec<Hook>,
+    /// What the scenario file has come on the days after the start
+    schedule: Vec<Hook>,
+    pub evacuation: Option<Evacuation>,
+    evacuation_ended: u64, // tick the last evacuation ended
+    /// The party out on a venture, if any
+    pub expedition: Option<Expedition>,
+    /// The goblin band raiding the camp, if any
+    pub raid: Option<Raid>,
+    raid_ended: u64, // tick the last raid ended
+    /// How the defense of the campfire is going, when playing the defense scenario
+    pub siege: Option<Siege>,
+    threat_seen: Option<u64>, // last tick a guard on watch had wolves or goblins in sight
+    /// The clan across the map, and the ground each clan has marked as its own
+    pub rivals: RivalClan,
+    pub territory: Territory,
+    /// Whether the clan is on alert, and what over
+    pub alarm: Alarm,
+    /// What the player has seen of the wildlife, and what the clan has done to it
+    pub bestiary: Bestiary,
+    pub seed: u64,
+    rng: StdRng,
+    /// Where every creature stood as of the end of the last tick
+    index: SpatialIndex,
+}
+
+impl Sim {
+    /// Start a new world whose generation and simulation are fully determined
+    /// by `seed`, playing `scenario`, set up as `setup` says
+    pub fn with_seed(seed: u64, scenario: Scenario, setup: &WorldConfig) -> Self {
+        Sim::settle(seed, scenario, setup, None)
+    }
+
+    /// Start a new world from `seed` as the scenario file `custom` sets it out
+    pub fn with_custom(seed: u64, custom: &Custom, setup: &WorldConfig) -> Self {
+        Sim::settle(seed, custom.scenario, setup, Some(custom))
+    }
+
+    fn settle(seed: u64, scenario: Scenario, setup: &WorldConfig, custom: Option<&Custom>) -> Self {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut world = World::generate(&mut rng);
+        world.bush_regrowth = setup.bush_regrowth;
+        world.day_length = setup.day_length;
+        if let Some(custom) = custom {
+            custom.lay_out(&mut world);
+        }
+        let mut orcs = Orc::spawn_clan(custom.map_or(setup.clan, |c| c.clan(setup.clan)), &world, &mut rng);
+        if let Some(custom) = custom {
+            custom.found(&mut orcs);
+        }
+        let mut herds = Herds::default();
+        let mut packs = Packs::default();
+        let animals = Animal::spawn_initial(setup.animals, &world, &mut herds, &mut packs, &mut rng);
+        let names: Vec<String> = orcs.iter().map(|o| o.name.clone()).collect();
+        let rivals = RivalClan::settle(&world, &names, &mut rng);
+        let mut event_log = EventLog::new();
+
+        if let Some(name) = custom.and_then(|c| c.name.as_ref()) {
+            event_log.log(0, format!("Scenario: {}", name), Severity::Info);
+        }
+        event_log.log(0, "A clan of orcs settles in a new land...".to_string(), Severity::Info);
+        for orc in &orcs {
+            event_log.log_as(0, EventCategory::Social, format!("{} joins the clan", orc.name), Severity::Good);
+        }
+
+        let stats = StatsCollector::new(world.food_stockpile);
+        let index = SpatialIndex::new(&orcs, &animals);
+
+        Sim {
+            world,
+            orcs,
+            animals,
+            herds,
+            packs,
+            event_log,
+            emotes: EmoteQueue::new(),
+            projectiles: Projectiles::new(),
+            stats,
+            stats_mark: 0,
+            notices: Notices::default(),
+            stores_empty: true,
+            news_path: None,
+            dump: None,
+            dawn_news: true,
+            tick: 0,
+            paused: false,
+            ai: AiConfig::default(),
+            limits: Limits::default(),
+            needs: NeedRates::default(),
+            childcare: Childcare::default(),
+            hooks: Vec::new(),
+            schedule: custom.map_or_else(Vec::new, |c| c.events.clone()),
+            evacuation: None,
+            evacuation_ended: 0,
+            expedition: None,
+            raid: None,
+            raid_ended: 0,
+            siege: (scenario == Scenario::Defense).then(Siege::new),
+            threat_seen: None,
+            rivals,
+            territory: Territory::new(),
+            alarm: Alarm::Calm,
+            bestiary: Bestiary::new(),
+            seed,
+            rng,
+            index,
+        }
+    }
+
+    pub fn tick(&mut self) {
+        if self.paused {
+            return;
+        }
+
+        self.tick += 1;
+        for orc in &mut self.orcs {
+            orc.was = (orc.x, orc.y);
+        }
+        for animal in &mut self.animals {
+            animal.was = (animal.x, animal.y);
+        }
+        self.world.path_budget.reset(self.limits.path_budget);
+
+        // Day/night transition messages
+        let time_of_day = calendar::time_of_day(self.tick, self.world.day_length);
+        if time_of_day == 0 {
+            self.publish_day_summary();
+            let day = calendar::day_number(self.tick, self.world.day_length);
+            self.event_log.log(self.tick, format!("=== Day {} begins ===", day), Severity::Info);
+            let season = Season::of(self.tick, self.world.day_length);
+            if (day - 1).is_multiple_of(calendar::SEASON_LENGTH) {
+                self.event_log.log(self.tick, season.herald().to_string(), Severity::Info);
+            }
+            self.world.grow_seasonal_food(season, &mut self.rng);
+            self.weather_structures(season);
+            let spoiled = self.world.spoil_meat();
+            if spoiled > 0 {
+                self.event_log.log_as(self.tick, EventCategory::Food, format!("{} meat on the rack has gone off (stockpile: {})", spoiled, self.world.food_stockpile), Severity::Warning);
+            }
+            self.heed_spirits();
+            self.celebrate_birthdays();
+            self.breed_livestock();
+        } else if time_of_day == calendar::dusk(self.world.day_length) {
+            self.event_log.log(self.tick, "Dusk settles, the clan heads back to camp".to_string(), Severity::Info);
+            self.call_raid();
+        } else if time_of_day == calendar::nightfall(self.world.day_length) {
+            self.event_log.log(self.tick, "Night falls...".to_string(), Severity::Info);
+        }
+
+        let phase = self.phase();
+
+        // Work out who's out beyond the light before anything goes looking for easy prey
+        self.find_the_dark(phase);
+
+        // Update animals, each minding only the orcs close enough to spook it,
+        // and let the wolves pounce on whatever they've run down
+        self.herds.drift(&self.world, &mut self.rng, AnimalKind::Deer.is_active(phase));
+        for a in 0..self.animals.len() {
+            let animal = &self.animals[a];
+            let near: Vec<(usize, usize)> = self.index.within(animal.x, animal.y, animal::WARY_RADIUS)
+                .filter_map(|entity| match entity {
+                    Entity::Orc(i) if self.orcs[i].alive => Some((self.orcs[i].x, self.orcs[i].y)),
+                    _ => None,
+                })
+                .collect();
+            let lead = match animal.herd {
+                Some(herd) => self.herds.lead(herd, self.tick),
+                None => self.prowl(a, phase),
+            };
+            // One deer spotting an orc sends its whole herd running
+            if let Some((ox, oy)) = self.animals[a].update(&mut self.world, &near, lead, &mut self.rng, phase, self.tick)
+                && let Some(herd) = self.animals[a].herd
+            {
+                self.herds.scatter(herd, ox, oy, &self.world, self.tick);
+            }
+        }
+        self.wolves_strike(phase);
+        self.scavenge(phase);
+        for kind in self.world.rot_carcasses(self.tick) {
+            self.event_log.log_as(self.tick, EventCategory::Food, format!("A {} carcass has rotted away", kind.name().to_lowercase()), Severity::Warning);
+        }
+
+        // Raiders close in on the rack, and the clan turns out to fight them
+        self.run_raid();
+
+        // The rival clan roams its own ground, and comes to blows with ours where the two meet
+        self.run_rivals();
+
+        // Guards on watch call the clan in at the first sight of trouble
+        self.keep_watch();
+
+        // Trouble at the camp itself puts the whole clan on alert
+        self.sound_alarm();
+        self.rally_to_alarm();
+
+        // Bring down whatever wore through since last tick before handing out work
+        self.settle_collapses();
+
+        // Rebuild food claims from what each orc is currently pursuing
+        self.refresh_claims();
+
+        // Lead the clan out of a camp it can't hold, and keep it together on the way
+        self.watch_for_danger();
+        self.run_evacuation();
+
+        // Keep any party out on a venture on its way
+        self.run_expedition();
+
+        // Send healthy orcs to feed clanmates too weak to fetch food
+        self.assign_food_deliveries();
+
+        // Send healers to dress the worst wounds, or out for herbs
+        self.assign_healing();
+
+        // Have the dead carried to the graveyard
+        self.assign_burials();
+
+        // Put idle orcs to work carrying meat left on the ground back to the rack
+        self.assign_haul_tasks();
+
+        // Send water carriers off to refill the barrel by the campfire
+        self.assign_water_hauling();
+
+        // Patch up anything worn, the hearth before it can be fed
+        self.assign_repairs();
+
+        // Keep the campfire fed
+        self.assign_stoking();
+
+        // Then on supplying and raising any marked-out buildings
+        self.assign_build_tasks();
+
+        // Then on making tools at any workbench
+        self.assign_crafting();
+
+        // Then on seeing to the livestock in the pens
+        self.assign_husbandry();
+
+        // Then on the player's standing orders to chop and gather
+        self.assign_designations();
+
+        // Guards left idle go back on watch
+        self.assign_guard_duty();
+
+        // Give every empty hut to an orc still sleeping out in the open
+        self.assign_huts();
+
+        // Update each orc
+        let num_orcs = self.orcs.len();
+        for i in 0..num_orcs {
+            let mut orc = std::mem::replace(&mut self.orcs[i], Orc::new(String::new(), 0, 0));
+            orc.wear(&self.world, &self.needs, self.tick);
+            orc.update(&mut self.world, &mut self.animals, &mut self.rng, &mut self.event_log, self.tick, &self.ai);
+            self.orcs[i] = orc;
+        }
+
+        // Babies go where their carers went, and get fed when they cry for it
+        self.tend_infants();
+
+        // Captives get loose if their herder is gone, and livestock left unfed starves
+        self.tend_livestock();
+
+        self.complete_food_deliveries();
+        self.complete_treatments();
+
+        // Both clans leave their scent wherever they've been
+        self.mark_territory();
+
+        // Fill in the bestiary from what the clan did
+        let deeds = self.world.take_deeds();
+        for &(kind, deed) in &deeds {
+            self.bestiary.record(kind, deed);
+        }
+
+        // The clan takes heart from its dead laid to rest, and none from those left lying
+        self.mourn_the_dead();
+
+        // Emotes: expire old ones and pick up whatever the orcs felt this tick
+        self.emotes.prune(self.tick);
+        for orc in &mut self.orcs {
+            if let Some(kind) = orc.emote.take() {
+                self.emotes.push(orc.x, orc.y, kind, self.tick);
+            }
+        }
+
+        // Diaries: the living put down how they're faring and what they're about
+        for orc in self.orcs.iter_mut().filter(|o| o.alive) {
+            orc.diary.note(self.tick, orc.needs(), orc.activity.label());
+        }
+
+        // Spears: let the landed ones go and send this tick's throws on their way
+        self.projectiles.prune(self.tick);
+        for orc in &mut self.orcs {
+            if let Some(to) = orc.threw.take() {
+                self.projectiles.launch(orc.x, orc.y, to, self.tick);
+            }
+        }
+
+        // Remove dead orcs after a few ticks (show tombstone briefly), leaving
+        // their bodies, and any they were carrying, lying there to be buried
+        self.orcs.retain(|orc| {
+            if !orc.alive && let Some(death_tick) = orc.death_tick {
+                if self.tick - death_tick < 20 {
+                    return true; // keep tombstone for 20 ticks
+                }
+                if let Activity::Burying { name, carrying: true, .. } = &orc.activity {
+                    self.world.lay_corpse(name.clone(), orc.x, orc.y);
+                }
+                self.world.lay_corpse(orc.name.clone(), orc.x, orc.y);
+                self.world.markers.note(Mark::Death, orc.x, orc.y, death_tick, format!("{} died", orc.name));
+                return false;
+            }
+            true
+        });
+
+        // Remove dead animals
+        self.animals.retain(|a| a.alive);
+        self.herds.prune(&self.animals);
+
+        // Animal respawn
+        self.draw_crows(phase);
+        if !self.world.spirits.game_is_scarce() {
+            animal::try_respawn(&mut self.animals, &mut self.herds, &mut self.packs, &self.world, &mut self.rng, self.tick, self.limits.max_animals);
+        }
+
+        // Bush regrowth
+        self.world.tick_regrowth(self.tick);
+
+        if self.world.burn_fire() {
+            self.event_log.log_as(self.tick, EventCategory::Alert, "The campfire has burned out!".to_string(), Severity::Danger);
+            self.notify(notify::Kind::Fire, "The campfire has burned out!".to_string());
+        }
+
+        // Birth system - check every 300 ticks
+        let clan = self.orcs.len();
+        if self.tick.is_multiple_of(300) {
+            self.check_birth();
+        }
+
+        // The player's scripts, and the scenario's timed events, have their
+        // say on what came of the tick
+        if !self.hooks.is_empty() || !self.schedule.is_empty() {
+            let killed: Vec<AnimalKind> = deeds.iter().filter(|(_, deed)| matches!(deed, Deed::Killed)).map(|&(kind, _)| kind).collect();
+            self.run_hooks(&killed, clan);
+        }
+
+        // File everyone under where they ended up, for the frame and the next tick
+        self.reindex();
+
+        // Under siege, the run is over once the hearth has been smashed
+        self.check_siege();
+
+        // Game over if all orcs are gone
+        if self.orcs.is_empty() {
+            self.event_log.log_as(self.tick, EventCategory::Alert, "The clan has perished...".to_string(), Severity::Danger);
+            self.paused = true;
+        }
+
+        self.raise_notices();
+
+        // Tally this tick's events into the daily statistics
+        self.stats.record(self.event_log.since(self.stats_mark));
+        self.stats_mark = self.event_log.total;
+
+        // Snapshot the state for anyone following the game from outside
+        if let Some(mut dump) = self.dump.take() {
+            match dump.record(self) {
+                Ok(()) => self.dump = Some(dump),
+                Err(e) => self.event_log.log(self.tick, format!("Could not write the state dump: {}", e), Severity::Danger),
+            }
+        }
+    }
+
+    /// Re-file every creature under where it stands now. The simulation does
+    /// this at the end of each tick; anything else that moves, adds or
+    /// removes creatures should call it before querying.
+    pub fn reindex(&mut self) {
+        self.index.rebuild(&self.orcs, &self.animals);
+    }
+
+    /// Living orcs within `radius` steps of (x, y), with their positions in `orcs`
+    pub fn orcs_within(&self, x: usize, y: usize, radius: usize) -> impl Iterator<Item = (usize, &Orc)> + '_ {
+        self.index.within(x, y, radius).filter_map(|entity| match entity {
+            Entity::Orc(i) if self.orcs[i].alive => Some((i, &self.orcs[i])),
+            _ => None,
+        })
+    }
+
+    /// Every creature on (x, y), orcs first and including the dead not yet
+    /// buried, by their positions in `orcs` and `animals`
+    pub fn entities_at(&self, x: usize, y: usize) -> impl Iterator<Item = Entity> + '_ {
+        self.index.at(x, y)
+    }
+
+    pub fn phase(&self) -> DayPhase {
+        DayPhase::of(self.tick, self.world.day_length)
+    }
+
+    pub fn is_night(&self) -> bool {
+        self.phase() == DayPhase::Night
+    }
+
+    /// Crowd the world for a stress test, raising the caps to fit
+    pub fn populate(&mut self, stress: Stress) {
+        let mut names: Vec<String> = self.orcs.iter().map(|o| o.name.clone()).collect();
+        let extra = stress.orcs.saturating_sub(self.orcs.len());
+        for mut orc in Orc::spawn_clan(extra, &self.world, &mut self.rng) {
+            // spawn_clan only keeps names unique within the new batch
+            if names.contains(&orc.name) {
+                orc.name = format!("{} {}", orc.name, names.len() + 1);
+            }
+            names.push(orc.name.clone());
+            self.orcs.push(orc);
+        }
+        let extra = stress.animals.saturating_sub(self.animals.len());
+        self.animals.extend(Animal::spawn_herd(extra, &self.world, &mut self.herds, &mut self.packs, &mut self.rng));
+
+        self.limits.max_clan = self.limits.max_clan.max(stress.orcs);
+        self.limits.max_animals = self.limits.max_animals.max(stress.animals);
+        self.reindex();
+        self.event_log.log(
+            self.tick,
+            format!("Stress test: {} orcs and {} animals", self.orcs.len(), self.animals.len()),
+            Severity::Good,
+        );
+    }
+
+    /// Where the wolf at `a` is going: after the quarry its pack's leader has
+    /// picked, or else keeping about the leader. A crow makes for carrion.
+    fn prowl(&self, a: usize, phase: DayPhase) -> Lead {
+        let wolf = &self.animals[a];
+        if wolf.kind == AnimalKind::Crow && wolf.kind.is_active(phase) {
+            return carcass::carrion(&self.world.carcasses, wolf.x, wolf.y, self.tick, self.world.day_length).map_or(Lead::Alone, |c| Lead::Prey(c.x, c.y));
+        }
+        if wolf.kind != AnimalKind::Wolf {
+            return Lead::Alone;
+        }
+        let leader = wolf.pack.and_then(|id| pack::leader(&self.animals, id)).unwrap_or(a);
+        match self.quarry(leader, phase) {
+            Some((x, y)) => Lead::Prey(x, y),
+            None if leader != a => Lead::Herd(self.animals[leader].x, self.animals[leader].y),
+            None => Lead::Alone,
+        }
+    }
+
+    /// What the wolf at `w` would lead a hunt after: whichever deer or
+    /// livestock, or at night lone orc, ranks best for being near and weak.
+    /// A ripe carcass is the easiest meal of all. Nothing while it's bedded
+    /// down or still full.
+    fn quarry(&self, w: usize, phase: DayPhase) -> Option<(usize, usize)> {
+        let wolf = &self.animals[w];
+        if !wolf.kind.is_active(phase) || self.tick < wolf.rests_until {
+            return None;
+        }
+        let dist = |x: usize, y: usize| wolf.x.abs_diff(x) + wolf.y.abs_diff(y);
+        let orcs = self.orcs_within(wolf.x, wolf.y, PROWL_RADIUS)
+            .filter(|&(i, _)| phase == DayPhase::Night && self.is_prey(i))
+            .map(|(_, o)| (o.x, o.y, 100.0 - o.health));
+        let animals = self.index.within(wolf.x, wolf.y, PROWL_RADIUS)
+            .filter_map(|entity| match entity {
+                Entity::Animal(j) if self.is_quarry(j) => Some(&self.animals[j]),
+                _ => None,
+            })
+            .map(|a| (a.x, a.y, a.hunger.max(a.thirst)));
+        let carrion = carcass::carrion(&self.world.carcasses, wolf.x, wolf.y, self.tick, self.world.day_length).map(|c| (c.x, c.y, 100.0));
+        orcs.chain(animals).chain(carrion)
+            .min_by(|&(ax, ay, aw), &(bx, by, bw)| {
+                pack::rank(dist(ax, ay), aw).total_cmp(&pack::rank(dist(bx, by), bw)).then((ax, ay).cmp(&(bx, by)))
+            })
+            .map(|(x, y, _)| (x, y))
+    }
+
+    /// Scavengers that have reached a ripe carcass feed off it: a wolf bolts
+    /// down its fill and its pack is sated, and crows peck away at it. A crow
+    /// with no carrion left to smell flies off.
+    fn scavenge(&mut self, phase: DayPhase) {
+        for s in 0..self.animals.len() {
+            let animal = &self.animals[s];
+            if !animal.alive || !animal.kind.is_scavenger() || !animal.kind.is_active(phase) || self.tick < animal.rests_until {
+                continue;
+            }
+            let Some(carrion) = carcass::carrion(&self.world.carcasses, animal.x, animal.y, self.tick, self.world.day_length) else {
+                if animal.kind == AnimalKind::Crow && self.rng.gen_bool(CROW_DEPARTS) {
+                    self.animals[s].alive = false;
+                }
+                continue;
+            };
+            if carrion.x.abs_diff(animal.x).max(carrion.y.abs_diff(animal.y)) > 1 {
+                continue;
+            }
+            let (cx, cy, kind) = (carrion.x, carrion.y, carrion.kind);
+            if animal.kind == AnimalKind::Wolf {
+                for _ in 0..WOLF_FEED {
+                    self.world.butcher(cx, cy);
+                }
+                self.sate_pack(s);
+                self.event_log.log_as(self.tick, EventCategory::Hunt, format!("A wolf feeds on a rotting {} carcass", kind.name().to_lowercase()), Severity::Info);
+            } else if self.rng.gen_bool(PECK_CHANCE) {
+                self.world.butcher(cx, cy);
+            }
+        }
+    }
+
+    /// The smell of a ripe carcass brings crows down out of the sky, a few to
+    /// each, landing somewhere about and flying in
+    fn draw_crows(&mut self, phase: DayPhase) {
+        if !AnimalKind::Crow.is_active(phase) {
+            return;
+        }
+        for c in 0..self.world.carcasses.len() {
+            let carcass = &self.world.carcasses[c];
+            let (x, y) = (carcass.x, carcass.y);
+            let crows = self.animals.iter()
+                .filter(|a| a.alive && a.kind == AnimalKind::Crow && a.x.abs_diff(x).max(a.y.abs_diff(y)) <= carcass::CARRION_SCENT)
+                .count();
+            if !carcass.is_ripe(self.tick, self.world.day_length) || crows >= CROWS_PER_CARCASS || !self.rng.gen_bool(CROW_CHANCE) {
+                continue;
+            }
+            let spot = (0..10)
+                .map(|_| {
+                    let sx = (x as i32 + self.rng.gen_range(-10..=10)).clamp(0, MAP_WIDTH as i32 - 1) as usize;
+                    let sy = (y as i32 + self.rng.gen_range(-10..=10)).clamp(0, MAP_HEIGHT as i32 - 1) as usize;
+                    (sx, sy)
+                })
+                .find(|&(sx, sy)| self.world.is_open_to_wildlife(sx, sy));
+            if let Some((sx, sy)) = spot {
+                self.animals.push(Animal::new(AnimalKind::Crow, sx, sy));
+            }
+        }
+    }
+
+    /// The wolf at `w` has made a kill, which its whole pack shares and then
+    /// leaves prey alone for a while
+    fn sate_pack(&mut self, w: usize) {
+        let pack = self.animals[w].pack;
+        let until = self.tick + WOLF_RESPITE;
+        self.animals[w].rests_until = until;
+        for wolf in self.animals.iter_mut().filter(|a| a.pack.is_some() && a.pack == pack) {
+            wolf.rests_until = until;
+        }
+    }
+
+    /// Whether the animal at `j` is one a wolf runs down: a deer, or livestock in the pens
+    fn is_quarry(&self, j: usize) -> bool {
+        let animal = &self.animals[j];
+        animal.alive && (animal.kind == AnimalKind::Deer || husbandry::is_livestock(animal) && animal.carried_by.is_none())
+    }
+
+    /// Whether orc `i` is out where a wolf will go for it: away from the
+    /// firelight, not inside a building, and either alone or groping about in
+    /// the dark with no torch. One already fighting stays fair game.
+    fn is_prey(&self, i: usize) -> bool {
+        let orc = &self.orcs[i];
+        if matches!(orc.activity, Activity::Fighting | Activity::Defending { .. }) {
+            return true;
+        }
+        if orc.is_infant(self.tick, self.world.day_length) {
+            return false;
+        }
+        let in_firelight = self.world.fire_lit() && animal::near_fire(&self.world, orc.x, orc.y);
+        let indoors = matches!(self.world.get(orc.x, orc.y), Terrain::Built(_));
+        !in_firelight && !indoors && (orc.in_dark || self.orcs_within(orc.x, orc.y, LONE_RADIUS).count() == 1)
+    }
+
+    /// Where the light falls, which only makes a difference at night
+    pub fn lights(&self) -> Lights {
+        Lights::of(&self.world, &self.orcs)
+    }
+
+    /// Note which orcs are out in the dark: at night, beyond the campfire's
+    /// light and that of any torch
+    fn find_the_dark(&mut self, phase: DayPhase) {
+        let lights = self.lights();
+        for orc in &mut self.orcs {
+            orc.in_dark = phase == DayPhase::Night && !lights.lit(orc.x, orc.y);
+        }
+    }
+
+    /// Wolves that have caught up with their prey pounce: on livestock or a
+    /// deer whenever they're out hunting, and at night on a lone orc, who then
+    /// fights back or runs for the fire
+    fn wolves_strike(&mut self, phase: DayPhase) {
+        for w in 0..self.animals.len() {
+            let wolf = &self.animals[w];
+            if wolf.kind != AnimalKind::Wolf || !wolf.alive || !wolf.kind.is_active(phase) || self.tick < wolf.rests_until {
+                continue;
+            }
+            let (wx, wy) = (wolf.x, wolf.y);
+            let within_reach = |x: usize, y: usize| wx.abs_diff(x).max(wy.abs_diff(y)) <= 1;
+            // An orc close by the pens stands guard and has the wolf to fight instead
+            if let Some(l) = (0..self.animals.len()).find(|&l| self.is_quarry(l) && self.animals[l].tame.is_some() && within_reach(self.animals[l].x, self.animals[l].y)) {
+                let (lx, ly) = (self.animals[l].x, self.animals[l].y);
+                let guard = self.orcs_within(lx, ly, husbandry::GUARD_RADIUS)
+                    .filter(|(_, o)| !o.is_infant(self.tick, self.world.day_length))
+                    .map(|(i, _)| i)
+                    .min();
+                if let Some(o) = guard {
+                    self.wolf_attack(w, o);
+                    continue;
+                }
+                let Ok([wolf, beast]) = self.animals.get_disjoint_mut([w, l]) else {
+                    continue;
+                };
+                let kind = beast.kind.name().to_lowercase();
+                match combat::attack(&*wolf, &animal::BITE, beast, &mut self.rng, self.tick) {
+                    Blow::Missed => {}
+                    Blow::Landed(_) | Blow::Routed(_) => {
+                        self.event_log.log_at(self.tick, EventCategory::Alert, format!("A wolf gets into the pens and mauls a {}!", kind), Severity::Danger, (lx, ly));
+                    }
+                    Blow::Killed => {
+                        let meat = beast.kind.meat_yield() - 1;
+                        self.world.lay_carcass(lx, ly, beast.kind, meat, self.tick);
+                        self.sate_pack(w);
+                        self.event_log.log_at(self.tick, EventCategory::Alert, format!("A wolf gets into the pens and kills a {}!", kind), Severity::Danger, (lx, ly));
+                    }
+                }
+                continue;
+            }
+            if let Some(d) = self.animals.iter().position(|a| a.alive && a.kind == AnimalKind::Deer && within_reach(a.x, a.y)) {
+                let Ok([wolf, deer]) = self.animals.get_disjoint_mut([w, d]) else {
+                    continue;
+                };
+                match combat::attack(&*wolf, &animal::BITE, deer, &mut self.rng, self.tick) {
+                    Blow::Missed => {}
+                    Blow::Landed(_) | Blow::Routed(_) => {
+                        deer.flee_from(wx, wy, &self.world);
+                        self.event_log.log_as(self.tick, EventCategory::Hunt, "A wolf wounds a deer, and it bolts".to_string(), Severity::Info);
+                    }
+                    Blow::Killed => {
+                        // The pack eats its fill and leaves the rest
+                        let (dx, dy, meat) = (deer.x, deer.y, deer.kind.meat_yield() - 1);
+                        self.world.lay_carcass(dx, dy, AnimalKind::Deer, meat, self.tick);
+                        self.sate_pack(w);
+                        self.event_log.log_at(self.tick, EventCategory::Hunt, "A wolf brings down a deer".to_string(), Severity::Info, (dx, dy));
+                    }
+                }
+                continue;
+            }
+            if phase != DayPhase::Night {
+                continue;
+            }
+            let victim = self.orcs_within(wx, wy, 2)
+                .map(|(i, _)| i)
+                .filter(|&i| within_reach(self.orcs[i].x, self.orcs[i].y) && self.is_prey(i))
+                .min();
+            if let Some(o) = victim {
+                self.wolf_attack(w, o);
+            }
+        }
+
+        // Orcs left standing once the wolf is dead or gone go back to their business
+        for orc in self.orcs.iter_mut().filter(|o| o.activity == Activity::Fighting) {
+            let beset = self.animals.iter().any(|a| {
+                a.alive && a.kind == AnimalKind::Wolf && self.tick >= a.rests_until && a.x.abs_diff(orc.x).max(a.y.abs_diff(orc.y)) <= 1
+            });
+            if !beset {
+                orc.activity = Activity::Idle;
+            }
+        }
+    }
+
+    /// Banner the deaths this tick, and the food stores if they've just run
+    /// out, and take down a banner that's been up long enough
+    fn raise_notices(&mut self) {
+        self.notices.expire(self.tick, self.world.day_length);
+        let died: Vec<String> = self.orcs.iter().filter(|o| o.death_tick == Some(self.tick)).map(|o| o.name.clone()).collect();
+        for name in died {
+            // The death as the log tells it, with what killed it
+            let told = self.event_log.since(self.stats_mark)
+                .rev()
+                .find(|e| e.category == EventCategory::Death && e.message.starts_with(&name))
+                .map(|e| e.message.clone());
+            self.notify(notify::Kind::Death, told.unwrap_or_else(|| format!("{} has died!", name)));
+        }
+        let empty = self.world.stockpiled_meals() == 0;
+        if empty && !self.stores_empty {
+            self.notify(notify::Kind::Stores, "The food stores have run out!".to_string());
+        }
+        self.stores_empty = empty;
+    }
+
+    /// Put up a banner for the player, pausing if they've asked to be
+    /// stopped for this kind
+    fn notify(&mut self, kind: notify::Kind, message: String) {
+        if self.notices.raise(kind, message, self.tick) {
+            self.paused = true;
+        }
+    }
+
+    /// Take down the banner showing
+    pub fn dismiss_notice(&mut self) {
+        self.notices.dismiss(self.tick);
+    }
+
+    /// End the defense scenario once the campfire's hearth has been smashed,
+    /// pausing on the final score
+    fn check_siege(&mut self) {
+        let (cx, cy) = self.world.campfire_pos;
+        let day = calendar::day_number(self.tick, self.world.day_length);
+        let Some(siege) = self.siege.as_mut().filter(|s| s.fallen.is_none()) else {
+            return;
+        };
+        if !self.world.is_broken(cx, cy) {
+            return;
+        }
+        siege.fallen = Some(day);
+        let message = format!(
+            "The campfire has been smashed! The clan held out {} days against {} waves and killed {} goblins: final score {}",
+            day, siege.waves, siege.killed, siege.score(day),
+        );
+        self.event_log.log_as(self.tick, EventCategory::Alert, message, Severity::Danger);
+        self.paused = true;
+    }
+
+    /// Now and then at dusk, once the clan has lasted long enough to be worth
+    /// robbing, send a band of goblins against the camp. Under siege, the
+    /// waves come on their own schedule instead.
+    fn call_raid(&mut self) {
+        let days = calendar::day_number(self.tick, self.world.day_length);
+        if let Some(siege) = &mut self.siege {
+            if self.raid.is_some() {
+                return;
+            }
+            let Some(size) = siege.call_wave(days) else {
+                return;
+            };
+            let wave = siege.waves;
+            if let Some(raid) = Raid::arrive(size, &self.world, &mut self.rng) {
+                let message = format!("Wave {}: a band of {} goblins comes out of the {}, making for the campfire!", wave, raid.size, raid.direction());
+                self.event_log.log_as(self.tick, EventCategory::Alert, message.clone(), Severity::Danger);
+                self.notify(notify::Kind::Raid, message);
+                self.raid = Some(raid);
+            }
+            return;
+        }
+        let meals = self.world.stockpiled_meals();
+        let resting = self.raid_ended > 0 && self.tick < self.raid_ended + raid::respite(self.world.day_length);
+        if self.raid.is_some() || days <= raid::GRACE_DAYS || resting || !self.rng.gen_bool(raid::chance(days, meals)) {
+            return;
+        }
+        if let Some(raid) = Raid::arrive(raid::band_size(days, meals), &self.world, &mut self.rng) {
+            let message = format!("A band of {} goblins comes out of the {}, making for the camp!", raid.size, raid.direction());
+            self.event_log.log_as(self.tick, EventCategory::Alert, message.clone(), Severity::Danger);
+            self.notify(notify::Kind::Raid, message);
+            self.raid = Some(raid);
+        }
+    }
+
+    /// Move the raid on: each goblin makes for the meat rack, grabs what it
+    /// can carry and runs back off the map the way the band came, fighting
+    /// any orc within reach on the way. Under siege the goblins make for the
+    /// campfire instead, and batter at its hearth until they're beaten off.
+    fn run_raid(&mut self) {
+        let Some(mut raid) = self.raid.take() else {
+            return;
+        };
+        let (rx, ry) = match self.siege {
+            Some(_) => self.world.campfire_pos,
+            None => self.world.meat_rack_pos().unwrap_or(self.world.campfire_pos),
+        };
+        let (ex, ey) = raid.edge;
+        let routed = raid.is_routed();
+        for goblin in &mut raid.goblins {
+            goblin.fleeing |= routed;
+            if goblin.fleeing {
+                goblin.advance(ex, ey, &self.world);
+            } else if self.siege.is_some() && goblin.x.abs_diff(rx).max(goblin.y.abs_diff(ry)) <= 1 {
+                self.world.wear(rx, ry, scenario::SMASH);
+                if !raid.smashing {
+                    raid.smashing = true;
+                    self.event_log.log_as(self.tick, EventCategory::Combat, "The goblins set about smashing the campfire!".to_string(), Severity::Danger);
+                }
+            } else if goblin.x.abs_diff(rx).max(goblin.y.abs_diff(ry)) <= 1 {
+                while goblin.loot < raid::LOOT && self.world.take_meal() {
+                    goblin.loot += 1;
+                }
+                goblin.fleeing = true;
+                let message = match goblin.loot {
+                    0 => "A goblin finds the rack bare and runs".to_string(),
+                    loot => format!("A goblin grabs {} meals off the rack and runs!", loot),
+                };
+                self.event_log.log_as(self.tick, EventCategory::Alert, message, Severity::Danger);
+            } else {
+                goblin.advance(rx, ry, &self.world);
+            }
+        }
+
+        // Goblins back at the edge get away with whatever they're carrying
+        let mut escaped = 0;
+        raid.goblins.retain(|g| {
+            let away = g.fleeing && g.x.abs_diff(ex).max(g.y.abs_diff(ey)) <= 1;
+            if away {
+                escaped += 1;
+                raid.stolen += g.loot;
+            }
+            !away
+        });
+        if escaped > 0 {
+            let message = if escaped == 1 { "A goblin gets away".to_string() } else { format!("{} goblins get away", escaped) };
+            self.event_log.log_as(self.tick, EventCategory::Alert, message, Severity::Danger);
+        }
+
+        self.raid_fights(&mut raid);
+        if !raid.goblins.is_empty() {
+            self.raid = Some(raid);
+            return;
+        }
+        let message = match raid.stolen {
+            0 => format!("The goblin raid is over: {} of {} killed and nothing taken", raid.killed, raid.size),
+            stolen => format!("The goblin raid is over: {} of {} killed, {} meals carried off", raid.killed, raid.size, stolen),
+        };
+        self.event_log.log_as(self.tick, EventCategory::Alert, message, Severity::Warning);
+        self.raid_ended = self.tick;
+    }
+
+    /// Each goblin clubs an orc within reach who is out defending the camp,
+    /// and each such orc hits back at a goblin within reach. A goblin beaten
+    /// or killed drops whatever it was carrying.
+    fn raid_fights(&mut self, raid: &mut Raid) {
+        for goblin in &raid.goblins {
+            let target = self.orcs_within(goblin.x, goblin.y, 1)
+                .filter(|(_, o)| matches!(o.activity, Activity::Defending { .. }))
+                .map(|(i, _)| i)
+                .min();
+            let Some(o) = target else {
+                continue;
+            };
+            let orc = &mut self.orcs[o];
+            match combat::attack(goblin, &raid::CLUB, orc, &mut self.rng, self.tick) {
+                Blow::Missed => {}
+                Blow::Killed => {
+                    orc.died_of = Some(Cause::Goblin);
+                    self.event_log.log_at(self.tick, EventCategory::Death, format!("{} is clubbed to death by a goblin!", orc.name), Severity::Danger, (orc.x, orc.y));
+                }
+                Blow::Landed(_) | Blow::Routed(_) => {
+                    self.event_log.log_at(self.tick, EventCategory::Combat, format!("A goblin clubs {} ({:.0} health left)", orc.name, orc.health), Severity::Danger, (orc.x, orc.y));
+                }
+            }
+        }
+
+        for orc in self.orcs.iter_mut().filter(|o| o.alive && matches!(o.activity, Activity::Defending { .. })) {
+            let Some(goblin) = raid.goblins.iter_mut().find(|g| !g.is_dead() && g.x.abs_diff(orc.x).max(g.y.abs_diff(orc.y)) <= 1) else {
+                continue;
+            };
+            let blow = orc.fight(goblin, &mut self.rng, &mut self.event_log, self.tick);
+            let beaten_off = matches!(blow, Blow::Routed(_)) && !goblin.fleeing;
+            if goblin.loot > 0 && (blow == Blow::Killed || beaten_off) {
+                self.world.add_food(goblin.x, goblin.y, goblin.loot);
+                goblin.loot = 0;
+            }
+            if blow == Blow::Killed {
+                orc.morale = (orc.morale + 15.0).clamp(0.0, 100.0);
+                self.world.markers.note(Mark::Raid, goblin.x, goblin.y, self.tick, format!("A goblin cut down by {}", orc.name));
+                self.event_log.log_at(self.tick, EventCategory::Combat, format!("{} kills a goblin!", orc.name), Severity::Good, (orc.x, orc.y));
+            } else if beaten_off {
+                goblin.fleeing = true;
+                self.event_log.log_at(self.tick, EventCategory::Combat, format!("{} beats a goblin off, and it drops what it stole and runs", orc.name), Severity::Good, (orc.x, orc.y));
+            }
+        }
+        let before = raid.goblins.len();
+        raid.goblins.retain(|g| !g.is_dead());
+        raid.killed += before - raid.goblins.len();
+        if let Some(siege) = &mut self.siege {
+            siege.killed += before - raid.goblins.len();
+        }
+    }
+
+    /// Move the rival clan on: each rival forages out from its own fire, unless
+    /// one of our grown orcs is within sight on contested ground, when it goes
+    /// for it instead and trades blows once within reach. The rivals make
+    /// good their dead in time.
+    fn run_rivals(&mut self) {
+        let daylight = self.phase().is_daylight();
+        let camp = self.rivals.camp;
+        let mut rivals = std::mem::take(&mut self.rivals.members);
+        for rival in &mut rivals {
+            let quarry = if daylight && !rival.beaten && self.territory.is_contested(rival.x, rival.y) {
+                self.orcs_within(rival.x, rival.y, rival::SIGHT)
+                    .filter(|(_, o)| o.is_adult(self.tick, self.world.day_length) && !matches!(o.activity, Activity::Marching { .. } | Activity::Expedition { .. }))
+                    .min_by_key(|(_, o)| o.x.abs_diff(rival.x).max(o.y.abs_diff(rival.y)))
+                    .map(|(i, _)| i)
+            } else {
+                None
+            };
+            let Some(o) = quarry else {
+                rival.hostile = false;
+                rival.roam(camp, daylight, &self.world, &mut self.rng, self.tick);
+                continue;
+            };
+            let (ox, oy) = (self.orcs[o].x, self.orcs[o].y);
+            if !rival.hostile {
+                rival.hostile = true;
+                self.event_log.log_as(
+                    self.tick,
+                    EventCategory::Alert,
+                    format!("{} of the rival clan comes at {} on contested ground!", rival.name, self.orcs[o].name),
+                    Severity::Danger,
+                );
+            }
+            if ox.abs_diff(rival.x).max(oy.abs_diff(rival.y)) <= 1 {
+                self.skirmish(rival, o);
+            } else {
+                rival.advance(ox, oy, &self.world);
+            }
+        }
+        self.rivals.members = rivals;
+        self.rivals.clear_dead(self.tick);
+        let names: Vec<String> = self.orcs.iter().map(|o| o.name.clone()).collect();
+        if let Some(name) = self.rivals.make_good(&names, &mut self.rng, self.tick, self.world.day_length) {
+            self.event_log.log(self.tick, format!("{} joins the rival clan across the map", name), Severity::Info);
+        }
+    }
+
+    /// One exchange between a rival and orc `o`: the rival swings, then the
+    /// orc hits back or runs for home
+    fn skirmish(&mut self, rival: &mut Rival, o: usize) {
+        let orc = &mut self.orcs[o];
+        match combat::attack(&*rival, &rival::AXE, orc, &mut self.rng, self.tick) {
+            Blow::Missed => {}
+            Blow::Killed => {
+                orc.died_of = Some(Cause::Rival);
+                self.event_log.log_at(self.tick, EventCategory::Death, format!("{} is cut down by {} of the rival clan!", orc.name, rival.name), Severity::Danger, (orc.x, orc.y));
+                return;
+            }
+            Blow::Landed(_) | Blow::Routed(_) => {
+                self.event_log.log_at(self.tick, EventCategory::Combat, format!("{} strikes {} ({:.0} health left)", rival.name, orc.name, orc.health), Severity::Danger, (orc.x, orc.y));
+            }
+        }
+        if !orc.stands_ground() {
+            orc.flee_to_camp(&self.world, &mut self.event_log, self.tick);
+            return;
+        }
+        match orc.fight(rival, &mut self.rng, &mut self.event_log, self.tick) {
+            Blow::Missed | Blow::Landed(_) => {}
+            Blow::Routed(_) => {
+                rival.beaten = true;
+                self.event_log.log_at(self.tick, EventCategory::Combat, format!("{} beats {} off, and it runs for home", orc.name, rival.name), Severity::Good, (orc.x, orc.y));
+            }
+            Blow::Killed => {
+                orc.morale = (orc.morale + 15.0).clamp(0.0, 100.0);
+                self.event_log.log_at(self.tick, EventCategory::Combat, format!("{} kills {} of the rival clan!", orc.name, rival.name), Severity::Good, (orc.x, orc.y));
+            }
+        }
+    }
+
+    /// Every orc up and about leaves its clan's scent where it stands, and the
+    /// scent already on the ground fades a little
+    fn mark_territory(&mut self) {
+        self.territory.fade();
+        for orc in self.orcs.iter().filter(|o| o.leaves_scent()) {
+            self.territory.mark(Clan::Ours, orc.x, orc.y);
+        }
+        for rival in &self.rivals.members {
+            self.territory.mark(Clan::Rivals, rival.x, rival.y);
+        }
+    }
+
+    /// Whether the animal is a wolf out on the prowl, rather than one sated,
+    /// beaten or asleep
+    fn is_menace(&self, animal: &Animal, phase: DayPhase) -> bool {
+        animal.alive && animal.kind == AnimalKind::Wolf && animal.kind.is_active(phase) && self.tick >= animal.rests_until
+    }
+
+    /// Put the clan on alert while goblins or wolves on the prowl are close
+    /// to camp, and stand it down once they've been gone a while
+    fn sound_alarm(&mut self) {
+        let phase = self.phase();
+        let (cx, cy) = self.world.campfire_pos;
+        let from_camp = |x: usize, y: usize| x.abs_diff(cx).max(y.abs_diff(cy));
+        let goblin = self.raid.iter().flat_map(|r| &r.goblins)
+            .filter(|g| from_camp(g.x, g.y) <= alarm::ALERT_RADIUS)
+            .min_by_key(|g| from_camp(g.x, g.y))
+            .map(|g| (Threat::Goblins, g.x, g.y));
+        let wolf = self.animals.iter()
+            .filter(|a| self.is_menace(a, phase) && from_camp(a.x, a.y) <= alarm::ALERT_RADIUS)
+            .min_by_key(|a| from_camp(a.x, a.y))
+            .map(|a| (Threat::Wolves, a.x, a.y));
+        match goblin.or(wolf) {
+            Some((threat, x, y)) => {
+                if self.alarm.sight(threat, x, y, self.tick) {
+                    self.event_log.log_as(
+                        self.tick,
+                        EventCategory::Alert,
+                        format!("The clan is on alert: {} to the {} of the camp!", threat.name(), watch::bearing((cx, cy), (x, y))),
+                        Severity::Danger,
+                    );
+                }
+            }
+            None => {
+                if self.alarm.lapse(self.tick) {
+                    self.event_log.log_as(self.tick, EventCategory::Alert, "The danger has passed, and the clan stands down".to_string(), Severity::Warning);
+                }
+            }
+        }
+    }
+
+    /// While the clan is on alert the young and the old make for the fire,
+    /// and everyone near camp fit to fight goes after the nearest goblin or
+    /// wolf there, trading blows with any wolf within reach. Any who are too
+    /// hurt or timid for it or have more pressing needs go back to their
+    /// business, as does everyone else once the clan stands down.
+    fn rally_to_alarm(&mut self) {
+        let phase = self.phase();
+        let (cx, cy) = self.world.campfire_pos;
+        let near_camp = |x: usize, y: usize| x.abs_diff(cx).max(y.abs_diff(cy)) <= alarm::ALERT_RADIUS;
+        let mut threats: Vec<(usize, usize)> = self.raid.iter().flat_map(|r| &r.goblins).map(|g| (g.x, g.y)).collect();
+        threats.extend(self.animals.iter().filter(|a| self.is_menace(a, phase)).map(|a| (a.x, a.y)));
+        threats.retain(|&(x, y)| near_camp(x, y));
+        let raised = self.alarm.is_raised();
+
+        let day_length = self.world.day_length;
+        for orc in self.orcs.iter_mut().filter(|o| o.alive && !o.is_infant(self.tick, day_length)) {
+            if matches!(orc.activity, Activity::Fighting | Activity::Marching { .. } | Activity::Expedition { .. }) {
+                continue;
+            }
+            let defending = matches!(orc.activity, Activity::Defending { .. });
+            if !orc.is_adult(self.tick, self.world.day_length) || orc.is_elder(self.tick, self.world.day_length) {
+                let sheltered = matches!(
+                    orc.activity,
+                    Activity::Sleeping | Activity::Sheltering { .. } | Activity::GoingTo { purpose: Purpose::Camp, .. }
+                );
+                if raised && !sheltered && !orc.carrying_food && !orc.near_campfire(&self.world) && !orc.has_pressing_need(&self.ai) {
+                    orc.cancel(&mut self.world, &mut self.event_log, self.tick);
+                    let (sx, sy) = orc.find_spot_near(cx, cy, &self.world, &mut self.rng);
+                    orc.go_to(sx, sy, Purpose::Camp, alarm::TAKING_COVER, &self.world);
+                } else if defending {
+                    orc.activity = Activity::Idle;
+                }
+                continue;
+            }
+
+            if defending
+                && let Some(wolf) = self.animals.iter_mut().find(|a| {
+                    a.alive && a.kind == AnimalKind::Wolf && self.tick >= a.rests_until && a.x.abs_diff(orc.x).max(a.y.abs_diff(orc.y)) <= 1
+                })
+            {
+                wolf.behaviour = Behaviour::Fighting;
+                orc.set_upon(wolf, &mut self.world, &mut self.rng, &mut self.event_log, self.tick);
+                continue;
+            }
+            let nearest = threats.iter().copied()
+                .filter(|_| near_camp(orc.x, orc.y))
+                .min_by_key(|&(x, y)| x.abs_diff(orc.x) + y.abs_diff(orc.y));
+            match nearest {
+                Some((x, y)) if orc.stands_ground() && !orc.has_pressing_need(&self.ai) => {
+                    if !defending {
+                        self.event_log.log_at(self.tick, EventCategory::Alert, format!("{} goes out to meet the threat", orc.name), Severity::Danger, (orc.x, orc.y));
+                    }
+                    orc.defend(x, y, &self.world);
+                }
+                // Hold where the threat was last seen until the clan stands down, or hunger or thirst calls
+                None if raised && defending && !orc.has_pressing_need(&self.ai) => {}
+                _ if defending => orc.activity = Activity::Idle,
+                _ => {}
+            }
+        }
+    }
+
+    /// Guards fit for duty go on watch: up the nearest watchtower nobody is
+    /// manning, or else on to the next post of the round about the camp
+    fn assign_guard_duty(&mut self) {
+        let phase = self.phase();
+        let towers: Vec<(usize, usize)> = self.world.buildings.iter()
+            .filter(|&&(_, _, kind)| kind == Building::Watchtower)
+            .map(|&(x, y, _)| (x, y))
+            .collect();
+        let posts: Vec<(usize, usize)> = self.around_camp(watch::ROUND_DISTANCE).filter(|&(x, y)| self.world.is_walkable(x, y)).collect();
+        for i in 0..self.orcs.len() {
+            let orc = &self.orcs[i];
+            if orc.job != Job::Guard || !orc.can_help() || orc.is_infant(self.tick, self.world.day_length) || orc.is_bedtime(phase) || orc.needs_a_break(&self.ai) {
+                continue;
+            }
+            let (ox, oy) = (orc.x, orc.y);
+            let manned = |(x, y): (usize, usize)| self.orcs.iter().any(|o| o.activity == Activity::Guarding { x, y, tower: true });
+            let tower = towers.iter().copied().filter(|&t| !manned(t)).min_by_key(|&(x, y)| x.abs_diff(ox) + y.abs_diff(oy));
+            if let Some((x, y)) = tower {
+                self.event_log.log(self.tick, format!("{} climbs the watchtower to keep watch", self.orcs[i].name), Severity::Info);
+                self.orcs[i].stand_guard(x, y, true, &self.world);
+            } else if let Some(here) = (0..posts.len()).min_by_key(|&p| posts[p].0.abs_diff(ox) + posts[p].1.abs_diff(oy)) {
+                let (x, y) = posts[(here + 1) % posts.len()];
+                self.orcs[i].stand_guard(x, y, false, &self.world);
+            }
+        }
+    }
+
+    /// Guards on watch look out for wolves on the prowl and goblins coming
+    /// in. At the first sight of either, once nothing has been in sight for a
+    /// while, they raise the alarm and everyone out beyond the camp comes back in.
+    fn keep_watch(&mut self) {
+        let phase = self.phase();
+        let sighting = self.orcs.iter().filter(|o| o.alive).find_map(|o| {
+            let Activity::Guarding { x, y, tower } = o.activity else {
+                return None;
+            };
+            let on_tower = tower && o.x.abs_diff(x).max(o.y.abs_diff(y)) <= 1;
+            let sight = if on_tower { watch::TOWER_SIGHT } else { watch::SIGHT };
+            let in_sight = |x: usize, y: usize| x.abs_diff(o.x).max(y.abs_diff(o.y)) <= sight;
+            let goblin = self.raid.iter().flat_map(|r| &r.goblins).find(|g| in_sight(g.x, g.y)).map(|g| (Threat::Goblins, g.x, g.y));
+            let wolf = self.animals.iter()
+                .find(|a| a.alive && a.kind == AnimalKind::Wolf && a.kind.is_active(phase) && in_sight(a.x, a.y))
+                .map(|a| (Threat::Wolves, a.x, a.y));
+            goblin.or(wolf).map(|(threat, tx, ty)| (o.name.clone(), on_tower, threat, (tx, ty)))
+        });
+        let Some((guard, on_tower, threat, at)) = sighting else {
+            return;
+        };
+        let fresh = self.threat_seen.is_none_or(|seen| self.tick >= seen + watch::ALARM_RESPITE);
+        self.threat_seen = Some(self.tick);
+        if !fresh || self.evacuation.is_some() {
+            return;
+        }
+
+        let (cx, cy) = self.world.campfire_pos;
+        let from = if on_tower { "from the watchtower" } else { "on the rounds" };
+        self.event_log.log_as(
+            self.tick,
+            EventCategory::Alert,
+            format!("{} spots {} to the {} {} and raises the alarm!", guard, threat.name(), watch::bearing((cx, cy), at), from),
+            Severity::Danger,
+        );
+        let day_length = self.world.day_length;
+        for orc in self.orcs.iter_mut().filter(|o| o.alive && !o.is_infant(self.tick, day_length) && !o.carrying_food) {
+            let busy = matches!(
+                orc.activity,
+                Activity::Sleeping | Activity::Fighting | Activity::Defending { .. } | Activity::Guarding { .. } | Activity::Marching { .. }
+                    | Activity::Sheltering { .. } | Activity::Expedition { .. } | Activity::GoingTo { purpose: Purpose::Camp, .. }
+            );
+            if busy || orc.x.abs_diff(cx).max(orc.y.abs_diff(cy)) <= watch::CALL_BACK {
+                continue;
+            }
+            orc.cancel(&mut self.world, &mut self.event_log, self.tick);
+            orc.go_to(cx, cy, Purpose::Camp, watch::CALLED_BACK, &self.world);
+        }
+    }
+
+    /// One exchange between the wolf at `w` and orc `o`: the wolf bites, then
+    /// the orc hits back or breaks away
+    fn wolf_attack(&mut self, w: usize, o: usize) {
+        let orc = &mut self.orcs[o];
+        let wolf = &mut self.animals[w];
+        wolf.behaviour = Behaviour::Fighting;
+        match combat::attack(&*wolf, &animal::BITE, orc, &mut self.rng, self.tick) {
+            Blow::Missed => {}
+            Blow::Killed => {
+                orc.died_of = Some(Cause::Beast(AnimalKind::Wolf));
+                self.event_log.log_at(self.tick, EventCategory::Death, format!("{} is killed by a wolf!", orc.name), Severity::Danger, (orc.x, orc.y));
+                self.sate_pack(w);
+                return;
+            }
+            Blow::Landed(_) | Blow::Routed(_) => {
+                self.event_log.log_at(self.tick, EventCategory::Combat, format!("A wolf bites {} ({:.0} health left)", orc.name, orc.health), Severity::Danger, (orc.x, orc.y));
+            }
+        }
+        if orc.stands_ground() {
+            orc.strike(wolf, &mut self.world, &mut self.rng, &mut self.event_log, self.tick);
+        } else {
+            orc.flee_to_camp(&self.world, &mut self.event_log, self.tick);
+        }
+    }
+
+    /// Set off every hook due this tick: those on a tick, and those on a
+    /// death, a birth or a kill once for each there was. Babies are those from
+    /// `born` on in the clan.
+    fn run_hooks(&mut self, killed: &[AnimalKind], born: usize) {
+        let hooks = std::mem::take(&mut self.hooks);
+        let schedule = std::mem::take(&mut self.schedule);
+        let died: Vec<String> = self.orcs.iter().filter(|o| o.death_tick == Some(self.tick)).map(|o| o.name.clone()).collect();
+        let dawn = calendar::time_of_day(self.tick, self.world.day_length) == 0;
+        for hook in hooks.iter().chain(&schedule) {
+            // Whom or what the hook is about, and the newborn it's to see to
+            let occasions: Vec<(String, String, Option<usize>)> = match hook.trigger {
+                Trigger::Tick(every) if self.tick.is_multiple_of(every) => vec![(String::new(), String::new(), None)],
+                Trigger::Tick(_) => Vec::new(),
+                Trigger::Day(day) if dawn && calendar::day_number(self.tick, self.world.day_length) == day => vec![(String::new(), String::new(), None)],
+                Trigger::Day(_) => Vec::new(),
+                Trigger::Death => died.iter().map(|name| (name.clone(), String::new(), None)).collect(),
+                Trigger::Birth => (born..self.orcs.len()).map(|i| (self.orcs[i].name.clone(), String::new(), Some(i))).collect(),
+                Trigger::Hunt => killed.iter().map(|kind| (String::new(), kind.name().to_lowercase(), None)).collect(),
+            };
+            for (orc, creature, baby) in occasions {
+                if hook.chance < 1.0 && !self.rng.gen_bool(hook.chance) {
+                    continue;
+                }
+                for action in &hook.actions {
+                    self.carry_out(action, &orc, &creature, baby);
+                }
+            }
+        }
+        self.hooks = hooks;
+        self.schedule = schedule;
+    }
+
+    /// Do what a hook says, to `baby` if it's about one and to the whole clan otherwise
+    fn carry_out(&mut self, action: &Action, orc: &str, creature: &str, baby: Option<usize>) {
+        match action {
+            Action::Log(severity, text) => {
+                let day = calendar::day_number(self.tick, self.world.day_length).to_string();
+                let message = text.replace("{orc}", orc).replace("{creature}", creature).replace("{day}", &day);
+                self.event_log.log(self.tick, message, *severity);
+            }
+            &Action::Give(store, amount) => {
+                for _ in 0..amount {
+                    match store {
+                        Store::Meat => self.world.store_meat(),
+                        Store::Cooked => self.world.cooked_stockpile += 1,
+                        Store::Berries => self.world.berry_stockpile += 1,
+                        Store::Wood => self.world.wood_stockpile += 1,
+                        Store::Stone => self.world.stone_stockpile += 1,
+                        Store::Water => self.world.water_barrel = (self.world.water_barrel + 1).min(BARREL_CAPACITY),
+                    }
+                }
+            }
+            &Action::Take(store, amount) => {
+                for _ in 0..amount {
+                    let pile = match store {
+                        Store::Meat => {
+                            self.world.take_meat();
+                            continue;
+                        }
+                        Store::Cooked => &mut self.world.cooked_stockpile,
+                        Store::Berries => &mut self.world.berry_stockpile,
+                        Store::Wood => &mut self.world.wood_stockpile,
+                        Store::Stone => &mut self.world.stone_stockpile,
+                        Store::Water => &mut self.world.water_barrel,
+                    };
+                    *pile = pile.saturating_sub(1);
+                }
+            }
+            &Action::Set(need, value) | &Action::Add(need, value) => {
+                let add = matches!(action, Action::Add(..));
+                let orcs: Vec<usize> = match baby {
+                    Some(i) => vec![i],
+                    None => (0..self.orcs.len()).filter(|&i| self.orcs[i].alive).collect(),
+                };
+                for i in orcs {
+                    let field = self.orcs[i].need_mut(need);
+                    *field = if add { *field + value } else { value }.clamp(0.0, 100.0);
+                }
+            }
+            &Action::Arrive(Arrival::Animal(kind), count) => {
+                let animals = Animal::arrive(kind, count, &self.world, &mut self.herds, &mut self.packs, &mut self.rng);
+                self.animals.extend(animals);
+            }
+            &Action::Arrive(Arrival::Goblins, count) => {
+                if self.raid.is_some() {
+                    return;
+                }
+                if let Some(raid) = Raid::arrive(count, &self.world, &mut self.rng) {
+                    let message = format!("A band of {} goblins comes out of the {}, making for the camp!", raid.size, raid.direction());
+                    self.event_log.log_as(self.tick, EventCategory::Alert, message.clone(), Severity::Danger);
+                    self.notify(notify::Kind::Raid, message);
+                    self.raid = Some(raid);
+                }
+            }
+        }
+    }
+
+    /// Close the day's statistics, append them to the news file and announce them at dawn
+    fn publish_day_summary(&mut self) {
+        let population = self.orcs.iter().filter(|o| o.alive).count();
+        let summary = self.stats.end_day(self.world.food_stockpile, population);
+        let headline = summary.headline();
+        let report = summary.report();
+
+        if let Some(path) = &self.news_path {
+            let written = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .and_then(|mut file| file.write_all(report.as_bytes()));
+            if let Err(e) = written {
+                self.event_log.log(self.tick, format!("Could not write news to {}: {}", path.display(), e), Severity::Danger);
+                self.news_path = None;
+            }
+        }
+
+        if self.dawn_news {
+            self.event_log.log(self.tick, headline, Severity::Good);
+        }
+    }
+
+    /// Announce any change in the spirits' mood and let it take effect
+    fn heed_spirits(&mut self) {
+        let Some((mood, omen)) = self.world.spirits.dawn() else {
+            return;
+        };
+        self.event_log.log_as(self.tick, EventCategory::Spirit, omen.to_string(), Severity::Info);
+        match mood {
+            Mood::Generous => self.world.bless_harvest(),
+            Mood::Angry => {
+                // Half the game leaves for quieter woods
+                for animal in &mut self.animals {
+                    if self.rng.gen_bool(0.5) && !animal.claimed {
+                        animal.alive = false;
+                    }
+                }
+            }
+            Mood::Content | Mood::Uneasy => {}
+        }
+    }
+
+    fn celebrate_birthdays(&mut self) {
+        for orc in self.orcs.iter_mut().filter(|o| o.alive && o.is_birthday(self.tick, self.world.day_length)) {
+            let years = orc.age_days(self.tick, self.world.day_length) / calendar::YEAR_LENGTH;
+            orc.morale = (orc.morale + BIRTHDAY_MORALE).clamp(0.0, 100.0);
+            self.emotes.push(orc.x, orc.y, EmoteKind::Love, self.tick);
+            self.event_log.log_as(self.tick, EventCategory::Social, format!("It's {}'s birthday! {} turns {}", orc.name, orc.name, years), Severity::Good);
+        }
+    }
+
+    fn refresh_claims(&mut self) {
+        self.world.claimed_tiles.clear();
+        for animal in &mut self.animals {
+            animal.claimed = false;
+        }
+        for orc in self.orcs.iter().filter(|o| o.alive) {
+            orc::claim_target(&orc.activity, &mut self.world, &mut self.animals);
+        }
+    }
+
+    fn assign_food_deliveries(&mut self) {
+        // Keep couriers heading for their patient, or call them off if the patient is gone
+        for i in 0..self.orcs.len() {
+            let Activity::DeliveringFood { patient, .. } = &self.orcs[i].activity else {
+                continue;
+            };
+            let target = self.orcs.iter().find(|o| o.alive && &o.name == patient).map(|o| (o.x, o.y));
+            let courier = &mut self.orcs[i];
+            match (target, &mut courier.activity) {
+                (Some((px, py)), Activity::DeliveringFood { x, y, .. }) => {
+                    *x = px;
+                    *y = py;
+                }
+                (None, Activity::DeliveringFood { has_food, .. }) => {
+                    // Bring the meal back to the rack
+                    courier.carrying_food = *has_food;
+                    courier.activity = Activity::Idle;
+                }
+                _ => {}
+            }
+        }
+
+        let served: Vec<String> = self.orcs.iter()
+            .filter_map(|o| match &o.activity {
+                Activity::DeliveringFood { patient, .. } => Some(patient.clone()),
+                _ => None,
+            })
+            .collect();
+        for orc in &mut self.orcs {
+            orc.awaiting_food = served.contains(&orc.name);
+        }
+
+        if self.world.stockpiled_meals() == 0 {
+            return;
+        }
+        for p in 0..self.orcs.len() {
+            if !self.orcs[p].needs_feeding() || self.orcs[p].awaiting_food {
+                continue;
+            }
+            let (px, py) = (self.orcs[p].x, self.orcs[p].y);
+            let helper = self.orcs.iter().enumerate()
+                .filter(|(i, o)| *i != p && o.can_help())
+                .min_by_key(|(_, o)| o.x.abs_diff(px) + o.y.abs_diff(py))
+                .map(|(i, _)| i);
+            if let Some(h) = helper {
+                let patient = self.orcs[p].name.clone();
+                self.event_log.log_as(
+                    self.tick,
+                    EventCategory::Food,
+                    format!("{} sets out to bring food to {}", self.orcs[h].name, patient),
+                    Severity::Good,
+                );
+                self.orcs[h].start_food_delivery(patient, px, py, &self.world);
+                self.orcs[p].awaiting_food = true;
+                self.orcs[p].activity = Activity::Idle;
+            }
+        }
+    }
+
+    fn assign_haul_tasks(&mut self) {
+        if !self.phase().is_daylight() {
+            return;
+        }
+
+        // Units on each pile or carcass not already spoken for by a hauler or an orc coming to eat
+        let carcasses = self.world.carcasses.iter().map(|c| ((c.x, c.y), c.meat));
+        let mut piles: Vec<((usize, usize), u32)> = self.world.food_piles.iter()
+            .map(|(&pos, &units)| (pos, units))
+            .chain(carcasses)
+            .map(|(pos, units)| {
+                let hauling = self.orcs.iter()
+                    .filter(|o| matches!(o.activity, Activity::Hauling { x, y } if (x, y) == pos))
+                    .count() as u32;
+                let eating = u32::from(self.world.claimed_tiles.contains(&pos));
+                (pos, units.saturating_sub(hauling + eating))
+            })
+            .filter(|&(_, free)| free > 0)
+            .collect();
+        piles.sort(); // map order varies between runs; keep seeded games reproducible
+
+        for ((px, py), free) in piles {
+            for _ in 0..free {
+                let Some(h) = self.nearest_helper(px, py, HAUL_RADIUS) else {
+                    break;
+                };
+                self.event_log.log_as(
+                    self.tick,
+                    EventCategory::Food,
+                    format!("{} heads out to haul meat", self.orcs[h].name),
+                    Severity::Info,
+                );
+                self.orcs[h].start_haul(px, py, &self.world);
+            }
+        }
+    }
+
+    fn assign_water_hauling(&mut self) {
+        if !self.phase().is_daylight() {
+            return;
+        }
+        let Some((bx, by)) = self.world.barrel_pos() else {
+            return;
+        };
+        // Count the buckets already on their way so the barrel isn't overfilled
+        let underway = self.orcs.iter().filter(|o| matches!(o.activity, Activity::FetchingWater { .. })).count() as u32;
+        let mut room = BARREL_CAPACITY.saturating_sub(self.world.water_barrel + underway * BUCKET);
+        while room >= BUCKET {
+            let Some(h) = self.nearest_helper_where(bx, by, ORDER_RADIUS, |o| o.job == Job::WaterCarrier) else {
+                break;
+            };
+            let Some((wx, wy)) = self.world.find_water_adjacent(bx, by) else {
+                break;
+            };
+            self.event_log.log(self.tick, format!("{} takes a bucket to fetch water", self.orcs[h].name), Severity::Info);
+            self.orcs[h].start_fetching_water(wx, wy, &self.world);
+            room -= BUCKET;
+        }
+    }
+
+    fn assign_stoking(&mut self) {
+        let (cx, cy) = self.world.campfire_pos;
+        if !self.phase().is_daylight() || self.world.is_broken(cx, cy) {
+            return;
+        }
+        let underway = self.orcs.iter().filter(|o| o.supplies(cx, cy, Material::Wood)).count() as u32;
+        let mut room = FIRE_MAX_FUEL.saturating_sub(self.world.fire_fuel + underway * FUEL_PER_LOG);
+        // Anyone will fetch wood for a fire that's about to die; otherwise it's the woodcutters' chore
+        let mut dying = self.world.fire_fuel < FUEL_PER_LOG && underway == 0;
+        while room >= FUEL_PER_LOG {
+            let helper = self.nearest_helper_where(cx, cy, ORDER_RADIUS, |o| o.job == Job::Woodcutter)
+                .or_else(|| if dying { self.nearest_helper(cx, cy, ORDER_RADIUS) } else { None });
+            let Some(h) = helper else {
+                break;
+            };
+            if self.world.wood_stockpile > 0 {
+                self.world.wood_stockpile -= 1;
+                self.event_log.log(self.tick, format!("{} takes a log from the woodpile for the fire", self.orcs[h].name), Severity::Info);
+                self.orcs[h].start_stoking(&self.world);
+            } else {
+                let Some((tx, ty)) = self.world.find_nearest_unclaimed(cx, cy, Terrain::Tree) else {
+                    break;
+                };
+                self.event_log.log(self.tick, format!("{} goes to chop firewood", self.orcs[h].name), Severity::Info);
+                self.send_to_gather(h, Material::Wood, (tx, ty), (cx, cy));
+                self.world.claimed_tiles.insert((tx, ty));
+            }
+            room -= FUEL_PER_LOG;
+            dying = false;
+        }
+    }
+
+    /// Send idle orcs to whatever is most worn, one to each structure
+    fn assign_repairs(&mut self) {
+        if !self.phase().is_daylight() {
+            return;
+        }
+
+        let mut worn: Vec<(usize, usize, u32)> = self.world.worn().collect();
+        worn.sort_by_key(|&(_, _, condition)| condition);
+        for (x, y, _) in worn {
+            if self.orcs.iter().any(|o| o.activity == Activity::Repairing { x, y }) {
+                continue;
+            }
+            let Some(h) = self.nearest_helper(x, y, BUILD_RADIUS) else {
+                return;
+            };
+            self.event_log.log(
+                self.tick,
+                format!("{} goes to patch up the {}", self.orcs[h].name, self.world.structure_name(x, y)),
+                Severity::Info,
+            );
+            self.orcs[h].start_repairing(x, y, &self.world);
+        }
+    }
+
+    /// The day's weather on every structure, and a night's wear on each hut slept in
+    fn weather_structures(&mut self, season: Season) {
+        for orc in self.orcs.iter().filter(|o| o.alive && o.in_own_hut()) {
+            self.world.wear(orc.x, orc.y, HUT_WEAR);
+        }
+        self.world.weather(season);
+    }
+
+    /// Deal with anything that has worn right through this tick: buildings
+    /// fall down and have to be built again, the hearth goes cold and the
+    /// rack stops keeping meat until someone repairs them
+    fn settle_collapses(&mut self) {
+        for (x, y) in self.world.take_collapsed() {
+            let name = self.world.structure_name(x, y);
+            let message = match self.world.get(x, y) {
+                Terrain::Campfire => {
+                    self.world.fire_fuel = 0;
+                    "The campfire's hearth has fallen apart, and the fire with it. Nobody can light it until it's repaired".to_string()
+                }
+                Terrain::MeatRack => "The meat rack has come down. Raw meat left on it will go off by dawn".to_string(),
+                _ => {
+                    self.world.collapse(x, y);
+                    for orc in self.orcs.iter_mut().filter(|o| o.hut == Some((x, y))) {
+                        orc.hut = None;
+                    }
+                    for orc in self.orcs.iter_mut().filter(|o| o.alive && (o.x, o.y) == (x, y)) {
+                        orc.hurt(self.rng.gen_range(healing::COLLAPSE_INJURY), self.tick);
+                        let (category, message) = if orc.alive {
+                            (EventCategory::Alert, format!("{} is hurt as the {} comes down ({:.0} health left)", orc.name, name, orc.health))
+                        } else {
+                            orc.died_of = Some(Cause::Collapse);
+                            (EventCategory::Death, format!("{} is crushed as the {} comes down!", orc.name, name))
+                        };
+                        self.event_log.log_as(self.tick, category, message, Severity::Danger);
+                    }
+                    format!("The {} at ({}, {}) has collapsed and will have to be built again", name, x, y)
+                }
+            };
+            self.event_log.log_as(self.tick, EventCategory::Alert, message, Severity::Danger);
+        }
+    }
+
+    fn assign_build_tasks(&mut self) {
+        if !self.phase().is_daylight() {
+            return;
+        }
+
+        for s in 0..self.world.sites.len() {
+            let site = self.world.sites[s].clone();
+            let (sx, sy) = (site.x, site.y);
+
+            // Send for whatever hasn't been delivered or fetched yet, from the nearest trees and rocks
+            for material in Material::ALL {
+                let underway = self.orcs.iter().filter(|o| o.supplies(sx, sy, material)).count() as u32;
+                for _ in underway..site.missing(material) {
+                    let Some(h) = self.nearest_helper(sx, sy, BUILD_RADIUS) else {
+                        return;
+                    };
+                    let Some((rx, ry)) = self.world.find_nearest_unclaimed(sx, sy, material.source()) else {
+                        break;
+                    };
+                    self.event_log.log(
+                        self.tick,
+                        format!("{} sets off to fetch {} for the {}", self.orcs[h].name, material.name(), site.kind.name()),
+                        Severity::Info,
+                    );
+                    self.world.claimed_tiles.insert((rx, ry));
+                    self.send_to_gather(h, material, (rx, ry), (sx, sy));
+                }
+            }
+
+            if !site.is_stocked() {
+                continue;
+            }
+            let builders = self.orcs.iter()
+                .filter(|o| o.activity == Activity::Constructing { x: sx, y: sy })
+                .count();
+            for _ in builders..BUILDERS_PER_SITE {
+                let Some(h) = self.nearest_helper(sx, sy, BUILD_RADIUS) else {
+                    return;
+                };
+                self.event_log.log(
+                    self.tick,
+                    format!("{} starts work on the {}", self.orcs[h].name, site.kind.name()),
+                    Severity::Info,
+                );
+                self.orcs[h].start_construction(sx, sy, &self.world);
+            }
+        }
+    }
+
+    fn assign_crafting(&mut self) {
+        if !self.phase().is_daylight() {
+            return;
+        }
+
+        let benches: Vec<(usize, usize)> = self.world.workbenches().collect();
+        for (bx, by) in benches {
+            // An idle bench takes up whatever the rack is shortest of
+            if self.world.craft_mut(bx, by).is_none() {
+                let rack = &self.world.tool_rack;
+                let Some(kind) = ToolKind::ALL.into_iter()
+                    .filter(|&k| rack.count(k) < RACK_STOCK)
+                    .min_by_key(|&k| rack.count(k))
+                else {
+                    continue;
+                };
+                self.world.crafts.push(Craft::new(bx, by, kind));
+            }
+            let Some(craft) = self.world.craft_mut(bx, by).cloned() else {
+                continue;
+            };
+
+            // Wood comes off the woodpile while it lasts, the rest from the nearest trees and rocks
+            for material in Material::ALL {
+                let underway = self.orcs.iter().filter(|o| o.supplies(bx, by, material)).count() as u32;
+                for _ in underway..craft.missing(material) {
+                    let Some(h) = self.nearest_helper(bx, by, BUILD_RADIUS) else {
+                        return;
+                    };
+                    if material == Material::Wood && self.world.wood_stockpile > 0 {
+                        self.world.wood_stockpile -= 1;
+                        self.event_log.log(self.tick, format!("{} takes wood from the woodpile to the workbench", self.orcs[h].name), Severity::Info);
+                        self.orcs[h].start_delivering(material, bx, by, &self.world);
+                        continue;
+                    }
+                    let Some((rx, ry)) = self.world.find_nearest_unclaimed(bx, by, material.source()) else {
+                        break;
+                    };
+                    self.event_log.log(
+                        self.tick,
+                        format!("{} sets off to fetch {} to make {}", self.orcs[h].name, material.name(), craft.kind.with_article()),
+                        Severity::Info,
+                    );
+                    self.world.claimed_tiles.insert((rx, ry));
+                    self.send_to_gather(h, material, (rx, ry), (bx, by));
+                }
+            }
+
+            if !craft.is_stocked() || self.orcs.iter().any(|o| o.activity == Activity::Crafting { x: bx, y: by }) {
+                continue;
+            }
+            let Some(h) = self.nearest_helper(bx, by, BUILD_RADIUS) else {
+                return;
+            };
+            self.event_log.log(self.tick, format!("{} sits down at the workbench to make {}", self.orcs[h].name, craft.kind.with_article()), Severity::Info);
+            self.orcs[h].start_crafting(bx, by, &self.world);
+        }
+    }
+
+    /// Herders feed the hungry livestock, butcher tame animals beyond the
+    /// breeding stock, and go out after a boar while there's room in the pens
+    fn assign_husbandry(&mut self) {
+        if !self.phase().is_daylight() {
+            return;
+        }
+        let herder = |o: &Orc| o.job == Job::Herder;
+
+        // A feeder for each hungry animal not already being seen to
+        let feeding: Vec<(usize, usize)> = self.orcs.iter()
+            .filter_map(|o| match o.activity {
+                Activity::Feeding { x, y, .. } => Some((x, y)),
+                _ => None,
+            })
+            .collect();
+        let hungry: Vec<(usize, usize)> = self.animals.iter()
+            .filter(|a| husbandry::is_livestock(a) && a.carried_by.is_none() && a.hunger >= husbandry::FEED_AT)
+            .map(|a| (a.x, a.y))
+            .filter(|&(x, y)| !feeding.iter().any(|&(fx, fy)| fx.abs_diff(x).max(fy.abs_diff(y)) <= husbandry::PEN_REACH))
+            .collect();
+        for (x, y) in hungry {
+            if self.world.stockpiled_meals() == 0 {
+                break;
+            }
+            let Some(h) = self.nearest_helper_where(x, y, ORDER_RADIUS, herder) else {
+                break;
+            };
+            self.event_log.log(self.tick, format!("{} takes feed out to the pens", self.orcs[h].name), Severity::Info);
+            self.orcs[h].start_feeding(x, y, &self.world);
+        }
+
+        // Tame animals beyond the breeding stock go for meat
+        let tame: Vec<&Animal> = self.animals.iter()
+            .filter(|a| husbandry::is_livestock(a) && a.tame.is_some_and(|t| t >= husbandry::TAME))
+            .collect();
+        let butchering = self.orcs.iter().filter(|o| matches!(o.activity, Activity::Butchering { .. })).count();
+        if tame.len() > husbandry::BREEDING_STOCK + butchering
+            && let Some(h) = self.nearest_helper_where(tame[0].x, tame[0].y, ORDER_RADIUS, herder)
+        {
+            let (x, y, kind) = (tame[0].x, tame[0].y, tame[0].kind.name().to_lowercase());
+            self.event_log.log(self.tick, format!("{} goes to butcher a tame {}", self.orcs[h].name, kind), Severity::Info);
+            self.orcs[h].start_butchering(x, y, &self.world);
+        }
+
+        // One herder at a time goes out after a boar to bring in alive
+        let capturing = self.orcs.iter().any(|o| {
+            o.alive && o.job == Job::Herder && match o.activity {
+                Activity::Penning { .. } => true,
+                Activity::Hunting { target_idx } => self.animals.get(target_idx).is_some_and(|a| a.kind.stats().tameable),
+                _ => false,
+            }
+        });
+        if capturing || self.world.stockpiled_meals() < husbandry::CAPTURE_STOCK || husbandry::free_pen(&self.world, &self.animals).is_none() {
+            return;
+        }
+        let (cx, cy) = self.world.campfire_pos;
+        let Some(b) = self.animals.iter().enumerate()
+            .filter(|(_, a)| a.alive && !a.claimed && a.tame.is_none() && a.kind.stats().tameable)
+            .map(|(i, a)| (i, a.x.abs_diff(cx).max(a.y.abs_diff(cy))))
+            .filter(|&(_, dist)| dist <= husbandry::CAPTURE_RADIUS)
+            .min_by_key(|&(i, dist)| (dist, i))
+            .map(|(i, _)| i)
+        else {
+            return;
+        };
+        let Some(h) = self.nearest_helper_where(cx, cy, ORDER_RADIUS, herder) else {
+            return;
+        };
+        let kind = self.animals[b].kind.name().to_lowercase();
+        self.event_log.log_as(self.tick, EventCategory::Hunt, format!("{} sets out to bring a {} in alive", self.orcs[h].name, kind), Severity::Info);
+        self.animals[b].claimed = true;
+        self.orcs[h].take_tool(ToolKind::Spear, &mut self.world, &mut self.event_log, self.tick);
+        self.orcs[h].set_activity_with_path(Activity::Hunting { target_idx: b }, &self.world);
+    }
+
+    /// Send an orc for a unit of `material` from the tree or rock at `source`,
+    /// taking an axe down from the rack first if it's off to fell a tree
+    fn send_to_gather(&mut self, h: usize, material: Material, (x, y): (usize, usize), site: (usize, usize)) {
+        if material == Material::Wood {
+            self.orcs[h].take_tool(ToolKind::Axe, &mut self.world, &mut self.event_log, self.tick);
+        }
+        self.orcs[h].start_gathering(material, x, y, site, &self.world);
+    }
+
+    fn assign_designations(&mut self) {
+        // Orders lapse once the tree is felled or the bush is gone
+        let mut designations = std::mem::take(&mut self.world.designations);
+        designations.retain(|&(x, y), order| order.applies_to(self.world.get(x, y)));
+        self.world.designations = designations;
+        if !self.phase().is_daylight() {
+            return;
+        }
+
+        let orders: Vec<((usize, usize), Designation)> = self.world.designations.iter().map(|(&pos, &order)| (pos, order)).collect();
+        for ((x, y), order) in orders {
+            // Marked bushes wait until they have regrown
+            if self.world.claimed_tiles.contains(&(x, y)) || self.world.get(x, y) == Terrain::DepletedBush {
+                continue;
+            }
+            let job = order.job();
+            let Some(h) = self.nearest_helper_where(x, y, ORDER_RADIUS, |o| o.job == job && o.berries < BERRY_POUCH) else {
+                continue;
+            };
+            match order {
+                Designation::Chop => {
+                    let Some(rack) = self.world.meat_rack_pos() else {
+                        continue;
+                    };
+                    self.event_log.log(self.tick, format!("{} goes to chop a marked tree", self.orcs[h].name), Severity::Info);
+                    self.send_to_gather(h, Material::Wood, (x, y), rack);
+                }
+                Designation::Gather => {
+                    self.event_log.log(self.tick, format!("{} goes to pick a marked bush", self.orcs[h].name), Severity::Info);
+                    self.orcs[h].take_tool(ToolKind::Basket, &mut self.world, &mut self.event_log, self.tick);
+                    self.orcs[h].start_harvest(x, y, &self.world);
+                }
+            }
+            self.world.claimed_tiles.insert((x, y));
+        }
+    }
+
+    fn assign_huts(&mut self) {
+        let mut free: Vec<(usize, usize)> = self.world.huts()
+            .filter(|pos| !self.orcs.iter().any(|o| o.alive && o.hut == Some(*pos)))
+            .collect();
+        while !free.is_empty() {
+            // The homeless orc nearest any free hut moves in first
+            let Some((i, h)) = self.orcs.iter().enumerate()
+                .filter(|(_, o)| o.alive && o.hut.is_none())
+                .flat_map(|(i, o)| free.iter().enumerate().map(move |(h, &(x, y))| (i, h, o.x.abs_diff(x) + o.y.abs_diff(y))))
+                .min_by_key(|&(_, _, dist)| dist)
+                .map(|(i, h, _)| (i, h))
+            else {
+                break;
+            };
+            let (x, y) = free.remove(h);
+            self.orcs[i].hut = Some((x, y));
+            self.event_log.log_as(self.tick, EventCategory::Social, format!("{} claims the hut at ({}, {})", self.orcs[i].name, x, y), Severity::Info);
+        }
+    }
+
+    /// Closest orc within `radius` that is free to take on a chore
+    fn nearest_helper(&self, x: usize, y: usize, radius: usize) -> Option<usize> {
+        self.nearest_helper_where(x, y, radius, |_| true)
+    }
+
+    /// Like `nearest_helper`, but only among orcs that `suits` accepts
+    fn nearest_helper_where(&self, x: usize, y: usize, radius: usize, suits: impl Fn(&Orc) -> bool) -> Option<usize> {
+        self.orcs_within(x, y, radius)
+            .filter(|(_, o)| o.can_help() && !o.carrying_food && suits(o))
+            .map(|(i, o)| (i, o.x.abs_diff(x) + o.y.abs_diff(y)))
+            .min_by_key(|&(i, dist)| (dist, i))
+            .map(|(i, _)| i)
+    }
+
+    /// Healers with herbs go to dress the wounds of whoever is worst hurt
+    /// and not yet seen to, and by day healers short of herbs go to pick more
+    fn assign_healing(&mut self) {
+        // Keep healers heading for their patient, or call them off if the patient is gone
+        for i in 0..self.orcs.len() {
+            let Activity::Tending { patient, .. } = &self.orcs[i].activity else {
+                continue;
+            };
+            let target = self.orcs.iter().find(|o| o.alive && &o.name == patient).map(|o| (o.x, o.y));
+            let healer = &mut self.orcs[i];
+            match (target, &mut healer.activity) {
+                (Some((px, py)), Activity::Tending { x, y, .. }) => {
+                    *x = px;
+                    *y = py;
+                }
+                _ => healer.activity = Activity::Idle,
+            }
+        }
+
+        let tended: Vec<String> = self.orcs.iter()
+            .filter_map(|o| match &o.activity {
+                Activity::Tending { patient, .. } => Some(patient.clone()),
+                _ => None,
+            })
+            .collect();
+        let mut wounded: Vec<usize> = (0..self.orcs.len())
+            .filter(|&p| {
+                let orc = &self.orcs[p];
+                orc.alive && orc.wounds >= healing::TEND_AT && orc.dressed_ticks == 0 && !tended.contains(&orc.name)
+            })
+            .collect();
+        wounded.sort_by(|&a, &b| self.orcs[b].wounds.total_cmp(&self.orcs[a].wounds));
+        for p in wounded {
+            let (px, py) = (self.orcs[p].x, self.orcs[p].y);
+            let Some(h) = self.nearest_helper_where(px, py, ORDER_RADIUS, |o| o.job == Job::Healer && o.herbs > 0) else {
+                break;
+            };
+            let patient = self.orcs[p].name.clone();
+            if h != p {
+                self.event_log.log(self.tick, format!("{} goes to dress {}'s wounds", self.orcs[h].name, patient), Severity::Info);
+            }
+            self.orcs[h].start_tending(patient, px, py, &self.world);
+        }
+
+        if !self.phase().is_daylight() {
+            return;
+        }
+        let (cx, cy) = self.world.campfire_pos;
+        for i in 0..self.orcs.len() {
+            let orc = &self.orcs[i];
+            if orc.job != Job::Healer || !orc.can_help() || orc.carrying_food || orc.herbs >= healing::HERB_POUCH || orc.needs_a_break(&self.ai) {
+                continue;
+            }
+            let herb = self.world.find_nearest_unclaimed(orc.x, orc.y, Terrain::Herb)
+                .filter(|&(x, y)| x.abs_diff(cx).max(y.abs_diff(cy)) <= healing::HERB_RADIUS);
+            if let Some((x, y)) = herb {
+                self.world.claimed_tiles.insert((x, y));
+                self.orcs[i].pick_herbs(x, y, &self.world);
+            }
+        }
+    }
+
+    /// A grown orc free to help goes for each body nobody is already seeing to
+    fn assign_burials(&mut self) {
+        if !self.phase().is_daylight() {
+            return;
+        }
+        let seen_to: Vec<String> = self.orcs.iter()
+            .filter_map(|o| match &o.activity {
+                Activity::Burying { name, .. } => Some(name.clone()),
+                _ => None,
+            })
+            .collect();
+        let unburied: Vec<(String, usize, usize)> = self.world.corpses.iter()
+            .filter(|c| !seen_to.contains(&c.name))
+            .map(|c| (c.name.clone(), c.x, c.y))
+            .collect();
+        for (name, x, y) in unburied {
+            let Some(h) = self.nearest_helper_where(x, y, ORDER_RADIUS, |o| o.is_adult(self.tick, self.world.day_length)) else {
+                continue;
+            };
+            self.event_log.log(self.tick, format!("{} goes to bring {}'s body home for burial", self.orcs[h].name, name), Severity::Info);
+            self.orcs[h].start_burial(name, x, y, &self.world);
+        }
+    }
+
+    /// Lift everyone's spirits for each orc buried this tick, and weigh on
+    /// anyone near a body still lying unburied
+    fn mourn_the_dead(&mut self) {
+        for name in self.world.take_burials() {
+            for orc in self.orcs.iter_mut().filter(|o| o.alive) {
+                orc.morale = (orc.morale + burial::BURIAL_MORALE).min(100.0);
+            }
+            self.event_log.log_as(self.tick, EventCategory::Death, format!("{} is laid to rest, and the clan takes heart", name), Severity::Info);
+        }
+        for corpse in &self.world.corpses {
+            for orc in self.orcs.iter_mut().filter(|o| o.alive && o.x.abs_diff(corpse.x).max(o.y.abs_diff(corpse.y)) <= burial::GLOOM_RADIUS) {
+                orc.morale = (orc.morale - burial::CORPSE_GLOOM).max(0.0);
+            }
+        }
+    }
+
+    /// Healers who have reached their patient dress the wounds with herbs
+    fn complete_treatments(&mut self) {
+        for h in 0..self.orcs.len() {
+            let Activity::Tending { patient, .. } = &self.orcs[h].activity else {
+                continue;
+            };
+            let Some(p) = self.orcs.iter().position(|o| o.alive && &o.name == patient) else {
+                continue;
+            };
+            let (hx, hy) = (self.orcs[h].x, self.orcs[h].y);
+            if hx.abs_diff(self.orcs[p].x).max(hy.abs_diff(self.orcs[p].y)) > 1 {
+                continue;
+            }
+
+            let message = if h == p {
+                format!("{} dresses their own wounds with herbs", self.orcs[h].name)
+            } else {
+                format!("{} dresses {}'s wounds with herbs", self.orcs[h].name, self.orcs[p].name)
+            };
+            let healer = &mut self.orcs[h];
+            healer.herbs -= 1;
+            healer.activity = Activity::Idle;
+            let patient = &mut self.orcs[p];
+            patient.dressed_ticks = healing::DRESSING_TICKS;
+            self.emotes.push(patient.x, patient.y, EmoteKind::Love, self.tick);
+            self.event_log.log(self.tick, message, Severity::Info);
+        }
+    }
+
+    fn complete_food_deliveries(&mut self) {
+        for c in 0..self.orcs.len() {
+            let Activity::DeliveringFood { patient, has_food: true, .. } = &self.orcs[c].activity else {
+                continue;
+            };
+            let Some(p) = self.orcs.iter().position(|o| o.alive && &o.name == patient) else {
+                continue;
+            };
+            let (cx, cy) = (self.orcs[c].x, self.orcs[c].y);
+            if cx.abs_diff(self.orcs[p].x).max(cy.abs_diff(self.orcs[p].y)) > 1 {
+                continue;
+            }
+
+            let courier = self.orcs[c].name.clone();
+            let patient = &mut self.orcs[p];
+            patient.hunger = (patient.hunger - 40.0).clamp(0.0, 100.0);
+            patient.morale = (patient.morale + 10.0).clamp(0.0, 100.0);
+            patient.awaiting_food = false;
+            patient.activity = Activity::Eating;
+            self.emotes.push(patient.x, patient.y, EmoteKind::Love, self.tick);
+            self.event_log.log_as(
+                self.tick,
+                EventCategory::Food,
+                format!("{} feeds {}, too weak to fetch food", courier, patient.name),
+                Severity::Good,
+            );
+            self.orcs[c].activity = Activity::Idle;
+        }
+    }
+
+    fn check_birth(&mut self) {
+        let living: Vec<&Orc> = self.orcs.iter().filter(|o| o.alive).collect();
+        let count = living.len();
+
+        if !(2..self.limits.max_clan).contains(&count) {
+            return;
+        }
+
+        let avg_hunger: f32 = living.iter().map(|o| o.hunger).sum::<f32>() / count as f32;
+        let avg_energy: f32 = living.iter().map(|o| o.energy).sum::<f32>() / count as f32;
+
+        // It takes a grown orc of each sex, and a mother still carrying her
+        // last baby isn't ready for another
+        let (tick, day_length) = (self.tick, self.world.day_length);
+        let nursing = |mother: &str| self.orcs.iter().any(|b| {
+            b.alive && b.is_infant(tick, day_length) && b.parents.as_ref().is_some_and(|(m, _)| m == mother)
+        });
+        let parents = |sex: Sex| -> Vec<usize> {
+            (0..self.orcs.len())
+                .filter(|&i| self.orcs[i].alive && self.orcs[i].sex == sex && self.orcs[i].is_adult(tick, day_length))
+                .filter(|&i| sex == Sex::Male || !nursing(&self.orcs[i].name))
+                .collect()
+        };
+        let (mothers, fathers) = (parents(Sex::Female), parents(Sex::Male));
+        if mothers.is_empty() || fathers.is_empty() {
+            return;
+        }
+
+        // Birth conditions: well-fed, rested, have stockpile
+        if avg_hunger < 40.0 && avg_energy > 40.0 && self.world.take_meat() {
+
+            let existing_names: Vec<String> = self.orcs.iter().map(|o| o.name.clone()).collect();
+            let name = orc::pick_name(&mut self.rng, &existing_names);
+            let mother = &self.orcs[mothers[self.rng.gen_range(0..mothers.len())]];
+            let father = &self.orcs[fathers[self.rng.gen_range(0..fathers.len())]];
+            let (x, y) = (mother.x, mother.y);
+
+            self.event_log.log_at(
+                self.tick,
+                EventCategory::Birth,
+                format!("{} is born to {} and {}!", name, mother.name, father.name),
+                Severity::Good,
+                (x, y),
+            );
+            for orc in self.orcs.iter().filter(|o| o.alive) {
+                if orc.x.abs_diff(x) + orc.y.abs_diff(y) <= BIRTH_NOTICE_RADIUS {
+                    self.emotes.push(orc.x, orc.y, EmoteKind::Love, self.tick);
+                }
+            }
+            let mut baby = Orc::new(name, x, y); // newborns grow up to be clumsy foragers
+            baby.parents = Some((mother.name.clone(), father.name.clone()));
+            baby.activity = Activity::Infant { carried: false };
+            baby.traits = Trait::roll(&mut self.rng);
+            baby.sex = if self.rng.gen_bool(0.5) { Sex::Female } else { Sex::Male };
+            baby.birth_day = calendar::day_number(self.tick, self.world.day_length) as i64;
+            // Newborns will take up whichever job the clan is shortest of
+            baby.job = Job::ALL.into_iter()
+                .min_by_key(|&job| self.orcs.iter().filter(|o| o.alive && o.job == job).count())
+                .unwrap_or(Job::Woodcutter);
+            self.orcs.push(baby);
+        }
+    }
+
+    /// Turn loose any captive whose herder died or dropped it on the way to
+    /// the pens, and lose whatever livestock has gone unfed too long
+    fn tend_livestock(&mut self) {
+        for a in 0..self.animals.len() {
+            let animal = &self.animals[a];
+            if !husbandry::is_livestock(animal) {
+                continue;
+            }
+            let kind = animal.kind.name().to_lowercase();
+            if let Some(carrier) = &animal.carried_by
+                && !self.orcs.iter().any(|o| o.alive && &o.name == carrier && matches!(o.activity, Activity::Penning { .. }))
+            {
+                let animal = &mut self.animals[a];
+                animal.tame = None;
+                animal.carried_by = None;
+                self.event_log.log_as(self.tick, EventCategory::Hunt, format!("The captive {} struggles free and runs off", kind), Severity::Info);
+            } else if animal.hunger >= 100.0 {
+                self.animals[a].alive = false;
+                self.event_log.log_as(self.tick, EventCategory::Alert, format!("A penned {} has starved to death", kind), Severity::Danger);
+            }
+        }
+    }
+
+    /// At dawn, a tame pair in the pens that has been fed may have young,
+    /// if there's room for it
+    fn breed_livestock(&mut self) {
+        let fed = |a: &&Animal| husbandry::is_livestock(a) && a.tame.is_some_and(|t| t >= husbandry::TAME) && a.hunger < husbandry::FEED_AT;
+        let Some(kind) = AnimalKind::ALL.into_iter().find(|&k| self.animals.iter().filter(fed).filter(|a| a.kind == k).count() >= 2) else {
+            return;
+        };
+        let Some((x, y)) = husbandry::free_pen(&self.world, &self.animals) else {
+            return;
+        };
+        if !self.rng.gen_bool(husbandry::BREED_CHANCE) {
+            return;
+        }
+        self.animals.push(Animal { tame: Some(husbandry::BORN_TAMENESS), ..Animal::new(kind, x, y) });
+        self.event_log.log_at(self.tick, EventCategory::Food, format!("A young {} is born in the pens", kind.name().to_lowercase()), Severity::Info, (x, y));
+    }
+
+    /// Keep every baby with whoever is carrying it, find someone new for any
+    /// that lost their carer, and have carers stop to feed babies crying for
+    /// it. Babies old enough to walk are put down to get on with their lives.
+    fn tend_infants(&mut self) {
+        for b in 0..self.orcs.len() {
+            let baby = &self.orcs[b];
+            if !baby.alive || !matches!(baby.activity, Activity::Infant { .. }) {
+                continue;
+            }
+            if !baby.is_infant(self.tick, self.world.day_length) {
+                let baby = &mut self.orcs[b];
+                baby.activity = Activity::Idle;
+                baby.carer = None;
+                self.event_log.log_at(self.tick, EventCategory::Birth, format!("{} is up on their own feet and joins the clan as a {}", baby.name, baby.job.name()), Severity::Good, (baby.x, baby.y));
+                continue;
+            }
+
+            let carer = baby.carer.as_ref()
+                .and_then(|name| self.orcs.iter().position(|o| o.alive && o.name == *name))
+                .or_else(|| self.find_carer(b));
+            let Some(c) = carer else {
+                let baby = &mut self.orcs[b];
+                if baby.carer.take().is_some() {
+                    self.event_log.log_as(self.tick, EventCategory::Alert, format!("{} has nobody left to look after them", baby.name), Severity::Danger);
+                }
+                baby.activity = Activity::Infant { carried: false };
+                continue;
+            };
+
+            let (name, x, y) = (self.orcs[c].name.clone(), self.orcs[c].x, self.orcs[c].y);
+            let baby = &mut self.orcs[b];
+            if baby.carer.as_ref() != Some(&name) {
+                self.event_log.log_as(self.tick, EventCategory::Social, format!("{} takes {} into their care", name, baby.name), Severity::Good);
+                baby.carer = Some(name);
+            }
+            (baby.x, baby.y) = (x, y);
+            baby.activity = Activity::Infant { carried: true };
+            let crying = baby.hunger >= FEED_AT || baby.thirst >= FEED_AT;
+
+            if self.orcs[c].activity == Activity::Nursing {
+                let baby = &mut self.orcs[b];
+                baby.hunger = (baby.hunger - NURSE_RATE).max(0.0);
+                baby.thirst = (baby.thirst - NURSE_RATE).max(0.0);
+                let full = baby.hunger == 0.0 && baby.thirst == 0.0;
+                let carer = &mut self.orcs[c];
+                carer.hunger = (carer.hunger + NURSE_COST).min(100.0);
+                carer.thirst = (carer.thirst + NURSE_COST).min(100.0);
+                if full {
+                    carer.activity = Activity::Idle;
+                }
+            } else if crying
+                && !matches!(self.orcs[c].activity, Activity::Sleeping | Activity::Fighting | Activity::Marching { .. })
+                && !self.orcs[c].has_pressing_need(&self.ai)
+            {
+                // Whatever the carer was doing has to wait
+                let carer = &mut self.orcs[c];
+                carer.cancel(&mut self.world, &mut self.event_log, self.tick);
+                carer.activity = Activity::Nursing;
+            }
+        }
+    }
+
+    /// Who should take on the baby at `b` under the clan's childcare policy:
+    /// the grown orc allowed to with the fewest babies in arms already,
+    /// nearest first
+    fn find_carer(&self, b: usize) -> Option<usize> {
+        let baby = &self.orcs[b];
+        let carrying = |c: &Orc| self.orcs.iter().filter(|o| o.alive && o.carer.as_ref() == Some(&c.name)).count();
+        (0..self.orcs.len())
+            .filter(|&i| self.orcs[i].alive && self.orcs[i].is_adult(self.tick, self.world.day_length) && self.childcare.allows(&self.orcs[i], baby))
+            .min_by_key(|&i| (carrying(&self.orcs[i]), self.orcs[i].x.abs_diff(baby.x) + self.orcs[i].y.abs_diff(baby.y), i))
+    }
+
+    /// Wolves are prowling around the campfire in numbers, or around a cold one at all
+    pub fn overrun(&self) -> bool {
+        let (cx, cy) = self.world.campfire_pos;
+        let phase = self.phase();
+        let wolves = self.animals.iter()
+            .filter(|a| a.alive && a.kind == AnimalKind::Wolf && a.kind.is_active(phase))
+            .filter(|a| a.x.abs_diff(cx).max(a.y.abs_diff(cy)) <= OVERRUN_RADIUS)
+            .count();
+        wolves >= OVERRUN_WOLVES || (wolves > 0 && !self.world.fire_lit())
+    }
+
+    /// How hungry the clan is on average
+    fn clan_hunger(&self) -> f32 {
+        let living: Vec<&Orc> = self.orcs.iter().filter(|o| o.alive).collect();
+        living.iter().map(|o| o.hunger).sum::<f32>() / living.len().max(1) as f32
+    }
+
+    /// Leave camp if it can no longer be held, unless the clan has only just
+    /// come back. A famine is only worth walking away from by day, when
+    /// there's light to forage by.
+    fn watch_for_danger(&mut self) {
+        if self.evacuation.is_some() || self.tick < self.evacuation_ended + evacuation_cooldown(self.world.day_length) {
+            return;
+        }
+        let stores = self.world.food_stockpile + self.world.cooked_stockpile + self.world.berry_stockpile;
+        let reason = if self.overrun() {
+            Reason::Overrun
+        } else if stores == 0 && self.phase().is_daylight() && self.clan_hunger() >= FAMINE_HUNGER {
+            Reason::Famine
+        } else {
+            return;
+        };
+        if let Some(rally) = self.pick_rally() {
+            self.evacuate(reason, rally);
+        }
+    }
+
+    /// Eight spots `distance` out from camp, one in each direction, kept off the map's edge
+    fn around_camp(&self, distance: i32) -> impl Iterator<Item = (usize, usize)> + use<> {
+        let (cx, cy) = self.world.campfire_pos;
+        let directions = [(1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1), (0, -1), (1, -1)];
+        directions.into_iter().map(move |(dx, dy)| {
+            let x = (cx as i32 + dx * distance).clamp(5, MAP_WIDTH as i32 - 6) as usize;
+            let y = (cy as i32 + dy * distance).clamp(5, MAP_HEIGHT as i32 - 6) as usize;
+            (x, y)
+        })
+    }
+
+    /// How many tiles within `radius` of (x, y) are ones `wanted` picks out
+    fn count_around(&self, x: usize, y: usize, radius: usize, wanted: impl Fn(Terrain) -> bool) -> i32 {
+        (y.saturating_sub(radius)..(y + radius + 1).min(MAP_HEIGHT))
+            .flat_map(|ty| (x.saturating_sub(radius)..(x + radius + 1).min(MAP_WIDTH)).map(move |tx| (tx, ty)))
+            .filter(|&(tx, ty)| wanted(self.world.get(tx, ty)))
+            .count() as i32
+    }
+
+    /// Open ground some way out from camp, in whichever direction has the
+    /// most to eat and the fewest wolves
+    fn pick_rally(&self) -> Option<(usize, usize)> {
+        let (cx, cy) = self.world.campfire_pos;
+        self.around_camp(RALLY_DISTANCE)
+            .filter(|&(x, y)| self.world.is_walkable(x, y) && cx.abs_diff(x).max(cy.abs_diff(y)) >= MIN_RALLY_DISTANCE)
+            .max_by_key(|&(x, y)| self.rally_score(x, y))
+    }
+
+    fn rally_score(&self, x: usize, y: usize) -> i32 {
+        let food = self.count_around(x, y, 6, |t| matches!(t, Terrain::Bush | Terrain::FruitTree | Terrain::Mushroom));
+        let (game, wolves) = self.index.within(x, y, 15)
+            .filter_map(|entity| match entity {
+                Entity::Animal(i) if self.animals[i].alive => Some(self.animals[i].kind),
+                _ => None,
+            })
+            .fold((0, 0), |(game, wolves), kind| if kind.is_game() { (game + 1, wolves) } else { (game, wolves + 1) });
+        food + game - 20 * wolves
+    }
+
+    /// Lead the whole clan out to `rally`, dropping whatever they were doing
+    fn evacuate(&mut self, reason: Reason, rally: (usize, usize)) {
+        self.event_log.log_as(
+            self.tick,
+            EventCategory::Alert,
+            format!("{}! The clan abandons camp for ({},{})", reason.describe(), rally.0, rally.1),
+            Severity::Danger,
+        );
+        let day_length = self.world.day_length;
+        for orc in self.orcs.iter_mut().filter(|o| o.alive && !o.is_infant(self.tick, day_length)) {
+            orc.cancel(&mut self.world, &mut self.event_log, self.tick);
+            self.emotes.push(orc.x, orc.y, EmoteKind::Alarm, self.tick);
+        }
+        self.evacuation = Some(Evacuation::new(reason, self.world.campfire_pos, rally, &self.world, self.tick));
+    }
+
+    pub fn evacuate_to(&mut self, x: usize, y: usize) {
+        if let Some(evacuation) = &mut self.evacuation {
+            if !matches!(evacuation.stage, Stage::Marching { homeward: true }) {
+                self.event_log.log(self.tick, "The clan is called home".to_string(), Severity::Info);
+                evacuation.head_home(self.world.campfire_pos, self.tick);
+                for orc in self.orcs.iter_mut().filter(|o| matches!(o.activity, Activity::Marching { .. } | Activity::Sheltering { .. })) {
+                    orc.activity = Activity::Idle;
+                }
+            }
+            return;
+        }
+        let (cx, cy) = self.world.campfire_pos;
+        if x >= MAP_WIDTH || y >= MAP_HEIGHT || !self.world.is_walkable(x, y) {
+            self.event_log.log(self.tick, "The clan can't rally there".to_string(), Severity::Warning);
+        } else if cx.abs_diff(x).max(cy.abs_diff(y)) < MIN_RALLY_DISTANCE {
+            self.event_log.log(self.tick, "Pick a rally point further from camp".to_string(), Severity::Warning);
+        } else {
+            self.evacuate(Reason::Ordered, (x, y));
+        }
+    }
+
+    /// Move the evacuation along: gather everyone free at the current stop,
+    /// set off on the next leg once they're there, and head home once the
+    /// camp is safe again
+    fn run_evacuation(&mut self) {
+        let safe = self.phase().is_daylight() && !self.overrun() && self.clan_hunger() < FED_HUNGER;
+        let Some(evacuation) = &mut self.evacuation else {
+            return;
+        };
+        let (sx, sy) = evacuation.stop();
+        let near_stop = |o: &Orc| o.x.abs_diff(sx).max(o.y.abs_diff(sy)) <= STOP_RADIUS;
+        match evacuation.stage {
+            Stage::Marching { homeward } => {
+                // Anyone free falls in, and those on the march make for the new stop
+                for orc in self.orcs.iter_mut().filter(|o| o.alive) {
+                    let heading = match orc.activity {
+                        Activity::Idle | Activity::Warming => None,
+                        Activity::Marching { x, y } => Some((x, y)),
+                        _ => continue,
+                    };
+                    if heading.is_none_or(|(x, y)| x.abs_diff(sx).max(y.abs_diff(sy)) > STOP_RADIUS) {
+                        let (x, y) = orc.find_spot_near(sx, sy, &self.world, &mut self.rng);
+                        orc.march_to(x, y, &self.world);
+                    }
+                }
+                // Those who broke off to eat or drink catch up later
+                let mut marching = self.orcs.iter().filter(|o| o.alive && matches!(o.activity, Activity::Marching { .. })).peekable();
+                let all_arrived = marching.peek().is_some() && marching.all(near_stop);
+                if !evacuation.advance(all_arrived, self.tick) {
+                    return;
+                }
+                if homeward {
+                    for orc in self.orcs.iter_mut().filter(|o| matches!(o.activity, Activity::Marching { .. })) {
+                        orc.activity = Activity::Idle;
+                    }
+                    self.evacuation = None;
+                    self.evacuation_ended = self.tick;
+                    self.event_log.log(self.tick, "The clan is back at camp".to_string(), Severity::Good);
+                } else {
+                    evacuation.shelter(self.tick);
+                    self.event_log.log(self.tick, "The clan reaches the rally point and settles in to wait".to_string(), Severity::Warning);
+                }
+            }
+            Stage::Sheltering { since } => {
+                let waited = self.tick - since;
+                if waited >= max_shelter(self.world.day_length) || (waited >= MIN_SHELTER && safe) {
+                    self.event_log.log(self.tick, "The danger has passed, and the clan heads home".to_string(), Severity::Good);
+                    evacuation.head_home(self.world.campfire_pos, self.tick);
+                    for orc in self.orcs.iter_mut().filter(|o| matches!(o.activity, Activity::Sheltering { .. })) {
+                        orc.activity = Activity::Idle;
+                    }
+                    return;
+                }
+                for orc in self.orcs.iter_mut().filter(|o| o.alive && matches!(o.activity, Activity::Idle | Activity::Warming | Activity::Marching { .. })) {
+                    let (x, y) = orc.find_spot_near(sx, sy, &self.world, &mut self.rng);
+                    orc.shelter_at(x, y, &self.world);
+                }
+                // Huddled together through the night, they keep off half the chill
+                if self.phase() == DayPhase::Night {
+                    let chill = Season::of(self.tick, self.world.day_length).night_chill() / 2.0;
+                    for orc in self.orcs.iter_mut().filter(|o| o.alive && near_stop(o)) {
+                        orc.warmth = (orc.warmth + chill).min(100.0);
+                    }
+                }
+            }
+        }
+    }
+
+    /// A seed for the next world, drawn from this one's so that a session
+    /// played back starts the same new world
+    pub fn next_seed(&mut self) -> u64 {
+        self.rng.r#gen()
+    }
+
+    /// Why `venture` can't be taken up right now, if it can't
+    pub fn venture_barred(&self, venture: Venture) -> Option<&'static str> {
+        if self.expedition.is_some() {
+            Some("A party is already out")
+        } else if self.evacuation.is_some() {
+            Some("Not while the clan is away from camp")
+        } else {
+            venture.out_of_season(Season::of(self.tick, self.world.day_length))
+        }
+    }
+
+    /// Pick a party for `venture` and send it off
+    pub fn send_expedition(&mut self, venture: Venture) {
+        if let Some(reason) = self.venture_barred(venture) {
+            self.event_log.log(self.tick, reason.to_string(), Severity::Warning);
+            return;
+        }
+        let party = self.pick_party(venture);
+        if party.len() < expedition::MIN_PARTY {
+            self.event_log.log(self.tick, "Too few orcs are fit to go".to_string(), Severity::Warning);
+            return;
+        }
+        let (site, quarry) = match venture {
+            Venture::Hunt => match self.distant_herd(venture.distance()) {
+                Some(a) => (Some((self.animals[a].x, self.animals[a].y)), self.animals[a].herd),
+                None => (None, None),
+            },
+            Venture::Gathering => (self.scout(venture.distance(), |t| t == Terrain::Bush), None),
+            Venture::WinterForage => (self.scout(venture.distance(), |t| t == Terrain::Tree), None),
+        };
+        let Some(site) = site else {
+            let excuse = match venture {
+                Venture::Hunt => "There's no herd far enough out to be worth the trip",
+                _ => "Nowhere out there looks worth the trip",
+            };
+            self.event_log.log(self.tick, excuse.to_string(), Severity::Warning);
+            return;
+        };
+        let names: Vec<String> = party.iter().map(|&i| self.orcs[i].name.clone()).collect();
+        for &i in &party {
+            let orc = &mut self.orcs[i];
+            orc.cancel(&mut self.world, &mut self.event_log, self.tick);
+            let (x, y) = orc.find_spot_near(site.0, site.1, &self.world, &mut self.rng);
+            orc.venture_to(x, y, &self.world);
+        }
+        self.event_log.log(
+            self.tick,
+            format!("{} set out for ({},{}): {}", venture.party(), site.0, site.1, names.join(", ")),
+            Severity::Info,
+        );
+        self.expedition = Some(Expedition::new(venture, site, quarry, names, self.tick));
+    }
+
+    /// The fittest grown orcs for `venture`, the most skilled first, leaving
+    /// out anyone with a baby to carry
+    fn pick_party(&self, venture: Venture) -> Vec<usize> {
+        let carrying = |o: &Orc| self.orcs.iter().any(|b| b.alive && b.carer.as_ref() == Some(&o.name));
+        let mut fit: Vec<usize> = (0..self.orcs.len())
+            .filter(|&i| {
+                let o = &self.orcs[i];
+                o.alive && o.is_adult(self.tick, self.world.day_length) && o.health > 60.0 && !o.has_pressing_need(&self.ai) && !carrying(o)
+            })
+            .collect();
+        fit.sort_by(|&a, &b| venture_skill(&self.orcs[b], venture).total_cmp(&venture_skill(&self.orcs[a], venture)));
+        fit.truncate(expedition::PARTY_SIZE);
+        fit
+    }
+
+    /// A deer of the herd closest to camp that's still at least `distance` out
+    fn distant_herd(&self, distance: usize) -> Option<usize> {
+        let (cx, cy) = self.world.campfire_pos;
+        let away = |a: &Animal| cx.abs_diff(a.x).max(cy.abs_diff(a.y));
+        (0..self.animals.len())
+            .filter(|&a| self.animals[a].alive && self.animals[a].herd.is_some() && away(&self.animals[a]) >= distance)
+            .min_by_key(|&a| away(&self.animals[a]))
+    }
+
+    /// Open ground `distance` out from camp, in whichever direction has the
+    /// most of what `wanted` picks out about it, if any does
+    fn scout(&self, distance: usize, wanted: impl Fn(Terrain) -> bool) -> Option<(usize, usize)> {
+        self.around_camp(distance as i32)
+            .filter(|&(x, y)| self.world.is_walkable(x, y))
+            .map(|(x, y)| ((x, y), self.count_around(x, y, VENTURE_RADIUS, &wanted)))
+            .filter(|&(_, count)| count > 0)
+            .max_by_key(|&(_, count)| count)
+            .map(|(site, _)| site)
+    }
+
+    /// Keep the party together on its way out and back, see how the venture
+    /// turns out once it has been at it long enough, and take in the haul
+    /// when it's home
+    fn run_expedition(&mut self) {
+        let Some(expedition) = &mut self.expedition else {
+            return;
+        };
+        let venture = expedition.venture;
+        expedition.party.retain(|name| self.orcs.iter().any(|o| o.alive && o.name == *name));
+        if expedition.party.is_empty() {
+            self.event_log.log_as(self.tick, EventCategory::Alert, format!("The {} never come back", venture.party().to_lowercase()), Severity::Danger);
+            self.expedition = None;
+            return;
+        }
+        if self.evacuation.is_some() {
+            self.event_log.log(self.tick, format!("The {} give up and go with the clan", venture.party().to_lowercase()), Severity::Warning);
+            self.expedition = None;
+            return;
+        }
+        // A hunting party follows its herd about
+        if !matches!(expedition.stage, expedition::Stage::Homeward)
+            && let Some(deer) = self.animals.iter().find(|a| a.alive && a.herd.is_some() && a.herd == expedition.quarry)
+        {
+            expedition.site = (deer.x, deer.y);
+        }
+        let (sx, sy) = expedition.destination(self.world.campfire_pos);
+        // Anyone free falls back in
+        for orc in self.orcs.iter_mut().filter(|o| o.alive && expedition.includes(&o.name)) {
+            let heading = match orc.activity {
+                Activity::Idle | Activity::Warming => None,
+                Activity::Expedition { x, y } => Some((x, y)),
+                _ => continue,
+            };
+            if heading.is_none_or(|(x, y)| x.abs_diff(sx).max(y.abs_diff(sy)) > STOP_RADIUS) {
+                let (x, y) = orc.find_spot_near(sx, sy, &self.world, &mut self.rng);
+                orc.venture_to(x, y, &self.world);
+            }
+        }
+        let mut away = self.orcs.iter().filter(|o| o.alive && matches!(o.activity, Activity::Expedition { .. })).peekable();
+        let arrived = away.peek().is_some() && away.all(|o| o.x.abs_diff(sx).max(o.y.abs_diff(sy)) <= STOP_RADIUS);
+        let arrived = arrived || self.tick >= expedition.leg_started + expedition::patience(self.world.day_length);
+        match expedition.stage {
+            expedition::Stage::Outbound if arrived => {
+                expedition.stage = expedition::Stage::Working { until: self.tick + venture.work_ticks() };
+                self.event_log.log(self.tick, format!("The {} get to work", venture.party().to_lowercase()), Severity::Info);
+            }
+            expedition::Stage::Working { until } if self.tick >= until => self.settle_venture(),
+            expedition::Stage::Homeward if arrived => self.welcome_party(),
+            _ => {}
+        }
+    }
+
+    /// Find out how the party's venture went, and start them home
+    fn settle_venture(&mut self) {
+        let Some(expedition) = &self.expedition else {
+            return;
+        };
+        let (venture, site, quarry) = (expedition.venture, expedition.site, expedition.quarry);
+        let party: Vec<usize> = (0..self.orcs.len()).filter(|&i| self.orcs[i].alive && expedition.includes(&self.orcs[i].name)).collect();
+        let skill = party.iter().map(|&i| venture_skill(&self.orcs[i], venture)).sum::<f32>() / party.len().max(1) as f32;
+        let haul = if self.rng.gen_bool(venture.odds(skill).min(1.0)) { self.reap(venture, site, quarry, party.len()) } else { 0 };
+        let name = venture.party().to_lowercase();
+        let (cx, cy) = self.world.campfire_pos;
+        if haul > 0 {
+            self.event_log.log_as(self.tick, EventCategory::Food, format!("The {} have {} {} to carry home", name, haul, venture.food()), Severity::Good);
+        } else {
+            let failure = match venture {
+                Venture::Hunt => "The herd gets wind of the hunting party and is gone",
+                Venture::Gathering => "The gatherers find the bushes already picked over",
+                Venture::WinterForage => "The foragers are caught out in the cold and come away with nothing",
+            };
+            self.event_log.log_as(self.tick, EventCategory::Alert, failure.to_string(), Severity::Warning);
+        }
+        for &i in &party {
+            let orc = &mut self.orcs[i];
+            if haul > 0 {
+                orc.morale = (orc.morale + VENTURE_MORALE).min(100.0);
+            } else {
+                orc.morale = (orc.morale - VENTURE_MORALE).max(0.0);
+                if venture == Venture::WinterForage {
+                    orc.warmth = (orc.warmth - 40.0).max(0.0);
+                    orc.health = (orc.health - self.rng.gen_range(FROSTBITE)).max(0.0);
+                    self.emotes.push(orc.x, orc.y, EmoteKind::Alarm, self.tick);
+                }
+            }
+            if matches!(orc.activity, Activity::Expedition { .. }) {
+                let (x, y) = orc.find_spot_near(cx, cy, &self.world, &mut self.rng);
+                orc.venture_to(x, y, &self.world);
+            }
+        }
+        if let Some(expedition) = &mut self.expedition {
+            expedition.haul = haul;
+            expedition.stage = expedition::Stage::Homeward;
+            expedition.leg_started = self.tick;
+        }
+    }
+
+    /// Take what a party of `size` gets out of a venture at `site` that went
+    /// well, a hunting party bringing down deer of the herd it's after or any
+    /// other game close by
+    fn reap(&mut self, venture: Venture, (sx, sy): (usize, usize), quarry: Option<u32>, size: usize) -> u32 {
+        match venture {
+            Venture::Hunt => {
+                let mut meat = 0;
+                let near = |a: &Animal| a.x.abs_diff(sx).max(a.y.abs_diff(sy)) <= HUNTING_GROUND || (a.herd.is_some() && a.herd == quarry);
+                for animal in self.animals.iter_mut().filter(|a| a.alive && a.kind.is_game() && near(a)).take(size.div_ceil(2)) {
+                    // The party butchers the carcass where it fell
+                    if let Some((cx, cy)) = animal.kill(&mut self.world, &mut self.event_log, self.tick) {
+                        while self.world.butcher(cx, cy) {
+                            meat += 1;
+                        }
+                    }
+                }
+                meat
+            }
+            Venture::Gathering => {
+                let most = size * BERRY_POUCH as usize * 2;
+                let bushes: Vec<(usize, usize)> = (0..MAP_HEIGHT)
+                    .flat_map(|y| (0..MAP_WIDTH).map(move |x| (x, y)))
+                    .filter(|&(x, y)| x.abs_diff(sx).max(y.abs_diff(sy)) <= VENTURE_RADIUS && self.world.get(x, y) == Terrain::Bush)
+                    .take(most)
+                    .collect();
+                for &(x, y) in &bushes {
+                    self.world.deplete_bush(x, y, self.tick);
+                }
+                bushes.len() as u32 * BUSH_YIELD
+            }
+            Venture::WinterForage => self.rng.gen_range(1..=FORAGE_HAUL * size as u32),
+        }
+    }
+
+    /// Take in the haul of a party that's home, and stand them down
+    fn welcome_party(&mut self) {
+        let Some(expedition) = self.expedition.take() else {
+            return;
+        };
+        for orc in self.orcs.iter_mut().filter(|o| matches!(o.activity, Activity::Expedition { .. })) {
+            orc.activity = Activity::Idle;
+        }
+        let name = expedition.venture.party().to_lowercase();
+        if expedition.haul == 0 {
+            self.event_log.log(self.tick, format!("The {} are back, empty-handed", name), Severity::Warning);
+            return;
+        }
+        match expedition.venture {
+            Venture::Hunt => (0..expedition.haul).for_each(|_| self.world.store_meat()),
+            Venture::Gathering | Venture::WinterForage => {
+                self.world.berry_stockpile += expedition.haul;
+                self.world.wear_rack();
+            }
+        }
+        self.event_log.log_as(
+            self.tick,
+            EventCategory::Food,
+            format!("The {} are back with {} {}", name, expedition.haul, expedition.venture.food()),
+            Severity::Good,
+        );
+    }
+
+    /// Carry out a debug command, putting anything it spawns down at
+    /// (`x`, `y`), and say what it did
+    pub fn debug(&mut self, command: DebugCommand, (x, y): (usize, usize)) -> Result<String, String> {
+        let named = |orcs: &[Orc], name: &str| {
+            orcs.iter().position(|o| o.alive && o.name.eq_ignore_ascii_case(name)).ok_or_else(|| format!("no orc called {}", name))
+        };
+        match command {
+            DebugCommand::SpawnOrcs(count) => {
+                if !self.world.is_walkable(x, y) {
+                    return Err(format!("nobody can stand at ({}, {})", x, y));
+                }
+                let mut names: Vec<String> = self.orcs.iter().map(|o| o.name.clone()).collect();
+                for mut orc in Orc::spawn_clan(count, &self.world, &mut self.rng) {
+                    // spawn_clan only keeps names unique within the new batch
+                    if names.contains(&orc.name) {
+                        orc.name = format!("{} {}", orc.name, names.len() + 1);
+                    }
+                    (orc.x, orc.y, orc.was) = (x, y, (x, y));
+                    names.push(orc.name.clone());
+                    self.orcs.push(orc);
+                }
+                self.reindex();
+                Ok(format!("{} orcs put down at ({}, {})", count, x, y))
+            }
+            DebugCommand::SpawnAnimals(kind, count) => {
+                if !self.world.is_walkable(x, y) {
+                    return Err(format!("nothing can stand at ({}, {})", x, y));
+                }
+                self.animals.extend((0..count).map(|_| Animal::new(kind, x, y)));
+                self.reindex();
+                Ok(format!("{} {} put down at ({}, {})", count, kind.name().to_lowercase(), x, y))
+            }
+            DebugCommand::SetNeed { need, orc, value } => {
+                let i = named(&self.orcs, &orc)?;
+                let orc = &mut self.orcs[i];
+                *orc.need_mut(need) = value;
+                Ok(format!("{}'s {} set to {:.0}", orc.name, NEEDS[need].to_lowercase(), value))
+            }
+            DebugCommand::Teleport { orc, x, y } => {
+                if !self.world.is_walkable(x, y) {
+                    return Err(format!("nobody can stand at ({}, {})", x, y));
+                }
+                let i = named(&self.orcs, &orc)?;
+                let orc = &mut self.orcs[i];
+                orc.cancel(&mut self.world, &mut self.event_log, self.tick);
+                (orc.x, orc.y, orc.was) = (x, y, (x, y));
+                let name = orc.name.clone();
+                self.reindex();
+                Ok(format!("{} moved to ({}, {})", name, x, y))
+            }
+            DebugCommand::Reveal => {
+                for achievement in self.bestiary.watch(&mut self.animals, (0, 0, MAP_WIDTH, MAP_HEIGHT)) {
+                    self.event_log.log(self.tick, achievement.describe(), Severity::Good);
+                }
+                Ok("every animal on the map noted in the bestiary".to_string())
+            }
+            DebugCommand::Kill(Target::Kind(kind)) => {
+                let mut count = 0;
+                for animal in self.animals.iter_mut().filter(|a| a.alive && a.kind == kind) {
+                    animal.alive = false;
+                    count += 1;
+                }
+                Ok(format!("{} {} struck down", count, kind.name().to_lowercase()))
+            }
+            DebugCommand::Kill(Target::Orc(name)) => {
+                let i = named(&self.orcs, &name)?;
+                let orc = &mut self.orcs[i];
+                orc.health = 0.0;
+                orc.alive = false;
+                orc.death_tick = Some(self.tick);
+                orc.died_of = Some(Cause::Struck);
+                self.event_log.log_at(self.tick, EventCategory::Death, format!("{} has died!", orc.name), Severity::Danger, (orc.x, orc.y));
+                Ok(format!("{} struck down", orc.name))
+            }
+        }
+    }
+
+    /// Call the orc at `i` by a new name, and have everyone who knew it by
+    /// its old one know it by the new
+    pub fn rename_orc(&mut self, i: usize, name: String) {
+        let old = std::mem::replace(&mut self.orcs[i].name, name.clone());
+        for orc in &mut self.orcs {
+            orc.renamed(&old, &name);
+        }
+        for animal in &mut self.animals {
+            if animal.carried_by.as_ref() == Some(&old) {
+                animal.carried_by = Some(name.clone());
+            }
+        }
+        if let Some(expedition) = &mut self.expedition {
+            for member in expedition.party.iter_mut().filter(|m| **m == old) {
+                *member = name.clone();
+            }
+        }
+        let orc = &self.orcs[i];
+        self.event_log.log_at(self.tick, EventCategory::Social, format!("{} is now called {}", old, orc.name), Severity::Good, (orc.x, orc.y));
+    }
+
+    pub fn drop_food_at(&mut self, x: usize, y: usize, units: u32) {
+        if x >= MAP_WIDTH || y >= MAP_HEIGHT {
+            return;
+        }
+        if self.world.add_food(x, y, units) {
+            self.event_log.log(
+                self.tick,
+                format!("{} food dropped at ({}, {})", units, x, y),
+                Severity::Info,
+            );
+        } else {
+            self.event_log.log(
+                self.tick,
+                format!("Can't drop food at ({}, {}): no orc can get there", x, y),
+                Severity::Warning,
+            );
+        }
+    }
+
+    pub fn cancel_orc(&mut self, i: usize) {
+        let Some(orc) = self.orcs.get_mut(i).filter(|o| o.alive) else {
+            return;
+        };
+        if matches!(orc.activity, Activity::Idle | Activity::Infant { .. }) {
+            return;
+        }
+        let doing = orc.activity.label().to_lowercase();
+        self.event_log.log(self.tick, format!("{} is told to stop ({})", orc.name, doing), Severity::Info);
+        orc.cancel(&mut self.world, &mut self.event_log, self.tick);
+        self.emotes.push(orc.x, orc.y, EmoteKind::Confused, self.tick);
+    }
+
+    /// Call every orc away from camp back to the fire, whatever they were doing
+    pub fn recall_all(&mut self) {
+        if self.evacuation.take().is_some() {
+            self.evacuation_ended = self.tick;
+        }
+        self.expedition = None;
+        self.event_log.log_as(self.tick, EventCategory::Alert, "The clan is called back to camp!".to_string(), Severity::Danger);
+        let (cx, cy) = self.world.campfire_pos;
+        for orc in self.orcs.iter_mut() {
+            if !orc.alive || orc.is_infant(self.tick, self.world.day_length) || orc.near_campfire(&self.world) {
+                continue;
+            }
+            orc.cancel(&mut self.world, &mut self.event_log, self.tick);
+            let (sx, sy) = orc.find_spot_near(cx, cy, &self.world, &mut self.rng);
+            orc.go_to(sx, sy, Purpose::Camp, "Recalled to camp", &self.world);
+            self.emotes.push(orc.x, orc.y, EmoteKind::Alarm, self.tick);
+        }
+    }
+
+    pub fn mark_at(&mut self, order: Designation, x: usize, y: usize) {
+        if x >= MAP_WIDTH || y >= MAP_HEIGHT {
+            return;
+        }
+        self.world.mark(x, y, order);
+    }
+
+    pub fn designate_at(&mut self, kind: Building, x: usize, y: usize) {
+        if x >= MAP_WIDTH || y >= MAP_HEIGHT {
+            return;
+        }
+        if self.world.designate(x, y, kind) {
+            self.event_log.log_at(self.tick, EventCategory::General, format!("A {} is marked out at ({}, {})", kind.name(), x, y), Severity::Info, (x, y));
+        } else {
+            self.event_log.log(self.tick, format!("There's no room for a {} at ({}, {})", kind.name(), x, y), Severity::Warning);
+        }
+    }
+
+    pub fn designate_line(&mut self, kind: Building, from: (usize, usize), to: (usize, usize)) {
+        if from.0 >= MAP_WIDTH || from.1 >= MAP_HEIGHT || to.0 >= MAP_WIDTH || to.1 >= MAP_HEIGHT {
+            return;
+        }
+        let placed = self.world.designate_line(from, to, kind);
+        if placed > 0 {
+            self.event_log.log(self.tick, format!("{} {} segments are marked out from ({}, {}) to ({}, {})", placed, kind.name(), from.0, from.1, to.0, to.1), Severity::Info);
+        } else {
+            self.event_log.log(self.tick, format!("There's no room for a {} from ({}, {}) to ({}, {})", kind.name(), from.0, from.1, to.0, to.1), Severity::Warning);
+        }
+    }
+}
+
+/// What counts for a party on `venture`
+fn venture_skill(orc: &Orc, venture: Venture) -> f32 {
+    match venture {
+        Venture::Hunt => orc.hunt_skill,
+        Venture::Gathering | Venture::WinterForage => orc.forage_skill,
+    }
+}
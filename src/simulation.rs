@@ -0,0 +1,60 @@
+//! A game that can be shared between threads.
+
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock};
+
+use crate::gym::{Action, Env, Observation, Step};
+
+// Everything in a game must be safe to hand to another thread
+const _: fn() = || {
+    fn shareable<T: Send + Sync>() {}
+    shareable::<Env>();
+};
+
+#[derive(Clone)]
+pub struct Simulation {
+    env: Arc<Mutex<Env>>,
+    /// The world as of the last step or reset
+    latest: Arc<RwLock<Observation>>,
+}
+
+impl Simulation {
+    pub fn new(env: Env) -> Self {
+        let latest = Arc::new(RwLock::new(env.observe()));
+        Simulation { env: Arc::new(Mutex::new(env)), latest }
+    }
+
+    /// A thread that panicked mid-step leaves the game as it was at the
+    /// panic; carry on with it rather than take every other thread down too
+    fn env(&self) -> MutexGuard<'_, Env> {
+        self.env.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn publish(&self, observation: &Observation) {
+        *self.latest.write().unwrap_or_else(PoisonError::into_inner) = observation.clone();
+    }
+
+    /// Apply `actions` and advance one tick, as `Env::step`
+    pub fn step(&self, actions: &[Action]) -> Step {
+        let step = self.env().step(actions);
+        self.publish(&step.observation);
+        step
+    }
+
+    /// Start a new episode, as `Env::reset`
+    pub fn reset(&self, seed: u64) -> io::Result<Observation> {
+        let observation = self.env().reset(seed)?;
+        self.publish(&observation);
+        Ok(observation)
+    }
+
+    pub fn done(&self) -> bool {
+        self.env().done()
+    }
+
+    /// A copy of the world as of the last step, without waiting for one
+    /// that's underway
+    pub fn snapshot(&self) -> Observation {
+        self.latest.read().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+}
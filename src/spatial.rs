@@ -0,0 +1,62 @@
+//! A coarse grid over the map for finding creatures by position.
+
+use crate::animal::Animal;
+use crate::orc::Orc;
+use crate::world::{MAP_HEIGHT, MAP_WIDTH};
+
+/// Width and height of one cell in tiles
+const CELL: usize = 8;
+const COLS: usize = MAP_WIDTH.div_ceil(CELL);
+const ROWS: usize = MAP_HEIGHT.div_ceil(CELL);
+
+/// A creature, by its position in the app's orc or animal list
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Entity {
+    Orc(usize),
+    Animal(usize),
+}
+
+pub struct SpatialIndex {
+    cells: Vec<Vec<(usize, usize, Entity)>>,
+}
+
+impl SpatialIndex {
+    pub fn new(orcs: &[Orc], animals: &[Animal]) -> Self {
+        let mut index = SpatialIndex { cells: vec![Vec::new(); COLS * ROWS] };
+        index.rebuild(orcs, animals);
+        index
+    }
+
+    /// File every orc, living or dead, and every living animal afresh
+    pub fn rebuild(&mut self, orcs: &[Orc], animals: &[Animal]) {
+        for cell in &mut self.cells {
+            cell.clear();
+        }
+        for (i, orc) in orcs.iter().enumerate() {
+            self.insert(orc.x, orc.y, Entity::Orc(i));
+        }
+        for (i, animal) in animals.iter().enumerate().filter(|(_, a)| a.alive) {
+            self.insert(animal.x, animal.y, Entity::Animal(i));
+        }
+    }
+
+    fn insert(&mut self, x: usize, y: usize, entity: Entity) {
+        self.cells[(y / CELL) * COLS + x / CELL].push((x, y, entity));
+    }
+
+    /// Everything within `radius` steps of (x, y), counting steps the way the
+    /// rest of the game does, across plus down
+    pub fn within(&self, x: usize, y: usize, radius: usize) -> impl Iterator<Item = Entity> + '_ {
+        let cols = x.saturating_sub(radius) / CELL..=((x + radius) / CELL).min(COLS - 1);
+        let rows = y.saturating_sub(radius) / CELL..=((y + radius) / CELL).min(ROWS - 1);
+        rows.flat_map(move |row| cols.clone().map(move |col| &self.cells[row * COLS + col]))
+            .flatten()
+            .filter(move |&&(ex, ey, _)| x.abs_diff(ex) + y.abs_diff(ey) <= radius)
+            .map(|&(_, _, entity)| entity)
+    }
+
+    /// Everything standing on (x, y), orcs first, each in list order
+    pub fn at(&self, x: usize, y: usize) -> impl Iterator<Item = Entity> + '_ {
+        self.within(x, y, 0)
+    }
+}
@@ -0,0 +1,92 @@
+//! The spirits of the wild and what they make of the clan.
+
+/// Goodwill lost for each animal killed
+pub const HUNT_OFFENCE: i32 = 8;
+/// Goodwill lost for each bush picked over
+pub const PICK_OFFENCE: i32 = 2;
+/// Goodwill lost for each tree felled
+pub const FELL_OFFENCE: i32 = 3;
+
+/// Goodwill regained every dawn
+const DAILY_RECOVERY: i32 = 5;
+/// The balance never strays further than this either way
+const MAX_BALANCE: i32 = 100;
+/// At or above this the spirits bless the harvest, which uses the goodwill up
+const BLESSING: i32 = 80;
+/// At or below this the spirits grow uneasy
+const UNEASY: i32 = -20;
+/// At or below this they turn away and the game grows scarce
+const ANGRY: i32 = -50;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mood {
+    Generous,
+    Content,
+    Uneasy,
+    Angry,
+}
+
+impl Mood {
+    fn of(balance: i32) -> Mood {
+        if balance >= BLESSING {
+            Mood::Generous
+        } else if balance > UNEASY {
+            Mood::Content
+        } else if balance > ANGRY {
+            Mood::Uneasy
+        } else {
+            Mood::Angry
+        }
+    }
+
+    /// How the clan comes to hear that the spirits' mood has turned from `before`
+    fn omen(&self, before: Mood) -> &'static str {
+        match (self, before) {
+            (Mood::Generous, _) => "The spirits of the wild smile on the clan: every bush bends heavy with berries",
+            (Mood::Content, _) => "The wind settles. The spirits of the wild are at peace again",
+            (Mood::Uneasy, Mood::Angry) => "The spirits' anger cools, though the forest still watches the clan",
+            (Mood::Uneasy, _) => "Crows circle the camp at dawn. The spirits of the wild grow uneasy",
+            (Mood::Angry, _) => "The spirits of the wild turn their backs on the clan, and the herds slip away",
+        }
+    }
+}
+
+pub struct Spirits {
+    balance: i32,
+    mood: Mood,
+}
+
+impl Default for Spirits {
+    fn default() -> Self {
+        Spirits { balance: 0, mood: Mood::Content }
+    }
+}
+
+impl Spirits {
+    pub fn offend(&mut self, offence: i32) {
+        self.balance = (self.balance - offence).max(-MAX_BALANCE);
+    }
+
+    /// While the spirits are angry, no new game wanders in
+    pub fn game_is_scarce(&self) -> bool {
+        self.mood == Mood::Angry
+    }
+
+    /// Let the land heal for a day. Returns the new mood and its omen if the
+    /// mood has changed; a blessing spends the goodwill that earned it.
+    pub fn dawn(&mut self) -> Option<(Mood, &'static str)> {
+        self.balance = (self.balance + DAILY_RECOVERY).min(MAX_BALANCE);
+        let before = self.mood;
+        let mood = Mood::of(self.balance);
+        if mood == Mood::Generous {
+            self.balance -= BLESSING;
+            self.mood = Mood::of(self.balance);
+            return Some((mood, mood.omen(before)));
+        }
+        if mood == before {
+            return None;
+        }
+        self.mood = mood;
+        Some((mood, mood.omen(before)))
+    }
+}
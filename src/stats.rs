@@ -0,0 +1,113 @@
+use crate::event::{Event, EventCategory};
+
+/// Notable headlines kept per day for the summary
+const MAX_HEADLINES: usize = 5;
+
+pub struct DayStats {
+    pub day: u64,
+    pub births: u32,
+    pub deaths: u32,
+    pub hunts: u32,
+    pub food_start: u32,
+    pub food_end: u32,
+    pub population: usize,
+    pub headlines: Vec<String>,
+}
+
+impl DayStats {
+    fn new(day: u64, food_start: u32) -> Self {
+        DayStats {
+            day,
+            births: 0,
+            deaths: 0,
+            hunts: 0,
+            food_start,
+            food_end: food_start,
+            population: 0,
+            headlines: Vec::new(),
+        }
+    }
+
+    pub fn food_delta(&self) -> i64 {
+        self.food_end as i64 - self.food_start as i64
+    }
+
+    /// One-line summary for the event log
+    pub fn headline(&self) -> String {
+        format!(
+            "Day {} news: {} born, {} died, {} hunts, meat {:+}",
+            self.day,
+            self.births,
+            self.deaths,
+            self.hunts,
+            self.food_delta(),
+        )
+    }
+
+    /// Full "village newspaper" report for the export file
+    pub fn report(&self) -> String {
+        let mut out = format!("=== The Orc Village Herald - Day {} ===\n", self.day);
+        out.push_str(&format!(
+            "Population: {} ({} born, {} died)\n",
+            self.population, self.births, self.deaths
+        ));
+        out.push_str(&format!("Hunts: {}\n", self.hunts));
+        out.push_str(&format!(
+            "Meat stockpile: {} -> {} ({:+})\n",
+            self.food_start,
+            self.food_end,
+            self.food_delta()
+        ));
+        if !self.headlines.is_empty() {
+            out.push_str("Notable:\n");
+            for line in &self.headlines {
+                out.push_str(&format!("  - {}\n", line));
+            }
+        }
+        out.push('\n');
+        out
+    }
+}
+
+/// Tallies categorized events into per-day statistics
+pub struct StatsCollector {
+    pub current: DayStats,
+    pub history: Vec<DayStats>,
+}
+
+impl StatsCollector {
+    pub fn new(food_start: u32) -> Self {
+        StatsCollector {
+            current: DayStats::new(1, food_start),
+            history: Vec::new(),
+        }
+    }
+
+    pub fn record<'a>(&mut self, events: impl IntoIterator<Item = &'a Event>) {
+        for event in events {
+            match event.category {
+                EventCategory::Birth => self.current.births += 1,
+                EventCategory::Death => self.current.deaths += 1,
+                EventCategory::Hunt => self.current.hunts += 1,
+                _ => {}
+            }
+            let notable = matches!(
+                event.category,
+                EventCategory::Birth | EventCategory::Death | EventCategory::Alert | EventCategory::Spirit
+            );
+            if notable && self.current.headlines.len() < MAX_HEADLINES {
+                self.current.headlines.push(event.message.clone());
+            }
+        }
+    }
+
+    /// Close out the current day and start tracking the next one
+    pub fn end_day(&mut self, food_end: u32, population: usize) -> &DayStats {
+        let next = DayStats::new(self.current.day + 1, food_end);
+        let mut finished = std::mem::replace(&mut self.current, next);
+        finished.food_end = food_end;
+        finished.population = population;
+        self.history.push(finished);
+        self.history.last().unwrap()
+    }
+}
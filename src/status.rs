@@ -0,0 +1,80 @@
+//! The status bar along the bottom of the screen.
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::alarm::Alarm;
+use crate::app::{App, Screen};
+use crate::event::EventCategory;
+use crate::orc::{FREEZING, Orc, WASTING};
+
+/// The status bar as it stands
+pub fn line(app: &App) -> Line<'static> {
+    let theme = app.theme();
+    let (mode, doing) = mode(app);
+    let mut spans = vec![
+        Span::styled(format!(" {} ", mode), Style::default().fg(theme.accent_bright).add_modifier(Modifier::REVERSED | Modifier::BOLD)),
+        Span::raw(" "),
+    ];
+    if let Some(doing) = doing {
+        spans.push(Span::styled(format!("{}  ", doing), Style::default().fg(theme.accent_bright)));
+    }
+    for alert in alerts(app) {
+        spans.push(Span::styled(format!("⚠ {}  ", alert), Style::default().fg(theme.bad).add_modifier(Modifier::BOLD)));
+    }
+    let critical = app.sim.event_log.latest(&[EventCategory::Alert, EventCategory::Death]);
+    if let Some(event) = critical.filter(|e| e.tick + app.sim.world.day_length > app.sim.tick) {
+        spans.push(Span::styled(format!("[{}] ", event.tick), Style::default().fg(theme.muted)));
+        spans.push(Span::styled(event.message.clone(), theme.severity(event.severity).map_or_else(Style::default, |color| Style::default().fg(color))));
+    }
+    Line::from(spans)
+}
+
+/// What the keys are doing, and anything more to say about it
+fn mode(app: &App) -> (&'static str, Option<String>) {
+    let keys = app.input.pending();
+    if app.screen != Screen::Playing {
+        ("MENU", Some("The world stands still".to_string()))
+    } else if let Some(typed) = &app.console {
+        ("CONSOLE", Some(format!(":{}_", typed)))
+    } else if let Some((_, typed)) = &app.naming {
+        ("NAME", Some(format!("{}_", typed)))
+    } else if app.log_pick.is_some() {
+        ("LOG", Some("Up and down pick an event, Enter to go where it happened, Esc to stop".to_string()))
+    } else if app.ordering {
+        let name = app.selected_orc.and_then(|i| app.sim.orcs.get(i)).map_or("", |o| o.name.as_str());
+        ("ORDER", Some(format!("Send {} where? Enter to send, Esc to stop", name)))
+    } else if let Some(order) = app.designating {
+        ("DESIGNATE", Some(format!("Marking {}", order.describe())))
+    } else if let Some((x, y)) = app.wall_start {
+        ("BUILD", Some(format!("Wall from ({},{})", x, y)))
+    } else if !keys.is_empty() {
+        ("NORMAL", Some(format!("{} ...", keys)))
+    } else {
+        ("NORMAL", None)
+    }
+}
+
+/// Whatever needs seeing to at once
+fn alerts(app: &App) -> Vec<String> {
+    let living: Vec<&Orc> = app.sim.orcs.iter().filter(|o| o.alive).collect();
+    let mut alerts = Vec::new();
+    for (plight, count) in [
+        ("starving", living.iter().filter(|o| o.hunger >= WASTING).count()),
+        ("parched", living.iter().filter(|o| o.thirst >= WASTING).count()),
+        ("freezing", living.iter().filter(|o| o.warmth <= FREEZING).count()),
+    ] {
+        match count {
+            0 => {}
+            1 => alerts.push(format!("1 orc {}", plight)),
+            n => alerts.push(format!("{} orcs {}", n, plight)),
+        }
+    }
+    if !living.is_empty() && !app.sim.world.fire_lit() {
+        alerts.push("Fire out".to_string());
+    }
+    if let Alarm::Alert { threat, .. } = app.sim.alarm {
+        alerts.push(format!("Near camp: {}", threat.name()));
+    }
+    alerts
+}
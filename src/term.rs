@@ -0,0 +1,88 @@
+use std::env;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GlyphMode {
+    Unicode,
+    Ascii,
+}
+
+impl GlyphMode {
+    pub fn parse(s: &str) -> Option<GlyphMode> {
+        match s {
+            "unicode" => Some(GlyphMode::Unicode),
+            "ascii" => Some(GlyphMode::Ascii),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GlyphMode::Unicode => "unicode",
+            GlyphMode::Ascii => "ascii",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ColorDepth {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+impl ColorDepth {
+    pub fn parse(s: &str) -> Option<ColorDepth> {
+        match s {
+            "truecolor" | "24bit" => Some(ColorDepth::TrueColor),
+            "256" => Some(ColorDepth::Ansi256),
+            "16" => Some(ColorDepth::Ansi16),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorDepth::TrueColor => "truecolor",
+            ColorDepth::Ansi256 => "256",
+            ColorDepth::Ansi16 => "16",
+        }
+    }
+}
+
+/// What the terminal can display, used by the renderer to pick glyphs and palette
+#[derive(Clone, Copy, Debug)]
+pub struct TermCaps {
+    pub glyphs: GlyphMode,
+    pub colors: ColorDepth,
+}
+
+impl TermCaps {
+    /// Guess capabilities from the locale and the usual terminal environment variables
+    pub fn detect() -> TermCaps {
+        let var = |key: &str| env::var(key).unwrap_or_default().to_lowercase();
+        let windows_terminal = env::var_os("WT_SESSION").is_some();
+
+        // The first non-empty locale variable wins, as in setlocale(3)
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .map(|k| var(k))
+            .find(|v| !v.is_empty())
+            .unwrap_or_default();
+        let unicode = windows_terminal || locale.contains("utf-8") || locale.contains("utf8");
+
+        let colorterm = var("COLORTERM");
+        let term = var("TERM");
+        let colors = if windows_terminal || colorterm == "truecolor" || colorterm == "24bit" {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Ansi16
+        };
+
+        TermCaps {
+            glyphs: if unicode { GlyphMode::Unicode } else { GlyphMode::Ascii },
+            colors,
+        }
+    }
+}
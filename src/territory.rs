@@ -0,0 +1,87 @@
+//! The ground each clan holds.
+
+use crate::world::{MAP_HEIGHT, MAP_WIDTH};
+
+/// Tiles along each side of a region
+pub const REGION: usize = 10;
+const COLUMNS: usize = MAP_WIDTH.div_ceil(REGION);
+const ROWS: usize = MAP_HEIGHT.div_ceil(REGION);
+/// Scent an orc leaves on the region it's in each tick it's up and about
+const MARK: f32 = 1.0;
+/// Share of its scent a region keeps from one tick to the next
+const FADE: f32 = 0.998;
+/// Scent a clan must have left on a region to lay claim to it
+const CLAIM: f32 = 15.0;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Clan {
+    Ours,
+    Rivals,
+}
+
+/// Who holds a region
+#[derive(Clone, Copy, PartialEq)]
+pub enum Hold {
+    Open,
+    Held(Clan),
+    Contested,
+}
+
+impl Hold {
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Hold::Open => "open ground",
+            Hold::Held(Clan::Ours) => "our ground",
+            Hold::Held(Clan::Rivals) => "rival ground",
+            Hold::Contested => "contested ground",
+        }
+    }
+}
+
+/// Each clan's scent on every region of the map
+pub struct Territory {
+    scent: Vec<[f32; 2]>,
+}
+
+impl Default for Territory {
+    fn default() -> Self {
+        Territory::new()
+    }
+}
+
+impl Territory {
+    pub fn new() -> Self {
+        Territory { scent: vec![[0.0; 2]; COLUMNS * ROWS] }
+    }
+
+    fn region(x: usize, y: usize) -> usize {
+        (y / REGION) * COLUMNS + x / REGION
+    }
+
+    /// Leave `clan`'s scent on the region around (x, y)
+    pub fn mark(&mut self, clan: Clan, x: usize, y: usize) {
+        self.scent[Self::region(x, y)][clan as usize] += MARK;
+    }
+
+    /// Let a tick's worth of every clan's scent fade
+    pub fn fade(&mut self) {
+        for scent in self.scent.iter_mut().flatten() {
+            *scent *= FADE;
+        }
+    }
+
+    /// Who holds the region around (x, y)
+    pub fn hold(&self, x: usize, y: usize) -> Hold {
+        let [ours, rivals] = self.scent[Self::region(x, y)];
+        match (ours >= CLAIM, rivals >= CLAIM) {
+            (true, true) => Hold::Contested,
+            (true, false) => Hold::Held(Clan::Ours),
+            (false, true) => Hold::Held(Clan::Rivals),
+            (false, false) => Hold::Open,
+        }
+    }
+
+    pub fn is_contested(&self, x: usize, y: usize) -> bool {
+        self.hold(x, y) == Hold::Contested
+    }
+}
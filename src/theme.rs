@@ -0,0 +1,478 @@
+//! Color themes for the map and UI.
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use ratatui::style::Color;
+
+use crate::animal::AnimalKind;
+use crate::emote::EmoteKind;
+use crate::event::Severity;
+use crate::markers::Mark;
+use crate::toml::{self, Document, Value};
+use crate::world::Terrain;
+
+#[derive(Clone, Debug)]
+pub struct Theme {
+    pub name: String,
+    /// Share of its brightness a tile keeps at night, from 0 to 1
+    pub night_dim: f32,
+
+    // Terrain
+    pub grass: Color,
+    pub tree: Color,
+    pub rock: Color,
+    pub water: Color,
+    pub campfire: Color,
+    pub food: Color,
+    pub carcass: Color,
+    pub bush: Color,
+    pub depleted_bush: Color,
+    pub fruit_tree: Color,
+    pub mushroom: Color,
+    pub herb: Color,
+    pub grave: Color,
+    pub meat_rack: Color,
+    pub water_barrel: Color,
+    pub tool_rack: Color,
+    pub site: Color,
+    pub building: Color,
+
+    // Creatures and the cursor on the map
+    pub orc: Color,
+    pub orc_selected: Color,
+    pub orc_hurt: Color,
+    pub orc_carrying: Color,
+    pub tombstone: Color,
+    pub goblin: Color,
+    pub rival: Color,
+    /// A thrown spear in flight
+    pub spear: Color,
+    pub cursor: Color,
+    /// Background of tiles marked with a standing order
+    pub designation: Color,
+    /// Background of the ground each clan holds, and that both lay claim to,
+    /// in the territory view
+    pub territory_ours: Color,
+    pub territory_rivals: Color,
+    pub territory_contested: Color,
+    /// Background of the ground lit by the campfire or a torch at night
+    pub firelight: Color,
+    /// Background of the tiles along the way the selected orc is going
+    pub path: Color,
+
+    // Borders, headings and labels
+    pub border: Color,
+    pub border_night: Color,
+    /// What the map's border flashes while the clan is on alert
+    pub border_alert: Color,
+    pub accent: Color,
+    pub accent_bright: Color,
+    pub heading: Color,
+    pub muted: Color,
+    pub traits: Color,
+    pub sick: Color,
+
+    // Need bars in the clan panel
+    pub good: Color,
+    pub warn: Color,
+    pub bad: Color,
+    pub energy: Color,
+    pub water_bar: Color,
+    pub warmth: Color,
+    pub morale: Color,
+    pub forage: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            name: "Classic".to_string(),
+            night_dim: 1.0 / 3.0,
+
+            grass: Color::DarkGray,
+            tree: Color::Rgb(34, 139, 34),
+            rock: Color::Gray,
+            water: Color::Rgb(65, 105, 225),
+            campfire: Color::Rgb(255, 140, 0),
+            food: Color::Rgb(255, 100, 180),
+            carcass: Color::Rgb(150, 70, 60),
+            bush: Color::Rgb(220, 50, 80),
+            depleted_bush: Color::Rgb(80, 60, 60),
+            fruit_tree: Color::Rgb(230, 160, 40),
+            mushroom: Color::Rgb(190, 160, 130),
+            herb: Color::Rgb(120, 200, 150),
+            grave: Color::Rgb(170, 170, 180),
+            meat_rack: Color::Rgb(180, 120, 60),
+            water_barrel: Color::Rgb(110, 150, 200),
+            tool_rack: Color::Rgb(170, 160, 150),
+            site: Color::Rgb(150, 130, 90),
+            building: Color::Rgb(200, 170, 120),
+
+            orc: Color::LightGreen,
+            orc_selected: Color::White,
+            orc_hurt: Color::Red,
+            orc_carrying: Color::Rgb(180, 120, 60),
+            tombstone: Color::DarkGray,
+            goblin: Color::Rgb(200, 60, 40),
+            rival: Color::Rgb(230, 190, 70),
+            spear: Color::Rgb(200, 180, 150),
+            cursor: Color::White,
+            designation: Color::Rgb(90, 70, 20),
+            territory_ours: Color::Rgb(20, 55, 25),
+            territory_rivals: Color::Rgb(65, 50, 10),
+            territory_contested: Color::Rgb(75, 20, 20),
+            firelight: Color::Rgb(45, 30, 10),
+            path: Color::Rgb(30, 45, 75),
+
+            border: Color::White,
+            border_night: Color::DarkGray,
+            border_alert: Color::Red,
+            accent: Color::Green,
+            accent_bright: Color::LightGreen,
+            heading: Color::White,
+            muted: Color::DarkGray,
+            traits: Color::Magenta,
+            sick: Color::LightRed,
+
+            good: Color::Green,
+            warn: Color::Yellow,
+            bad: Color::Red,
+            energy: Color::Cyan,
+            water_bar: Color::Rgb(65, 105, 225),
+            warmth: Color::Rgb(255, 140, 0),
+            morale: Color::LightMagenta,
+            forage: Color::Rgb(220, 50, 80),
+        }
+    }
+}
+
+impl Theme {
+    /// The themes that come with the game, the classic one first
+    pub fn built_in() -> Vec<Theme> {
+        vec![Theme::default(), Theme::high_contrast(), Theme::colorblind()]
+    }
+
+    fn high_contrast() -> Theme {
+        Theme {
+            name: "High contrast".to_string(),
+            night_dim: 0.6,
+
+            grass: Color::Rgb(120, 120, 120),
+            tree: Color::Rgb(0, 230, 0),
+            rock: Color::Rgb(255, 255, 255),
+            water: Color::Rgb(0, 160, 255),
+            campfire: Color::Rgb(255, 200, 0),
+            food: Color::Rgb(255, 0, 255),
+            carcass: Color::Rgb(255, 90, 90),
+            bush: Color::Rgb(255, 0, 0),
+            depleted_bush: Color::Rgb(150, 100, 100),
+            fruit_tree: Color::Rgb(255, 255, 0),
+            mushroom: Color::Rgb(255, 220, 180),
+            herb: Color::Rgb(0, 255, 200),
+            grave: Color::Rgb(255, 255, 255),
+            meat_rack: Color::Rgb(255, 160, 60),
+            water_barrel: Color::Rgb(120, 200, 255),
+            tool_rack: Color::Rgb(230, 230, 230),
+            site: Color::Rgb(255, 220, 120),
+            building: Color::Rgb(255, 255, 200),
+
+            orc: Color::Rgb(255, 255, 255),
+            orc_selected: Color::Rgb(0, 255, 255),
+            orc_hurt: Color::Rgb(255, 0, 0),
+            orc_carrying: Color::Rgb(255, 160, 60),
+            tombstone: Color::Rgb(170, 170, 170),
+            goblin: Color::Rgb(255, 60, 0),
+            rival: Color::Rgb(255, 220, 0),
+            spear: Color::Rgb(255, 255, 255),
+            cursor: Color::Rgb(255, 255, 0),
+            designation: Color::Rgb(120, 90, 0),
+            territory_ours: Color::Rgb(0, 80, 0),
+            territory_rivals: Color::Rgb(100, 80, 0),
+            territory_contested: Color::Rgb(120, 0, 0),
+            firelight: Color::Rgb(80, 50, 0),
+            path: Color::Rgb(0, 50, 130),
+
+            border: Color::Rgb(255, 255, 255),
+            border_night: Color::Rgb(170, 170, 170),
+            border_alert: Color::Rgb(255, 0, 0),
+            accent: Color::Rgb(0, 255, 255),
+            accent_bright: Color::Rgb(255, 255, 255),
+            heading: Color::Rgb(255, 255, 255),
+            muted: Color::Rgb(170, 170, 170),
+            traits: Color::Rgb(255, 0, 255),
+            sick: Color::Rgb(255, 90, 90),
+
+            good: Color::Rgb(0, 255, 0),
+            warn: Color::Rgb(255, 255, 0),
+            bad: Color::Rgb(255, 0, 0),
+            energy: Color::Rgb(0, 255, 255),
+            water_bar: Color::Rgb(0, 160, 255),
+            warmth: Color::Rgb(255, 160, 0),
+            morale: Color::Rgb(255, 0, 255),
+            forage: Color::Rgb(255, 0, 0),
+        }
+    }
+
+    fn colorblind() -> Theme {
+        // Okabe-Ito: orange, sky blue, bluish green, yellow, blue, vermilion
+        // and reddish purple
+        let orange = Color::Rgb(230, 159, 0);
+        let sky_blue = Color::Rgb(86, 180, 233);
+        let bluish_green = Color::Rgb(0, 158, 115);
+        let yellow = Color::Rgb(240, 228, 66);
+        let blue = Color::Rgb(0, 114, 178);
+        let vermilion = Color::Rgb(213, 94, 0);
+        let purple = Color::Rgb(204, 121, 167);
+        Theme {
+            name: "Colorblind".to_string(),
+            night_dim: 0.4,
+
+            tree: bluish_green,
+            water: blue,
+            campfire: orange,
+            food: yellow,
+            carcass: vermilion,
+            bush: purple,
+            depleted_bush: Color::Rgb(90, 80, 90),
+            fruit_tree: orange,
+            herb: sky_blue,
+            meat_rack: vermilion,
+            water_barrel: sky_blue,
+
+            orc: Color::White,
+            orc_selected: yellow,
+            orc_hurt: vermilion,
+            orc_carrying: orange,
+            goblin: vermilion,
+            rival: purple,
+            designation: Color::Rgb(90, 70, 0),
+            territory_ours: Color::Rgb(0, 45, 80),
+            territory_rivals: Color::Rgb(85, 55, 0),
+            territory_contested: Color::Rgb(70, 30, 60),
+            path: Color::Rgb(50, 50, 60),
+
+            border_alert: vermilion,
+            accent: sky_blue,
+            accent_bright: Color::Rgb(150, 210, 245),
+            traits: purple,
+            sick: vermilion,
+
+            good: sky_blue,
+            warn: yellow,
+            bad: vermilion,
+            energy: bluish_green,
+            water_bar: blue,
+            warmth: orange,
+            morale: purple,
+            forage: purple,
+            ..Theme::default()
+        }
+    }
+
+    pub fn terrain(&self, terrain: Terrain) -> Color {
+        match terrain {
+            Terrain::Grass => self.grass,
+            Terrain::Tree => self.tree,
+            Terrain::Rock => self.rock,
+            Terrain::Water => self.water,
+            Terrain::Campfire => self.campfire,
+            Terrain::Carcass => self.carcass,
+            Terrain::Bush => self.bush,
+            Terrain::DepletedBush => self.depleted_bush,
+            Terrain::FruitTree => self.fruit_tree,
+            Terrain::BareFruitTree => self.tree,
+            Terrain::Mushroom => self.mushroom,
+            Terrain::Herb => self.herb,
+            Terrain::Grave => self.grave,
+            Terrain::MeatRack => self.meat_rack,
+            Terrain::WaterBarrel => self.water_barrel,
+            Terrain::ToolRack => self.tool_rack,
+            Terrain::Site(_) => self.site,
+            Terrain::Built(_) => self.building,
+        }
+    }
+
+    /// The color of a mark left on the map
+    pub fn mark(&self, mark: Mark) -> Color {
+        match mark {
+            Mark::Death => self.tombstone,
+            Mark::Raid => self.goblin,
+            Mark::Hunt => self.carcass,
+        }
+    }
+
+    /// The color an event of `severity` is told in, if not the plain text color
+    pub fn severity(&self, severity: Severity) -> Option<Color> {
+        match severity {
+            Severity::Info => None,
+            Severity::Good => Some(self.good),
+            Severity::Warning => Some(self.warn),
+            Severity::Danger => Some(self.bad),
+        }
+    }
+
+    /// The color an animal of `kind` is drawn in, the same in every theme
+    pub fn creature(&self, kind: AnimalKind) -> Color {
+        let (r, g, b) = kind.stats().color;
+        Color::Rgb(r, g, b)
+    }
+
+    /// The color an emote is drawn in, the same in every theme
+    pub fn emote(&self, kind: EmoteKind) -> Color {
+        match kind {
+            EmoteKind::Alarm => Color::Red,
+            EmoteKind::Love => Color::LightMagenta,
+            EmoteKind::Sleepy => Color::LightBlue,
+            EmoteKind::Confused => Color::Yellow,
+        }
+    }
+
+    /// Darken a color for night time
+    pub fn dim(&self, color: Color) -> Color {
+        let scale = |v: u8| (v as f32 * self.night_dim) as u8;
+        match color {
+            Color::Rgb(r, g, b) => Color::Rgb(scale(r), scale(g), scale(b)),
+            _ => Color::DarkGray,
+        }
+    }
+
+    /// Read one theme file, named after the file unless it sets `name`
+    pub fn load(path: &Path) -> Result<Theme, String> {
+        let src = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let mut doc = toml::parse(&src).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let stem = path.file_stem().map(|s| s.to_string_lossy().into_owned()).unwrap_or_default();
+        let theme = Theme::from_toml(&mut doc, stem).map_err(|e| format!("{}: {}", path.display(), e))?;
+        if let Some(key) = doc.keys().next() {
+            return Err(format!("{}: unknown setting '{}'", path.display(), key));
+        }
+        Ok(theme)
+    }
+
+    fn from_toml(doc: &mut Document, name: String) -> Result<Theme, String> {
+        let mut theme = Theme { name, ..Theme::default() };
+        if let Some(value) = doc.take("name") {
+            let Value::Str(name) = value else {
+                return Err(format!("name must be a string, got {}", value));
+            };
+            theme.name = name;
+        }
+        if let Some(value) = doc.take("night_dim") {
+            match value.as_f64() {
+                Some(v) if (0.0..=1.0).contains(&v) => theme.night_dim = v as f32,
+                _ => return Err(format!("night_dim must be a number from 0 to 1, got {}", value)),
+            }
+        }
+
+        let colors = [
+            ("terrain.grass", &mut theme.grass),
+            ("terrain.tree", &mut theme.tree),
+            ("terrain.rock", &mut theme.rock),
+            ("terrain.water", &mut theme.water),
+            ("terrain.campfire", &mut theme.campfire),
+            ("terrain.food", &mut theme.food),
+            ("terrain.carcass", &mut theme.carcass),
+            ("terrain.bush", &mut theme.bush),
+            ("terrain.depleted_bush", &mut theme.depleted_bush),
+            ("terrain.fruit_tree", &mut theme.fruit_tree),
+            ("terrain.mushroom", &mut theme.mushroom),
+            ("terrain.herb", &mut theme.herb),
+            ("terrain.grave", &mut theme.grave),
+            ("terrain.meat_rack", &mut theme.meat_rack),
+            ("terrain.water_barrel", &mut theme.water_barrel),
+            ("terrain.tool_rack", &mut theme.tool_rack),
+            ("terrain.site", &mut theme.site),
+            ("terrain.building", &mut theme.building),
+            ("map.orc", &mut theme.orc),
+            ("map.orc_selected", &mut theme.orc_selected),
+            ("map.orc_hurt", &mut theme.orc_hurt),
+            ("map.orc_carrying", &mut theme.orc_carrying),
+            ("map.tombstone", &mut theme.tombstone),
+            ("map.goblin", &mut theme.goblin),
+            ("map.rival", &mut theme.rival),
+            ("map.spear", &mut theme.spear),
+            ("map.cursor", &mut theme.cursor),
+            ("map.designation", &mut theme.designation),
+            ("map.territory_ours", &mut theme.territory_ours),
+            ("map.territory_rivals", &mut theme.territory_rivals),
+            ("map.territory_contested", &mut theme.territory_contested),
+            ("map.firelight", &mut theme.firelight),
+            ("map.path", &mut theme.path),
+            ("ui.border", &mut theme.border),
+            ("ui.border_night", &mut theme.border_night),
+            ("ui.border_alert", &mut theme.border_alert),
+            ("ui.accent", &mut theme.accent),
+            ("ui.accent_bright", &mut theme.accent_bright),
+            ("ui.heading", &mut theme.heading),
+            ("ui.muted", &mut theme.muted),
+            ("ui.traits", &mut theme.traits),
+            ("ui.sick", &mut theme.sick),
+            ("status.good", &mut theme.good),
+            ("status.warn", &mut theme.warn),
+            ("status.bad", &mut theme.bad),
+            ("status.energy", &mut theme.energy),
+            ("status.water", &mut theme.water_bar),
+            ("status.warmth", &mut theme.warmth),
+            ("status.morale", &mut theme.morale),
+            ("status.forage", &mut theme.forage),
+        ];
+        for (key, field) in colors {
+            let Some(value) = doc.take(key) else {
+                continue;
+            };
+            match &value {
+                Value::Str(s) => *field = parse_color(s).map_err(|e| format!("{}: {}", key, e))?,
+                _ => return Err(format!("{} must be a color like \"#228b22\" or \"dark_gray\", got {}", key, value)),
+            }
+        }
+        Ok(theme)
+    }
+}
+
+/// Parse `#rrggbb` or one of the 16 terminal color names
+pub fn parse_color(spec: &str) -> Result<Color, String> {
+    if let Some(hex) = spec.strip_prefix('#') {
+        let channel = |i: usize| hex.get(i..i + 2).and_then(|h| u8::from_str_radix(h, 16).ok());
+        return match (hex.len(), channel(0), channel(2), channel(4)) {
+            (6, Some(r), Some(g), Some(b)) => Ok(Color::Rgb(r, g, b)),
+            _ => Err(format!("invalid hex color '{}'", spec)),
+        };
+    }
+    let color = match spec.to_lowercase().replace('-', "_").as_str() {
+        "black" => Color::Black,
+        "red" => Color::Red,
+        "green" => Color::Green,
+        "yellow" => Color::Yellow,
+        "blue" => Color::Blue,
+        "magenta" => Color::Magenta,
+        "cyan" => Color::Cyan,
+        "gray" | "grey" => Color::Gray,
+        "dark_gray" | "dark_grey" => Color::DarkGray,
+        "light_red" => Color::LightRed,
+        "light_green" => Color::LightGreen,
+        "light_yellow" => Color::LightYellow,
+        "light_blue" => Color::LightBlue,
+        "light_magenta" => Color::LightMagenta,
+        "light_cyan" => Color::LightCyan,
+        "white" => Color::White,
+        _ => return Err(format!("unknown color '{}'", spec)),
+    };
+    Ok(color)
+}
+
+/// Every theme in `dir`, sorted by file name. A missing folder just means no
+/// custom themes.
+pub fn load_dir(dir: &Path) -> Result<Vec<Theme>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {}", dir.display(), e)),
+    };
+    let mut paths: Vec<_> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    paths.sort();
+    paths.iter().map(|path| Theme::load(path)).collect()
+}
@@ -0,0 +1,200 @@
+//! Minimal TOML reader for config files (no external dependencies).
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Str(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+}
+
+impl Value {
+    /// Integers are accepted wherever a float is expected
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            Value::Int(i) => Some(*i as f64),
+            Value::Float(f) => Some(*f),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Str(s) => write!(f, "{:?}", s),
+            Value::Int(i) => write!(f, "{}", i),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::Bool(b) => write!(f, "{}", b),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Parsed key/value pairs. Readers `take` the keys they understand, so
+/// whatever is left over can be reported as unknown.
+pub struct Document {
+    values: BTreeMap<String, Value>,
+    /// How many of each `[[table]]` there were
+    arrays: BTreeMap<String, usize>,
+}
+
+impl Document {
+    pub fn take(&mut self, key: &str) -> Option<Value> {
+        self.values.remove(key)
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(|k| k.as_str())
+    }
+
+    /// How many `[[name]]` tables there were
+    pub fn count(&self, name: &str) -> usize {
+        self.arrays.get(name).copied().unwrap_or(0)
+    }
+}
+
+pub fn parse(src: &str) -> Result<Document, ParseError> {
+    let mut values = BTreeMap::new();
+    let mut arrays: BTreeMap<String, usize> = BTreeMap::new();
+    let mut table = String::new();
+
+    let mut lines = src.lines().enumerate();
+    while let Some((i, raw)) = lines.next() {
+        let err = |message: String| ParseError { line: i + 1, message };
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix("[[") {
+            let end = rest.find("]]").ok_or_else(|| err("unclosed table header".to_string()))?;
+            expect_end(&rest[end + 2..]).map_err(err)?;
+            let name = rest[..end].trim();
+            if name.is_empty() || !name.split('.').all(is_bare_key) {
+                return Err(err(format!("invalid table name '{}'", name)));
+            }
+            let count = arrays.entry(name.to_string()).or_insert(0);
+            table = format!("{}.{}", name, count);
+            *count += 1;
+            continue;
+        }
+
+        if let Some(rest) = line.strip_prefix('[') {
+            let end = rest.find(']').ok_or_else(|| err("unclosed table header".to_string()))?;
+            expect_end(&rest[end + 1..]).map_err(err)?;
+            let name = rest[..end].trim();
+            if name.is_empty() || !name.split('.').all(is_bare_key) {
+                return Err(err(format!("invalid table name '{}'", name)));
+            }
+            table = name.to_string();
+            continue;
+        }
+
+        let (key, rest) = line.split_once('=').ok_or_else(|| err("expected key = value".to_string()))?;
+        let key = key.trim();
+        if !is_bare_key(key) {
+            return Err(err(format!("invalid key '{}'", key)));
+        }
+        let (value, rest) = match rest.trim_start().strip_prefix("\"\"\"") {
+            Some(first) => {
+                // Runs on to the closing quotes, leaving out the line break
+                // straight after the opening ones
+                let mut body = Vec::new();
+                let mut pending = first;
+                let rest = loop {
+                    if let Some(end) = pending.find("\"\"\"") {
+                        body.push(&pending[..end]);
+                        break &pending[end + 3..];
+                    }
+                    body.push(pending);
+                    pending = lines.next().ok_or_else(|| err("unterminated multi-line string".to_string()))?.1;
+                };
+                if body.len() > 1 && body[0].trim().is_empty() {
+                    body.remove(0);
+                }
+                (Value::Str(body.join("\n")), rest)
+            }
+            None => parse_value(rest.trim_start()).map_err(err)?,
+        };
+        expect_end(rest).map_err(err)?;
+
+        let path = if table.is_empty() { key.to_string() } else { format!("{}.{}", table, key) };
+        if values.insert(path.clone(), value).is_some() {
+            return Err(err(format!("duplicate key '{}'", path)));
+        }
+    }
+
+    Ok(Document { values, arrays })
+}
+
+fn is_bare_key(key: &str) -> bool {
+    !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// Only whitespace or a comment may follow a value or header
+fn expect_end(rest: &str) -> Result<(), String> {
+    let rest = rest.trim_start();
+    if rest.is_empty() || rest.starts_with('#') {
+        Ok(())
+    } else {
+        Err(format!("unexpected '{}'", rest))
+    }
+}
+
+/// Parse one value from the start of `s`, returning it and the unparsed remainder
+fn parse_value(s: &str) -> Result<(Value, &str), String> {
+    if let Some(body) = s.strip_prefix('"') {
+        let mut out = String::new();
+        let mut chars = body.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => return Ok((Value::Str(out), &body[i + 1..])),
+                '\\' => match chars.next().map(|(_, e)| e) {
+                    Some('n') => out.push('\n'),
+                    Some('t') => out.push('\t'),
+                    Some('"') => out.push('"'),
+                    Some('\\') => out.push('\\'),
+                    other => return Err(format!("invalid escape '\\{}'", other.unwrap_or(' '))),
+                },
+                _ => out.push(c),
+            }
+        }
+        return Err("unterminated string".to_string());
+    }
+
+    let end = s.find(|c: char| c.is_whitespace() || c == '#').unwrap_or(s.len());
+    let (token, rest) = s.split_at(end);
+    let value = match token {
+        "" => return Err("missing value".to_string()),
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => {
+            let digits = token.replace('_', "");
+            if let Ok(i) = digits.parse::<i64>() {
+                Value::Int(i)
+            } else if let Ok(f) = digits.parse::<f64>()
+                && f.is_finite()
+            {
+                Value::Float(f)
+            } else {
+                return Err(format!("invalid value '{}'", token));
+            }
+        }
+    };
+    Ok((value, rest))
+}
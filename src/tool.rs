@@ -0,0 +1,169 @@
+//! Tools the clan makes at a workbench and the rack they hang on.
+
+use crate::building::Material;
+
+/// Tools of each kind the workbench keeps spare on the rack
+pub const RACK_STOCK: u32 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ToolKind {
+    Axe,
+    Spear,
+    Basket,
+}
+
+impl ToolKind {
+    /// In the order the workbench makes them when the rack is short of several
+    pub const ALL: [ToolKind; 3] = [ToolKind::Axe, ToolKind::Spear, ToolKind::Basket];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ToolKind::Axe => "axe",
+            ToolKind::Spear => "spear",
+            ToolKind::Basket => "basket",
+        }
+    }
+
+    /// The name with its article, for the event log
+    pub fn with_article(&self) -> &'static str {
+        match self {
+            ToolKind::Axe => "an axe",
+            ToolKind::Spear => "a spear",
+            ToolKind::Basket => "a basket",
+        }
+    }
+
+    /// Units of `material` that go into one
+    pub fn cost(&self, material: Material) -> u32 {
+        match (self, material) {
+            (ToolKind::Axe, Material::Wood) => 1,
+            (ToolKind::Axe, Material::Stone) => 2,
+            (ToolKind::Spear, Material::Wood) => 2,
+            (ToolKind::Spear, Material::Stone) => 1,
+            (ToolKind::Basket, Material::Wood) => 2,
+            (ToolKind::Basket, Material::Stone) => 0,
+        }
+    }
+
+    /// Ticks at the workbench once the materials are in
+    pub fn work(&self) -> u32 {
+        match self {
+            ToolKind::Axe => 15,
+            ToolKind::Spear => 12,
+            ToolKind::Basket => 10,
+        }
+    }
+
+    /// Trees felled, lunges made or bushes picked before it breaks
+    pub fn uses(&self) -> u32 {
+        match self {
+            ToolKind::Axe => 12,
+            ToolKind::Spear => 10,
+            ToolKind::Basket => 15,
+        }
+    }
+}
+
+/// A tool in an orc's hands
+#[derive(Clone, Debug)]
+pub struct Tool {
+    pub kind: ToolKind,
+    pub uses_left: u32,
+}
+
+impl Tool {
+    pub fn new(kind: ToolKind) -> Tool {
+        Tool { kind, uses_left: kind.uses() }
+    }
+}
+
+/// Unused tools hanging by the campfire
+pub struct ToolRack {
+    counts: [u32; 3],
+}
+
+impl Default for ToolRack {
+    /// The founders bring a couple of axes and a spear and basket with them
+    fn default() -> Self {
+        ToolRack { counts: [2, 1, 1] }
+    }
+}
+
+impl ToolRack {
+    pub fn count(&self, kind: ToolKind) -> u32 {
+        self.counts[kind as usize]
+    }
+
+    pub fn put(&mut self, kind: ToolKind) {
+        self.counts[kind as usize] += 1;
+    }
+
+    pub fn take(&mut self, kind: ToolKind) -> bool {
+        let count = &mut self.counts[kind as usize];
+        if *count == 0 {
+            return false;
+        }
+        *count -= 1;
+        true
+    }
+
+    /// What's hanging there for the status line, e.g. `2 axe 0 spear 1 basket`
+    pub fn describe(&self) -> String {
+        let tools: Vec<String> = ToolKind::ALL.iter().map(|&k| format!("{} {}", self.count(k), k.name())).collect();
+        tools.join(" ")
+    }
+}
+
+/// The tool a workbench is making and what has been brought for it so far
+#[derive(Clone, Debug)]
+pub struct Craft {
+    pub x: usize,
+    pub y: usize,
+    pub kind: ToolKind,
+    pub wood: u32,
+    pub stone: u32,
+    pub work: u32,
+}
+
+impl Craft {
+    pub fn new(x: usize, y: usize, kind: ToolKind) -> Craft {
+        Craft { x, y, kind, wood: 0, stone: 0, work: 0 }
+    }
+
+    pub fn delivered(&self, material: Material) -> u32 {
+        match material {
+            Material::Wood => self.wood,
+            Material::Stone => self.stone,
+        }
+    }
+
+    /// Units of `material` still to be brought
+    pub fn missing(&self, material: Material) -> u32 {
+        self.kind.cost(material).saturating_sub(self.delivered(material))
+    }
+
+    pub fn deliver(&mut self, material: Material) {
+        match material {
+            Material::Wood => self.wood += 1,
+            Material::Stone => self.stone += 1,
+        }
+    }
+
+    /// Every material is at the bench and work can begin
+    pub fn is_stocked(&self) -> bool {
+        Material::ALL.iter().all(|&m| self.missing(m) == 0)
+    }
+
+    /// Short progress note for the status line, e.g. `axe 1/2 stone` or `axe 40%`
+    pub fn describe(&self) -> String {
+        if self.is_stocked() {
+            return format!("{} {}%", self.kind.name(), self.work * 100 / self.kind.work());
+        }
+        let needs: Vec<String> = Material::ALL
+            .iter()
+            .filter(|&&m| self.kind.cost(m) > 0)
+            .map(|&m| format!("{}/{} {}", self.delivered(m), self.kind.cost(m), m.name()))
+            .collect();
+        format!("{} {}", self.kind.name(), needs.join(" "))
+    }
+}
@@ -0,0 +1,97 @@
+//! What the cursor is over, told in a small box beside it on the map.
+
+use crate::app::App;
+use crate::world::{BARREL_CAPACITY, MAX_CONDITION, Terrain};
+
+/// What there is to say about the tile at (x, y), a line at a time
+pub fn describe(app: &App, x: usize, y: usize) -> Vec<String> {
+    let mut lines = vec![format!("({},{}) {}", x, y, ground(app, x, y))];
+    for orc in app.sim.orcs.iter().filter(|o| (o.x, o.y) == (x, y)) {
+        if orc.alive {
+            lines.push(format!("{}, {}", orc.name, orc.activity.label().to_lowercase()));
+        } else {
+            lines.push(format!("{}, dead", orc.name));
+        }
+    }
+    if let Some(rival) = app.sim.rivals.member_at(x, y) {
+        lines.push(format!("{} of the rival clan ({:.0} health)", rival.name, rival.health));
+    }
+    if let Some(goblin) = app.sim.raid.as_ref().and_then(|r| r.goblin_at(x, y)) {
+        let loot = if goblin.loot > 0 { format!(", carrying {} meals", goblin.loot) } else { String::new() };
+        lines.push(format!("A goblin ({:.0} health){}", goblin.health, loot));
+    }
+    for animal in app.sim.animals.iter().filter(|a| a.alive && (a.x, a.y) == (x, y)) {
+        lines.push(format!("{}, {}", animal.kind.name(), animal.behaviour.name().to_lowercase()));
+    }
+    if let Some(corpse) = app.sim.world.corpse_at(x, y) {
+        lines.push(format!("The body of {}", corpse.name));
+    }
+    match app.sim.world.food_at(x, y) {
+        0 => {}
+        units => lines.push(format!("{} food lying on the ground", units)),
+    }
+    if let Some(marker) = app.sim.world.markers.at(x, y) {
+        lines.push(format!("{} {}", marker.mark.symbol(), marker.describe(app.sim.world.day_length)));
+    }
+    if let Some(order) = app.sim.world.designations.get(&(x, y)) {
+        lines.push(format!("Marked to {}", order.name()));
+    }
+    lines
+}
+
+/// The ground at (x, y), and what's kept or going on there
+fn ground(app: &App, x: usize, y: usize) -> String {
+    let world = &app.sim.world;
+    let text = match world.get(x, y) {
+        Terrain::Grass => "Grass".to_string(),
+        Terrain::Tree => "Tree".to_string(),
+        Terrain::Rock => "Rock".to_string(),
+        Terrain::Water => "Water".to_string(),
+        Terrain::Campfire if app.sim.rivals.camp == (x, y) => "The rival clan's campfire".to_string(),
+        Terrain::Campfire if world.fire_lit() => format!("Campfire, fuel for {} ticks", world.fire_fuel),
+        Terrain::Campfire => "Campfire, gone out".to_string(),
+        Terrain::Carcass => match world.carcass_at(x, y) {
+            Some(carcass) => capitalize(&carcass.describe(app.sim.tick, app.sim.world.day_length)),
+            None => "Carcass".to_string(),
+        },
+        Terrain::Bush => "Berry bush, ripe".to_string(),
+        Terrain::DepletedBush => match world.regrowth_timers.iter().find(|&&(bx, by, _)| (bx, by) == (x, y)) {
+            Some(&(_, _, regrow_at)) => format!("Picked bush, fruits again in {} ticks", regrow_at.saturating_sub(app.sim.tick)),
+            None => "Picked bush".to_string(),
+        },
+        Terrain::FruitTree => "Fruit tree, bearing".to_string(),
+        Terrain::BareFruitTree => "Fruit tree, bare".to_string(),
+        Terrain::Mushroom => "Mushrooms".to_string(),
+        Terrain::Herb => "Healing herbs".to_string(),
+        Terrain::Grave => match world.grave_at(x, y) {
+            Some(grave) => capitalize(&grave.describe()),
+            None => "Grave".to_string(),
+        },
+        Terrain::MeatRack => format!(
+            "Meat rack: {} raw, {} cooked, {} berries",
+            world.food_stockpile, world.cooked_stockpile, world.berry_stockpile
+        ),
+        Terrain::WaterBarrel => format!("Water barrel, {}/{} drinks", world.water_barrel, BARREL_CAPACITY),
+        Terrain::ToolRack => format!("Tool rack: {}", world.tool_rack.describe()),
+        Terrain::Site(_) => match world.sites.iter().find(|s| (s.x, s.y) == (x, y)) {
+            Some(site) => format!("Building site: {}", site.describe()),
+            None => "Building site".to_string(),
+        },
+        Terrain::Built(building) => match world.crafts.iter().find(|c| (c.x, c.y) == (x, y)) {
+            Some(craft) => format!("{}, making {}", capitalize(building.name()), craft.describe()),
+            None => capitalize(building.name()),
+        },
+    };
+    match world.condition(x, y) {
+        Some(condition) => format!("{} ({}% sound)", text, condition * 100 / MAX_CONDITION),
+        None => text,
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
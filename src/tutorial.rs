@@ -0,0 +1,165 @@
+//! The guided tutorial started with `--tutorial`.
+
+use crate::app::App;
+use crate::calendar::DayPhase;
+use crate::input::Command;
+
+/// World the tutorial is played in unless `--seed` picks another
+pub const TUTORIAL_SEED: u64 = 7;
+
+/// Hunger at which the tutorial suggests dropping food for an orc
+const HUNGRY: f32 = 50.0;
+
+pub struct Lesson {
+    pub title: &'static str,
+    /// Whether the game has reached the point this lesson is about
+    trigger: fn(&App) -> bool,
+    text: fn(&App) -> String,
+}
+
+const LESSONS: [Lesson; 8] = [
+    Lesson {
+        title: "Welcome",
+        trigger: |_| true,
+        text: |_| {
+            "Your clan of orcs has just set up camp. On the map, ☻ are orcs, ♨ is the campfire and ⌸ the meat rack where food is kept. \
+             Around the camp grow trees ♣ and berry bushes ✿, and deer δ and boars β roam the wilds.\n\n\
+             The orcs look after themselves, but they need your help to thrive. This tutorial pauses the game whenever there's something new to learn."
+                .to_string()
+        },
+    },
+    Lesson {
+        title: "Selecting orcs",
+        trigger: |app| app.sim.tick >= 10,
+        text: |app| {
+            let keys = &app.input.keymap;
+            format!(
+                "Press {} to pick an orc. It's highlighted on the map and marked with > in the clan panel on the right, \
+                 and {} jumps the cursor to it.",
+                keys.describe(Command::CycleOrc),
+                keys.describe(Command::GotoSelectedOrc),
+            )
+        },
+    },
+    Lesson {
+        title: "Reading needs",
+        trigger: |app| app.selected_orc.is_some(),
+        text: |_| {
+            "In the clan panel, next to each orc's name is what it's doing right now. Below are its needs: \
+             health (HP), hunger (Hun), energy (Nrg), thirst (H2O), warmth (Wrm) and morale (Mor), then how good a forager it is (Frg).\n\n\
+             Hunger and thirst bars fill up as the need grows; the rest empty. Bars turn yellow, then red, when something needs seeing to."
+                .to_string()
+        },
+    },
+    Lesson {
+        title: "Dropping food",
+        trigger: |app| app.sim.orcs.iter().any(|o| o.alive && o.hunger >= HUNGRY),
+        text: |app| {
+            let name = app.sim.orcs.iter().find(|o| o.alive && o.hunger >= HUNGRY).map_or("An orc", |o| o.name.as_str());
+            format!(
+                "{} is getting hungry. Orcs find their own food, but when they can't, you can help: move the cursor with the arrow keys \
+                 and press {} to drop food on the ground, typing how much and pressing Enter. Hungry orcs nearby will go and eat \
+                 it, and the haulers carry off whatever is left to the stockpile.",
+                name,
+                app.input.keymap.describe(Command::DropFood),
+            )
+        },
+    },
+    Lesson {
+        title: "Giving orders",
+        trigger: |app| app.sim.tick >= 150,
+        text: |app| {
+            let keys = &app.input.keymap;
+            format!(
+                "Every orc has a job, shown in the clan panel: woodcutters, foragers and water carriers. Water carriers get on with it, \
+                 but the others wait for your orders.\n\n\
+                 Press {} to start marking trees for the woodcutters to chop, and again to mark bushes for the foragers to pick, \
+                 then move the cursor over them. {} marks just the tile under the cursor. Press {} until marking is off again.",
+                keys.describe(Command::CycleDesignation),
+                keys.describe(Command::MarkTile),
+                keys.describe(Command::CycleDesignation),
+            )
+        },
+    },
+    Lesson {
+        title: "Building",
+        trigger: |app| !app.sim.world.designations.is_empty() || app.sim.tick >= 300,
+        text: |app| {
+            format!(
+                "Chopped wood is stacked by the meat rack. To build, move the cursor to open ground and press {} for a hut, \
+                 where an orc sleeps warm and dry. Idle orcs bring the wood and stone and put it up. \
+                 The README lists the other buildings, from walls to workbenches.",
+                app.input.keymap.describe(Command::BuildHut),
+            )
+        },
+    },
+    Lesson {
+        title: "Nightfall",
+        trigger: |app| app.sim.phase() == DayPhase::Dusk,
+        text: |_| {
+            "Night is coming. Orcs gather at the campfire to keep warm and sleep, and anyone caught out in the cold loses health. \
+             The fire burns wood, so keep some trees marked for the woodcutters, who feed it during the day."
+                .to_string()
+        },
+    },
+    Lesson {
+        title: "On your own",
+        trigger: |app| app.sim.phase() == DayPhase::Dawn,
+        text: |app| {
+            let keys = &app.input.keymap;
+            format!(
+                "The clan made it through the night. That's all you need to get started: keep them fed, watered and warm, and let the clan grow.\n\n\
+                 {} pauses, {} and {} change the speed, and the events panel below the map tells you what the clan is up to. Good luck!",
+                keys.describe(Command::TogglePause),
+                keys.describe(Command::SpeedUp),
+                keys.describe(Command::SpeedDown),
+            )
+        },
+    },
+];
+
+#[derive(Default)]
+pub struct Tutorial {
+    /// The next lesson, or the one on screen
+    next: usize,
+    showing: bool,
+    /// Whether the game was already paused when the lesson popped up
+    was_paused: bool,
+}
+
+impl Tutorial {
+    /// The lesson on screen, with its number
+    pub fn showing(&self) -> Option<(usize, &'static Lesson)> {
+        self.showing.then(|| (self.next + 1, &LESSONS[self.next]))
+    }
+
+    /// What the lesson on screen says, filled in from the game as it stands
+    pub fn text(&self, app: &App) -> Option<String> {
+        self.showing().map(|(_, lesson)| (lesson.text)(app))
+    }
+
+    pub fn lessons(&self) -> usize {
+        LESSONS.len()
+    }
+
+    /// Pop up the next lesson if its moment has come. Returns true if one did,
+    /// in which case the caller should pause the game.
+    pub fn check(&mut self, app: &App, paused: bool) -> bool {
+        if self.showing || self.next >= LESSONS.len() || !(LESSONS[self.next].trigger)(app) {
+            return false;
+        }
+        self.showing = true;
+        self.was_paused = paused;
+        true
+    }
+
+    /// Close the lesson on screen. Returns whether the game should resume.
+    pub fn dismiss(&mut self) -> bool {
+        if !self.showing {
+            return false;
+        }
+        self.showing = false;
+        self.next += 1;
+        !self.was_paused
+    }
+}
@@ -0,0 +1,106 @@
+//! Ways of coloring the orcs on the map.
+
+use ratatui::style::Color;
+
+use crate::orc::Orc;
+use crate::theme::Theme;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ViewMode {
+    #[default]
+    Normal,
+    Health,
+    Hunger,
+    Energy,
+    Morale,
+    Needs,
+    Territory,
+}
+
+impl ViewMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ViewMode::Normal => "normal",
+            ViewMode::Health => "health",
+            ViewMode::Hunger => "hunger",
+            ViewMode::Energy => "energy",
+            ViewMode::Morale => "morale",
+            ViewMode::Needs => "needs",
+            ViewMode::Territory => "territory",
+        }
+    }
+
+    pub fn next(self) -> ViewMode {
+        match self {
+            ViewMode::Normal => ViewMode::Health,
+            ViewMode::Health => ViewMode::Hunger,
+            ViewMode::Hunger => ViewMode::Energy,
+            ViewMode::Energy => ViewMode::Morale,
+            ViewMode::Morale => ViewMode::Needs,
+            ViewMode::Needs => ViewMode::Territory,
+            ViewMode::Territory => ViewMode::Normal,
+        }
+    }
+
+    /// What to draw `orc` in under this view, or None to draw it as usual
+    pub fn color(&self, orc: &Orc, theme: &Theme) -> Option<Color> {
+        let (bad, warn) = match self {
+            ViewMode::Normal | ViewMode::Territory => return None,
+            ViewMode::Health => health(orc),
+            ViewMode::Hunger => hunger(orc),
+            ViewMode::Energy => energy(orc),
+            ViewMode::Morale => morale(orc),
+            ViewMode::Needs => match worst_need(orc) {
+                Some((_, bad)) => (bad, true),
+                None => (false, false),
+            },
+        };
+        Some(if bad { theme.bad } else if warn { theme.warn } else { theme.good })
+    }
+
+    /// The letter to put beside `orc` under this view, for the need that
+    /// most wants seeing to, in the color of how bad it is
+    pub fn mark(&self, orc: &Orc, theme: &Theme) -> Option<(char, Color)> {
+        if *self != ViewMode::Needs {
+            return None;
+        }
+        let (letter, bad) = worst_need(orc)?;
+        Some((letter, if bad { theme.bad } else { theme.warn }))
+    }
+}
+
+/// Whether each need is bad and whether it's at least worrying, with the
+/// same cut-offs as the bars in the clan panel
+fn health(orc: &Orc) -> (bool, bool) {
+    (orc.health < 30.0, orc.health < 60.0)
+}
+
+fn hunger(orc: &Orc) -> (bool, bool) {
+    (orc.hunger > 70.0, orc.hunger > 40.0)
+}
+
+fn energy(orc: &Orc) -> (bool, bool) {
+    (orc.energy < 20.0, orc.energy < 50.0)
+}
+
+fn thirst(orc: &Orc) -> (bool, bool) {
+    (orc.thirst > 70.0, orc.thirst > 40.0)
+}
+
+fn warmth(orc: &Orc) -> (bool, bool) {
+    (orc.warmth < 20.0, orc.warmth < 50.0)
+}
+
+fn morale(orc: &Orc) -> (bool, bool) {
+    (orc.morale < 25.0, orc.morale < 45.0)
+}
+
+/// The letter for the need of `orc` that most wants seeing to, and whether
+/// it's bad rather than just worrying, or None if none is even worrying. A
+/// bad need comes before a worrying one, and otherwise health before food,
+/// food before water and so on down to morale.
+fn worst_need(orc: &Orc) -> Option<(char, bool)> {
+    let needs = [('+', health(orc)), ('F', hunger(orc)), ('W', thirst(orc)), ('C', warmth(orc)), ('Z', energy(orc)), ('M', morale(orc))];
+    let bad = needs.iter().find(|(_, (bad, _))| *bad).map(|&(letter, _)| (letter, true));
+    bad.or_else(|| needs.iter().find(|(_, (_, warn))| *warn).map(|&(letter, _)| (letter, false)))
+}
@@ -0,0 +1,37 @@
+//! Guards keeping watch over the camp.
+
+/// How far out from the campfire the guards walk their round
+pub const ROUND_DISTANCE: i32 = 12;
+/// How far a guard sees on foot, and from a watchtower
+pub const SIGHT: usize = 15;
+pub const TOWER_SIGHT: usize = 30;
+/// Orcs further than this from the campfire come back in when the alarm goes up
+pub const CALL_BACK: usize = 8;
+/// Ticks after an alarm before the guards raise another
+pub const ALARM_RESPITE: u64 = 50;
+/// What an orc called back to camp tells anyone who asks
+pub const CALLED_BACK: &str = "Called back by the guards";
+
+/// Something worth raising the alarm over
+#[derive(Clone, Copy)]
+pub enum Threat {
+    Wolves,
+    Goblins,
+}
+
+impl Threat {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Threat::Wolves => "wolves",
+            Threat::Goblins => "goblins",
+        }
+    }
+}
+
+/// Which way (x, y) lies from (fx, fy), as one of the eight points of the compass
+pub fn bearing((fx, fy): (usize, usize), (x, y): (usize, usize)) -> &'static str {
+    let (dx, dy) = (x as f32 - fx as f32, y as f32 - fy as f32);
+    // Screen y runs south, so turn it round to measure the angle counterclockwise from east
+    let octant = ((-dy).atan2(dx).to_degrees() / 45.0).round().rem_euclid(8.0) as usize;
+    ["east", "north-east", "north", "north-west", "west", "south-west", "south", "south-east"][octant]
+}
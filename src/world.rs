@@ -1,19 +1,78 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+
 use rand::Rng;
 
+use crate::animal::AnimalKind;
+use crate::bestiary::Deed;
+use crate::burial::{self, Corpse, Grave};
+use crate::building::{Building, Material, Site};
+use crate::carcass::Carcass;
+use crate::calendar::{self, Season};
+use crate::designation::Designation;
+use crate::larder::{CELLAR_SPACE, Larder};
+use crate::markers::Markers;
+use crate::pathfinding::{PathBudget, PathfindingContext};
+use crate::spirits::{self, Spirits};
+use crate::tool::{Craft, ToolKind, ToolRack};
+
 pub const MAP_WIDTH: usize = 300;
 pub const MAP_HEIGHT: usize = 150;
 
-#[derive(Clone, Copy, PartialEq)]
+/// Ticks a picked bush takes to fruit again, unless `[world] bush_regrowth` says otherwise
+pub const BUSH_REGROWTH: u64 = 80;
+/// One tree in this many is a fruit tree
+const FRUIT_TREE_ODDS: u32 = 15;
+/// One rock in this many has a mushroom ring
+const MUSHROOM_RING_ODDS: u32 = 12;
+/// Chance each autumn morning that an empty spot in a ring sprouts a mushroom
+const MUSHROOM_SPROUT_CHANCE: f64 = 0.3;
+/// One tile of open ground in this many along the water's edge grows herbs
+const HERB_ODDS: u32 = 6;
+/// Chance each morning outside winter that a picked herb patch grows back
+const HERB_SPROUT_CHANCE: f64 = 0.1;
+/// Drinks the water barrel by the campfire holds
+pub const BARREL_CAPACITY: u32 = 12;
+/// Drinks a water carrier brings back in one trip
+pub const BUCKET: u32 = 4;
+/// Ticks the campfire burns on one log
+pub const FUEL_PER_LOG: u32 = 100;
+/// Ticks of fuel the campfire can hold
+pub const FIRE_MAX_FUEL: u32 = 300;
+/// Condition of a structure in perfect repair
+pub const MAX_CONDITION: u32 = 1000;
+/// Below this condition idle orcs come to patch a structure up
+const REPAIR_AT: u32 = 600;
+/// Condition every structure loses to the weather each day, twice over in winter
+const WEATHER_WEAR: u32 = 20;
+/// Wear from a night slept in a hut
+pub const HUT_WEAR: u32 = 10;
+/// Wear on the hearth from each log put on the fire
+const HEARTH_WEAR: u32 = 10;
+/// Wear on the meat rack each time food goes on or comes off it
+const RACK_WEAR: u32 = 5;
+/// Wear on a workbench from each tool made at it
+const BENCH_WEAR: u32 = 20;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Terrain {
     Grass,
     Tree,
     Rock,
     Water,
     Campfire,
-    Food,
+    Carcass,
     Bush,
     DepletedBush,
+    FruitTree, // bearing ripe fruit
+    BareFruitTree,
+    Mushroom,
+    Herb, // healing herbs, picked by the healers
+    Grave,
     MeatRack,
+    WaterBarrel,
+    ToolRack,
+    Site(Building),
+    Built(Building),
 }
 
 impl Terrain {
@@ -24,41 +83,86 @@ impl Terrain {
             Terrain::Rock => '◆',
             Terrain::Water => '≈',
             Terrain::Campfire => '♨',
-            Terrain::Food => '⚘',
+            Terrain::Carcass => '✕',
             Terrain::Bush => '✿',
             Terrain::DepletedBush => '✿',
+            Terrain::FruitTree => '♠',
+            Terrain::BareFruitTree => '♠',
+            Terrain::Mushroom => '∩',
+            Terrain::Herb => '⚕',
+            Terrain::Grave => '✝',
             Terrain::MeatRack => '⌸',
+            Terrain::WaterBarrel => '○',
+            Terrain::ToolRack => 'Ш',
+            Terrain::Site(_) => '◌',
+            Terrain::Built(building) => building.symbol(),
         }
     }
 
     pub fn walkable(&self) -> bool {
         match self {
-            Terrain::Rock | Terrain::Water => false,
+            // Nobody can cross a bridge until it's finished
+            Terrain::Rock | Terrain::Water | Terrain::Site(Building::Bridge) => false,
+            Terrain::Built(building) => building.walkable(),
             _ => true,
         }
     }
 
-    pub fn color(&self) -> ratatui::style::Color {
-        use ratatui::style::Color;
-        match self {
-            Terrain::Grass => Color::DarkGray,
-            Terrain::Tree => Color::Rgb(34, 139, 34),
-            Terrain::Rock => Color::Gray,
-            Terrain::Water => Color::Rgb(65, 105, 225),
-            Terrain::Campfire => Color::Rgb(255, 140, 0),
-            Terrain::Food => Color::Rgb(255, 100, 180),
-            Terrain::Bush => Color::Rgb(220, 50, 80),
-            Terrain::DepletedBush => Color::Rgb(80, 60, 60),
-            Terrain::MeatRack => Color::Rgb(180, 120, 60),
-        }
+    /// Every terrain an orc can drink from
+    pub const WATERS: [Terrain; 2] = [Terrain::Water, Terrain::Built(Building::Well)];
+
+    /// Ponds and wells, where an orc can drink or fill a bucket
+    pub fn holds_water(&self) -> bool {
+        Terrain::WATERS.contains(self)
+    }
+
+    /// Animals keep out of every building, gates included
+    pub fn open_to_wildlife(&self) -> bool {
+        !matches!(self, Terrain::Built(_)) && self.walkable()
     }
 }
 
 pub struct World {
-    pub tiles: Vec<Vec<Terrain>>,
+    tiles: Vec<Vec<Terrain>>, // changed only through set(), which keeps spots in step
+    /// Where every tile of each terrain is, by row then column
+    spots: HashMap<Terrain, BTreeSet<(usize, usize)>>,
     pub campfire_pos: (usize, usize),
-    pub food_stockpile: u32,
+    pub food_stockpile: u32, // raw meat on the rack
+    /// How old the raw meat is, kept in step with food_stockpile
+    larder: Larder,
+    pub cooked_stockpile: u32,
+    pub berry_stockpile: u32, // extra berries brought back by skilled foragers
+    pub wood_stockpile: u32, // chopped on the player's orders and stacked by the rack
+    pub stone_stockpile: u32,
+    pub water_barrel: u32, // drinks left in the barrel by the campfire
+    pub fire_fuel: u32, // ticks the campfire keeps burning without another log
+    pub tool_rack: ToolRack, // tools nobody has taken down yet
     pub regrowth_timers: Vec<(usize, usize, u64)>, // (x, y, regrow_at_tick)
+    pub bush_regrowth: u64, // ticks a picked bush takes to fruit again
+    pub day_length: u64, // ticks from one dawn to the next
+    pub mushroom_spots: Vec<(usize, usize)>, // rings around rocks where mushrooms come up in autumn
+    pub herb_spots: Vec<(usize, usize)>, // patches by the water where herbs grow back once picked
+    pub claimed_tiles: HashSet<(usize, usize)>, // food tiles an orc is already heading for
+    pub food_piles: HashMap<(usize, usize), u32>, // units of food lying on the ground, on any walkable tile
+    pub carcasses: Vec<Carcass>, // kills lying where they fell, one on each Carcass tile
+    pub corpses: Vec<Corpse>, // dead orcs still to be buried
+    pub graves: Vec<Grave>, // one on each Grave tile
+    pub markers: Markers, // where orcs died, raiders fell and the last big hunt was
+    pub sites: Vec<Site>, // buildings marked out but not finished yet
+    pub buildings: Vec<(usize, usize, Building)>, // finished buildings
+    /// How sound every building, the campfire and the meat rack are, out of MAX_CONDITION
+    condition: BTreeMap<(usize, usize), u32>,
+    /// Structures worn down to nothing since the app last looked
+    collapsed: Vec<(usize, usize)>,
+    /// Animals the clan has killed or tamed since the app last looked
+    deeds: Vec<(AnimalKind, Deed)>,
+    /// Orcs buried since the app last looked
+    burials: Vec<String>,
+    pub crafts: Vec<Craft>, // the tool each workbench is making
+    pub designations: BTreeMap<(usize, usize), Designation>, // standing orders from the player
+    pub path_budget: PathBudget,
+    pub path_context: PathfindingContext, // scratch space every path search reuses
+    pub spirits: Spirits, // hidden goodwill of the wild towards the clan
 }
 
 impl World {
@@ -70,13 +174,15 @@ impl World {
         let cy = MAP_HEIGHT / 2;
         tiles[cy][cx] = Terrain::Campfire;
 
-        // Place meat rack near campfire
+        // Place meat rack, water barrel and tool rack near campfire
         tiles[cy + 2][cx + 2] = Terrain::MeatRack;
+        tiles[cy + 2][cx - 2] = Terrain::WaterBarrel;
+        tiles[cy - 2][cx + 2] = Terrain::ToolRack;
 
         // Scatter trees and rocks
-        for y in 0..MAP_HEIGHT {
-            for x in 0..MAP_WIDTH {
-                if tiles[y][x] != Terrain::Grass {
+        for (y, row) in tiles.iter_mut().enumerate() {
+            for (x, tile) in row.iter_mut().enumerate() {
+                if *tile != Terrain::Grass {
                     continue;
                 }
                 // Keep area around campfire clear
@@ -86,9 +192,37 @@ impl World {
                     continue;
                 }
                 if rng.gen_ratio(12, 100) {
-                    tiles[y][x] = Terrain::Tree;
+                    *tile = Terrain::Tree;
                 } else if rng.gen_ratio(3, 100) {
-                    tiles[y][x] = Terrain::Rock;
+                    *tile = Terrain::Rock;
+                }
+            }
+        }
+
+        // A few trees bear fruit in summer
+        for tile in tiles.iter_mut().flatten() {
+            if *tile == Terrain::Tree && rng.gen_ratio(1, FRUIT_TREE_ODDS) {
+                *tile = Terrain::BareFruitTree;
+            }
+        }
+
+        // Some rocks have a ring of open ground around them where mushrooms grow
+        let mut mushroom_spots = Vec::new();
+        for y in 2..MAP_HEIGHT - 2 {
+            for x in 2..MAP_WIDTH - 2 {
+                if tiles[y][x] != Terrain::Rock || !rng.gen_ratio(1, MUSHROOM_RING_ODDS) {
+                    continue;
+                }
+                for dy in -2i32..=2 {
+                    for dx in -2i32..=2 {
+                        if dx.abs().max(dy.abs()) != 2 {
+                            continue;
+                        }
+                        let (rx, ry) = ((x as i32 + dx) as usize, (y as i32 + dy) as usize);
+                        if tiles[ry][rx] == Terrain::Grass && !mushroom_spots.contains(&(rx, ry)) {
+                            mushroom_spots.push((rx, ry));
+                        }
+                    }
                 }
             }
         }
@@ -127,30 +261,79 @@ impl World {
                 for dx in 0..pw {
                     let y = wy + dy;
                     let x = wx + dx;
-                    if y < MAP_HEIGHT && x < MAP_WIDTH && tiles[y][x] != Terrain::Campfire && tiles[y][x] != Terrain::MeatRack {
+                    // Keep the camp clearing dry so the fire stays reachable
+                    let in_camp = x.abs_diff(cx) <= 3 && y.abs_diff(cy) <= 3;
+                    if y < MAP_HEIGHT && x < MAP_WIDTH && !in_camp {
                         tiles[y][x] = Terrain::Water;
                     }
                 }
             }
         }
 
-        // Ensure there's a pond near the campfire (within 15 tiles)
+        // Ensure there's a pond near the campfire (within 15 tiles). It floods
+        // whatever grew there, so no tree is left stranded in the middle.
         let pond_near = (cx.saturating_sub(6), cy.saturating_sub(8));
         for dy in 0..3 {
             for dx in 0..4 {
                 let y = pond_near.1 + dy;
                 let x = pond_near.0 + dx;
-                if y < MAP_HEIGHT && x < MAP_WIDTH && tiles[y][x] == Terrain::Grass {
+                if y < MAP_HEIGHT && x < MAP_WIDTH {
                     tiles[y][x] = Terrain::Water;
                 }
             }
         }
 
+        // Healing herbs grow here and there along the water's edge
+        let mut herb_spots = Vec::new();
+        for y in 1..MAP_HEIGHT - 1 {
+            for x in 1..MAP_WIDTH - 1 {
+                let by_water = [(0, 1), (0, -1), (1, 0), (-1, 0)]
+                    .iter()
+                    .any(|&(dx, dy)| tiles[(y as i32 + dy) as usize][(x as i32 + dx) as usize] == Terrain::Water);
+                if tiles[y][x] == Terrain::Grass && by_water && rng.gen_ratio(1, HERB_ODDS) {
+                    herb_spots.push((x, y));
+                }
+            }
+        }
+        for &(x, y) in &herb_spots {
+            tiles[y][x] = Terrain::Herb;
+        }
+
         World {
+            spots: index(&tiles),
             tiles,
             campfire_pos: (cx, cy),
             food_stockpile: 3, // start with a small stockpile
+            larder: Larder::new(3),
+            cooked_stockpile: 0,
+            berry_stockpile: 0,
+            wood_stockpile: 0,
+            stone_stockpile: 0,
+            water_barrel: BARREL_CAPACITY,
+            fire_fuel: FIRE_MAX_FUEL,
+            tool_rack: ToolRack::default(),
             regrowth_timers: Vec::new(),
+            bush_regrowth: BUSH_REGROWTH,
+            day_length: calendar::DAY_LENGTH,
+            mushroom_spots,
+            herb_spots,
+            claimed_tiles: HashSet::new(),
+            food_piles: HashMap::new(),
+            carcasses: Vec::new(),
+            corpses: Vec::new(),
+            graves: Vec::new(),
+            markers: Markers::default(),
+            sites: Vec::new(),
+            buildings: Vec::new(),
+            condition: BTreeMap::from([((cx, cy), MAX_CONDITION), ((cx + 2, cy + 2), MAX_CONDITION)]),
+            collapsed: Vec::new(),
+            deeds: Vec::new(),
+            burials: Vec::new(),
+            crafts: Vec::new(),
+            designations: BTreeMap::new(),
+            path_budget: PathBudget::default(),
+            path_context: PathfindingContext::default(),
+            spirits: Spirits::default(),
         }
     }
 
@@ -158,8 +341,19 @@ impl World {
         self.tiles[y][x]
     }
 
+    /// The whole map, row by row
+    pub fn tiles(&self) -> &[Vec<Terrain>] {
+        &self.tiles
+    }
+
     pub fn set(&mut self, x: usize, y: usize, terrain: Terrain) {
-        self.tiles[y][x] = terrain;
+        let was = std::mem::replace(&mut self.tiles[y][x], terrain);
+        if was != terrain {
+            if let Some(spots) = self.spots.get_mut(&was) {
+                spots.remove(&(y, x));
+            }
+            self.spots.entry(terrain).or_default().insert((y, x));
+        }
     }
 
     pub fn is_walkable(&self, x: usize, y: usize) -> bool {
@@ -169,10 +363,432 @@ impl World {
         self.tiles[y][x].walkable()
     }
 
+    pub fn is_open_to_wildlife(&self, x: usize, y: usize) -> bool {
+        if x >= MAP_WIDTH || y >= MAP_HEIGHT {
+            return false;
+        }
+        self.tiles[y][x].open_to_wildlife()
+    }
+
+    /// Whether a diagonal step from (x, y) to (nx, ny) squeezes between two
+    /// buildings that `open` doesn't let through, so that a wall laid out
+    /// corner to corner still closes the gap
+    pub fn cuts_corner(&self, x: usize, y: usize, nx: usize, ny: usize, open: fn(&Terrain) -> bool) -> bool {
+        if x == nx || y == ny {
+            return false;
+        }
+        let sealed = |t: Terrain| matches!(t, Terrain::Built(_)) && !open(&t);
+        sealed(self.tiles[y][nx]) && sealed(self.tiles[ny][x])
+    }
+
+    /// Meals of any kind waiting on the rack
+    pub fn stockpiled_meals(&self) -> u32 {
+        self.food_stockpile + self.cooked_stockpile + self.berry_stockpile
+    }
+
+    pub fn fire_lit(&self) -> bool {
+        self.fire_fuel > 0
+    }
+
+    /// Burn one tick's worth of fuel. Returns true if the fire has just gone out.
+    pub fn burn_fire(&mut self) -> bool {
+        if self.fire_fuel == 0 {
+            return false;
+        }
+        self.fire_fuel -= 1;
+        self.fire_fuel == 0
+    }
+
+    /// Put a log on the fire. Returns true if that lit it again.
+    pub fn feed_fire(&mut self) -> bool {
+        let (cx, cy) = self.campfire_pos;
+        self.wear(cx, cy, HEARTH_WEAR);
+        let relit = !self.fire_lit();
+        self.fire_fuel = (self.fire_fuel + FUEL_PER_LOG).min(FIRE_MAX_FUEL);
+        relit
+    }
+
+    /// Take the best meal off the rack: cooked, then berries, then raw meat
+    pub fn take_meal(&mut self) -> bool {
+        let Some(pile) = [&mut self.cooked_stockpile, &mut self.berry_stockpile].into_iter().find(|p| **p > 0) else {
+            return self.take_meat();
+        };
+        *pile -= 1;
+        self.wear_rack();
+        true
+    }
+
+    /// Hang a fresh piece of raw meat on the rack
+    pub fn store_meat(&mut self) {
+        self.food_stockpile += 1;
+        self.larder.store();
+        self.wear_rack();
+    }
+
+    /// Put `meat` on the rack in place of what's there, all of it fresh
+    pub fn restock(&mut self, meat: u32) {
+        self.food_stockpile = meat;
+        self.larder = Larder::new(meat);
+    }
+
+    /// Take the oldest raw meat off the rack, if there is any
+    pub fn take_meat(&mut self) -> bool {
+        if self.food_stockpile == 0 {
+            return false;
+        }
+        self.food_stockpile -= 1;
+        self.larder.take();
+        self.wear_rack();
+        true
+    }
+
+    /// Meat kept safe in storage huts
+    pub fn cellared_meat(&self) -> u32 {
+        self.larder.cellared()
+    }
+
+    /// Let a day pass over the raw meat. Returns how much of it has spoiled.
+    pub fn spoil_meat(&mut self) -> u32 {
+        let stores = self.buildings.iter().filter(|b| b.2 == Building::Storage).count() as u32;
+        let mut spoiled = self.larder.age(stores * CELLAR_SPACE);
+        // A rack lying on the ground keeps nothing
+        if self.meat_rack_pos().is_some_and(|(x, y)| self.is_broken(x, y)) {
+            spoiled += self.larder.spoil_rack();
+        }
+        self.food_stockpile -= spoiled;
+        spoiled
+    }
+
+    /// Units of food lying on the ground at (x, y)
+    pub fn food_at(&self, x: usize, y: usize) -> u32 {
+        self.food_piles.get(&(x, y)).copied().unwrap_or(0)
+    }
+
+    /// Drop food on the ground, stacking onto any pile already there. A pile
+    /// lies on top of whatever is underfoot, so any tile an orc can walk onto
+    /// will do. Returns false if it can't be reached.
+    pub fn add_food(&mut self, x: usize, y: usize, units: u32) -> bool {
+        if !self.is_walkable(x, y) {
+            return false;
+        }
+        *self.food_piles.entry((x, y)).or_insert(0) += units;
+        true
+    }
+
+    /// The nearest pile of food no orc has claimed yet
+    pub fn nearest_unclaimed_food(&self, from_x: usize, from_y: usize) -> Option<(usize, usize)> {
+        self.food_piles.keys()
+            .filter(|pos| !self.claimed_tiles.contains(pos))
+            .min_by_key(|&&(x, y)| (from_x.abs_diff(x) + from_y.abs_diff(y), x, y))
+            .copied()
+    }
+
+    /// Leave the carcass of a `kind` with `meat` on it where it fell at (x, y),
+    /// or on open ground next to it. Returns where it lies, if there was anywhere.
+    pub fn lay_carcass(&mut self, x: usize, y: usize, kind: AnimalKind, meat: u32, tick: u64) -> Option<(usize, usize)> {
+        if meat == 0 {
+            return None;
+        }
+        let spots = [(0i32, 0i32), (1, 0), (-1, 0), (0, 1), (0, -1), (1, 1), (-1, -1), (1, -1), (-1, 1)];
+        let (cx, cy) = spots.iter()
+            .map(|&(dx, dy)| ((x as i32 + dx).clamp(0, MAP_WIDTH as i32 - 1) as usize, (y as i32 + dy).clamp(0, MAP_HEIGHT as i32 - 1) as usize))
+            .find(|&(cx, cy)| self.tiles[cy][cx] == Terrain::Grass)?;
+        self.set(cx, cy, Terrain::Carcass);
+        self.carcasses.push(Carcass { x: cx, y: cy, kind, meat, fallen: tick });
+        Some((cx, cy))
+    }
+
+    pub fn carcass_at(&self, x: usize, y: usize) -> Option<&Carcass> {
+        self.carcasses.iter().find(|c| c.x == x && c.y == y)
+    }
+
+    /// Cut a unit of meat off the carcass at (x, y), clearing the tile once
+    /// it's picked clean
+    pub fn butcher(&mut self, x: usize, y: usize) -> bool {
+        let Some(i) = self.carcasses.iter().position(|c| c.x == x && c.y == y) else {
+            return false;
+        };
+        self.carcasses[i].meat -= 1;
+        if self.carcasses[i].meat == 0 {
+            self.carcasses.remove(i);
+            self.set(x, y, Terrain::Grass);
+        }
+        true
+    }
+
+    /// Take a unit of meat from whatever lies at (x, y), a pile or a carcass
+    pub fn take_meat_at(&mut self, x: usize, y: usize) -> bool {
+        self.take_food(x, y) || self.butcher(x, y)
+    }
+
+    /// Let ripe carcasses rot a little further. Returns the kinds of those
+    /// that have rotted away to nothing.
+    pub fn rot_carcasses(&mut self, tick: u64) -> Vec<AnimalKind> {
+        let mut gone = Vec::new();
+        let mut cleared = Vec::new();
+        for carcass in self.carcasses.iter_mut().filter(|c| c.rots(tick, self.day_length)) {
+            carcass.meat -= 1;
+            if carcass.meat == 0 {
+                gone.push(carcass.kind);
+                cleared.push((carcass.x, carcass.y));
+            }
+        }
+        for (x, y) in cleared {
+            self.set(x, y, Terrain::Grass);
+        }
+        self.carcasses.retain(|c| c.meat > 0);
+        gone
+    }
+
+    /// Stack one unit of `material` by the rack, returning how much is there now
+    pub fn store(&mut self, material: Material) -> u32 {
+        let stock = match material {
+            Material::Wood => &mut self.wood_stockpile,
+            Material::Stone => &mut self.stone_stockpile,
+        };
+        *stock += 1;
+        *stock
+    }
+
+    /// Place a standing order on (x, y). Returns false if it doesn't fit the tile.
+    pub fn mark(&mut self, x: usize, y: usize, order: Designation) -> bool {
+        if !order.applies_to(self.tiles[y][x]) {
+            return false;
+        }
+        self.designations.insert((x, y), order);
+        true
+    }
+
+    /// Take one unit from the pile at (x, y), which is gone once it runs out
+    pub fn take_food(&mut self, x: usize, y: usize) -> bool {
+        let Some(units) = self.food_piles.get_mut(&(x, y)) else {
+            return false;
+        };
+        *units -= 1;
+        if *units == 0 {
+            self.food_piles.remove(&(x, y));
+        }
+        true
+    }
+
+    /// Mark out a building on open ground, or a bridge on water. Returns false
+    /// if the tile is taken.
+    pub fn designate(&mut self, x: usize, y: usize, kind: Building) -> bool {
+        if self.tiles[y][x] != kind.ground() {
+            return false;
+        }
+        self.set(x, y, Terrain::Site(kind));
+        self.sites.push(Site::new(x, y, kind));
+        true
+    }
+
+    /// Mark out buildings on every free tile of the line from (x0, y0) to
+    /// (x1, y1), returning how many went down
+    pub fn designate_line(&mut self, (x0, y0): (usize, usize), (x1, y1): (usize, usize), kind: Building) -> usize {
+        let steps = x0.abs_diff(x1).max(y0.abs_diff(y1));
+        let mut placed = 0;
+        for i in 0..=steps {
+            let along = |a: usize, b: usize| {
+                if steps == 0 { a } else { (a as f64 + (b as f64 - a as f64) * i as f64 / steps as f64).round() as usize }
+            };
+            if self.designate(along(x0, x1), along(y0, y1), kind) {
+                placed += 1;
+            }
+        }
+        placed
+    }
+
+    pub fn site_mut(&mut self, x: usize, y: usize) -> Option<&mut Site> {
+        self.sites.iter_mut().find(|s| s.x == x && s.y == y)
+    }
+
+    /// Replace a finished site with its building
+    pub fn complete_site(&mut self, x: usize, y: usize) -> Option<Building> {
+        let i = self.sites.iter().position(|s| s.x == x && s.y == y)?;
+        let site = self.sites.remove(i);
+        self.set(x, y, Terrain::Built(site.kind));
+        self.buildings.push((x, y, site.kind));
+        self.condition.insert((x, y), MAX_CONDITION);
+        Some(site.kind)
+    }
+
+    /// How sound the structure at (x, y) is, if there's one there
+    pub fn condition(&self, x: usize, y: usize) -> Option<u32> {
+        self.condition.get(&(x, y)).copied()
+    }
+
+    /// Worn right through: a campfire nobody can light or a rack that keeps nothing
+    pub fn is_broken(&self, x: usize, y: usize) -> bool {
+        self.condition(x, y) == Some(0)
+    }
+
+    /// What the structure at (x, y) is called in the event log
+    pub fn structure_name(&self, x: usize, y: usize) -> &'static str {
+        match self.tiles[y][x] {
+            Terrain::Campfire => "campfire",
+            Terrain::MeatRack => "meat rack",
+            Terrain::Built(building) => building.name(),
+            _ => "structure",
+        }
+    }
+
+    /// Wear the structure at (x, y) down, noting it if it gives out
+    pub fn wear(&mut self, x: usize, y: usize, amount: u32) {
+        let Some(condition) = self.condition.get_mut(&(x, y)) else {
+            return;
+        };
+        if *condition > 0 && *condition <= amount {
+            self.collapsed.push((x, y));
+        }
+        *condition = condition.saturating_sub(amount);
+    }
+
+    pub fn wear_rack(&mut self) {
+        if let Some((x, y)) = self.meat_rack_pos() {
+            self.wear(x, y, RACK_WEAR);
+        }
+    }
+
+    /// A day's wind and rain on every structure
+    pub fn weather(&mut self, season: Season) {
+        let wear = if season == Season::Winter { WEATHER_WEAR * 2 } else { WEATHER_WEAR };
+        let all: Vec<(usize, usize)> = self.condition.keys().copied().collect();
+        for (x, y) in all {
+            self.wear(x, y, wear);
+        }
+    }
+
+    /// Structures that need patching up, in map order
+    pub fn worn(&self) -> impl Iterator<Item = (usize, usize, u32)> + '_ {
+        self.condition.iter().filter(|&(_, &c)| c < REPAIR_AT).map(|(&(x, y), &c)| (x, y, c))
+    }
+
+    /// Put `amount` of work into the structure at (x, y). Returns whether it's
+    /// as good as new, or None if there's nothing left there to repair.
+    pub fn repair(&mut self, x: usize, y: usize, amount: u32) -> Option<bool> {
+        let condition = self.condition.get_mut(&(x, y))?;
+        *condition = (*condition + amount).min(MAX_CONDITION);
+        Some(*condition == MAX_CONDITION)
+    }
+
+    /// Structures that have given out since the last call
+    pub fn take_collapsed(&mut self) -> Vec<(usize, usize)> {
+        std::mem::take(&mut self.collapsed)
+    }
+
+    /// Leave the body of the orc called `name` lying at (x, y)
+    pub fn lay_corpse(&mut self, name: String, x: usize, y: usize) {
+        self.corpses.push(Corpse { name, x, y });
+    }
+
+    /// Lift the body of the orc called `name`, if it's still lying about
+    pub fn take_corpse(&mut self, name: &str) -> Option<Corpse> {
+        let i = self.corpses.iter().position(|c| c.name == name)?;
+        Some(self.corpses.remove(i))
+    }
+
+    pub fn corpse_at(&self, x: usize, y: usize) -> Option<&Corpse> {
+        self.corpses.iter().find(|c| c.x == x && c.y == y)
+    }
+
+    pub fn grave_at(&self, x: usize, y: usize) -> Option<&Grave> {
+        self.graves.iter().find(|g| g.x == x && g.y == y)
+    }
+
+    /// The open ground in the graveyard where the next grave goes: as close
+    /// in to the rest as there's room for
+    pub fn grave_plot(&self) -> Option<(usize, usize)> {
+        let (cx, cy) = self.campfire_pos;
+        let graveyard = (cx, (cy + burial::GRAVEYARD_DISTANCE).min(MAP_HEIGHT - 1));
+        self.nearest_of(graveyard.0, graveyard.1, Terrain::Grass, |x, y| {
+            self.food_at(x, y) == 0 && !self.claimed_tiles.contains(&(x, y)) && self.corpse_at(x, y).is_none()
+        })
+    }
+
+    /// Bury the orc called `name` under a grave at (x, y) on `day`
+    pub fn dig_grave(&mut self, x: usize, y: usize, name: String, day: u64) {
+        self.set(x, y, Terrain::Grave);
+        self.burials.push(name.clone());
+        self.graves.push(Grave { name, x, y, day });
+    }
+
+    /// Orcs buried since the last call
+    pub fn take_burials(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.burials)
+    }
+
+    /// Note that the clan has killed or tamed an animal of `kind`
+    pub fn record(&mut self, kind: AnimalKind, deed: Deed) {
+        self.deeds.push((kind, deed));
+    }
+
+    /// Animals killed or tamed since the last call
+    pub fn take_deeds(&mut self) -> Vec<(AnimalKind, Deed)> {
+        std::mem::take(&mut self.deeds)
+    }
+
+    /// Bring down the building at (x, y), leaving its site marked out to be
+    /// built again from scratch
+    pub fn collapse(&mut self, x: usize, y: usize) -> Option<Building> {
+        let i = self.buildings.iter().position(|b| b.0 == x && b.1 == y)?;
+        let (_, _, kind) = self.buildings.remove(i);
+        self.condition.remove(&(x, y));
+        self.set(x, y, Terrain::Site(kind));
+        self.sites.push(Site::new(x, y, kind));
+        Some(kind)
+    }
+
+    /// The workbench order at (x, y), if one is under way
+    pub fn craft_mut(&mut self, x: usize, y: usize) -> Option<&mut Craft> {
+        self.crafts.iter_mut().find(|c| c.x == x && c.y == y)
+    }
+
+    /// Hang the finished tool from the workbench at (x, y) on the rack
+    pub fn complete_craft(&mut self, x: usize, y: usize) -> Option<ToolKind> {
+        let i = self.crafts.iter().position(|c| c.x == x && c.y == y)?;
+        let kind = self.crafts.remove(i).kind;
+        self.tool_rack.put(kind);
+        self.wear(x, y, BENCH_WEAR);
+        Some(kind)
+    }
+
+    /// Positions of every finished workbench
+    pub fn workbenches(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.buildings.iter().filter(|b| b.2 == Building::Workbench).map(|&(x, y, _)| (x, y))
+    }
+
+    /// Positions of every finished pen tile
+    pub fn pens(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.buildings.iter().filter(|b| b.2 == Building::Pen).map(|&(x, y, _)| (x, y))
+    }
+
+    /// Positions of every finished hut
+    pub fn huts(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.buildings.iter().filter(|b| b.2 == Building::Hut).map(|&(x, y, _)| (x, y))
+    }
+
     pub fn deplete_bush(&mut self, x: usize, y: usize, current_tick: u64) {
         if self.tiles[y][x] == Terrain::Bush {
-            self.tiles[y][x] = Terrain::DepletedBush;
-            self.regrowth_timers.push((x, y, current_tick + 80));
+            self.browse_bush(x, y, current_tick);
+            self.spirits.offend(spirits::PICK_OFFENCE);
+        }
+    }
+
+    /// An animal stripping a bush. It grows back like any other, and the
+    /// spirits don't hold it against the clan.
+    pub fn browse_bush(&mut self, x: usize, y: usize, current_tick: u64) {
+        if self.tiles[y][x] == Terrain::Bush {
+            self.set(x, y, Terrain::DepletedBush);
+            self.regrowth_timers.push((x, y, current_tick + self.bush_regrowth));
+        }
+    }
+
+    /// The spirits' blessing: every picked-over bush fruits again at once
+    pub fn bless_harvest(&mut self) {
+        for (x, y, _) in std::mem::take(&mut self.regrowth_timers) {
+            if self.tiles[y][x] == Terrain::DepletedBush {
+                self.set(x, y, Terrain::Bush);
+            }
         }
     }
 
@@ -188,46 +804,140 @@ impl World {
         });
         for (x, y) in regrown {
             if self.tiles[y][x] == Terrain::DepletedBush {
-                self.tiles[y][x] = Terrain::Bush;
+                self.set(x, y, Terrain::Bush);
             }
         }
     }
 
+    /// Bring on each morning's seasonal food: fruit ripens overnight all summer,
+    /// and in autumn fresh mushrooms push up around the rocks. Whatever is
+    /// left when the season turns rots away. Herbs die back in winter and
+    /// grow back in their patches the rest of the year.
+    pub fn grow_seasonal_food(&mut self, season: Season, rng: &mut impl Rng) {
+        let turns: &[(Terrain, Terrain)] = match season {
+            Season::Summer => &[(Terrain::BareFruitTree, Terrain::FruitTree), (Terrain::Mushroom, Terrain::Grass)],
+            Season::Autumn => &[(Terrain::FruitTree, Terrain::BareFruitTree)],
+            Season::Spring => &[(Terrain::FruitTree, Terrain::BareFruitTree), (Terrain::Mushroom, Terrain::Grass)],
+            Season::Winter => &[(Terrain::FruitTree, Terrain::BareFruitTree), (Terrain::Mushroom, Terrain::Grass), (Terrain::Herb, Terrain::Grass)],
+        };
+        for &(from, to) in turns {
+            for (x, y) in self.tiles_of(from).collect::<Vec<_>>() {
+                self.set(x, y, to);
+            }
+        }
+        if season != Season::Winter {
+            for (x, y) in self.herb_spots.clone() {
+                if self.tiles[y][x] == Terrain::Grass && rng.gen_bool(HERB_SPROUT_CHANCE) {
+                    self.set(x, y, Terrain::Herb);
+                }
+            }
+        }
+        if season == Season::Autumn {
+            for (x, y) in self.mushroom_spots.clone() {
+                if self.tiles[y][x] == Terrain::Grass && rng.gen_bool(MUSHROOM_SPROUT_CHANCE) {
+                    self.set(x, y, Terrain::Mushroom);
+                }
+            }
+        }
+    }
+
+    /// Every tile of a given type, row by row from the top left
+    pub fn tiles_of(&self, terrain: Terrain) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.spots.get(&terrain).into_iter().flatten().map(|&(y, x)| (x, y))
+    }
+
     /// Find the nearest tile of a given type from position
     pub fn find_nearest(&self, from_x: usize, from_y: usize, terrain: Terrain) -> Option<(usize, usize)> {
-        let mut best: Option<(usize, usize, usize)> = None;
-        for y in 0..MAP_HEIGHT {
-            for x in 0..MAP_WIDTH {
-                if self.tiles[y][x] == terrain {
-                    let dist = from_x.abs_diff(x) + from_y.abs_diff(y);
-                    if best.is_none() || dist < best.unwrap().2 {
-                        best = Some((x, y, dist));
+        self.nearest_of(from_x, from_y, terrain, |_, _| true)
+    }
+
+    /// Like `find_nearest`, but skips tiles another orc has already claimed
+    pub fn find_nearest_unclaimed(&self, from_x: usize, from_y: usize, terrain: Terrain) -> Option<(usize, usize)> {
+        self.nearest_of(from_x, from_y, terrain, |x, y| !self.claimed_tiles.contains(&(x, y)))
+    }
+
+    /// The nearest tile of `terrain` that `keeps` lets through, the topmost
+    /// then leftmost of any as near. It looks in rings spreading out from
+    /// (from_x, from_y) for as long as that's less work than going through
+    /// every tile of the kind, so a tree turns up a few steps off without a
+    /// sweep of the map and a carcass is picked out of the handful there are.
+    fn nearest_of(&self, from_x: usize, from_y: usize, terrain: Terrain, keeps: impl Fn(usize, usize) -> bool) -> Option<(usize, usize)> {
+        let spots = self.spots.get(&terrain)?;
+        let mut looked = 0;
+        for dist in 0..MAP_WIDTH + MAP_HEIGHT {
+            if looked >= spots.len() {
+                break;
+            }
+            for y in from_y.saturating_sub(dist)..=(from_y + dist).min(MAP_HEIGHT - 1) {
+                let across = dist - from_y.abs_diff(y);
+                let xs = [from_x.checked_sub(across), Some(from_x + across).filter(|_| across > 0)];
+                for x in xs.into_iter().flatten().filter(|&x| x < MAP_WIDTH) {
+                    looked += 1;
+                    if self.tiles[y][x] == terrain && keeps(x, y) {
+                        return Some((x, y));
                     }
                 }
             }
         }
-        best.map(|(x, y, _)| (x, y))
+        self.tiles_of(terrain)
+            .filter(|&(x, y)| keeps(x, y))
+            .min_by_key(|&(x, y)| (from_x.abs_diff(x) + from_y.abs_diff(y), y, x))
     }
 
-    /// Find a walkable tile adjacent to the nearest water
+    /// The closest tile to (x, y) no more than `radius` steps off in any
+    /// direction that holds `terrain`. Unlike `find_nearest`, this only
+    /// searches the square around (x, y), which is cheap enough for wildlife
+    /// to look about every tick.
+    pub fn nearest_within(&self, x: usize, y: usize, radius: usize, terrain: Terrain) -> Option<(usize, usize)> {
+        let xs = x.saturating_sub(radius)..(x + radius + 1).min(MAP_WIDTH);
+        let ys = y.saturating_sub(radius)..(y + radius + 1).min(MAP_HEIGHT);
+        ys.flat_map(|ty| xs.clone().map(move |tx| (tx, ty)))
+            .filter(|&(tx, ty)| self.tiles[ty][tx] == terrain)
+            .min_by_key(|&(tx, ty)| (x.abs_diff(tx).max(y.abs_diff(ty)), ty, tx))
+    }
+
+    /// Find a walkable tile adjacent to the nearest water, a pond or a well
     pub fn find_water_adjacent(&self, from_x: usize, from_y: usize) -> Option<(usize, usize)> {
-        // Find nearest water tile, then return a walkable neighbor
-        if let Some((wx, wy)) = self.find_nearest(from_x, from_y, Terrain::Water) {
-            let neighbors = [(0i32, 1i32), (0, -1), (1, 0), (-1, 0)];
-            let mut best: Option<(usize, usize, usize)> = None;
-            for &(dx, dy) in &neighbors {
-                let nx = (wx as i32 + dx).clamp(0, MAP_WIDTH as i32 - 1) as usize;
-                let ny = (wy as i32 + dy).clamp(0, MAP_HEIGHT as i32 - 1) as usize;
-                if self.is_walkable(nx, ny) {
-                    let dist = from_x.abs_diff(nx) + from_y.abs_diff(ny);
-                    if best.is_none() || dist < best.unwrap().2 {
-                        best = Some((nx, ny, dist));
-                    }
+        let (wx, wy) = Terrain::WATERS.into_iter()
+            .filter_map(|terrain| self.find_nearest(from_x, from_y, terrain))
+            .min_by_key(|&(x, y)| (from_x.abs_diff(x) + from_y.abs_diff(y), y, x))?;
+        self.walkable_neighbor(wx, wy, from_x, from_y)
+    }
+
+    /// Where to go for a drink: the water barrel if it has any left and is
+    /// nearer than the closest pond or well
+    pub fn find_drink(&self, from_x: usize, from_y: usize) -> Option<(usize, usize)> {
+        let water = self.find_water_adjacent(from_x, from_y);
+        let barrel = self.barrel_pos().filter(|_| self.water_barrel > 0);
+        let dist = |&(x, y): &(usize, usize)| from_x.abs_diff(x) + from_y.abs_diff(y);
+        water.into_iter().chain(barrel).min_by_key(dist)
+    }
+
+    /// The walkable tile next to (x, y) that is closest to (from_x, from_y)
+    pub fn walkable_neighbor(&self, x: usize, y: usize, from_x: usize, from_y: usize) -> Option<(usize, usize)> {
+        let neighbors = [(0i32, 1i32), (0, -1), (1, 0), (-1, 0)];
+        let mut best: Option<(usize, usize, usize)> = None;
+        for &(dx, dy) in &neighbors {
+            let nx = (x as i32 + dx).clamp(0, MAP_WIDTH as i32 - 1) as usize;
+            let ny = (y as i32 + dy).clamp(0, MAP_HEIGHT as i32 - 1) as usize;
+            if self.is_walkable(nx, ny) {
+                let dist = from_x.abs_diff(nx) + from_y.abs_diff(ny);
+                if best.is_none() || dist < best.unwrap().2 {
+                    best = Some((nx, ny, dist));
                 }
             }
-            return best.map(|(x, y, _)| (x, y));
         }
-        None
+        best.map(|(x, y, _)| (x, y))
+    }
+
+    pub fn barrel_pos(&self) -> Option<(usize, usize)> {
+        let (cx, cy) = self.campfire_pos;
+        let (x, y) = (cx.checked_sub(2)?, cy + 2);
+        if y < MAP_HEIGHT && self.tiles[y][x] == Terrain::WaterBarrel {
+            Some((x, y))
+        } else {
+            None
+        }
     }
 
     pub fn meat_rack_pos(&self) -> Option<(usize, usize)> {
@@ -241,3 +951,14 @@ impl World {
         }
     }
 }
+
+/// Where every tile of each terrain is on a freshly made map
+fn index(tiles: &[Vec<Terrain>]) -> HashMap<Terrain, BTreeSet<(usize, usize)>> {
+    let mut spots: HashMap<Terrain, BTreeSet<(usize, usize)>> = HashMap::new();
+    for (y, row) in tiles.iter().enumerate() {
+        for (x, &terrain) in row.iter().enumerate() {
+            spots.entry(terrain).or_default().insert((y, x));
+        }
+    }
+    spots
+}
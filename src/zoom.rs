@@ -0,0 +1,86 @@
+//! Zooming the map out to take in more of the world at once.
+
+use crate::world::{MAP_HEIGHT, MAP_WIDTH, Terrain, World};
+
+/// Percentage of a block that has to be trees, water or the like for it to
+/// show as such rather than as grass
+const FEATURE_SHARE: usize = 19;
+
+/// How far out the map is zoomed
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Zoom {
+    #[default]
+    Full,
+    Half,
+    Quarter,
+}
+
+impl Zoom {
+    /// Tiles along each side of the block a character stands for
+    pub fn scale(&self) -> usize {
+        match self {
+            Zoom::Full => 1,
+            Zoom::Half => 2,
+            Zoom::Quarter => 4,
+        }
+    }
+
+    pub fn next(&self) -> Zoom {
+        match self {
+            Zoom::Full => Zoom::Half,
+            Zoom::Half => Zoom::Quarter,
+            Zoom::Quarter => Zoom::Full,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Zoom::Full => "1:1",
+            Zoom::Half => "1:2",
+            Zoom::Quarter => "1:4",
+        }
+    }
+}
+
+/// The tiles of the block `scale` tiles square with (x, y) at its top left
+pub fn block(x: usize, y: usize, scale: usize) -> impl Iterator<Item = (usize, usize)> + Clone {
+    (y..(y + scale).min(MAP_HEIGHT)).flat_map(move |ty| (x..(x + scale).min(MAP_WIDTH)).map(move |tx| (tx, ty)))
+}
+
+/// The ground to show for the block with (x, y) at its top left
+pub fn ground(world: &World, x: usize, y: usize, scale: usize) -> Terrain {
+    let mut counts: Vec<(Terrain, usize)> = Vec::new();
+    let mut tiles = 0;
+    for (tx, ty) in block(x, y, scale) {
+        let terrain = world.get(tx, ty);
+        if is_landmark(terrain) {
+            return terrain;
+        }
+        tiles += 1;
+        if terrain == Terrain::Grass {
+            continue;
+        }
+        match counts.iter_mut().find(|(t, _)| *t == terrain) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((terrain, 1)),
+        }
+    }
+    match counts.into_iter().max_by_key(|&(_, n)| n) {
+        Some((terrain, n)) if n * 100 >= tiles * FEATURE_SHARE => terrain,
+        _ => Terrain::Grass,
+    }
+}
+
+/// Whether the clan put it there, and so it should show when zoomed out
+fn is_landmark(terrain: Terrain) -> bool {
+    matches!(
+        terrain,
+        Terrain::Campfire
+            | Terrain::Grave
+            | Terrain::MeatRack
+            | Terrain::WaterBarrel
+            | Terrain::ToolRack
+            | Terrain::Site(_)
+            | Terrain::Built(_)
+    )
+}